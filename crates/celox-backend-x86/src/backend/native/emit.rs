@@ -5458,6 +5458,7 @@ pub fn emit_prepared_eu(
     let state_size = layout
         .merged_total_size
         .checked_add(layout.triggered_bits_total_size)
+        .and_then(|size| size.checked_add(options.arena_offset))
         .expect("native simulation-state size overflow");
     let result = if label == "eval_comb_apply_ff" && options.native_tick_loop {
         let check_runtime_events = sir_eu.blocks.values().any(|block| {
@@ -49,6 +49,10 @@ pub struct X86BackendOptions {
     pub slp: bool,
     pub native_tick_loop: bool,
     pub diagnostics: NativeDiagnostics,
+    /// Bytes reserved between the simulation state and this function's
+    /// spill/scratch arena. Functions that run concurrently on one state
+    /// buffer are emitted with disjoint arenas. Must be a multiple of 16.
+    pub arena_offset: usize,
 }
 
 impl Default for X86BackendOptions {
@@ -57,6 +61,7 @@ impl Default for X86BackendOptions {
             slp: true,
             native_tick_loop: true,
             diagnostics: NativeDiagnostics::default(),
+            arena_offset: 0,
         }
     }
 }
@@ -72,6 +72,120 @@ pub struct RuntimeEventSite {
     pub arg_is_string: Vec<bool>,
 }
 
+/// Which arm of a branching statement a coverage point counts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CoverageArmKind {
+    /// The taken side of an `if`, or the reset side of an `if_reset`.
+    Then,
+    /// The `else` side of an `if` or `if_reset`, including an implicit one.
+    Else,
+    /// A `case`/`switch` arm, numbered from 0 in source order.
+    CaseArm(u32),
+    /// The `default` arm of a `case`/`switch`, including an implicit one.
+    Default,
+}
+
+/// Procedure kind that contains a coverage point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CoverageProcess {
+    Comb,
+    Ff,
+}
+
+/// One branch-coverage hit counter.
+///
+/// `counter` is a 64-bit two-state state object that is incremented every
+/// time the arm executes. Counters are not design variables: they have no
+/// name and are not visible through signal lookup.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoveragePoint<A> {
+    pub counter: A,
+    pub arm: CoverageArmKind,
+    pub process: CoverageProcess,
+    /// Source file of the branching statement.
+    pub file: String,
+    /// 1-based line of the branching statement.
+    pub line: u32,
+    /// 1-based column of the branching statement.
+    pub column: u32,
+    /// Fully elaborated module-instance scope of the counter.
+    pub scope: Option<String>,
+}
+
+impl<A> CoveragePoint<A> {
+    pub fn map_addr<B>(&self, f: impl FnOnce(&A) -> B) -> CoveragePoint<B> {
+        CoveragePoint {
+            counter: f(&self.counter),
+            arm: self.arm,
+            process: self.process,
+            file: self.file.clone(),
+            line: self.line,
+            column: self.column,
+            scope: self.scope.clone(),
+        }
+    }
+}
+
+/// One variant of an enum type, in declaration order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsmState {
+    pub name: String,
+    pub value: u64,
+}
+
+/// A register of enum type assigned in `always_ff`, tracked as a state
+/// machine by FSM coverage.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsmRegister<A> {
+    pub var: A,
+    /// Variable name within its module.
+    pub name: String,
+    /// Name of the enum type.
+    pub type_name: String,
+    pub states: Vec<FsmState>,
+    /// Fully elaborated module-instance scope of the register.
+    pub scope: Option<String>,
+}
+
+impl<A> FsmRegister<A> {
+    pub fn map_addr<B>(&self, f: impl FnOnce(&A) -> B) -> FsmRegister<B> {
+        FsmRegister {
+            var: f(&self.var),
+            name: self.name.clone(),
+            type_name: self.type_name.clone(),
+            states: self.states.clone(),
+            scope: self.scope.clone(),
+        }
+    }
+}
+
+/// A `$writememh` / `$writememb` call in a `final` block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryDump<A> {
+    pub memory: A,
+    /// File path as written in the source. Relative paths are resolved
+    /// against the working directory when the dump runs.
+    pub path: String,
+    /// `$writememb` rather than `$writememh`.
+    pub binary: bool,
+    /// First element written; defaults to 0.
+    pub start: Option<usize>,
+    /// Last element written, inclusive; defaults to the last element.
+    pub end: Option<usize>,
+}
+
+impl<A> MemoryDump<A> {
+    pub fn map_addr<B>(&self, f: impl FnOnce(&A) -> B) -> MemoryDump<B> {
+        MemoryDump {
+            memory: f(&self.memory),
+            path: self.path.clone(),
+            binary: self.binary,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
 /// Runtime activation recipe for one combinational event site.
 ///
 /// Expression trees used to emit the event have already been lowered into
@@ -127,6 +241,15 @@ pub struct RuntimeSchema<A> {
     /// Bit ranges written by RTL execution units. External component outputs
     /// may not overlap these ranges because that would create multiple drivers.
     pub rtl_writes: HashSet<VarAtomBase<A>>,
+    /// Branch-coverage counters, empty unless coverage was requested.
+    pub coverage_points: Vec<CoveragePoint<A>>,
+    /// Enum-typed flip-flop registers sampled by FSM coverage.
+    pub fsm_registers: Vec<FsmRegister<A>>,
+    /// Memory dumps run when the simulation finishes.
+    pub final_memory_dumps: Vec<MemoryDump<A>>,
+    /// State written by flip-flop units, i.e. the registers an initial-state
+    /// policy fills.
+    pub registers: HashSet<A>,
 }
 
 impl<A> Default for RuntimeSchema<A> {
@@ -137,6 +260,10 @@ impl<A> Default for RuntimeSchema<A> {
             comb_observers: Vec::new(),
             testbench_read_roots: HashSet::default(),
             rtl_writes: HashSet::default(),
+            coverage_points: Vec::new(),
+            fsm_registers: Vec::new(),
+            final_memory_dumps: Vec::new(),
+            registers: HashSet::default(),
         }
     }
 }
@@ -1186,6 +1186,9 @@ pub fn lower_frontend_artifact(
         comb_boundaries: HashMap::default(),
         arena,
         reset_clock_map,
+        coverage_points: Vec::new(),
+        fsm_registers: Vec::new(),
+        final_memory_dumps: Vec::new(),
     };
     let symbolic = SymbolicRtl {
         modules: [(module_id, sim_module.clone())].into_iter().collect(),
@@ -1,8 +1,8 @@
 use std::{collections::BTreeSet, fmt};
 
 use celox_design::{
-    InitialStateValue, ModuleId, RegionedAbsoluteAddrBase, RegionedVarAddrBase, RuntimeErrorInfo,
-    RuntimeEventSite, TriggerSet, VariableMetadata,
+    CoveragePoint, FsmRegister, InitialStateValue, MemoryDump, ModuleId, RegionedAbsoluteAddrBase,
+    RegionedVarAddrBase, RuntimeErrorInfo, RuntimeEventSite, TriggerSet, VariableMetadata,
 };
 use celox_sir::ExecutionUnit;
 use celox_slt::{CombObserver, FfAccessSummary, GlueBlockBase, LogicPath, SLTNodeArena};
@@ -44,6 +44,10 @@ pub struct SimModule {
     pub comb_boundaries: HashMap<SourceVarId, BTreeSet<usize>>,
     pub arena: SLTNodeArena<SourceVarId>,
     pub reset_clock_map: HashMap<SourceVarId, SourceVarId>,
+    /// Branch-coverage points whose counters are numbered past `variables`.
+    pub coverage_points: Vec<CoveragePoint<SourceVarId>>,
+    pub fsm_registers: Vec<FsmRegister<SourceVarId>>,
+    pub final_memory_dumps: Vec<MemoryDump<SourceVarId>>,
 }
 
 impl fmt::Debug for SimModule {
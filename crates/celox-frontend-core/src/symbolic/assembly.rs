@@ -9,10 +9,11 @@ use crate::{
     SourceLocation, SourceVarId, VariableInfo, flattening,
 };
 use celox_design::{
-    BitAccess, DomainKind, ElaboratedDesign, EventTopology, InitialStateValue, InstanceId,
-    ModuleId, RegionedAbsoluteAddrBase, RegionedStateAddr, RuntimeCombObserver, RuntimeErrorInfo,
-    RuntimeEventKind, RuntimeEventSite, RuntimeSchema, STABLE_REGION, StateAddr, StateObjectId,
-    TriggerSet, VarAtomBase, VariableMetadata,
+    BitAccess, CoveragePoint, DomainKind, ElaboratedDesign, EventTopology, FsmRegister,
+    InitialStateValue, InstanceId, MemoryDump, ModuleId, PortTypeKind, RegionedAbsoluteAddrBase,
+    RegionedStateAddr, RuntimeCombObserver, RuntimeErrorInfo, RuntimeEventKind, RuntimeEventSite,
+    RuntimeSchema, STABLE_REGION, StateAddr, StateObjectId, TriggerSet, VarAtomBase,
+    VariableMetadata,
 };
 use celox_sir::{BasicBlock, ExecutionUnit, SIRInstruction, SIRTerminator, SirProgram};
 use celox_slt::{
@@ -288,6 +289,7 @@ pub fn schedule_symbolic_rtl(
         usize,
     )],
     four_state: bool,
+    comb_partitions: usize,
     trace_opts: &FrontendTraceOptions,
     mut trace: Option<&mut FrontendTrace>,
 ) -> Result<ScheduledRtlOutput, ParserError> {
@@ -579,6 +581,7 @@ pub fn schedule_symbolic_rtl(
         &var_widths,
         &unpacked_element_widths,
         next_runtime_error_code,
+        comb_partitions,
     ) {
         Ok(schedule) => schedule,
         Err(error) => {
@@ -745,7 +748,7 @@ pub fn schedule_symbolic_rtl(
                 })
         })
         .collect();
-    let state_objects: HashMap<AbsoluteAddr, VariableMetadata> = instance_modules
+    let mut state_objects: HashMap<AbsoluteAddr, VariableMetadata> = instance_modules
         .iter()
         .flat_map(|(&instance_id, module_id)| {
             modules[module_id]
@@ -762,6 +765,57 @@ pub fn schedule_symbolic_rtl(
                 })
         })
         .collect();
+    // Branch-coverage counters are hidden stable objects: they have no
+    // source variable, so lookups, dumps and toggle coverage never see them.
+    let mut coverage_points: Vec<CoveragePoint<AbsoluteAddr>> = Vec::new();
+    let mut fsm_registers: Vec<FsmRegister<AbsoluteAddr>> = Vec::new();
+    let mut final_memory_dumps: Vec<MemoryDump<AbsoluteAddr>> = Vec::new();
+    for (path, &instance_id) in &expanded {
+        let module = &modules[&instance_modules[&instance_id]];
+        final_memory_dumps.extend(module.final_memory_dumps.iter().map(|dump| {
+            dump.map_addr(|memory| AbsoluteAddr {
+                instance_id,
+                var_id: *memory,
+            })
+        }));
+        if module.coverage_points.is_empty() && module.fsm_registers.is_empty() {
+            continue;
+        }
+        let scope =
+            elaborated_scope_name(&module_names[&root_id], path, &expanded, &indexed_instances);
+        for register in &module.fsm_registers {
+            let mut register = register.map_addr(|var| AbsoluteAddr {
+                instance_id,
+                var_id: *var,
+            });
+            register.scope = Some(scope.clone());
+            fsm_registers.push(register);
+        }
+        for point in &module.coverage_points {
+            let counter = AbsoluteAddr {
+                instance_id,
+                var_id: point.counter,
+            };
+            state_objects.insert(
+                counter,
+                VariableMetadata {
+                    width: 64,
+                    is_4state: false,
+                    kind: DomainKind::Other,
+                    type_kind: PortTypeKind::Bit,
+                    array_dims: Vec::new(),
+                },
+            );
+            let mut point = point.map_addr(|_| counter);
+            point.scope = Some(scope.clone());
+            coverage_points.push(point);
+        }
+    }
+    coverage_points.sort_by(|a, b| {
+        (&a.scope, &a.file, a.line, a.column).cmp(&(&b.scope, &b.file, b.line, b.column))
+    });
+    fsm_registers.sort_by(|a, b| (&a.scope, &a.name).cmp(&(&b.scope, &b.name)));
+    final_memory_dumps.sort_by_key(|dump| dump.memory);
     let runtime_comb_observers: Vec<RuntimeCombObserver<AbsoluteAddr>> = comb_observers
         .iter()
         .map(|observer| RuntimeCombObserver {
@@ -913,6 +967,19 @@ pub fn schedule_symbolic_rtl(
         }
     }
 
+    let coverage_points = coverage_points
+        .into_iter()
+        .map(|point| point.map_addr(|counter| project(*counter)))
+        .collect();
+    let fsm_registers = fsm_registers
+        .into_iter()
+        .map(|register| register.map_addr(|var| project(*var)))
+        .collect();
+    let final_memory_dumps = final_memory_dumps
+        .into_iter()
+        .map(|dump| dump.map_addr(|memory| project(*memory)))
+        .collect();
+
     let state_to_source = source_to_state
         .iter()
         .map(|(source, state)| (*state, *source))
@@ -942,6 +1009,10 @@ pub fn schedule_symbolic_rtl(
             comb_observers,
             testbench_read_roots: Default::default(),
             rtl_writes,
+            coverage_points,
+            fsm_registers,
+            final_memory_dumps,
+            registers: Default::default(),
         },
     };
 
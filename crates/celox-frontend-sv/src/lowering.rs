@@ -612,6 +612,7 @@ pub fn schedule_sources(
         usize,
     )],
     four_state: bool,
+    comb_partitions: usize,
     trace_options: &FrontendTraceOptions,
     trace: Option<&mut FrontendTrace>,
 ) -> Result<ScheduledRtlOutput, FrontendError> {
@@ -721,6 +722,7 @@ pub fn schedule_sources(
         ignored_loops,
         true_loops,
         four_state,
+        comb_partitions,
         trace_options,
         trace,
     )
@@ -986,6 +988,9 @@ fn lower_module_with_overrides(
             comb_boundaries: HashMap::default(),
             arena: SLTNodeArena::new(),
             reset_clock_map,
+            coverage_points: Vec::new(),
+            fsm_registers: Vec::new(),
+            final_memory_dumps: Vec::new(),
         },
         variables,
         port_order,
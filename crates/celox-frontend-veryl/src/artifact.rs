@@ -1,8 +1,9 @@
 use std::{collections::BTreeSet, fmt};
 
 use celox_design::{
-    DomainKind, InitialStateValue, PortTypeKind, RegionedVarAddrBase, RuntimeErrorInfo,
-    RuntimeEventSite, TriggerSet, VarAtomBase, VariableMetadata,
+    CoveragePoint, DomainKind, FsmRegister, InitialStateValue, MemoryDump, PortTypeKind,
+    RegionedVarAddrBase, RuntimeErrorInfo, RuntimeEventSite, TriggerSet, VarAtomBase,
+    VariableMetadata,
 };
 use celox_sir::{BasicBlock, ExecutionUnit};
 use celox_slt::{
@@ -50,6 +51,12 @@ pub struct VerylSimModule {
     pub store: SymbolicStore<VarId, NodeId>,
     /// Maps reset VarId to clock VarId, derived from FF declarations.
     pub reset_clock_map: HashMap<VarId, VarId>,
+    /// Branch-coverage points, empty unless `BuildConfig::branch_coverage`.
+    pub coverage_points: Vec<CoveragePoint<VarId>>,
+    /// Enum-typed registers assigned in `always_ff`.
+    pub fsm_registers: Vec<FsmRegister<VarId>>,
+    /// `$writememh` / `$writememb` calls in `final` blocks.
+    pub final_memory_dumps: Vec<MemoryDump<VarId>>,
 }
 
 impl fmt::Debug for VerylSimModule {
@@ -281,7 +288,7 @@ pub(crate) fn project_module_with_ids(
     module: &VerylSimModule,
     ir: &veryl_analyzer::ir::Module,
     config: &BuildConfig,
-    ids: HashMap<VarId, SourceVarId>,
+    mut ids: HashMap<VarId, SourceVarId>,
     child_ids: &HashMap<celox_design::ModuleId, HashMap<VarId, SourceVarId>>,
 ) -> Result<
     (
@@ -292,6 +299,18 @@ pub(crate) fn project_module_with_ids(
 > {
     let mut source_variables = ir.variables.iter().collect::<Vec<_>>();
     source_variables.sort_unstable_by_key(|(id, _)| **id);
+    // Counters follow the real variables so each instance relocates them
+    // together with its own state.
+    for (index, point) in module.coverage_points.iter().enumerate() {
+        let source_id = u32::try_from(source_variables.len() + index).map_err(|_| {
+            ParserError::illegal_context(
+                "frontend source identity projection",
+                "module has more than u32::MAX variables",
+                None,
+            )
+        })?;
+        ids.insert(point.counter, SourceVarId(source_id));
+    }
 
     let variables = source_variables
         .into_iter()
@@ -460,6 +479,21 @@ pub(crate) fn project_module_with_ids(
                 .iter()
                 .map(|(reset, clock)| (ids[reset], ids[clock]))
                 .collect(),
+            coverage_points: module
+                .coverage_points
+                .iter()
+                .map(|point| point.map_addr(|counter| ids[counter]))
+                .collect(),
+            fsm_registers: module
+                .fsm_registers
+                .iter()
+                .map(|register| register.map_addr(|var| ids[var]))
+                .collect(),
+            final_memory_dumps: module
+                .final_memory_dumps
+                .iter()
+                .map(|dump| dump.map_addr(|memory| ids[memory]))
+                .collect(),
         },
         ids,
     ))
@@ -1,9 +1,54 @@
+use veryl_analyzer::ir::Module;
 use veryl_metadata::{ClockType, ResetType};
 
-#[derive(Debug, Clone, Copy)]
+/// How `if` and `case` statements treat a condition that is X or Z in
+/// four-state simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum XPropMode {
+    /// SystemVerilog LRM semantics: an unknown `if` condition takes the
+    /// `else` branch and an unknown `case` selector matches no item, so the
+    /// X never reaches the assigned variables.
+    #[default]
+    Lrm,
+    /// Both branches are evaluated and merged bit by bit: bits the branches
+    /// agree on keep their value, the rest become X.
+    TMerge,
+    /// Every bit assigned in either branch becomes X.
+    XMerge,
+}
+
+#[derive(Debug, Clone)]
 pub struct BuildConfig {
     pub clock_type: ClockType,
     pub reset_type: ResetType,
+    /// Insert a hit counter at the start of every `if`/`case` arm in
+    /// `always_comb` and `always_ff` blocks.
+    pub branch_coverage: bool,
+    /// Maximum number of independent combinational execution units emitted
+    /// per dependency level. `1` keeps the ordinary sequential schedule.
+    pub comb_partitions: usize,
+    /// X-propagation semantics of `if`/`case` conditions.
+    pub xprop: XPropMode,
+    /// Per-module overrides of `xprop`, keyed by module name.
+    pub module_xprop: Vec<(String, XPropMode)>,
+    /// Reuse modules lowered by earlier builds on the same thread when their
+    /// sources and configuration are unchanged.
+    pub module_cache: bool,
+}
+
+impl BuildConfig {
+    /// The X-propagation mode `module` is lowered with.
+    pub fn xprop_for(&self, module: &Module) -> XPropMode {
+        if self.module_xprop.is_empty() {
+            return self.xprop;
+        }
+        let name = veryl_parser::resource_table::get_str_value(module.name).unwrap_or_default();
+        self.module_xprop
+            .iter()
+            .rev()
+            .find(|(module, _)| *module == name)
+            .map_or(self.xprop, |(_, mode)| *mode)
+    }
 }
 
 impl Default for BuildConfig {
@@ -11,6 +56,11 @@ impl Default for BuildConfig {
         Self {
             clock_type: ClockType::PosEdge,
             reset_type: ResetType::AsyncLow,
+            branch_coverage: false,
+            comb_partitions: 1,
+            xprop: XPropMode::Lrm,
+            module_xprop: Vec::new(),
+            module_cache: true,
         }
     }
 }
@@ -20,6 +70,7 @@ impl From<&veryl_metadata::Build> for BuildConfig {
         Self {
             clock_type: build.clock_type,
             reset_type: build.reset_type,
+            ..Self::default()
         }
     }
 }
@@ -3,7 +3,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use veryl_analyzer::ir::{Component, Declaration, Module, VarKind};
 use veryl_analyzer::{symbol::SymbolKind, symbol_table};
 use veryl_parser::{
-    resource_table::{self, StrId},
+    resource_table::{self, PathId, StrId},
     text_table,
 };
 
@@ -11,6 +11,7 @@ use super::{
     artifact::{VerylSymbolicRtl, project_id_map, project_module_with_ids},
     loop_provenance::LoopProvenance,
     module::ModuleParser,
+    module_cache::{self, SourceIndex},
 };
 use crate::{
     BuildConfig, HashMap, HashSet, LoweringPhase, ParserError,
@@ -134,6 +135,8 @@ pub fn parse_ir_with_external_hierarchy<'a>(
 
     let mut worklist = vec![(root_id, *root_ir)];
     let mut inst_sequences = HashMap::default();
+    // Parameterized instances are elaborated with values from the parent.
+    let mut elaborating_parents = HashMap::default();
     let mut validated_external = HashSet::default();
     let mut index = 0;
     while index < worklist.len() {
@@ -178,6 +181,7 @@ pub fn parse_ir_with_external_hierarchy<'a>(
                                 resource_table::get_str_value(child_name).unwrap_or_default(),
                             );
                             module_ir.insert(child_id, child_module);
+                            elaborating_parents.insert(child_id, module_id);
                             worklist.push((child_id, child_module));
                             inst_ids.push(child_id);
                         } else {
@@ -224,6 +228,54 @@ pub fn parse_ir_with_external_hierarchy<'a>(
             (module_id, ir_module, inst_ids)
         })
         .collect::<Vec<_>>();
+
+    // Reuse modules lowered by an earlier build whose inputs are unchanged.
+    // External hierarchies carry their own ids, so they are never cached.
+    let mut source_index = symbols
+        .as_deref()
+        .filter(|_| config.module_cache && external_modules_by_global.is_empty())
+        .map(SourceIndex::collect);
+    if source_index.is_some() {
+        module_cache::begin_build();
+    }
+    let mut cached_modules = Vec::new();
+    let mut cache_keys = HashMap::default();
+    let tasks = if let Some(source_index) = &mut source_index {
+        let mut paths = HashMap::default();
+        let mut misses = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let (module_id, ir_module, inst_ids) = task;
+            let key = elaboration_paths(
+                module_id,
+                &module_ir,
+                &elaborating_parents,
+                source_index,
+                &mut paths,
+            )
+            .and_then(|paths| {
+                source_index.key(
+                    ir_module,
+                    &paths,
+                    inst_ids,
+                    config,
+                    &loop_provenance.candidates_for_module(ir_module),
+                )
+            });
+            match key {
+                Some(key) => match module_cache::get(&key) {
+                    Some(module) => cached_modules.push((module_id, module)),
+                    None => {
+                        cache_keys.insert(module_id, key);
+                        misses.push(task);
+                    }
+                },
+                None => misses.push(task),
+            }
+        }
+        misses
+    } else {
+        tasks
+    };
     #[cfg(not(target_arch = "wasm32"))]
     let worker_count = tasks
         .len()
@@ -285,6 +337,13 @@ pub fn parse_ir_with_external_hierarchy<'a>(
             Ok::<_, ParserError>(parsed_modules)
         })?
     };
+    for (module_id, module) in &parsed_modules {
+        if let Some(key) = cache_keys.remove(module_id) {
+            module_cache::insert(key, module.clone());
+        }
+    }
+    let mut parsed_modules = parsed_modules;
+    parsed_modules.extend(cached_modules);
     let mut source_id_maps = module_ir
         .iter()
         .map(|(&module_id, module)| Ok((module_id, project_id_map(module)?)))
@@ -341,6 +400,37 @@ pub fn parse_ir_with_external_hierarchy<'a>(
     })
 }
 
+/// Files `module_id`'s elaboration depends on, memoized in `paths`. A
+/// parameterized instance also depends on everything its parent does.
+fn elaboration_paths(
+    module_id: ModuleId,
+    module_ir: &HashMap<ModuleId, &Module>,
+    elaborating_parents: &HashMap<ModuleId, ModuleId>,
+    source_index: &SourceIndex,
+    paths: &mut HashMap<ModuleId, Option<Vec<PathId>>>,
+) -> Option<Vec<PathId>> {
+    if let Some(known) = paths.get(&module_id) {
+        return known.clone();
+    }
+    let mut result = source_index.module_paths(module_ir[&module_id]);
+    if let Some(&parent) = elaborating_parents.get(&module_id) {
+        result = result
+            .zip(elaboration_paths(
+                parent,
+                module_ir,
+                elaborating_parents,
+                source_index,
+                paths,
+            ))
+            .map(|(mut own, inherited)| {
+                own.extend(inherited);
+                own
+            });
+    }
+    paths.insert(module_id, result.clone());
+    result
+}
+
 fn validate_external_module_graph(
     module_id: ModuleId,
     external: &ExternalHierarchy,
@@ -12,6 +12,7 @@ pub mod hierarchy;
 pub mod loop_provenance;
 pub(crate) mod lowering;
 pub mod module;
+pub mod module_cache;
 mod schedule;
 mod source;
 mod testbench;
@@ -26,7 +27,7 @@ pub(crate) use celox_frontend_core::{
     FrontendLookup, FrontendTrace, FrontendTraceOptions, FusedSirOptimizationHints, InstancePath,
     ScheduledRtl, ScheduledRtlOutput, SourceAddr, SourceVarId, VariableInfo, VariableKind,
 };
-pub use config::BuildConfig;
+pub use config::{BuildConfig, XPropMode};
 pub use dynamic_for_check::{check_dynamic_for_bounds, check_elaborated_dynamic_for_bounds};
 pub use error::{FrontendDiagnostic, LoweringPhase, ParserError, SourceLocation};
 pub use hierarchy::{parse_ir, parse_ir_with_external_hierarchy, parse_ir_with_loop_provenance};
@@ -40,7 +41,9 @@ pub use source::{
 };
 pub use testbench::{collect_testbench_observability, compile_semantic_testbench};
 
-pub(crate) use lowering::{bitaccess, bitslicer, case, context_width, ff, logic_tree, registry};
+pub(crate) use lowering::{
+    bitaccess, bitslicer, case, context_width, coverage, ff, fsm, logic_tree, memory_task, registry,
+};
 pub(crate) use source::{function_call_arg, function_call_has_arg};
 
 use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
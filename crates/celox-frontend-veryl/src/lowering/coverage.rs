@@ -0,0 +1,134 @@
+//! Branch-coverage points of one module.
+//!
+//! Every arm of an `if`, `if_reset` and `case` in an `always_comb` or
+//! `always_ff` block gets one 64-bit hit counter. Counters are addressed
+//! through synthetic [`VarId`]s which the source-identity projection maps
+//! past the module's real variables, so they relocate per instance exactly
+//! like ordinary state.
+
+use celox_design::{CoverageArmKind, CoveragePoint, CoverageProcess};
+use celox_sir::{SIRBuilder, SIRInstruction, SIROffset, SIRValue};
+use veryl_analyzer::ir::{Declaration, Module, Statement, VarId};
+use veryl_parser::{resource_table::TokenId, token_range::TokenRange};
+
+use crate::HashMap;
+
+/// Width of every hit counter.
+pub(crate) const COVERAGE_COUNTER_WIDTH: usize = 64;
+
+/// Synthetic counter IDs count down from just below `VarId::SYNTHETIC`.
+const COUNTER_ID_BASE: u32 = u32::MAX - 1;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CoverageTable {
+    points: Vec<CoveragePoint<VarId>>,
+    counters: HashMap<(TokenId, CoverageArmKind), VarId>,
+}
+
+impl CoverageTable {
+    /// Collect the points of `module` in declaration order.
+    pub(crate) fn collect(module: &Module) -> Self {
+        let mut table = Self::default();
+        for declaration in &module.declarations {
+            match declaration {
+                Declaration::Comb(comb) => {
+                    table.collect_statements(&comb.statements, CoverageProcess::Comb)
+                }
+                Declaration::Ff(ff) => {
+                    table.collect_statements(&ff.statements, CoverageProcess::Ff)
+                }
+                _ => {}
+            }
+        }
+        table
+    }
+
+    pub(crate) fn points(&self) -> &[CoveragePoint<VarId>] {
+        &self.points
+    }
+
+    /// Counter of `arm` of the branching statement spanning `token`.
+    pub(crate) fn counter(&self, token: &TokenRange, arm: CoverageArmKind) -> Option<VarId> {
+        self.counters.get(&(token.beg.id, arm)).copied()
+    }
+
+    fn collect_statements(&mut self, statements: &[Statement], process: CoverageProcess) {
+        for statement in statements {
+            match statement {
+                Statement::If(statement) => {
+                    self.add(&statement.token, CoverageArmKind::Then, process);
+                    self.collect_statements(&statement.true_side, process);
+                    self.add(&statement.token, CoverageArmKind::Else, process);
+                    self.collect_statements(&statement.false_side, process);
+                }
+                Statement::IfReset(statement) => {
+                    self.add(&statement.token, CoverageArmKind::Then, process);
+                    self.collect_statements(&statement.true_side, process);
+                    self.add(&statement.token, CoverageArmKind::Else, process);
+                    self.collect_statements(&statement.false_side, process);
+                }
+                Statement::Case(statement) => {
+                    for (index, arm) in statement.arms.iter().enumerate() {
+                        self.add(
+                            &statement.token,
+                            CoverageArmKind::CaseArm(index as u32),
+                            process,
+                        );
+                        self.collect_statements(&arm.body, process);
+                    }
+                    self.add(&statement.token, CoverageArmKind::Default, process);
+                    self.collect_statements(&statement.default, process);
+                }
+                Statement::For(statement) => self.collect_statements(&statement.body, process),
+                _ => {}
+            }
+        }
+    }
+
+    fn add(&mut self, token: &TokenRange, arm: CoverageArmKind, process: CoverageProcess) {
+        let key = (token.beg.id, arm);
+        if self.counters.contains_key(&key) {
+            return;
+        }
+        let counter = VarId::from_raw(COUNTER_ID_BASE - self.points.len() as u32);
+        self.counters.insert(key, counter);
+        self.points.push(CoveragePoint {
+            counter,
+            arm,
+            process,
+            file: token.beg.source.to_string(),
+            line: token.beg.line,
+            column: token.beg.column,
+            scope: None,
+        });
+    }
+}
+
+/// Emit `counter += 1` at the current position of `builder`. `counter` is
+/// asked once for the load and once for the store address.
+pub(crate) fn emit_counter_increment<A>(builder: &mut SIRBuilder<A>, counter: impl Fn() -> A) {
+    let count = builder.alloc_bit(COVERAGE_COUNTER_WIDTH, false);
+    builder.emit(SIRInstruction::Load(
+        count,
+        counter(),
+        SIROffset::Static(0),
+        COVERAGE_COUNTER_WIDTH,
+    ));
+    let one = builder.alloc_bit(COVERAGE_COUNTER_WIDTH, false);
+    builder.emit(SIRInstruction::Imm(one, SIRValue::new(1u64)));
+    let next = builder.alloc_bit(COVERAGE_COUNTER_WIDTH, false);
+    builder.emit(SIRInstruction::Binary(
+        next,
+        count,
+        celox_design::BinaryOp::Add,
+        one,
+    ));
+    builder.emit(SIRInstruction::Store(
+        counter(),
+        SIROffset::Static(0),
+        COVERAGE_COUNTER_WIDTH,
+        next,
+        Vec::new(),
+        Vec::new(),
+    ));
+}
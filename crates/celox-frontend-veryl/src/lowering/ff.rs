@@ -1,15 +1,16 @@
 use std::collections::VecDeque;
 
 use crate::{
-    BuildConfig, HashMap, HashSet, LoweringPhase, ParserError, RegionedVarAddr,
+    BuildConfig, HashMap, HashSet, LoweringPhase, ParserError, RegionedVarAddr, XPropMode,
     bitaccess::{celox_value_from_comptime_in_context, eval_constexpr},
     case::case_arm_condition_expr,
+    coverage::{CoverageTable, emit_counter_increment},
     resolve_total_width,
 };
 use bit_set::BitSet;
 use celox_design::{
-    BinaryOp, RuntimeErrorInfo, RuntimeEventKind, RuntimeEventSite, TriggerSet, UnaryOp,
-    VarAtomBase, WORKING_REGION,
+    BinaryOp, CoverageArmKind, RuntimeErrorInfo, RuntimeEventKind, RuntimeEventSite, STABLE_REGION,
+    TriggerSet, UnaryOp, VarAtomBase, WORKING_REGION,
 };
 use celox_sir::{
     BlockId, RegisterId, RegisterType, SIRBuilder, SIRInstruction, SIROffset, SIRTerminator,
@@ -206,7 +207,7 @@ mod function_state_coercion_tests {
             })
             .unwrap();
 
-        let parser = FfParser::new(&module, BuildConfig::default());
+        let parser = FfParser::new(&module, &BuildConfig::default());
         let coerced = parser
             .coerce_function_state_assignment(assignment.expr.clone(), &assignment.dst[0])
             .unwrap();
@@ -302,7 +303,7 @@ mod signed_div_rem_tests {
                 _ => None,
             })
             .collect::<Vec<_>>();
-        let mut parser = FfParser::new(&module, BuildConfig::default());
+        let mut parser = FfParser::new(&module, &BuildConfig::default());
         let mut builder = SIRBuilder::new();
         parser.parse_ff_group(&declarations, &mut builder).unwrap();
         let execution_unit = builder.flush_eu().unwrap();
@@ -415,9 +416,23 @@ pub struct FfParser<'a> {
     next_runtime_error_code: i64,
     runtime_error_code_map: Option<HashMap<i64, i64>>,
     runtime_event_site_base: u32,
+    coverage: CoverageTable,
+    xprop: XPropMode,
+    // Writes made by the arms of the `if`/`case` statements currently lowered
+    // with X-propagation, innermost statement last.
+    xprop_writes: Vec<Vec<XPropWrite>>,
     config: BuildConfig,
 }
 
+/// A store made inside an X-propagating branch. `access` is `None` for a
+/// dynamic store, which may write anywhere in the variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct XPropWrite {
+    var_id: VarId,
+    region: u32,
+    access: Option<celox_design::BitAccess>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ControlFlow {
     Continue,
@@ -425,7 +440,7 @@ enum ControlFlow {
 }
 
 impl<'a> FfParser<'a> {
-    pub fn new(module: &'a Module, config: BuildConfig) -> Self {
+    pub fn new(module: &'a Module, config: &BuildConfig) -> Self {
         let local_working_vars = module
             .variables
             .iter()
@@ -459,7 +474,14 @@ impl<'a> FfParser<'a> {
             next_runtime_error_code: 2000,
             runtime_error_code_map: None,
             runtime_event_site_base: 0,
-            config,
+            coverage: if config.branch_coverage {
+                CoverageTable::collect(module)
+            } else {
+                CoverageTable::default()
+            },
+            xprop: config.xprop_for(module),
+            xprop_writes: Vec::new(),
+            config: config.clone(),
         }
     }
 
@@ -496,6 +518,19 @@ impl<'a> FfParser<'a> {
         &self.runtime_event_sites
     }
 
+    /// Count one execution of `arm` of the branching statement at `token`.
+    fn emit_coverage_hit<A>(
+        &self,
+        token: &TokenRange,
+        arm: CoverageArmKind,
+        convert: &impl Fn(VarId, u32) -> A,
+        ir_builder: &mut SIRBuilder<A>,
+    ) {
+        if let Some(counter) = self.coverage.counter(token, arm) {
+            emit_counter_increment(ir_builder, || convert(counter, STABLE_REGION));
+        }
+    }
+
     fn runtime_error(&mut self, message: impl Into<String>, signals: Vec<VarId>) -> i64 {
         let local_code = self.next_runtime_error_code;
         self.next_runtime_error_code += 1;
@@ -974,11 +1009,12 @@ impl<'a> FfParser<'a> {
     ) -> Result<ControlFlow, ParserError> {
         // Constant folding: if condition is compile-time constant, inline the appropriate side
         if let Some(cond_is_true) = Self::get_constant_procedural_truth(&stmt.cond) {
-            let side = if cond_is_true {
-                &stmt.true_side
+            let (side, arm) = if cond_is_true {
+                (&stmt.true_side, CoverageArmKind::Then)
             } else {
-                &stmt.false_side
+                (&stmt.false_side, CoverageArmKind::Else)
             };
+            self.emit_coverage_hit(&stmt.token, arm, convert, ir_builder);
             return self.parse_statement_list(side, targets, domain, convert, sources, ir_builder);
         }
 
@@ -987,6 +1023,41 @@ impl<'a> FfParser<'a> {
             &stmt.cond, targets, domain, convert, sources, ir_builder, None,
         )?;
         let cond_reg = self.stack.pop_back().unwrap();
+        if let Some(truth) = self.xprop_truth(
+            cond_reg,
+            [stmt.true_side.as_slice(), stmt.false_side.as_slice()],
+            ir_builder,
+        ) {
+            return self.parse_xprop_branches(
+                truth,
+                targets,
+                convert,
+                sources,
+                ir_builder,
+                |this, targets, sources, ir_builder| {
+                    this.emit_coverage_hit(&stmt.token, CoverageArmKind::Then, convert, ir_builder);
+                    this.parse_statement_list(
+                        &stmt.true_side,
+                        targets,
+                        domain,
+                        convert,
+                        sources,
+                        ir_builder,
+                    )
+                },
+                |this, targets, sources, ir_builder| {
+                    this.emit_coverage_hit(&stmt.token, CoverageArmKind::Else, convert, ir_builder);
+                    this.parse_statement_list(
+                        &stmt.false_side,
+                        targets,
+                        domain,
+                        convert,
+                        sources,
+                        ir_builder,
+                    )
+                },
+            );
+        }
         let cond_reg = self.lower_procedural_condition(cond_reg, ir_builder);
 
         let then_bb = ir_builder.new_block();
@@ -1007,6 +1078,7 @@ impl<'a> FfParser<'a> {
 
         // 3. Then Path
         ir_builder.switch_to_block(then_bb);
+        self.emit_coverage_hit(&stmt.token, CoverageArmKind::Then, convert, ir_builder);
         let then_flow = self.parse_statement_list(
             &stmt.true_side,
             targets,
@@ -1025,6 +1097,7 @@ impl<'a> FfParser<'a> {
 
         // 4. Else Path
         ir_builder.switch_to_block(else_bb);
+        self.emit_coverage_hit(&stmt.token, CoverageArmKind::Else, convert, ir_builder);
         let else_flow = self.parse_statement_list(
             &stmt.false_side,
             targets,
@@ -1088,6 +1161,7 @@ impl<'a> FfParser<'a> {
         ir_builder: &mut SIRBuilder<A>,
     ) -> Result<ControlFlow, ParserError> {
         let Some(arm) = stmt.arms.get(arm_index) else {
+            self.emit_coverage_hit(&stmt.token, CoverageArmKind::Default, convert, ir_builder);
             return self.parse_statement_list(
                 &stmt.default,
                 targets,
@@ -1101,6 +1175,12 @@ impl<'a> FfParser<'a> {
         let cond = case_arm_condition_expr(&stmt.case_target, &arm.patterns);
         if let Some(cond_is_true) = Self::get_constant_procedural_truth(&cond) {
             return if cond_is_true {
+                self.emit_coverage_hit(
+                    &stmt.token,
+                    CoverageArmKind::CaseArm(arm_index as u32),
+                    convert,
+                    ir_builder,
+                );
                 self.parse_statement_list(&arm.body, targets, domain, convert, sources, ir_builder)
             } else {
                 self.parse_case_arm(
@@ -1117,6 +1197,41 @@ impl<'a> FfParser<'a> {
 
         self.parse_expression(&cond, targets, domain, convert, sources, ir_builder, None)?;
         let cond_reg = self.stack.pop_back().unwrap();
+        let remaining = stmt.arms[arm_index..]
+            .iter()
+            .map(|arm| arm.body.as_slice())
+            .chain([stmt.default.as_slice()]);
+        if let Some(truth) = self.xprop_truth(cond_reg, remaining, ir_builder) {
+            return self.parse_xprop_branches(
+                truth,
+                targets,
+                convert,
+                sources,
+                ir_builder,
+                |this, targets, sources, ir_builder| {
+                    this.emit_coverage_hit(
+                        &stmt.token,
+                        CoverageArmKind::CaseArm(arm_index as u32),
+                        convert,
+                        ir_builder,
+                    );
+                    this.parse_statement_list(
+                        &arm.body, targets, domain, convert, sources, ir_builder,
+                    )
+                },
+                |this, targets, sources, ir_builder| {
+                    this.parse_case_arm(
+                        stmt,
+                        arm_index + 1,
+                        targets,
+                        domain,
+                        convert,
+                        sources,
+                        ir_builder,
+                    )
+                },
+            );
+        }
         let cond_reg = self.lower_procedural_condition(cond_reg, ir_builder);
 
         let then_bb = ir_builder.new_block();
@@ -1133,6 +1248,12 @@ impl<'a> FfParser<'a> {
         });
 
         ir_builder.switch_to_block(then_bb);
+        self.emit_coverage_hit(
+            &stmt.token,
+            CoverageArmKind::CaseArm(arm_index as u32),
+            convert,
+            ir_builder,
+        );
         let then_flow =
             self.parse_statement_list(&arm.body, targets, domain, convert, sources, ir_builder)?;
         let then_defined = std::mem::replace(&mut self.defined_ranges, pre_case_defined.clone());
@@ -1180,6 +1301,317 @@ impl<'a> FfParser<'a> {
         }
     }
 
+    /// The X-preserving truth value of an `if`/`case` condition, when the
+    /// statement is lowered with X-propagation. Two-state conditions cannot be
+    /// unknown, and branches that `break` out of a loop cannot be merged, so
+    /// both keep the LRM lowering.
+    fn xprop_truth<'s, A>(
+        &self,
+        condition: RegisterId,
+        branches: impl IntoIterator<Item = &'s [Statement]>,
+        ir_builder: &mut SIRBuilder<A>,
+    ) -> Option<RegisterId> {
+        if self.xprop == XPropMode::Lrm
+            || matches!(ir_builder.register(&condition), RegisterType::Bit { .. })
+            || branches
+                .into_iter()
+                .any(|statements| statements.iter().any(Self::statement_breaks))
+        {
+            return None;
+        }
+        if ir_builder.register(&condition).width() == 1 {
+            return Some(condition);
+        }
+        let truth = ir_builder.alloc_logic(1);
+        ir_builder.emit(SIRInstruction::Unary(truth, UnaryOp::Or, condition));
+        Some(truth)
+    }
+
+    /// Whether `statement` can leave the enclosing loop.
+    fn statement_breaks(statement: &Statement) -> bool {
+        match statement {
+            Statement::Break => true,
+            Statement::If(stmt) => stmt
+                .true_side
+                .iter()
+                .chain(&stmt.false_side)
+                .any(Self::statement_breaks),
+            Statement::IfReset(stmt) => stmt
+                .true_side
+                .iter()
+                .chain(&stmt.false_side)
+                .any(Self::statement_breaks),
+            Statement::Case(stmt) => stmt
+                .arms
+                .iter()
+                .flat_map(|arm| &arm.body)
+                .chain(&stmt.default)
+                .any(Self::statement_breaks),
+            _ => false,
+        }
+    }
+
+    /// Lower a two-way branch whose condition may be unknown.
+    ///
+    /// A branch runs when its condition is known to select it or is unknown.
+    /// The pending values of everything either branch writes are loaded
+    /// before the statement, after the `then` branch, and after the `else`
+    /// branch. An unknown condition restores the pre-statement values before
+    /// the `else` branch runs, and the final values are the two branch
+    /// results merged under the condition. When the condition is known the
+    /// loads and stores reproduce the value the taken branch left.
+    #[allow(clippy::too_many_arguments)]
+    fn parse_xprop_branches<A, T, E>(
+        &mut self,
+        truth: RegisterId,
+        targets: &mut Vec<VarAtomBase<A>>,
+        convert: &impl Fn(VarId, u32) -> A,
+        sources: &mut Vec<VarAtomBase<A>>,
+        ir_builder: &mut SIRBuilder<A>,
+        then_side: T,
+        else_side: E,
+    ) -> Result<ControlFlow, ParserError>
+    where
+        T: FnOnce(
+            &mut Self,
+            &mut Vec<VarAtomBase<A>>,
+            &mut Vec<VarAtomBase<A>>,
+            &mut SIRBuilder<A>,
+        ) -> Result<ControlFlow, ParserError>,
+        E: FnOnce(
+            &mut Self,
+            &mut Vec<VarAtomBase<A>>,
+            &mut Vec<VarAtomBase<A>>,
+            &mut SIRBuilder<A>,
+        ) -> Result<ControlFlow, ParserError>,
+    {
+        let known_true = ir_builder.alloc_bit(1, false);
+        ir_builder.emit(SIRInstruction::Unary(
+            known_true,
+            UnaryOp::ToTwoState,
+            truth,
+        ));
+        let falsity = ir_builder.alloc_logic(1);
+        ir_builder.emit(SIRInstruction::Unary(falsity, UnaryOp::LogicNot, truth));
+        let known_false = ir_builder.alloc_bit(1, false);
+        ir_builder.emit(SIRInstruction::Unary(
+            known_false,
+            UnaryOp::ToTwoState,
+            falsity,
+        ));
+        let take_then = ir_builder.alloc_bit(1, false);
+        ir_builder.emit(SIRInstruction::Unary(
+            take_then,
+            UnaryOp::LogicNot,
+            known_false,
+        ));
+        let take_else = ir_builder.alloc_bit(1, false);
+        ir_builder.emit(SIRInstruction::Unary(
+            take_else,
+            UnaryOp::LogicNot,
+            known_true,
+        ));
+
+        let entry_bb = ir_builder.current_block();
+        let then_bb = ir_builder.new_block();
+        let mid_bb = ir_builder.new_block();
+        let else_bb = ir_builder.new_block();
+        let join_bb = ir_builder.new_block();
+        let entry_terminator = SIRTerminator::Branch {
+            cond: take_then,
+            true_block: (then_bb, vec![]),
+            false_block: (mid_bb, vec![]),
+        };
+        let mid_terminator = SIRTerminator::Branch {
+            cond: take_else,
+            true_block: (else_bb, vec![]),
+            false_block: (join_bb, vec![]),
+        };
+
+        let pre_defined = self.defined_ranges.clone();
+        let pre_dynamic = self.dynamic_defined_vars.clone();
+        self.xprop_writes.push(Vec::new());
+
+        ir_builder.seal_block(entry_terminator.clone());
+        ir_builder.switch_to_block(then_bb);
+        then_side(self, targets, sources, ir_builder)?;
+        let then_defined = std::mem::replace(&mut self.defined_ranges, pre_defined);
+        let then_dynamic = std::mem::replace(&mut self.dynamic_defined_vars, pre_dynamic);
+        ir_builder.seal_block(SIRTerminator::Jump(mid_bb, vec![]));
+
+        ir_builder.switch_to_block(else_bb);
+        else_side(self, targets, sources, ir_builder)?;
+        let else_defined = std::mem::take(&mut self.defined_ranges);
+        let else_dynamic = std::mem::take(&mut self.dynamic_defined_vars);
+        ir_builder.seal_block(SIRTerminator::Jump(join_bb, vec![]));
+        self.defined_ranges = self.intersect_defined_states(then_defined, else_defined);
+        self.dynamic_defined_vars = self.intersect_dynamic_vars(then_dynamic, else_dynamic);
+
+        let writes = self.take_xprop_writes();
+
+        // The write set is only known once both branches are lowered, so the
+        // snapshots are appended to the already sealed entry and middle blocks.
+        ir_builder.switch_to_block(entry_bb);
+        let before = self.load_xprop_writes(&writes, convert, ir_builder)?;
+        ir_builder.seal_block(entry_terminator);
+
+        ir_builder.switch_to_block(mid_bb);
+        let after_then = self.load_xprop_writes(&writes, convert, ir_builder)?;
+        for (write, (then_value, before_value)) in writes.iter().zip(after_then.iter().zip(&before))
+        {
+            let restored = ir_builder.alloc_reg(ir_builder.register(then_value).clone());
+            ir_builder.emit(SIRInstruction::Mux(
+                restored,
+                known_true,
+                *then_value,
+                *before_value,
+            ));
+            self.store_xprop_write(*write, restored, convert, ir_builder)?;
+        }
+        ir_builder.seal_block(mid_terminator);
+
+        ir_builder.switch_to_block(join_bb);
+        let after_else = self.load_xprop_writes(&writes, convert, ir_builder)?;
+        for (write, (then_value, else_value)) in
+            writes.iter().zip(after_then.iter().zip(&after_else))
+        {
+            let merged = self.emit_xprop_merge(truth, *then_value, *else_value, ir_builder);
+            self.store_xprop_write(*write, merged, convert, ir_builder)?;
+        }
+        Ok(ControlFlow::Continue)
+    }
+
+    /// Pop the innermost X-propagation write log, coalesced to one entry per
+    /// variable region, and pass it on to the enclosing statement's log.
+    fn take_xprop_writes(&mut self) -> Vec<XPropWrite> {
+        let writes = self.xprop_writes.pop().unwrap_or_default();
+        if let Some(outer) = self.xprop_writes.last_mut() {
+            outer.extend(writes.iter().copied());
+        }
+        let mut ranges = std::collections::BTreeMap::<(VarId, u32), Vec<_>>::new();
+        for write in writes {
+            ranges
+                .entry((write.var_id, write.region))
+                .or_default()
+                .push(write.access);
+        }
+        let mut coalesced = Vec::new();
+        for ((var_id, region), accesses) in ranges {
+            if accesses.iter().any(Option::is_none) {
+                coalesced.push(XPropWrite {
+                    var_id,
+                    region,
+                    access: None,
+                });
+                continue;
+            }
+            let mut accesses = accesses.into_iter().flatten().collect::<Vec<_>>();
+            accesses.sort_unstable_by_key(|access| (access.lsb, access.msb));
+            let mut merged = Vec::<celox_design::BitAccess>::new();
+            for access in accesses {
+                if let Some(previous) = merged.last_mut()
+                    && access.lsb <= previous.msb.saturating_add(1)
+                {
+                    previous.msb = previous.msb.max(access.msb);
+                } else {
+                    merged.push(access);
+                }
+            }
+            coalesced.extend(merged.into_iter().map(|access| XPropWrite {
+                var_id,
+                region,
+                access: Some(access),
+            }));
+        }
+        coalesced
+    }
+
+    /// The bit offset and width a logged write covers.
+    fn xprop_write_span(&self, write: XPropWrite) -> Result<(usize, usize), ParserError> {
+        Ok(match write.access {
+            Some(access) => (access.lsb, access.msb - access.lsb + 1),
+            None => (
+                0,
+                resolve_total_width(self.module, &self.module.variables[&write.var_id])?,
+            ),
+        })
+    }
+
+    fn load_xprop_writes<A>(
+        &self,
+        writes: &[XPropWrite],
+        convert: &impl Fn(VarId, u32) -> A,
+        ir_builder: &mut SIRBuilder<A>,
+    ) -> Result<Vec<RegisterId>, ParserError> {
+        writes
+            .iter()
+            .map(|write| {
+                let (lsb, width) = self.xprop_write_span(*write)?;
+                let value = if self.module.variables[&write.var_id].r#type.is_2state() {
+                    ir_builder.alloc_bit(width, false)
+                } else {
+                    ir_builder.alloc_logic(width)
+                };
+                ir_builder.emit(SIRInstruction::Load(
+                    value,
+                    convert(write.var_id, write.region),
+                    SIROffset::Static(lsb),
+                    width,
+                ));
+                Ok(value)
+            })
+            .collect()
+    }
+
+    fn store_xprop_write<A>(
+        &self,
+        write: XPropWrite,
+        value: RegisterId,
+        convert: &impl Fn(VarId, u32) -> A,
+        ir_builder: &mut SIRBuilder<A>,
+    ) -> Result<(), ParserError> {
+        let (lsb, width) = self.xprop_write_span(write)?;
+        ir_builder.emit(SIRInstruction::Store(
+            convert(write.var_id, write.region),
+            SIROffset::Static(lsb),
+            width,
+            value,
+            Vec::new(),
+            Vec::new(),
+        ));
+        Ok(())
+    }
+
+    /// Merge the results of the two branches under an unknown-capable
+    /// condition. A known condition selects one branch in every mode.
+    fn emit_xprop_merge<A>(
+        &self,
+        truth: RegisterId,
+        then_value: RegisterId,
+        else_value: RegisterId,
+        ir_builder: &mut SIRBuilder<A>,
+    ) -> RegisterId {
+        let ty = ir_builder.register(&then_value).clone();
+        let (then_value, else_value) = if self.xprop == XPropMode::XMerge {
+            // Pairing each branch with its complement turns every bit into a
+            // disagreement when the condition is unknown.
+            let not_then = ir_builder.alloc_reg(ty.clone());
+            ir_builder.emit(SIRInstruction::Unary(not_then, UnaryOp::BitNot, then_value));
+            let not_else = ir_builder.alloc_reg(ty.clone());
+            ir_builder.emit(SIRInstruction::Unary(not_else, UnaryOp::BitNot, else_value));
+            let then_or_x = ir_builder.alloc_reg(ty.clone());
+            ir_builder.emit(SIRInstruction::Mux(then_or_x, truth, then_value, not_then));
+            let else_or_x = ir_builder.alloc_reg(ty.clone());
+            ir_builder.emit(SIRInstruction::Mux(else_or_x, truth, not_else, else_value));
+            (then_or_x, else_or_x)
+        } else {
+            (then_value, else_value)
+        };
+        let merged = ir_builder.alloc_reg(ty);
+        ir_builder.emit(SIRInstruction::Mux(merged, truth, then_value, else_value));
+        merged
+    }
+
     /// Helper to take intersection of dynamic defined variables
     fn intersect_dynamic_vars(
         &self,
@@ -2240,6 +2672,7 @@ impl<'a> FfParser<'a> {
         let true_side: Vec<&Statement> = stmt.true_side.iter().collect();
         let false_side: Vec<&Statement> = stmt.false_side.iter().collect();
         self.parse_if_reset_internal(
+            &[&stmt.token],
             &true_side,
             &false_side,
             targets,
@@ -2252,6 +2685,7 @@ impl<'a> FfParser<'a> {
 
     fn parse_if_reset_internal<A>(
         &mut self,
+        tokens: &[&TokenRange],
         true_side: &[&Statement],
         false_side: &[&Statement],
         targets: &mut Vec<VarAtomBase<A>>,
@@ -2318,6 +2752,9 @@ impl<'a> FfParser<'a> {
 
         // 3. Then Path (Reset active)
         ir_builder.switch_to_block(then_bb);
+        for token in tokens {
+            self.emit_coverage_hit(token, CoverageArmKind::Then, convert, ir_builder);
+        }
         let then_flow =
             self.parse_statement_refs(true_side, targets, domain, convert, sources, ir_builder)?;
         let then_defined = std::mem::replace(&mut self.defined_ranges, pre_if_defined.clone());
@@ -2328,6 +2765,9 @@ impl<'a> FfParser<'a> {
 
         // 4. Else Path (Normal operation)
         ir_builder.switch_to_block(else_bb);
+        for token in tokens {
+            self.emit_coverage_hit(token, CoverageArmKind::Else, convert, ir_builder);
+        }
         let else_flow =
             self.parse_statement_refs(false_side, targets, domain, convert, sources, ir_builder)?;
         let else_defined = std::mem::take(&mut self.defined_ranges);
@@ -2414,6 +2854,7 @@ impl<'a> FfParser<'a> {
         let mut targets = Vec::new();
         let mut sources = Vec::new();
 
+        let mut if_reset_tokens = Vec::new();
         let mut all_true_sides = Vec::new();
         let mut all_false_sides = Vec::new();
         let mut other_statements = Vec::new();
@@ -2421,6 +2862,7 @@ impl<'a> FfParser<'a> {
         for decl in decls {
             for stmt in &decl.statements {
                 if let Statement::IfReset(if_reset) = stmt {
+                    if_reset_tokens.push(&if_reset.token);
                     all_true_sides.extend(if_reset.true_side.iter().collect::<Vec<_>>());
                     all_false_sides.extend(if_reset.false_side.iter().collect::<Vec<_>>());
                 } else {
@@ -2442,6 +2884,7 @@ impl<'a> FfParser<'a> {
 
         if !all_true_sides.is_empty() || !all_false_sides.is_empty() {
             self.parse_if_reset_internal(
+                &if_reset_tokens,
                 &all_true_sides,
                 &all_false_sides,
                 &mut targets,
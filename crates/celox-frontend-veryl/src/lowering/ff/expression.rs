@@ -1,4 +1,4 @@
-use super::{Domain, FfParser, FunctionArrayLiteralItemCache, FunctionArrayView};
+use super::{Domain, FfParser, FunctionArrayLiteralItemCache, FunctionArrayView, XPropWrite};
 use crate::context_width::{
     ValueContext, binary_semantics, cast_semantics, expression_signed, resolve_binary_op,
 };
@@ -2762,6 +2762,14 @@ impl<'a> FfParser<'a> {
             Vec::new(),
         ));
 
+        if let Some(writes) = self.xprop_writes.last_mut() {
+            writes.push(XPropWrite {
+                var_id: dst.id,
+                region: store_region,
+                access: is_static.then_some(access),
+            });
+        }
+
         // Use conservative range from eval_var_select for tracking (covers all possible bits).
         if is_static {
             let bits = self.defined_ranges.entry(dst.id).or_default();
@@ -4857,7 +4865,7 @@ module Top (
             .find_map(|(&id, variable)| (variable.path.to_string() == "in_flight").then_some(id))
             .unwrap();
 
-        let mut parser = FfParser::new(&module, BuildConfig::default());
+        let mut parser = FfParser::new(&module, &BuildConfig::default());
         let mut builder = SIRBuilder::new();
         let result = parser.parse_ff_group(&declarations, &mut builder).unwrap();
 
@@ -4922,7 +4930,7 @@ module Top (
             .unwrap();
         let direct_ranges = [(state, vec![BitAccess::new(8, 15)])].into_iter().collect();
 
-        let mut parser = FfParser::new(&module, BuildConfig::default())
+        let mut parser = FfParser::new(&module, &BuildConfig::default())
             .with_direct_write_ranges(direct_ranges, HashSet::default());
         let mut builder = SIRBuilder::new();
         parser.parse_ff_group(&declarations, &mut builder).unwrap();
@@ -5019,7 +5027,7 @@ module Top (
             .collect::<Vec<_>>();
         assert_eq!(effect_ids.len(), 3);
 
-        let mut parser = FfParser::new(&module, BuildConfig::default());
+        let mut parser = FfParser::new(&module, &BuildConfig::default());
         let mut builder = SIRBuilder::new();
         parser.parse_ff_group(&declarations, &mut builder).unwrap();
         builder.flush_eu().unwrap().verify();
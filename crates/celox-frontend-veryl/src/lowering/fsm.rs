@@ -0,0 +1,100 @@
+//! State-machine registers of one module.
+//!
+//! A variable of enum type that is assigned inside `always_ff` is treated as
+//! the state register of a state machine. Its variants are read from the
+//! analyzer's symbol table so that FSM coverage can report them by name.
+
+use celox_design::{FsmRegister, FsmState};
+use veryl_analyzer::{
+    ir::{Declaration, Module, Statement, TypeKind, VarId},
+    symbol::SymbolKind,
+    symbol_table,
+};
+
+use crate::HashSet;
+
+/// Collect the state registers of `module`, ordered by variable ID.
+pub(crate) fn collect_fsm_registers(module: &Module) -> Vec<FsmRegister<VarId>> {
+    let mut assigned = HashSet::default();
+    for declaration in &module.declarations {
+        if let Declaration::Ff(ff) = declaration {
+            collect_assigned(&ff.statements, &mut assigned);
+        }
+    }
+    let mut assigned = assigned.into_iter().collect::<Vec<_>>();
+    assigned.sort_unstable();
+    assigned
+        .into_iter()
+        .filter_map(|id| fsm_register(module, id))
+        .collect()
+}
+
+fn collect_assigned(statements: &[Statement], assigned: &mut HashSet<VarId>) {
+    for statement in statements {
+        match statement {
+            Statement::Assign(statement) => {
+                assigned.extend(statement.dst.iter().map(|dst| dst.id));
+            }
+            Statement::If(statement) => {
+                collect_assigned(&statement.true_side, assigned);
+                collect_assigned(&statement.false_side, assigned);
+            }
+            Statement::IfReset(statement) => {
+                collect_assigned(&statement.true_side, assigned);
+                collect_assigned(&statement.false_side, assigned);
+            }
+            Statement::Case(statement) => {
+                for arm in &statement.arms {
+                    collect_assigned(&arm.body, assigned);
+                }
+                collect_assigned(&statement.default, assigned);
+            }
+            Statement::For(statement) => collect_assigned(&statement.body, assigned),
+            _ => {}
+        }
+    }
+}
+
+/// Describe `id` as a state register if it is a scalar of enum type whose
+/// variants all have values that fit in 64 bits.
+fn fsm_register(module: &Module, id: VarId) -> Option<FsmRegister<VarId>> {
+    let variable = module.variables.get(&id)?;
+    if !variable.r#type.array.is_empty() {
+        return None;
+    }
+    let TypeKind::Enum(r#enum) = &variable.r#type.kind else {
+        return None;
+    };
+    let symbol = symbol_table::get(r#enum.id)?;
+    let SymbolKind::Enum(property) = &symbol.kind else {
+        return None;
+    };
+    let states = property
+        .members
+        .iter()
+        .map(|member| {
+            let member = symbol_table::get(*member)?;
+            let SymbolKind::EnumMember(property) = &member.kind else {
+                return None;
+            };
+            let value = u64::try_from(property.value.value()?).ok()?;
+            Some(FsmState {
+                name: member.token.to_string(),
+                value,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(FsmRegister {
+        var: id,
+        name: variable
+            .path
+            .0
+            .iter()
+            .map(|part| veryl_parser::resource_table::get_str_value(*part).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("."),
+        type_name: symbol.token.to_string(),
+        states,
+        scope: None,
+    })
+}
@@ -17,14 +17,15 @@ pub(crate) mod node_facts {
 pub use celox_slt::{LogicPath, LogicPathTarget};
 pub use state::{BoundaryMap, SymbolicStore};
 
-use std::{collections::BTreeSet, hash::Hash};
+use std::{cell::Cell, collections::BTreeSet, hash::Hash, rc::Rc};
 
 use crate::{
-    HashMap, HashSet, LoweringPhase, ParserError,
+    HashMap, HashSet, LoweringPhase, ParserError, XPropMode,
     bitaccess::{
         PartSelectGeometry, celox_value_from_comptime, eval_constexpr, eval_var_select,
         eval_var_select_with_geometry, select_geometry,
     },
+    coverage::{COVERAGE_COUNTER_WIDTH, CoverageTable},
     function_call_has_arg,
     loop_provenance::LoopRecoveryCandidate,
     resolve_total_width,
@@ -219,7 +220,29 @@ fn parse_comb(
     ),
     ParserError,
 > {
-    parse_comb_with_loop_recovery(module, decl, arena, &[], 0)
+    parse_comb_with_loop_recovery(module, decl, arena, &[], 0, None, XPropMode::Lrm)
+}
+
+thread_local! {
+    // The symbolic evaluator is a tree of free functions; the X-propagation
+    // mode of the process being lowered is scoped here instead of being
+    // threaded through every one of them.
+    static COMB_XPROP: Cell<XPropMode> = const { Cell::new(XPropMode::Lrm) };
+}
+
+/// Restores the previous X-propagation mode when a process finishes lowering.
+struct CombXPropScope(XPropMode);
+
+impl CombXPropScope {
+    fn enter(mode: XPropMode) -> Self {
+        Self(COMB_XPROP.with(|current| current.replace(mode)))
+    }
+}
+
+impl Drop for CombXPropScope {
+    fn drop(&mut self) {
+        COMB_XPROP.with(|current| current.set(self.0));
+    }
 }
 
 pub fn parse_comb_with_loop_recovery(
@@ -228,6 +251,8 @@ pub fn parse_comb_with_loop_recovery(
     arena: &mut SLTNodeArena<VarId>,
     loop_candidates: &[LoopRecoveryCandidate],
     capture_namespace: u32,
+    coverage: Option<&Rc<CoverageTable>>,
+    xprop: XPropMode,
 ) -> Result<
     (
         Vec<LogicPath<VarId>>,
@@ -238,6 +263,7 @@ pub fn parse_comb_with_loop_recovery(
     ),
     ParserError,
 > {
+    let _xprop = CombXPropScope::enter(xprop);
     let mut written_accesses = HashMap::default();
     collect_written_accesses(module, &decl.statements, &mut written_accesses)?;
 
@@ -261,8 +287,9 @@ pub fn parse_comb_with_loop_recovery(
         .collect();
 
     // 2. Symbolic Execution: Evaluate statements sequentially to update the symbolic state.
-    let effect_initial_store =
-        statements_contain_runtime_effect(module, &decl.statements).then(|| current_store.fork());
+    let effect_initial_store = (coverage.is_some()
+        || statements_contain_runtime_effect(module, &decl.statements))
+    .then(|| current_store.fork());
     let (final_store, boundaries) = recover_unrolled::eval_statements(
         module,
         current_store,
@@ -273,6 +300,9 @@ pub fn parse_comb_with_loop_recovery(
         None,
     )?;
     let mut effects = CombEffectCollector::with_capture_namespace(capture_namespace);
+    if let Some(coverage) = coverage {
+        effects = effects.with_coverage(coverage.clone());
+    }
     if let Some(effect_initial_store) = effect_initial_store {
         collect_comb_effects_statements(
             module,
@@ -339,6 +369,33 @@ pub fn parse_comb_with_loop_recovery(
             }
         }
     }
+    for probe in std::mem::take(&mut effects.coverage_probes) {
+        paths.push(LogicPath::<VarId> {
+            target: LogicPathTarget::Coverage(VarAtomBase::new(
+                probe.counter,
+                0,
+                COVERAGE_COUNTER_WIDTH - 1,
+            )),
+            previous_sources: probe
+                .sources
+                .iter()
+                .copied()
+                .filter(|source| {
+                    written_atoms.iter().any(|written| {
+                        written.id == source.id && written.access.overlaps(&source.access)
+                    })
+                })
+                .collect(),
+            sources: probe.sources,
+            address_sources: HashSet::default(),
+            local_inputs: Vec::new(),
+            order_before: HashSet::default(),
+            comb_capture_enable_sites: Vec::new(),
+            comb_capture_enable_always: false,
+            pre_lower_nodes: Vec::new(),
+            expr: probe.guard,
+        });
+    }
     let mut process_sensitivity = effects.sensitivity;
     for path in &paths {
         process_sensitivity.extend(path.sources.iter().copied());
@@ -414,6 +471,7 @@ fn dump_comb_path_stats_if_requested(
             LogicPathTarget::CombCaptureEvent { site_id, .. } => {
                 format!("capture_event({site_id})")
             }
+            LogicPathTarget::Coverage(counter) => format!("coverage({counter})"),
         };
         entries.push((
             stats.nodes,
@@ -1115,14 +1173,7 @@ fn merge_symbolic_versions(
                 sources.extend(t_sources);
                 sources.extend(e_sources);
 
-                Some((
-                    arena.alloc(SLTNode::Mux {
-                        cond: cond_expr,
-                        then_expr: t_expr,
-                        else_expr: e_expr,
-                    })?,
-                    sources,
-                ))
+                Some((conditional_mux(arena, cond_expr, t_expr, e_expr)?, sources))
             };
 
             merged_range_store.ranges.insert(
@@ -1137,6 +1188,54 @@ fn merge_symbolic_versions(
     Ok(merged_store)
 }
 
+/// Select between two branch results. Procedural conditions are lowered to
+/// `ToTwoState(truth)`, which is what makes an unknown condition take the
+/// else branch; X-propagation modes select on the unknown-capable truth
+/// value instead.
+fn conditional_mux(
+    arena: &mut SLTNodeArena<VarId>,
+    cond: NodeId,
+    then_expr: NodeId,
+    else_expr: NodeId,
+) -> Result<NodeId, SLTNodeFactsError> {
+    let mode = COMB_XPROP.with(Cell::get);
+    let truth = match arena.get(cond) {
+        SLTNode::Unary(UnaryOp::ToTwoState, truth) if mode != XPropMode::Lrm => *truth,
+        _ => {
+            return arena.alloc(SLTNode::Mux {
+                cond,
+                then_expr,
+                else_expr,
+            });
+        }
+    };
+    let (then_expr, else_expr) = if mode == XPropMode::XMerge {
+        // Pairing each branch with its complement turns every bit into a
+        // disagreement when the condition is unknown.
+        let not_then = arena.alloc(SLTNode::Unary(UnaryOp::BitNot, then_expr))?;
+        let not_else = arena.alloc(SLTNode::Unary(UnaryOp::BitNot, else_expr))?;
+        (
+            arena.alloc(SLTNode::Mux {
+                cond: truth,
+                then_expr,
+                else_expr: not_then,
+            })?,
+            arena.alloc(SLTNode::Mux {
+                cond: truth,
+                then_expr: not_else,
+                else_expr,
+            })?,
+        )
+    } else {
+        (then_expr, else_expr)
+    };
+    arena.alloc(SLTNode::Mux {
+        cond: truth,
+        then_expr,
+        else_expr,
+    })
+}
+
 fn apply_loop_continue_guard(
     module: &Module,
     state: LoopControlState,
@@ -1,5 +1,9 @@
+use std::rc::Rc;
+
 use super::state::FunctionLoopControlState;
 use super::*;
+use crate::coverage::CoverageTable;
+use celox_design::CoverageArmKind;
 
 pub(crate) fn subtract_written_sensitivity<A: Copy + Eq + std::hash::Hash>(
     atoms: impl IntoIterator<Item = VarAtomBase<A>>,
@@ -46,6 +50,15 @@ pub(crate) struct CombEffectCollector {
     loop_effects: Option<Vec<SLTForEffect>>,
     capture_namespace: u32,
     next_capture_id: u32,
+    coverage: Option<Rc<CoverageTable>>,
+    pub(crate) coverage_probes: Vec<CoverageProbe>,
+}
+
+/// Guard under which one branch-coverage counter is incremented.
+pub(crate) struct CoverageProbe {
+    pub(crate) counter: VarId,
+    pub(crate) guard: NodeId,
+    pub(crate) sources: HashSet<VarAtomBase<VarId>>,
 }
 
 impl CombEffectCollector {
@@ -56,6 +69,33 @@ impl CombEffectCollector {
         }
     }
 
+    /// Also record a [`CoverageProbe`] for every branch arm of `coverage`.
+    pub(crate) fn with_coverage(mut self, coverage: Rc<CoverageTable>) -> Self {
+        self.coverage = Some(coverage);
+        self
+    }
+
+    fn record_coverage_hit(
+        &mut self,
+        token: &veryl_parser::token_range::TokenRange,
+        arm: CoverageArmKind,
+    ) {
+        // Guards inside dynamic loops depend on the symbolic loop variable.
+        if self.loop_effects.is_some() {
+            return;
+        }
+        let (Some(coverage), Some(guard)) = (&self.coverage, self.active_guard) else {
+            return;
+        };
+        if let Some(counter) = coverage.counter(token, arm) {
+            self.coverage_probes.push(CoverageProbe {
+                counter,
+                guard,
+                sources: self.active_guard_sources.clone(),
+            });
+        }
+    }
+
     fn next_capture_key(
         &mut self,
         token: &veryl_parser::token_range::TokenRange,
@@ -2630,6 +2670,7 @@ pub(super) fn collect_comb_effects_statements(
                 true_sources.extend(sources.iter().copied());
                 collector.active_guard = Some(true_guard);
                 collector.active_guard_sources = true_sources;
+                collector.record_coverage_hit(&if_stmt.token, CoverageArmKind::Then);
                 let side_store = collect_comb_effects_statements(
                     module,
                     store.fork(),
@@ -2647,6 +2688,7 @@ pub(super) fn collect_comb_effects_statements(
                 false_sources.extend(sources.iter().copied());
                 collector.active_guard = Some(false_guard);
                 collector.active_guard_sources = false_sources;
+                collector.record_coverage_hit(&if_stmt.token, CoverageArmKind::Else);
                 let else_store = collect_comb_effects_statements(
                     module,
                     store,
@@ -2695,6 +2737,7 @@ fn collect_comb_effects_case(
         collector: &mut CombEffectCollector,
     ) -> Result<SymbolicStore<VarId>, ParserError> {
         let Some(arm) = case_stmt.arms.get(arm_index) else {
+            collector.record_coverage_hit(&case_stmt.token, CoverageArmKind::Default);
             return collect_comb_effects_statements(
                 module,
                 store,
@@ -2735,6 +2778,7 @@ fn collect_comb_effects_case(
         true_sources.extend(sources.iter().copied());
         collector.active_guard = Some(true_guard);
         collector.active_guard_sources = true_sources;
+        collector.record_coverage_hit(&case_stmt.token, CoverageArmKind::CaseArm(arm_index as u32));
         let side_store =
             collect_comb_effects_statements(module, store.fork(), &arm.body, arena, collector)?;
 
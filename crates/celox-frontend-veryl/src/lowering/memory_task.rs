@@ -0,0 +1,243 @@
+//! Memory image system tasks the analyzer does not model.
+//!
+//! The analyzer only knows the two-argument `$readmemh`. `$readmemb`,
+//! `$writememh`, `$writememb`, and any call with start/end address arguments
+//! reach the IR as an unsupported statement covering the call, so the call is
+//! recovered from the source text of that statement.
+
+use veryl_parser::{text_table, token_range::TokenRange, veryl_token::TokenSource};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MemoryTaskKind {
+    Readmemh,
+    Readmemb,
+    Writememh,
+    Writememb,
+}
+
+impl MemoryTaskKind {
+    pub(crate) fn radix(self) -> u32 {
+        match self {
+            Self::Readmemh | Self::Writememh => 16,
+            Self::Readmemb | Self::Writememb => 2,
+        }
+    }
+
+    pub(crate) fn is_write(self) -> bool {
+        matches!(self, Self::Writememh | Self::Writememb)
+    }
+
+    /// Feature names used in diagnostics about the call.
+    pub(crate) fn features(self) -> MemoryTaskFeatures {
+        let [destination, address, file, data] = match self {
+            Self::Readmemh => [
+                "$readmemh destination",
+                "$readmemh address",
+                "$readmemh file",
+                "$readmemh data",
+            ],
+            Self::Readmemb => [
+                "$readmemb destination",
+                "$readmemb address",
+                "$readmemb file",
+                "$readmemb data",
+            ],
+            Self::Writememh => [
+                "$writememh destination",
+                "$writememh address",
+                "$writememh file",
+                "$writememh data",
+            ],
+            Self::Writememb => [
+                "$writememb destination",
+                "$writememb address",
+                "$writememb file",
+                "$writememb data",
+            ],
+        };
+        MemoryTaskFeatures {
+            destination,
+            address,
+            file,
+            data,
+        }
+    }
+}
+
+pub(crate) struct MemoryTaskFeatures {
+    pub(crate) destination: &'static str,
+    pub(crate) address: &'static str,
+    pub(crate) file: &'static str,
+    pub(crate) data: &'static str,
+}
+
+/// A memory task call recovered from source text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MemoryTaskCall {
+    pub(crate) kind: MemoryTaskKind,
+    pub(crate) filename: String,
+    /// Dot-separated path of the memory, e.g. `["u_ram", "mem"]`.
+    pub(crate) memory: Vec<String>,
+    /// Start address argument as written.
+    pub(crate) start: Option<String>,
+    /// End address argument as written.
+    pub(crate) end: Option<String>,
+}
+
+/// Recover the memory task call covered by an unsupported statement, if it
+/// is one.
+pub(crate) fn recover_memory_task(token: &TokenRange) -> Option<MemoryTaskCall> {
+    let TokenSource::File { text, .. } = token.beg.source else {
+        return None;
+    };
+    let info = text_table::get(text)?;
+    let begin = token.beg.pos as usize;
+    let end = (token.end.pos + token.end.length) as usize;
+    parse_memory_task(info.text.get(begin..end)?)
+}
+
+fn parse_memory_task(call: &str) -> Option<MemoryTaskCall> {
+    let call = call.trim().trim_end_matches(';').trim_end();
+    let (name, args) = call.split_once('(')?;
+    let kind = match name.trim() {
+        "$readmemh" => MemoryTaskKind::Readmemh,
+        "$readmemb" => MemoryTaskKind::Readmemb,
+        "$writememh" => MemoryTaskKind::Writememh,
+        "$writememb" => MemoryTaskKind::Writememb,
+        _ => return None,
+    };
+    let args = split_args(args.trim_end().strip_suffix(')')?)?;
+    let (filename, memory, start, end) = match args.as_slice() {
+        [filename, memory] => (filename, memory, None, None),
+        [filename, memory, start] => (filename, memory, Some(start), None),
+        [filename, memory, start, end] => (filename, memory, Some(start), Some(end)),
+        _ => return None,
+    };
+    let memory = memory
+        .split('.')
+        .map(str::trim)
+        .map(|segment| is_identifier(segment).then(|| segment.to_string()))
+        .collect::<Option<Vec<_>>>()?;
+    Some(MemoryTaskCall {
+        kind,
+        filename: string_literal(filename)?,
+        memory,
+        start: start.map(|start| start.to_string()),
+        end: end.map(|end| end.to_string()),
+    })
+}
+
+/// Split call arguments at top-level commas.
+fn split_args(args: &str) -> Option<Vec<&str>> {
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut begin = 0;
+    for (index, ch) in args.char_indices() {
+        if in_string {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match ch {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+            ',' if depth == 0 => {
+                out.push(args[begin..index].trim());
+                begin = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_string || depth != 0 {
+        return None;
+    }
+    out.push(args[begin..].trim());
+    Some(out)
+}
+
+fn string_literal(arg: &str) -> Option<String> {
+    let body = arg.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Parse an address argument written as a decimal or based integer literal
+/// (`16`, `'h10`, `8'd16`), with optional `_` separators.
+pub(crate) fn parse_address_literal(text: &str) -> Option<usize> {
+    let text = text.trim().replace('_', "");
+    let Some((width, based)) = text.split_once('\'') else {
+        return text.parse().ok();
+    };
+    if !width.is_empty() {
+        width.parse::<usize>().ok()?;
+    }
+    let based = based.strip_prefix(['s', 'S']).unwrap_or(based);
+    let mut chars = based.chars();
+    let radix = match chars.next()? {
+        'h' | 'H' => 16,
+        'd' | 'D' => 10,
+        'o' | 'O' => 8,
+        'b' | 'B' => 2,
+        _ => return None,
+    };
+    usize::from_str_radix(chars.as_str(), radix).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_calls_with_address_ranges() {
+        let call =
+            parse_memory_task(r#"$writememb("out\\ram.mem", u_ram.mem, 'h10, WORDS);"#).unwrap();
+        assert_eq!(call.kind, MemoryTaskKind::Writememb);
+        assert_eq!(call.filename, r"out\ram.mem");
+        assert_eq!(call.memory, ["u_ram", "mem"]);
+        assert_eq!(call.start.as_deref(), Some("'h10"));
+        assert_eq!(call.end.as_deref(), Some("WORDS"));
+
+        let call = parse_memory_task(r#"$readmemb("a,b.mem", mem)"#).unwrap();
+        assert_eq!(call.filename, "a,b.mem");
+        assert_eq!(call.start, None);
+
+        assert!(parse_memory_task(r#"$display("x", mem)"#).is_none());
+        assert!(parse_memory_task(r#"$readmemh("x", mem[1][2])"#).is_none());
+    }
+
+    #[test]
+    fn parses_address_literals() {
+        assert_eq!(parse_address_literal("1_024"), Some(1024));
+        assert_eq!(parse_address_literal("'h3f"), Some(0x3f));
+        assert_eq!(parse_address_literal("8'b1010"), Some(10));
+        assert_eq!(parse_address_literal("32'sd7"), Some(7));
+        assert_eq!(parse_address_literal("DEPTH"), None);
+    }
+}
@@ -4,8 +4,11 @@ pub(crate) mod bitaccess;
 pub(crate) mod bitslicer;
 pub(crate) mod case;
 pub(crate) mod context_width;
+pub(crate) mod coverage;
 pub(crate) mod ff;
+pub(crate) mod fsm;
 pub(crate) mod global_ff;
 pub(crate) mod logic_tree;
+pub(crate) mod memory_task;
 pub(crate) mod registry;
 pub(crate) mod types;
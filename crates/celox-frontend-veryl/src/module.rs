@@ -1,16 +1,19 @@
 use std::collections::BTreeSet;
+use std::rc::Rc;
 use std::time::Instant;
 
 use super::ExternalModule;
 use crate::{
     BuildConfig, GlueAddr, GlueBlock, HashMap, HashSet, LoweringPhase, ModuleInitialMemoryValue,
-    ParserError, RegionedVarAddr, SimModule,
+    ParserError, RegionedVarAddr, SimModule, XPropMode,
     bitaccess::{
         PartSelectGeometry, SelectGeometry, eval_var_select, eval_var_select_with_geometry,
         is_static_access, select_geometry,
     },
     bitslicer::BitSlicer,
+    coverage::CoverageTable,
     ff::FfParser,
+    fsm::collect_fsm_registers,
     logic_tree::{
         CombEffectCollector, SymbolicStore, apply_assignment_destination, coerce_node_width,
         collect_and_advance_expression, collect_expression_effects, collect_written_expression,
@@ -19,12 +22,13 @@ use crate::{
         subtract_written_sensitivity,
     },
     loop_provenance::{LoopProvenance, LoopRecoveryCandidate},
+    memory_task::{MemoryTaskCall, MemoryTaskKind, parse_address_literal, recover_memory_task},
     registry::get_port_type,
     resolve_total_width,
 };
 use celox_design::{
     BinaryOp, BitAccess, InitialStateData as InitialMemoryData,
-    InitialStateWriteRun as InitialMemoryWriteRun, ModuleId, RuntimeEventSite,
+    InitialStateWriteRun as InitialMemoryWriteRun, MemoryDump, ModuleId, RuntimeEventSite,
     SPARSE_WORKING_REGION, STABLE_REGION, TriggerSet, UnaryOp, VarAtomBase, WORKING_REGION,
 };
 use celox_sir::{BlockId, ExecutionUnit, SIRBuilder, SIRInstruction, SIROffset, SIRTerminator};
@@ -54,12 +58,15 @@ pub struct ModuleParser<'a> {
     comb_boundaries: HashMap<VarId, BTreeSet<usize>>,
     glue_blocks: HashMap<StrId, Vec<GlueBlock>>,
     initial_memory_values: Vec<ModuleInitialMemoryValue>,
+    final_memory_dumps: Vec<MemoryDump<VarId>>,
     ff_parser: FfParser<'a>,
     arena: SLTNodeArena<VarId>,
     reset_clock_map: HashMap<VarId, VarId>,
     loop_candidates: Vec<LoopRecoveryCandidate>,
     external_modules: &'a HashMap<ModuleId, ExternalModule>,
     external_output_targets: Vec<(VarId, BitAccess)>,
+    coverage: Option<Rc<CoverageTable>>,
+    xprop: XPropMode,
 }
 
 static EMPTY_EXTERNAL_MODULES: std::sync::LazyLock<HashMap<ModuleId, ExternalModule>> =
@@ -1263,12 +1270,17 @@ impl<'a> ModuleParser<'a> {
             comb_boundaries: HashMap::default(),
             glue_blocks: HashMap::default(),
             initial_memory_values: Vec::new(),
-            ff_parser: FfParser::new(module, *config),
+            final_memory_dumps: Vec::new(),
+            ff_parser: FfParser::new(module, config),
             arena: SLTNodeArena::new(),
             reset_clock_map: HashMap::default(),
             loop_candidates,
             external_modules,
             external_output_targets: Vec::new(),
+            coverage: config
+                .branch_coverage
+                .then(|| Rc::new(CoverageTable::collect(module))),
+            xprop: config.xprop_for(module),
         })
     }
 
@@ -1284,6 +1296,8 @@ impl<'a> ModuleParser<'a> {
             &mut self.arena,
             &self.loop_candidates,
             site_offset,
+            self.coverage.as_ref(),
+            self.xprop,
         )?;
         for observer in &mut observers {
             observer.site_id += site_offset;
@@ -2079,8 +2093,7 @@ impl<'a> ModuleParser<'a> {
         match stmt {
             Statement::SystemFunctionCall(call) => {
                 if let SystemFunctionKind::Readmemh(filename, output) = &call.kind {
-                    let value =
-                        self.parse_readmem_file(filename, output.0.as_slice(), 16, context)?;
+                    let value = self.parse_readmem_file(filename, output.0.as_slice(), context)?;
                     self.initial_memory_values.push(value);
                 }
                 Ok(())
@@ -2122,20 +2135,163 @@ impl<'a> ModuleParser<'a> {
                 Ok(())
             }
             Statement::Null => Ok(()),
-            Statement::Unsupported(token) => Err(ParserError::illegal_context(
-                "initial statement",
-                "only direct $readmemh calls are valid in simulator-lowered initial blocks",
-                Some(token),
-            )),
+            Statement::Unsupported(token) => match recover_memory_task(token) {
+                Some(call) if !call.kind.is_write() => {
+                    let value = self.parse_recovered_readmem(&call, token, context)?;
+                    self.initial_memory_values.push(value);
+                    Ok(())
+                }
+                // Dumps belong to the native testbench, like the other
+                // testbench-only calls of an initial block.
+                Some(_) => Ok(()),
+                _ => Err(ParserError::illegal_context(
+                    "initial statement",
+                    "only direct $readmemh and $readmemb calls are valid in simulator-lowered initial blocks",
+                    Some(token),
+                )),
+            },
             _ => Ok(()),
         }
     }
 
+    /// Record the `$writememh` / `$writememb` calls of a `final` block. Other
+    /// final statements have no simulator lowering and are ignored.
+    fn parse_final_declaration(
+        &mut self,
+        decl: &veryl_analyzer::ir::FinalDeclaration,
+    ) -> Result<(), ParserError> {
+        let mut context = veryl_analyzer::Context::default();
+        context.variables = self.module.variables.clone();
+        for stmt in &decl.statements {
+            let Statement::Unsupported(token) = stmt else {
+                continue;
+            };
+            let Some(call) = recover_memory_task(token).filter(|call| call.kind.is_write()) else {
+                continue;
+            };
+            let memory = self.memory_task_variable(&call, token)?;
+            let (depth, _) = self.memory_task_shape(call.kind, memory, token)?;
+            let start = self.memory_task_address(&call, call.start.as_deref(), token, &context)?;
+            let end = self.memory_task_address(&call, call.end.as_deref(), token, &context)?;
+            Self::check_memory_task_range(call.kind, start, end, depth, token)?;
+            self.final_memory_dumps.push(MemoryDump {
+                memory,
+                path: call.filename,
+                binary: call.kind == MemoryTaskKind::Writememb,
+                start,
+                end,
+            });
+        }
+        Ok(())
+    }
+
+    fn parse_recovered_readmem(
+        &self,
+        call: &MemoryTaskCall,
+        token: &veryl_parser::token_range::TokenRange,
+        context: &veryl_analyzer::Context,
+    ) -> Result<ModuleInitialMemoryValue, ParserError> {
+        let memory = self.memory_task_variable(call, token)?;
+        let start = self.memory_task_address(call, call.start.as_deref(), token, context)?;
+        let end = self.memory_task_address(call, call.end.as_deref(), token, context)?;
+        self.read_memory_image(
+            call.kind,
+            &call.filename,
+            memory,
+            0,
+            (start, end),
+            token,
+            token,
+        )
+    }
+
+    /// The whole unpacked array named by a recovered memory task.
+    fn memory_task_variable(
+        &self,
+        call: &MemoryTaskCall,
+        token: &veryl_parser::token_range::TokenRange,
+    ) -> Result<VarId, ParserError> {
+        let [name] = call.memory.as_slice() else {
+            return Err(ParserError::unsupported(
+                111,
+                LoweringPhase::SimulatorParser,
+                call.kind.features().destination,
+                "hierarchical memories are only supported in testbenches",
+                Some(token),
+            ));
+        };
+        self.module
+            .variables
+            .values()
+            .find(|variable| variable.path.0.len() == 1 && variable.path.0[0].to_string() == *name)
+            .map(|variable| variable.id)
+            .ok_or_else(|| {
+                ParserError::illegal_context(
+                    call.kind.features().destination,
+                    format!("variable `{name}` was not found"),
+                    Some(token),
+                )
+            })
+    }
+
+    /// A start or end address argument: an integer literal, or a parameter
+    /// or loop variable with a known value.
+    fn memory_task_address(
+        &self,
+        call: &MemoryTaskCall,
+        argument: Option<&str>,
+        token: &veryl_parser::token_range::TokenRange,
+        context: &veryl_analyzer::Context,
+    ) -> Result<Option<usize>, ParserError> {
+        let Some(argument) = argument else {
+            return Ok(None);
+        };
+        let address = parse_address_literal(argument).or_else(|| {
+            context
+                .variables
+                .values()
+                .find(|variable| {
+                    variable.path.0.len() == 1 && variable.path.0[0].to_string() == argument
+                })
+                .and_then(|variable| variable.get_value(&[]))
+                .and_then(|value| value.to_usize())
+        });
+        address.map(Some).ok_or_else(|| {
+            ParserError::unsupported(
+                111,
+                LoweringPhase::SimulatorParser,
+                call.kind.features().address,
+                format!("address `{argument}` must be an integer literal or a constant name"),
+                Some(token),
+            )
+        })
+    }
+
+    fn check_memory_task_range(
+        kind: MemoryTaskKind,
+        start: Option<usize>,
+        end: Option<usize>,
+        depth: usize,
+        token: &veryl_parser::token_range::TokenRange,
+    ) -> Result<(), ParserError> {
+        let first = start.unwrap_or(0);
+        let last = end.unwrap_or(depth - 1);
+        if first > last || last >= depth {
+            return Err(ParserError::unsupported(
+                111,
+                LoweringPhase::SimulatorParser,
+                kind.features().address,
+                format!("address range {first}..={last} is outside a memory of {depth} elements"),
+                Some(token),
+            ));
+        }
+        Ok(())
+    }
+
     fn parse_readmem_file(
         &self,
         filename_arg: &SystemFunctionInput,
         output: &[AssignDestination],
-        radix: u32,
         context: &mut veryl_analyzer::Context,
     ) -> Result<ModuleInitialMemoryValue, ParserError> {
         let Some(filename) = Self::static_string_expr(&filename_arg.0) else {
@@ -2170,9 +2326,6 @@ impl<'a> ModuleParser<'a> {
         };
 
         let var = &self.module.variables[&dst.id];
-        let depth = var.r#type.total_array().ok_or_else(|| {
-            ParserError::unresolved_width(self.module, var, var.r#type.to_string())
-        })?;
         let start_addr = if dst.index.0.is_empty() {
             0
         } else {
@@ -2196,13 +2349,35 @@ impl<'a> ModuleParser<'a> {
             };
             index
         };
+        self.read_memory_image(
+            MemoryTaskKind::Readmemh,
+            &filename,
+            dst.id,
+            start_addr,
+            (None, None),
+            &filename_arg.0.comptime().token,
+            &dst.token,
+        )
+    }
+
+    /// Depth and element width of the unpacked array `memory`.
+    fn memory_task_shape(
+        &self,
+        kind: MemoryTaskKind,
+        memory: VarId,
+        token: &veryl_parser::token_range::TokenRange,
+    ) -> Result<(usize, usize), ParserError> {
+        let var = &self.module.variables[&memory];
+        let depth = var.r#type.total_array().ok_or_else(|| {
+            ParserError::unresolved_width(self.module, var, var.r#type.to_string())
+        })?;
         if depth <= 1 {
             return Err(ParserError::unsupported(
                 111,
                 LoweringPhase::SimulatorParser,
-                "$readmemh destination",
+                kind.features().destination,
                 "destination must be an unpacked array",
-                Some(&dst.token),
+                Some(token),
             ));
         }
 
@@ -2215,8 +2390,31 @@ impl<'a> ModuleParser<'a> {
                 var.r#type.to_string(),
             ));
         }
+        Ok((depth, element_width))
+    }
+
+    /// Load a memory image file into `memory`, offsetting every file
+    /// address by `start_addr`. `bounds` are the optional start and end
+    /// address arguments of the call: the first word goes to the start
+    /// address, and no word may go past the end address.
+    #[allow(clippy::too_many_arguments)]
+    fn read_memory_image(
+        &self,
+        kind: MemoryTaskKind,
+        filename: &str,
+        memory: VarId,
+        start_addr: usize,
+        bounds: (Option<usize>, Option<usize>),
+        filename_token: &veryl_parser::token_range::TokenRange,
+        dst_token: &veryl_parser::token_range::TokenRange,
+    ) -> Result<ModuleInitialMemoryValue, ParserError> {
+        let (depth, element_width) = self.memory_task_shape(kind, memory, dst_token)?;
+        if bounds != (None, None) {
+            Self::check_memory_task_range(kind, bounds.0, bounds.1, depth, dst_token)?;
+        }
+        let radix = kind.radix();
 
-        let path = self.resolve_readmem_path(&filename, &filename_arg.0.comptime().token);
+        let path = self.resolve_readmem_path(filename, filename_token);
         let timing = readmem_timing_enabled();
         let total_start = timing.then(Instant::now);
         if timing {
@@ -2235,9 +2433,9 @@ impl<'a> ModuleParser<'a> {
             ParserError::unsupported(
                 111,
                 LoweringPhase::SimulatorParser,
-                "$readmemh file",
+                kind.features().file,
                 format!("failed to read {}: {err}", path.display()),
-                Some(&filename_arg.0.comptime().token),
+                Some(filename_token),
             )
         })?;
         if let Some(start) = read_start {
@@ -2252,11 +2450,12 @@ impl<'a> ModuleParser<'a> {
         let parse_start = timing.then(Instant::now);
         let writes = parse_memory_write_runs(
             &content,
-            radix,
+            kind,
             element_width,
             start_addr,
+            (bounds.0.unwrap_or(0), bounds.1.unwrap_or(usize::MAX)),
             depth,
-            &dst.token,
+            dst_token,
         )?;
         if let Some(start) = parse_start {
             tracing::debug!(
@@ -2276,7 +2475,7 @@ impl<'a> ModuleParser<'a> {
         }
 
         Ok(ModuleInitialMemoryValue {
-            address: dst.id,
+            address: memory,
             data: InitialMemoryData::Writes(writes.runs),
         })
     }
@@ -2318,6 +2517,9 @@ impl<'a> ModuleParser<'a> {
                 Declaration::Initial(init_decl) => {
                     self.parse_initial_declaration(init_decl)?;
                 }
+                Declaration::Final(final_decl) => {
+                    self.parse_final_declaration(final_decl)?;
+                }
                 _ => {}
             }
         }
@@ -2516,10 +2718,16 @@ impl<'a> ModuleParser<'a> {
             runtime_errors: self.ff_parser.runtime_errors().clone(),
             runtime_event_sites,
             initial_memory_values: self.initial_memory_values,
+            final_memory_dumps: self.final_memory_dumps,
             comb_boundaries,
             arena: self.arena,
             store: self.store,
             reset_clock_map: self.reset_clock_map,
+            coverage_points: self
+                .coverage
+                .map(|coverage| coverage.points().to_vec())
+                .unwrap_or_default(),
+            fsm_registers: collect_fsm_registers(self.module),
         })
     }
 }
@@ -2853,14 +3061,15 @@ struct ParsedMemoryWrites {
 
 fn parse_memory_write_runs(
     content: &str,
-    radix: u32,
+    kind: MemoryTaskKind,
     width: usize,
     start_addr: usize,
+    bounds: (usize, usize),
     depth: usize,
     location: &veryl_parser::token_range::TokenRange,
 ) -> Result<ParsedMemoryWrites, ParserError> {
     let mut runs: Vec<InitialMemoryWriteRun> = Vec::new();
-    let mut addr = 0usize;
+    let mut addr = bounds.0;
     let mut words = 0usize;
     for word_token in memory_tokens(content) {
         if let Some(address) = word_token.strip_prefix('@') {
@@ -2868,19 +3077,28 @@ fn parse_memory_write_runs(
                 ParserError::unsupported(
                     111,
                     LoweringPhase::SimulatorParser,
-                    "$readmemh address",
+                    kind.features().address,
                     format!("invalid address directive {word_token}: {err}"),
                     None,
                 )
             })?;
             continue;
         }
-        let (value, mask) = parse_memory_word(&word_token, radix, width)?;
+        if addr > bounds.1 {
+            return Err(ParserError::unsupported(
+                111,
+                LoweringPhase::SimulatorParser,
+                kind.features().address,
+                format!("address {addr} is past the end address {}", bounds.1),
+                Some(location),
+            ));
+        }
+        let (value, mask) = parse_memory_word(&word_token, kind, width)?;
         let Some(dst_addr) = start_addr.checked_add(addr) else {
             return Err(ParserError::unsupported(
                 111,
                 LoweringPhase::SimulatorParser,
-                "$readmemh address",
+                kind.features().address,
                 "address exceeds destination depth",
                 Some(location),
             ));
@@ -2889,7 +3107,7 @@ fn parse_memory_write_runs(
             return Err(ParserError::unsupported(
                 111,
                 LoweringPhase::SimulatorParser,
-                "$readmemh address",
+                kind.features().address,
                 format!("address {dst_addr} exceeds destination depth {depth}"),
                 Some(location),
             ));
@@ -2922,7 +3140,7 @@ fn parse_memory_write_runs(
             ParserError::unsupported(
                 111,
                 LoweringPhase::SimulatorParser,
-                "$readmemh address",
+                kind.features().address,
                 "address exceeds destination depth",
                 Some(location),
             )
@@ -2982,9 +3200,10 @@ fn memory_tokens(content: &str) -> Vec<String> {
 
 fn parse_memory_word(
     token: &str,
-    radix: u32,
+    kind: MemoryTaskKind,
     width: usize,
 ) -> Result<(BigUint, BigUint), ParserError> {
+    let radix = kind.radix();
     let bits_per_digit = match radix {
         2 => 1,
         16 => 4,
@@ -2998,7 +3217,7 @@ fn parse_memory_word(
         match ch {
             '0'..='9' | 'a'..='f' | 'A'..='F' => {
                 let Some(digit) = ch.to_digit(radix) else {
-                    return Err(invalid_memory_word(token));
+                    return Err(invalid_memory_word(kind, token));
                 };
                 value |= BigUint::from(digit);
             }
@@ -3010,7 +3229,7 @@ fn parse_memory_word(
                 value |= &unknown;
                 mask |= unknown;
             }
-            _ => return Err(invalid_memory_word(token)),
+            _ => return Err(invalid_memory_word(kind, token)),
         }
     }
 
@@ -3021,11 +3240,11 @@ fn parse_memory_word(
     Ok((value & &keep, mask & keep))
 }
 
-fn invalid_memory_word(token: &str) -> ParserError {
+fn invalid_memory_word(kind: MemoryTaskKind, token: &str) -> ParserError {
     ParserError::unsupported(
         111,
         LoweringPhase::SimulatorParser,
-        "$readmemh data",
+        kind.features().data,
         format!("invalid data token {token}"),
         None,
     )
@@ -0,0 +1,221 @@
+//! Lowered modules reused across builds on one thread.
+//!
+//! Lowering a module is a pure function of its analyzer IR, the source files
+//! it was elaborated from, the build configuration, the ids assigned to its
+//! child instances and its loop-recovery candidates. A rebuild after editing
+//! one module therefore only has to lower the modules whose key changed.
+//!
+//! Analyzer `StrId`s and parser tables are thread-local, so the cache is as
+//! well: an entry is only ever looked up by the thread that interned the ids
+//! it contains.
+
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use celox_design::ModuleId;
+use veryl_analyzer::ir::{Module, VarId};
+use veryl_analyzer::symbol::Symbol;
+use veryl_parser::resource_table::PathId;
+use veryl_parser::text_table::{self, TextId};
+use veryl_parser::veryl_token::TokenSource;
+
+use crate::loop_provenance::LoopRecoveryCandidate;
+use crate::{BuildConfig, HashMap, SimModule};
+
+/// Number of builds an entry survives without being used.
+const RETAINED_BUILDS: u64 = 4;
+
+/// Everything lowering a module depends on.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct ModuleKey {
+    /// Analyzer IR, including instantiated child components and parameter
+    /// values.
+    ir: String,
+    /// Text of every file the module's elaboration depends on. Token
+    /// positions and source locations come from these.
+    sources: Vec<SourceText>,
+    inst_ids: Vec<ModuleId>,
+    config: String,
+    /// Statement spans and iteration variables of each candidate.
+    loops: Vec<((u32, u32), Vec<(VarId, usize)>)>,
+}
+
+/// A source file, hashed once per build and compared in full on lookup.
+#[derive(Clone)]
+struct SourceText {
+    path: PathId,
+    digest: u64,
+    text: Arc<str>,
+}
+
+impl PartialEq for SourceText {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+            && self.digest == other.digest
+            && (Arc::ptr_eq(&self.text, &other.text) || self.text == other.text)
+    }
+}
+
+impl Eq for SourceText {}
+
+impl Hash for SourceText {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.digest.hash(state);
+    }
+}
+
+struct Entry {
+    module: SimModule,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct ModuleCache {
+    generation: u64,
+    entries: HashMap<ModuleKey, Entry>,
+}
+
+thread_local!(static MODULE_CACHE: RefCell<ModuleCache> = RefCell::default());
+
+/// Source texts and file dependencies of the build in progress.
+pub(crate) struct SourceIndex {
+    text_ids: HashMap<PathId, TextId>,
+    dependencies: HashMap<PathId, Vec<PathId>>,
+    texts: HashMap<PathId, SourceText>,
+}
+
+impl SourceIndex {
+    /// Index the files that declared `symbols`. File dependencies come from
+    /// the analyzer's type DAG, which must describe the current build.
+    pub(crate) fn collect(symbols: &[Symbol]) -> Self {
+        let mut text_ids = HashMap::default();
+        for symbol in symbols {
+            if let TokenSource::File { path, text } = symbol.token.source {
+                text_ids.insert(path, text);
+            }
+        }
+        // The DAG lists, for each file, every file that depends on it.
+        let mut dependencies: HashMap<PathId, Vec<PathId>> = HashMap::default();
+        for (dependency, dependents) in veryl_analyzer::type_dag::dependent_files() {
+            for dependent in dependents {
+                dependencies.entry(dependent).or_default().push(dependency);
+            }
+        }
+        Self {
+            text_ids,
+            dependencies,
+            texts: HashMap::default(),
+        }
+    }
+
+    /// Paths `module`'s elaboration depends on: its own file and everything
+    /// that file references, transitively.
+    pub(crate) fn module_paths(&self, module: &Module) -> Option<Vec<PathId>> {
+        let TokenSource::File { path, .. } = module.token.beg.source else {
+            return None;
+        };
+        let mut paths = vec![path];
+        if let Some(dependencies) = self.dependencies.get(&path) {
+            paths.extend(dependencies);
+        }
+        Some(paths)
+    }
+
+    /// Build the cache key for lowering `module`. Returns `None` when one of
+    /// its source files is unknown, in which case the module is not cached.
+    pub(crate) fn key(
+        &mut self,
+        module: &Module,
+        paths: &[PathId],
+        inst_ids: &[ModuleId],
+        config: &BuildConfig,
+        loop_candidates: &[LoopRecoveryCandidate],
+    ) -> Option<ModuleKey> {
+        let mut paths = paths.to_vec();
+        paths.sort_unstable();
+        paths.dedup();
+        let sources = paths
+            .into_iter()
+            .map(|path| self.text(path))
+            .collect::<Option<Vec<_>>>()?;
+        Some(ModuleKey {
+            ir: module.to_string(),
+            sources,
+            inst_ids: inst_ids.to_vec(),
+            config: format!("{config:?}"),
+            loops: loop_candidates
+                .iter()
+                .map(|candidate| {
+                    let token = candidate.source.statement_token.beg;
+                    let iterations = candidate
+                        .unrolled
+                        .iterations
+                        .iter()
+                        .map(|iteration| (iteration.loop_var, iteration.value))
+                        .collect();
+                    ((token.line, token.column), iterations)
+                })
+                .collect(),
+        })
+    }
+
+    fn text(&mut self, path: PathId) -> Option<SourceText> {
+        if let Some(text) = self.texts.get(&path) {
+            return Some(text.clone());
+        }
+        let info = text_table::get(*self.text_ids.get(&path)?)?;
+        let mut hasher = fxhash::FxHasher64::default();
+        info.text.hash(&mut hasher);
+        let text = SourceText {
+            path,
+            digest: hasher.finish(),
+            text: info.text.into(),
+        };
+        self.texts.insert(path, text.clone());
+        Some(text)
+    }
+}
+
+/// Start a build: later lookups and insertions are stamped with it, and
+/// entries unused for [`RETAINED_BUILDS`] builds are dropped.
+pub(crate) fn begin_build() {
+    MODULE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.generation += 1;
+        let generation = cache.generation;
+        cache
+            .entries
+            .retain(|_, entry| generation - entry.last_used < RETAINED_BUILDS);
+    });
+}
+
+pub(crate) fn get(key: &ModuleKey) -> Option<SimModule> {
+    MODULE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let generation = cache.generation;
+        cache.entries.get_mut(key).map(|entry| {
+            entry.last_used = generation;
+            entry.module.clone()
+        })
+    })
+}
+
+pub(crate) fn insert(key: ModuleKey, module: SimModule) {
+    MODULE_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let last_used = cache.generation;
+        cache.entries.insert(key, Entry { module, last_used });
+    });
+}
+
+/// Drop every cached module on the calling thread.
+pub fn clear() {
+    MODULE_CACHE.with(|cache| cache.borrow_mut().entries.clear());
+}
+
+/// Number of modules cached on the calling thread.
+pub fn len() -> usize {
+    MODULE_CACHE.with(|cache| cache.borrow().entries.len())
+}
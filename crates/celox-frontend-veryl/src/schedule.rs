@@ -47,14 +47,18 @@ pub fn schedule_symbolic_rtl(
     let functions = root
         .map(|module| module.functions.clone())
         .unwrap_or_default();
-    let fused_ff_factory =
-        super::lowering::global_ff::VerylFusedFfFactory::new(&module_ir, &source_id_maps, *config);
+    let fused_ff_factory = super::lowering::global_ff::VerylFusedFfFactory::new(
+        &module_ir,
+        &source_id_maps,
+        config.clone(),
+    );
     let output = assembly::schedule_symbolic_rtl(
         symbolic,
         Some(&fused_ff_factory),
         ignored_loops,
         true_loops,
         four_state,
+        config.comb_partitions,
         trace_options,
         trace,
     )?;
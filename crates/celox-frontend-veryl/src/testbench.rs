@@ -11,9 +11,9 @@ use celox_testbench::{
 use fxhash::{FxHashMap as HashMap, FxHashSet};
 use num_traits::ToPrimitive as _;
 use veryl_analyzer::ir::{
-    ArrayLiteralItem, AssertKind, CasePattern, Expression, Factor, ForBound, ForRange, Function,
-    FunctionCall, HierVarRef, Op as VerylOp, Statement, SystemFunctionInput, SystemFunctionKind,
-    TbMethod, TbMethodCall, VarId, VarIndex, VarSelect, VarSelectOp,
+    ArrayLiteralItem, AssertKind, CasePattern, Comptime, Expression, Factor, ForBound, ForRange,
+    Function, FunctionCall, HierVarRef, Op as VerylOp, Statement, SystemFunctionInput,
+    SystemFunctionKind, TbMethod, TbMethodCall, VarId, VarIndex, VarPath, VarSelect, VarSelectOp,
 };
 use veryl_analyzer::value::byte_value_to_string;
 use veryl_parser::resource_table::{self, StrId};
@@ -25,6 +25,7 @@ use crate::{
     context_width::{
         ValueContext, binary_semantics, cast_semantics, expression_signed, get_expr_width,
     },
+    memory_task::{MemoryTaskCall, MemoryTaskKind, parse_address_literal, recover_memory_task},
 };
 
 type UnboundTbOpcode = TbOpcode<StateLocation<StateAddr>>;
@@ -326,6 +327,47 @@ fn resolve_hierarchical_reference_from<'a>(
     Ok((address, info))
 }
 
+/// A `$writememh` / `$writememb` call covered by an unsupported testbench
+/// statement, with the memory as a reference from the testbench module.
+fn testbench_memory_dump(
+    token: &veryl_parser::token_range::TokenRange,
+) -> Option<(MemoryTaskCall, HierVarRef)> {
+    let call = recover_memory_task(token).filter(|call| call.kind.is_write())?;
+    let (name, inst_path) = call.memory.split_last()?;
+    let reference = HierVarRef {
+        inst_path: inst_path
+            .iter()
+            .map(|segment| resource_table::insert_str(segment))
+            .collect(),
+        var_path: VarPath(vec![resource_table::insert_str(name)]),
+        index: VarIndex::default(),
+        select: VarSelect::default(),
+        comptime: Comptime::create_unknown(*token),
+    };
+    Some((call, reference))
+}
+
+/// Start and end addresses of a testbench memory dump. Testbenches accept
+/// integer literals only.
+fn testbench_memory_dump_range(
+    call: &MemoryTaskCall,
+    token: &veryl_parser::token_range::TokenRange,
+) -> Result<(Option<usize>, Option<usize>), ParserError> {
+    let address = |argument: &Option<String>| match argument {
+        None => Ok(None),
+        Some(argument) => parse_address_literal(argument).map(Some).ok_or_else(|| {
+            ParserError::unsupported(
+                111,
+                LoweringPhase::SimulatorParser,
+                call.kind.features().address,
+                format!("address `{argument}` must be an integer literal"),
+                Some(token),
+            )
+        }),
+    };
+    Ok((address(&call.start)?, address(&call.end)?))
+}
+
 /// Resolve the flattened state bits read by a hierarchical reference.
 ///
 /// Static unpacked and packed indices retain their precise range. A dynamic
@@ -766,7 +808,12 @@ fn collect_statement_reads(
                     TbMethod::RandomGet { .. } | TbMethod::RandomGetSeed => {}
                 }
             }
-            Statement::Break | Statement::Unsupported(_) | Statement::Null => {}
+            Statement::Unsupported(token) => {
+                if let Some((_, reference)) = testbench_memory_dump(token) {
+                    reads.push(TestbenchRead::Hierarchical(Box::new(reference)));
+                }
+            }
+            Statement::Break | Statement::Null => {}
         }
     }
 }
@@ -2632,6 +2679,21 @@ impl<'a> SemanticTestbenchBuilder<'a> {
             }),
             Statement::Break => Some(GenericTestbenchStatement::Break),
             Statement::FunctionCall(fc) => self.convert_function_call(fc, ec, next_assert_site_id),
+            Statement::Unsupported(token) => {
+                let (call, reference) = testbench_memory_dump(token)?;
+                let (start, end) = testbench_memory_dump_range(&call, token).ok()?;
+                let (address, info) = ec.hierarchical_variable(&reference).ok()?;
+                Some(GenericTestbenchStatement::WriteMemory {
+                    memory: SemanticSignal {
+                        address,
+                        width: info.width,
+                    },
+                    path: call.filename,
+                    binary: call.kind == MemoryTaskKind::Writememb,
+                    start,
+                    end,
+                })
+            }
             _ => None,
         }
     }
@@ -3313,7 +3375,35 @@ fn validate_testbench_statements(
                     TbMethod::FileClose | TbMethod::FileFlush => {}
                 }
             }
-            Statement::Break | Statement::Unsupported(_) | Statement::Null => {}
+            Statement::Unsupported(token) => {
+                if let Some((call, reference)) = testbench_memory_dump(token) {
+                    let (_, info) = resolve_hierarchical_reference(lookup, &reference)?;
+                    let depth = info.array_dims.iter().product::<usize>();
+                    if depth <= 1 {
+                        return Err(ParserError::unsupported(
+                            111,
+                            LoweringPhase::SimulatorParser,
+                            call.kind.features().destination,
+                            "destination must be an unpacked array",
+                            Some(token),
+                        ));
+                    }
+                    let (start, end) = testbench_memory_dump_range(&call, token)?;
+                    let (first, last) = (start.unwrap_or(0), end.unwrap_or(depth - 1));
+                    if first > last || last >= depth {
+                        return Err(ParserError::unsupported(
+                            111,
+                            LoweringPhase::SimulatorParser,
+                            call.kind.features().address,
+                            format!(
+                                "address range {first}..={last} is outside a memory of {depth} elements"
+                            ),
+                            Some(token),
+                        ));
+                    }
+                }
+            }
+            Statement::Break | Statement::Null => {}
         }
     }
     Ok(())
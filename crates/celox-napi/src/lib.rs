@@ -74,11 +74,37 @@ pub struct NapiOptimizeOptions {
     pub coalesce_stores: Option<bool>,
 }
 
+/// One `$dumpvars`-style scope: an instance path and how many levels of it
+/// to dump (0 or omitted for all).
+#[napi(object)]
+pub struct NapiDumpScope {
+    pub path: String,
+    pub depth: Option<u32>,
+}
+
+/// Restricts which signals the waveform records, and when.
+#[napi(object)]
+pub struct NapiDumpFilter {
+    /// Scopes to dump; every instance when omitted.
+    pub scopes: Option<Vec<NapiDumpScope>>,
+    /// Include unpacked arrays. Default: true.
+    pub arrays: Option<bool>,
+    /// Record from this time on.
+    pub start: Option<f64>,
+    /// Record up to and including this time.
+    pub end: Option<f64>,
+}
+
 /// Options for creating a simulator/simulation handle.
 #[napi(object)]
 pub struct NapiOptions {
     pub four_state: Option<bool>,
     pub vcd: Option<String>,
+    /// Path to write waveform output; a `.fst` extension selects FST.
+    /// Takes precedence over `vcd`.
+    pub waveform: Option<String>,
+    /// Restrict the waveform to some scopes, a time window, or non-array signals.
+    pub dump_filter: Option<NapiDumpFilter>,
     /// Optimization level preset: "O0", "O1", or "O2".
     /// Takes precedence over `optimize` and `optimize_options`.
     pub opt_level: Option<String>,
@@ -110,6 +136,23 @@ pub struct NapiOptions {
     pub parameters: Option<Vec<NapiParamOverride>>,
     /// Dead store elimination policy: "off", "preserve_top_ports", or "preserve_all_ports".
     pub dead_store_policy: Option<String>,
+    /// Let `force` hold nets driven by combinational logic. Forces SIR O0.
+    pub force_support: Option<bool>,
+    /// Count per-bit transitions for toggle coverage and SAIF export.
+    /// Only timed simulations report the counts.
+    pub toggle_coverage: Option<bool>,
+    /// Count state visits and transitions of enum-typed registers.
+    /// Only timed simulations report the counts.
+    pub fsm_coverage: Option<bool>,
+    /// Count the executions of every `if`/`case` arm for statement and
+    /// branch coverage. Only timed simulations report the counts.
+    pub branch_coverage: Option<bool>,
+    /// Time every execution unit for `profileTable` and `writeProfile`.
+    /// Only timed simulations report the times.
+    pub profile: Option<bool>,
+    /// Worker threads for evaluating independent execution units on the
+    /// native backend. Defaults to 1 (sequential).
+    pub threads: Option<u32>,
 }
 
 /// Parsed builder options from NapiOptions (common fields available on all targets).
@@ -139,6 +182,13 @@ struct ParsedOptions {
     common: ParsedOptionsCommon,
     cranelift_options: celox::CraneliftOptions,
     dead_store_policy: celox::DeadStorePolicy,
+    force_support: bool,
+    dump_filter: celox::DumpFilter,
+    toggle_coverage: bool,
+    fsm_coverage: bool,
+    branch_coverage: bool,
+    profile: bool,
+    threads: usize,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -378,7 +428,7 @@ fn parse_options_common(options: &Option<NapiOptions>) -> Result<ParsedOptionsCo
             Ok(ParsedOptionsCommon {
                 four_state: o.four_state.unwrap_or(false),
                 optimize_options,
-                vcd: o.vcd.clone(),
+                vcd: o.waveform.clone().or_else(|| o.vcd.clone()),
                 false_loops,
                 true_loops,
                 clock_type,
@@ -437,16 +487,48 @@ fn parse_options(options: &Option<NapiOptions>) -> Result<ParsedOptions> {
                 common,
                 cranelift_options,
                 dead_store_policy,
+                force_support: o.force_support.unwrap_or(false),
+                dump_filter: o
+                    .dump_filter
+                    .as_ref()
+                    .map(convert_dump_filter)
+                    .unwrap_or_default(),
+                toggle_coverage: o.toggle_coverage.unwrap_or(false),
+                fsm_coverage: o.fsm_coverage.unwrap_or(false),
+                branch_coverage: o.branch_coverage.unwrap_or(false),
+                profile: o.profile.unwrap_or(false),
+                threads: o.threads.map_or(1, |threads| threads as usize),
             })
         }
         None => Ok(ParsedOptions {
             common,
             cranelift_options: celox::CraneliftOptions::default(),
             dead_store_policy: celox::DeadStorePolicy::Off,
+            force_support: false,
+            dump_filter: celox::DumpFilter::default(),
+            toggle_coverage: false,
+            fsm_coverage: false,
+            branch_coverage: false,
+            profile: false,
+            threads: 1,
         }),
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn convert_dump_filter(f: &NapiDumpFilter) -> celox::DumpFilter {
+    let mut filter = celox::DumpFilter::new().arrays(f.arrays.unwrap_or(true));
+    for scope in f.scopes.iter().flatten() {
+        filter = filter.scope(&scope.path, scope.depth.unwrap_or(0) as usize);
+    }
+    if f.start.is_some() || f.end.is_some() {
+        let start = f.start.map_or(0, |t| t as u64);
+        let end = f.end.map_or(u64::MAX, |t| t as u64);
+        filter = filter.window(start, end);
+    }
+    filter
+}
+
 /// Append extra source as a separate file entry if provided.
 fn append_extra_source(sources: &mut Vec<(String, std::path::PathBuf)>, extra: &Option<String>) {
     if let Some(extra) = extra {
@@ -638,6 +720,13 @@ fn apply_options<'a, T>(
         builder = builder.param(name, *value);
     }
     builder = builder.dead_store_policy(opts.dead_store_policy);
+    builder = builder.force_support(opts.force_support);
+    builder = builder.dump_filter(opts.dump_filter.clone());
+    builder = builder.toggle_coverage(opts.toggle_coverage);
+    builder = builder.fsm_coverage(opts.fsm_coverage);
+    builder = builder.branch_coverage(opts.branch_coverage);
+    builder = builder.profile(opts.profile);
+    builder = builder.threads(opts.threads);
     builder
 }
 
@@ -686,6 +775,7 @@ struct CacheKey {
     enable_alias_analysis: bool,
     enable_verifier: bool,
     dead_store_policy: u8,
+    force_support: bool,
     clock_type: Option<u8>,
     reset_type: Option<u8>,
     parameters: Vec<(String, u64)>,
@@ -702,6 +792,11 @@ struct CacheKey {
     /// None when using the `new` constructor (no metadata).
     metadata_clock_type: Option<u8>,
     metadata_reset_type: Option<u8>,
+    /// Selects the cached waveform descriptors.
+    dump_filter: celox::DumpFilter,
+    branch_coverage: bool,
+    profile: bool,
+    threads: usize,
 }
 
 /// Collision-free representation of every SIR code-generation option.
@@ -759,6 +854,7 @@ fn build_cache_key(
         enable_alias_analysis: opts.cranelift_options.enable_alias_analysis,
         enable_verifier: opts.cranelift_options.enable_verifier,
         dead_store_policy: opts.dead_store_policy as u8,
+        force_support: opts.force_support,
         clock_type: opts.clock_type.map(|ct| ct as u8),
         reset_type: opts.reset_type.map(|rt| rt as u8),
         parameters: opts.parameters.clone(),
@@ -766,6 +862,10 @@ fn build_cache_key(
         true_loops: opts.true_loops.clone(),
         metadata_clock_type: metadata.map(|m| m.build.clock_type as u8),
         metadata_reset_type: metadata.map(|m| m.build.reset_type as u8),
+        dump_filter: opts.dump_filter.clone(),
+        branch_coverage: opts.branch_coverage,
+        profile: opts.profile,
+        threads: opts.threads,
     }
 }
 
@@ -822,6 +922,164 @@ fn napi_runtime_error(
     }
 }
 
+/// A value change delivered to a `watch` callback.
+#[cfg(not(target_arch = "wasm32"))]
+#[napi(object)]
+pub struct NapiValueChange {
+    pub id: u32,
+    pub time: f64,
+    pub old_value: BigInt,
+    pub old_mask: BigInt,
+    pub new_value: BigInt,
+    pub new_mask: BigInt,
+}
+
+/// A failed attempt of a property registered with `assertProperty`.
+#[cfg(not(target_arch = "wasm32"))]
+#[napi(object)]
+pub struct NapiPropertyFailure {
+    pub id: u32,
+    pub name: String,
+    pub start_time: f64,
+    pub time: f64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type WatchErrorSlot = Arc<Mutex<Option<String>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+struct NapiWatchCallback {
+    env: Env,
+    callback: FunctionRef<NapiValueChange, ()>,
+    error: WatchErrorSlot,
+}
+
+// Watch callbacks are registered and fired by synchronous handle methods on
+// the JS thread which supplied this Env. `celox::Simulation::watch` requires
+// Send because Rust callers may move the simulation between threads.
+#[cfg(not(target_arch = "wasm32"))]
+unsafe impl Send for NapiWatchCallback {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl NapiWatchCallback {
+    /// Invoke the JS callback. The first exception is parked in the error
+    /// slot and suppresses further callbacks until the handle reports it.
+    fn call(&self, change: &celox::ValueChange) {
+        if lock_watch_error(&self.error).is_some() {
+            return;
+        }
+        let change = NapiValueChange {
+            id: change.id,
+            time: change.time as f64,
+            old_value: napi_biguint(&change.old_value),
+            old_mask: napi_biguint(&change.old_mask),
+            new_value: napi_biguint(&change.new_value),
+            new_mask: napi_biguint(&change.new_mask),
+        };
+        if let Err(error) = self
+            .callback
+            .borrow_back(&self.env)
+            .and_then(|callback| callback.call(change))
+        {
+            *lock_watch_error(&self.error) = Some(error.to_string());
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn lock_watch_error(slot: &WatchErrorSlot) -> std::sync::MutexGuard<'_, Option<String>> {
+    slot.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Surface an exception thrown by a watch callback during the last call.
+#[cfg(not(target_arch = "wasm32"))]
+fn take_watch_error(slot: &WatchErrorSlot) -> Result<()> {
+    match lock_watch_error(slot).take() {
+        Some(message) => Err(Error::from_reason(format!(
+            "watch callback failed: {message}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Report changes on the watched signals of a bare backend handle.
+#[cfg(not(target_arch = "wasm32"))]
+fn notify_napi_watchers(
+    backend: &celox::DefaultBackend,
+    watchers: &mut celox::WatchList<NapiWatchCallback>,
+    time: u64,
+    error: &WatchErrorSlot,
+) -> Result<()> {
+    watchers.notify(
+        time,
+        |signal| backend.get_four_state(signal),
+        |callback, change| callback.call(change),
+    );
+    take_watch_error(error)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn napi_biguint(value: &celox::BigUint) -> BigInt {
+    let mut words = value.to_u64_digits();
+    if words.is_empty() {
+        words.push(0);
+    }
+    BigInt {
+        sign_bit: false,
+        words,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn biguint_from_napi(value: &BigInt) -> Result<celox::BigUint> {
+    if value.sign_bit {
+        return Err(Error::from_reason("signal values must be non-negative"));
+    }
+    Ok(celox::BigUint::from_slice(
+        &value
+            .words
+            .iter()
+            .flat_map(|word| [*word as u32, (*word >> 32) as u32])
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Parse the `(value, mask)` pair passed to `force` and `deposit`.
+#[cfg(not(target_arch = "wasm32"))]
+fn four_state_from_napi(
+    value: &BigInt,
+    mask: Option<&BigInt>,
+) -> Result<(celox::BigUint, celox::BigUint)> {
+    Ok((
+        biguint_from_napi(value)?,
+        mask.map(biguint_from_napi).transpose()?.unwrap_or_default(),
+    ))
+}
+
+/// The signal described by a JS layout entry.
+#[cfg(not(target_arch = "wasm32"))]
+fn handle_signal(offset: u32, width: u32, is_4state: bool) -> celox::SignalRef {
+    celox::SignalRef {
+        offset: offset as usize,
+        width: width as usize,
+        is_4state,
+        array_layout: None,
+    }
+}
+
+/// Open a waveform writer for `descs`, recording within the filter's window.
+#[cfg(not(target_arch = "wasm32"))]
+fn open_waveform(
+    path: &str,
+    descs: &[celox::VcdSignalDesc],
+    filter: &celox::DumpFilter,
+) -> Result<celox::WaveformWriter> {
+    let mut writer = celox::WaveformWriter::new(path, descs)
+        .map_err(|e| Error::from_reason(format!("Failed to create waveform: {}", e)))?;
+    writer.set_window(filter.time_window());
+    Ok(writer)
+}
+
 /// Low-level handle wrapping the default backend and optional VCD writer.
 ///
 /// JS holds this as an opaque class; all operations go through methods.
@@ -830,13 +1088,18 @@ fn napi_runtime_error(
 pub struct NativeSimulatorHandle {
     backend: Option<celox::DefaultBackend>,
     runtime_errors: HashMap<i64, (String, Vec<String>)>,
-    vcd_writer: Option<celox::VcdWriter>,
+    vcd_writer: Option<celox::WaveformWriter>,
     layout_json: String,
     events_json: String,
     hierarchy_json: String,
     warnings_json: String,
     stable_size: u32,
     total_size: u32,
+    watchers: celox::WatchList<NapiWatchCallback>,
+    watch_error: WatchErrorSlot,
+    /// Completed ticks, reported as the time of value changes.
+    ticks: u64,
+    forces: celox::ForceSet,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -850,7 +1113,7 @@ impl NativeSimulatorHandle {
     /// here without serializing an artifact to JSON. Signal metadata is always
     /// derived from the simulator's actual memory layout.
     pub fn from_simulator(simulator: celox::Simulator, vcd_path: Option<&str>) -> Result<Self> {
-        Self::build_and_cache(simulator, vcd_path, None)
+        Self::build_and_cache(simulator, vcd_path, &celox::DumpFilter::default(), None)
     }
 
     /// Build an N-API handle directly from an in-memory frontend artifact.
@@ -866,7 +1129,7 @@ impl NativeSimulatorHandle {
         let simulator = builder
             .build()
             .map_err(|error| Error::from_reason(error.to_string()))?;
-        Self::from_simulator(simulator, opts.vcd.as_deref())
+        Self::build_and_cache(simulator, opts.vcd.as_deref(), &opts.dump_filter, None)
     }
 }
 
@@ -878,6 +1141,7 @@ impl NativeSimulatorHandle {
     fn build_and_cache(
         sim: celox::Simulator,
         vcd_path: Option<&str>,
+        dump_filter: &celox::DumpFilter,
         cache_key: Option<CacheKey>,
     ) -> Result<Self> {
         let four_state = sim.layout().four_state;
@@ -887,7 +1151,7 @@ impl NativeSimulatorHandle {
         let hierarchy = sim.named_hierarchy();
         let (_, total_size) = sim.memory_as_ptr();
         let stable_size = sim.stable_region_size();
-        let vcd_descs = sim.build_vcd_descs(four_state);
+        let vcd_descs = sim.build_filtered_vcd_descs(four_state, dump_filter);
         let runtime_errors = runtime_errors_by_name(sim.program());
 
         let layout_map = build_signal_layout(&signals, four_state);
@@ -918,15 +1182,10 @@ impl NativeSimulatorHandle {
             cache.insert(key, cached);
         }
 
-        // Create VcdWriter if requested
-        let vcd_writer = if let Some(path) = vcd_path {
-            Some(
-                celox::VcdWriter::new(path, &vcd_descs)
-                    .map_err(|e| Error::from_reason(format!("Failed to create VCD: {}", e)))?,
-            )
-        } else {
-            None
-        };
+        // Create the waveform writer if requested
+        let vcd_writer = vcd_path
+            .map(|path| open_waveform(path, &vcd_descs, dump_filter))
+            .transpose()?;
 
         // Extract the backend from Simulator (drops runtime metadata which is no longer needed)
         let backend = sim.into_backend();
@@ -941,23 +1200,27 @@ impl NativeSimulatorHandle {
             warnings_json,
             stable_size: stable_size as u32,
             total_size: total_size as u32,
+
+            watchers: celox::WatchList::new(),
+            watch_error: WatchErrorSlot::default(),
+            ticks: 0,
+            forces: celox::ForceSet::new(),
         })
     }
 
     /// Create a handle from a cached build (shared compiled code + fresh memory).
-    fn from_cached(cached: &CachedBuild, vcd_path: Option<&str>) -> Result<Self> {
+    fn from_cached(
+        cached: &CachedBuild,
+        vcd_path: Option<&str>,
+        dump_filter: &celox::DumpFilter,
+    ) -> Result<Self> {
         #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
         let backend = celox::NativeBackend::from_shared(Arc::clone(&cached.shared_code));
         #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
         let backend = celox::JitBackend::from_shared(Arc::clone(&cached.shared_code));
-        let vcd_writer = if let Some(path) = vcd_path {
-            Some(
-                celox::VcdWriter::new(path, &cached.vcd_descs)
-                    .map_err(|e| Error::from_reason(format!("Failed to create VCD: {}", e)))?,
-            )
-        } else {
-            None
-        };
+        let vcd_writer = vcd_path
+            .map(|path| open_waveform(path, &cached.vcd_descs, dump_filter))
+            .transpose()?;
         Ok(Self {
             backend: Some(backend),
             runtime_errors: cached.runtime_errors.clone(),
@@ -968,6 +1231,11 @@ impl NativeSimulatorHandle {
             warnings_json: cached.warnings_json.clone(),
             stable_size: cached.stable_size,
             total_size: cached.total_size,
+
+            watchers: celox::WatchList::new(),
+            watch_error: WatchErrorSlot::default(),
+            ticks: 0,
+            forces: celox::ForceSet::new(),
         })
     }
 
@@ -990,7 +1258,7 @@ impl NativeSimulatorHandle {
         {
             let cache = JIT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
             if let Some(cached) = cache.get(&cache_key) {
-                return Self::from_cached(cached, opts.vcd.as_deref());
+                return Self::from_cached(cached, opts.vcd.as_deref(), &opts.dump_filter);
             }
         }
 
@@ -1003,7 +1271,7 @@ impl NativeSimulatorHandle {
             .build()
             .map_err(|e| Error::from_reason(format!("{}", e)))?;
 
-        Self::build_and_cache(sim, opts.vcd.as_deref(), Some(cache_key))
+        Self::build_and_cache(sim, opts.vcd.as_deref(), &opts.dump_filter, Some(cache_key))
     }
 
     /// Create a simulator from a versioned external-frontend artifact.
@@ -1037,7 +1305,7 @@ impl NativeSimulatorHandle {
         {
             let cache = JIT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
             if let Some(cached) = cache.get(&cache_key) {
-                return Self::from_cached(cached, opts.vcd.as_deref());
+                return Self::from_cached(cached, opts.vcd.as_deref(), &opts.dump_filter);
             }
         }
 
@@ -1054,7 +1322,7 @@ impl NativeSimulatorHandle {
             .build()
             .map_err(|e| Error::from_reason(format!("{}", e)))?;
 
-        Self::build_and_cache(sim, opts.vcd.as_deref(), Some(cache_key))
+        Self::build_and_cache(sim, opts.vcd.as_deref(), &opts.dump_filter, Some(cache_key))
     }
 
     /// Returns the signal layout as a JSON string.
@@ -1102,12 +1370,17 @@ impl NativeSimulatorHandle {
             .as_mut()
             .ok_or_else(|| Error::from_reason("Simulator has been disposed"))?;
         let event = b.id_to_event_slice()[event_id as usize];
-        b.eval_comb()
+        self.forces
+            .eval_comb(b)
             .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
         b.eval_apply_ff_at(event)
             .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
-        b.eval_comb()
-            .map_err(|e| napi_runtime_error(&runtime_errors, e))
+        self.forces.apply(b);
+        self.forces
+            .eval_comb(b)
+            .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
+        self.ticks += 1;
+        notify_napi_watchers(b, &mut self.watchers, self.ticks, &self.watch_error)
     }
 
     /// Trigger a clock/event N times in a single NAPI call.
@@ -1120,12 +1393,17 @@ impl NativeSimulatorHandle {
             .ok_or_else(|| Error::from_reason("Simulator has been disposed"))?;
         let event = b.id_to_event_slice()[event_id as usize];
         for _ in 0..count {
-            b.eval_comb()
+            self.forces
+                .eval_comb(b)
                 .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
             b.eval_apply_ff_at(event)
                 .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
-            b.eval_comb()
+            self.forces.apply(b);
+            self.forces
+                .eval_comb(b)
                 .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
+            self.ticks += 1;
+            notify_napi_watchers(b, &mut self.watchers, self.ticks, &self.watch_error)?;
         }
         Ok(())
     }
@@ -1138,8 +1416,95 @@ impl NativeSimulatorHandle {
             .backend
             .as_mut()
             .ok_or_else(|| Error::from_reason("Simulator has been disposed"))?;
-        b.eval_comb()
-            .map_err(|e| napi_runtime_error(&runtime_errors, e))
+        self.forces
+            .eval_comb(b)
+            .map_err(|e| napi_runtime_error(&runtime_errors, e))?;
+        notify_napi_watchers(b, &mut self.watchers, self.ticks, &self.watch_error)
+    }
+
+    /// Call `callback` with a value-change record whenever the signal at
+    /// `offset` changes after `tick`, `tickN`, or `evalComb`. The reported
+    /// time counts completed ticks. Returns an ID for `unwatch`.
+    #[napi]
+    pub fn watch(
+        &mut self,
+        env: Env,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        callback: FunctionRef<NapiValueChange, ()>,
+    ) -> Result<u32> {
+        let b = self
+            .backend
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulator has been disposed"))?;
+        let signal = handle_signal(offset, width, is_4state);
+        let callback = NapiWatchCallback {
+            env,
+            callback,
+            error: Arc::clone(&self.watch_error),
+        };
+        Ok(self
+            .watchers
+            .watch(signal, b.get_four_state(signal), callback))
+    }
+
+    /// Remove a watch registered with `watch`.
+    #[napi]
+    pub fn unwatch(&mut self, id: u32) {
+        self.watchers.unwatch(id);
+    }
+
+    /// Hold the signal at `offset` at `value` until `release`. The forced
+    /// value is written immediately; call `evalComb` to settle its fanout.
+    #[napi]
+    pub fn force(
+        &mut self,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        value: BigInt,
+        mask: Option<BigInt>,
+    ) -> Result<()> {
+        let b = self
+            .backend
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulator has been disposed"))?;
+        let signal = handle_signal(offset, width, is_4state);
+        let (value, mask) = four_state_from_napi(&value, mask.as_ref())?;
+        self.forces.force(signal, value.clone(), mask.clone());
+        b.set_four_state(signal, value, mask);
+        Ok(())
+    }
+
+    /// Release a force placed by `force`. Combinational nets take their
+    /// driven value again on the next `evalComb`.
+    #[napi]
+    pub fn release(&mut self, offset: u32, width: u32, is_4state: bool) {
+        self.forces.release(handle_signal(offset, width, is_4state));
+    }
+
+    /// Write `value` once without holding it. Ignored while the signal is
+    /// forced.
+    #[napi]
+    pub fn deposit(
+        &mut self,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        value: BigInt,
+        mask: Option<BigInt>,
+    ) -> Result<()> {
+        let b = self
+            .backend
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulator has been disposed"))?;
+        let signal = handle_signal(offset, width, is_4state);
+        if !self.forces.contains(signal) {
+            let (value, mask) = four_state_from_napi(&value, mask.as_ref())?;
+            b.set_four_state(signal, value, mask);
+        }
+        Ok(())
     }
 
     /// Write VCD dump at the given timestamp.
@@ -1154,11 +1519,27 @@ impl NativeSimulatorHandle {
             let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
             writer
                 .dump(timestamp as u64, memory)
-                .map_err(|e| Error::from_reason(format!("VCD write error: {}", e)))?;
+                .map_err(|e| Error::from_reason(format!("Waveform write error: {}", e)))?;
         }
         Ok(())
     }
 
+    /// Resume waveform recording; the next dump writes every value.
+    #[napi]
+    pub fn dump_on(&mut self) {
+        if let Some(writer) = self.vcd_writer.as_mut() {
+            writer.set_enabled(true);
+        }
+    }
+
+    /// Stop waveform recording; signals read as X until `dump_on`.
+    #[napi]
+    pub fn dump_off(&mut self) {
+        if let Some(writer) = self.vcd_writer.as_mut() {
+            writer.set_enabled(false);
+        }
+    }
+
     /// Return the simulator's stable memory region as a zero-copy `Uint8Array`.
     /// JS can access `.buffer` to get the underlying `ArrayBuffer`.
     #[napi]
@@ -1194,6 +1575,7 @@ pub struct NativeSimulationHandle {
     /// Default `maxSteps` for `waitUntil` / `waitForCycles`, sourced from
     /// `[simulation] max_steps` in `celox.toml`. `None` when not set.
     default_max_steps: Option<u32>,
+    watch_error: WatchErrorSlot,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -1251,6 +1633,8 @@ impl NativeSimulationHandle {
             stable_size: stable_size as u32,
             total_size: total_size as u32,
             default_max_steps: None,
+
+            watch_error: WatchErrorSlot::default(),
         })
     }
 
@@ -1297,6 +1681,8 @@ impl NativeSimulationHandle {
             stable_size: stable_size as u32,
             total_size: total_size as u32,
             default_max_steps: None,
+
+            watch_error: WatchErrorSlot::default(),
         })
     }
 
@@ -1353,6 +1739,8 @@ impl NativeSimulationHandle {
             stable_size: stable_size as u32,
             total_size: total_size as u32,
             default_max_steps: celox_cfg.simulation.max_steps,
+
+            watch_error: WatchErrorSlot::default(),
         })
     }
 
@@ -1410,6 +1798,274 @@ impl NativeSimulationHandle {
         Ok(())
     }
 
+    /// Register a clock with an explicit high time and first rising edge.
+    #[napi]
+    pub fn add_clock_with(
+        &mut self,
+        event_id: u32,
+        period: f64,
+        high_time: f64,
+        phase: f64,
+    ) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let (period, high_time) = (period as u64, high_time as u64);
+        if high_time == 0 || high_time >= period {
+            return Err(Error::from_reason(format!(
+                "clock high time {high_time} must be between 0 and the period {period} (exclusive)"
+            )));
+        }
+        let spec = celox::ClockSpec::new(period)
+            .high_time(high_time)
+            .phase(phase as u64);
+        sim.add_clock_with_by_id(event_id, spec)
+            .map_err(|e| Error::from_reason(format!("{}", e)))
+    }
+
+    /// Stop a clock by event ID, holding its current level.
+    #[napi]
+    pub fn pause_clock(&mut self, event_id: u32) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.pause_clock_by_id(event_id)
+            .map_err(|e| Error::from_reason(format!("{}", e)))
+    }
+
+    /// Restart a paused clock by event ID on its original edge grid.
+    #[napi]
+    pub fn resume_clock(&mut self, event_id: u32) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.resume_clock_by_id(event_id)
+            .map_err(|e| Error::from_reason(format!("{}", e)))
+    }
+
+    /// Unregister a clock by event ID.
+    #[napi]
+    pub fn remove_clock(&mut self, event_id: u32) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.remove_clock_by_id(event_id)
+            .map_err(|e| Error::from_reason(format!("{}", e)))
+    }
+
+    /// Resume waveform recording at the current time.
+    #[napi]
+    pub fn dump_on(&mut self) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.dump_on();
+        Ok(())
+    }
+
+    /// Stop waveform recording; signals read as X until `dump_on`.
+    #[napi]
+    pub fn dump_off(&mut self) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.dump_off();
+        Ok(())
+    }
+
+    /// Write the switching activity counted since the start to a SAIF file.
+    #[napi]
+    pub fn write_saif(&self, path: String) -> Result<()> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.write_saif(&path)
+            .map_err(|e| Error::from_reason(format!("Failed to write SAIF: {}", e)))
+    }
+
+    /// Write the toggle-coverage counts to a JSON report.
+    #[napi]
+    pub fn write_toggle_coverage(&self, path: String) -> Result<()> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let coverage = sim.toggle_coverage().ok_or_else(|| {
+            Error::from_reason("toggle coverage is not enabled for this simulation")
+        })?;
+        std::fs::File::create(&path)
+            .map(std::io::BufWriter::new)
+            .and_then(|mut writer| {
+                coverage.write_json(&mut writer)?;
+                std::io::Write::flush(&mut writer)
+            })
+            .map_err(|e| Error::from_reason(format!("Failed to write toggle coverage: {}", e)))
+    }
+
+    /// Write the FSM state and transition counts to a JSON report.
+    #[napi]
+    pub fn write_fsm_coverage(&self, path: String) -> Result<()> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let coverage = sim
+            .fsm_coverage()
+            .ok_or_else(|| Error::from_reason("FSM coverage is not enabled for this simulation"))?;
+        std::fs::File::create(&path)
+            .map(std::io::BufWriter::new)
+            .and_then(|mut writer| {
+                coverage.write_json(&mut writer)?;
+                std::io::Write::flush(&mut writer)
+            })
+            .map_err(|e| Error::from_reason(format!("Failed to write FSM coverage: {}", e)))
+    }
+
+    /// Ranked table of the time spent per execution unit and instance.
+    #[napi]
+    pub fn profile_table(&self) -> Result<String> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let profile = sim
+            .profile()
+            .ok_or_else(|| Error::from_reason("profiling is not enabled for this simulation"))?;
+        Ok(profile.to_string())
+    }
+
+    /// Write the profile as folded stacks for flamegraph tools.
+    #[napi]
+    pub fn write_profile(&self, path: String) -> Result<()> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let profile = sim
+            .profile()
+            .ok_or_else(|| Error::from_reason("profiling is not enabled for this simulation"))?;
+        std::fs::File::create(&path)
+            .map(std::io::BufWriter::new)
+            .and_then(|mut writer| {
+                profile.write_folded(&mut writer)?;
+                std::io::Write::flush(&mut writer)
+            })
+            .map_err(|e| Error::from_reason(format!("Failed to write profile: {}", e)))
+    }
+
+    /// Write the branch hit counts as `"lcov"`, `"cobertura"` or `"json"`.
+    #[napi]
+    pub fn write_coverage(&mut self, path: String, format: String) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let coverage = sim.coverage();
+        std::fs::File::create(&path)
+            .map(std::io::BufWriter::new)
+            .and_then(|mut writer| {
+                match format.as_str() {
+                    "lcov" => coverage.write_lcov(&mut writer)?,
+                    "cobertura" => coverage.write_cobertura(&mut writer)?,
+                    "json" => coverage.write_json(&mut writer)?,
+                    other => {
+                        return Err(std::io::Error::other(format!(
+                            "unknown coverage format '{other}' (expected lcov, cobertura or json)"
+                        )));
+                    }
+                }
+                std::io::Write::flush(&mut writer)
+            })
+            .map_err(|e| Error::from_reason(format!("Failed to write coverage: {}", e)))
+    }
+
+    /// Check an SVA property such as
+    /// `@(posedge clk) disable iff (rst) req |-> ##[1:4] ack` on every edge of
+    /// its clock. Returns an ID for `removeProperty`.
+    #[napi]
+    pub fn assert_property(&mut self, name: String, property: String) -> Result<u32> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.assert_property(&name, &property)
+            .map_err(|e| Error::from_reason(format!("Invalid property '{}': {}", name, e)))
+    }
+
+    /// Stop checking a property registered with `assertProperty`.
+    #[napi]
+    pub fn remove_property(&mut self, id: u32) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.remove_property(id);
+        Ok(())
+    }
+
+    /// Return and clear the property failures collected so far.
+    #[napi]
+    pub fn take_property_failures(&mut self) -> Result<Vec<NapiPropertyFailure>> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        Ok(sim
+            .take_property_failures()
+            .into_iter()
+            .map(|failure| NapiPropertyFailure {
+                id: failure.id,
+                name: failure.name,
+                start_time: failure.start_time as f64,
+                time: failure.time as f64,
+            })
+            .collect())
+    }
+
+    /// Set the physical length of one tick, e.g. `"1ns"` or `"10ps"`.
+    #[napi]
+    pub fn set_timescale(&mut self, timescale: String) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let timescale = timescale
+            .parse::<celox::Timescale>()
+            .map_err(|e| Error::from_reason(e.to_string()))?;
+        sim.set_timescale(timescale)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Convert a duration such as `"10ns"` into ticks of the timescale.
+    #[napi]
+    pub fn ticks(&self, duration: String) -> Result<f64> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.ticks(&duration)
+            .map(|ticks| ticks as f64)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    /// Returns the current simulation time in `unit`.
+    #[napi]
+    pub fn time_in(&self, unit: String) -> Result<f64> {
+        let sim = self
+            .sim
+            .as_ref()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.time_in(&unit)
+            .map_err(|e| Error::from_reason(e.to_string()))
+    }
+
     /// Schedule a one-shot event by event ID.
     #[napi]
     pub fn schedule(&mut self, event_id: u32, time: f64, value: f64) -> Result<()> {
@@ -1421,6 +2077,53 @@ impl NativeSimulationHandle {
             .map_err(|e| Error::from_reason(format!("{}", e)))
     }
 
+    /// Queue a write of the signal at `offset` at `time`. Writes land after
+    /// the clock edges at the same time.
+    #[napi]
+    pub fn schedule_value(
+        &mut self,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        time: f64,
+        value: BigInt,
+        mask: Option<BigInt>,
+    ) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let (value, mask) = four_state_from_napi(&value, mask.as_ref())?;
+        sim.schedule_value_four_state(
+            handle_signal(offset, width, is_4state),
+            time as u64,
+            value,
+            mask,
+        );
+        Ok(())
+    }
+
+    /// Fail `run_until` and `step` once a call runs longer than
+    /// `wall_clock_ms`, or a time step needs more than `max_cascade_rounds`
+    /// cascade rounds. `null` removes a limit.
+    #[napi]
+    pub fn set_run_limits(
+        &mut self,
+        wall_clock_ms: Option<f64>,
+        max_cascade_rounds: Option<u32>,
+    ) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.set_run_limits(celox::RunLimits {
+            wall_clock: wall_clock_ms
+                .map(|ms| std::time::Duration::from_secs_f64(ms.max(0.0) / 1000.0)),
+            max_cascade_rounds,
+        });
+        Ok(())
+    }
+
     /// Advance simulation until `end_time`.
     #[napi]
     pub fn run_until(&mut self, end_time: f64) -> Result<()> {
@@ -1429,7 +2132,8 @@ impl NativeSimulationHandle {
             .as_mut()
             .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
         sim.run_until(end_time as u64)
-            .map_err(|e| Error::from_reason(format!("{}", e)))
+            .map_err(|e| Error::from_reason(format!("{}", e)))?;
+        take_watch_error(&self.watch_error)
     }
 
     /// Advance to the next event. Returns the new time, or null if no events.
@@ -1439,9 +2143,12 @@ impl NativeSimulationHandle {
             .sim
             .as_mut()
             .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
-        sim.step()
+        let time = sim
+            .step()
             .map(|opt| opt.map(|t| t as f64))
-            .map_err(|e| Error::from_reason(format!("{}", e)))
+            .map_err(|e| Error::from_reason(format!("{}", e)))?;
+        take_watch_error(&self.watch_error)?;
+        Ok(time)
     }
 
     /// Returns the current simulation time.
@@ -1472,7 +2179,97 @@ impl NativeSimulationHandle {
             .as_mut()
             .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
         sim.eval_comb()
-            .map_err(|e| Error::from_reason(format!("{}", e)))
+            .map_err(|e| Error::from_reason(format!("{}", e)))?;
+        take_watch_error(&self.watch_error)
+    }
+
+    /// Call `callback` with a value-change record whenever the signal at
+    /// `offset` changes after `step`, `runUntil`, or `evalComb`. The reported
+    /// time is the simulation time. Returns an ID for `unwatch`.
+    #[napi]
+    pub fn watch(
+        &mut self,
+        env: Env,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        callback: FunctionRef<NapiValueChange, ()>,
+    ) -> Result<u32> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let callback = NapiWatchCallback {
+            env,
+            callback,
+            error: Arc::clone(&self.watch_error),
+        };
+        Ok(
+            sim.watch(handle_signal(offset, width, is_4state), move |change| {
+                callback.call(change)
+            }),
+        )
+    }
+
+    /// Remove a watch registered with `watch`.
+    #[napi]
+    pub fn unwatch(&mut self, id: u32) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.unwatch(id);
+        Ok(())
+    }
+
+    /// Hold the signal at `offset` at `value` until `release`.
+    #[napi]
+    pub fn force(
+        &mut self,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        value: BigInt,
+        mask: Option<BigInt>,
+    ) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let (value, mask) = four_state_from_napi(&value, mask.as_ref())?;
+        sim.force_four_state(handle_signal(offset, width, is_4state), value, mask);
+        take_watch_error(&self.watch_error)
+    }
+
+    /// Release a force placed by `force`.
+    #[napi]
+    pub fn release(&mut self, offset: u32, width: u32, is_4state: bool) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        sim.release(handle_signal(offset, width, is_4state));
+        take_watch_error(&self.watch_error)
+    }
+
+    /// Write `value` once without holding it. Ignored while the signal is
+    /// forced.
+    #[napi]
+    pub fn deposit(
+        &mut self,
+        offset: u32,
+        width: u32,
+        is_4state: bool,
+        value: BigInt,
+        mask: Option<BigInt>,
+    ) -> Result<()> {
+        let sim = self
+            .sim
+            .as_mut()
+            .ok_or_else(|| Error::from_reason("Simulation has been disposed"))?;
+        let (value, mask) = four_state_from_napi(&value, mask.as_ref())?;
+        sim.deposit_four_state(handle_signal(offset, width, is_4state), value, mask);
+        take_watch_error(&self.watch_error)
     }
 
     /// Write VCD dump at the given timestamp.
@@ -2960,6 +3757,13 @@ mod tests {
             },
             cranelift_options: celox::CraneliftOptions::default(),
             dead_store_policy: celox::DeadStorePolicy::Off,
+            force_support: false,
+            dump_filter: celox::DumpFilter::default(),
+            toggle_coverage: false,
+            fsm_coverage: false,
+            branch_coverage: false,
+            profile: false,
+            threads: 1,
         }
     }
 
@@ -3157,6 +3961,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn force_support_differs() {
+        let src = make_sources(&[("module Top {}", "a.veryl")]);
+        let o1 = default_opts();
+        let mut o2 = default_opts();
+        o2.force_support = true;
+        assert_ne!(
+            build_cache_key(&src, "Top", &o1, None),
+            build_cache_key(&src, "Top", &o2, None),
+        );
+    }
+
+    #[test]
+    fn profile_differs() {
+        let src = make_sources(&[("module Top {}", "a.veryl")]);
+        let o1 = default_opts();
+        let mut o2 = default_opts();
+        o2.profile = true;
+        assert_ne!(
+            build_cache_key(&src, "Top", &o1, None),
+            build_cache_key(&src, "Top", &o2, None),
+        );
+    }
+
+    #[test]
+    fn branch_coverage_differs() {
+        let src = make_sources(&[("module Top {}", "a.veryl")]);
+        let o1 = default_opts();
+        let mut o2 = default_opts();
+        o2.branch_coverage = true;
+        assert_ne!(
+            build_cache_key(&src, "Top", &o1, None),
+            build_cache_key(&src, "Top", &o2, None),
+        );
+    }
+
     #[test]
     fn clock_type_differs() {
         let src = make_sources(&[("module Top {}", "a.veryl")]);
@@ -3221,6 +4061,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dump_filter_differs() {
+        let src = make_sources(&[("module Top {}", "a.veryl")]);
+        let o1 = default_opts();
+        let mut o2 = default_opts();
+        // Cached waveform descriptors are already filtered.
+        o2.dump_filter = celox::DumpFilter::new().arrays(false);
+        assert_ne!(
+            build_cache_key(&src, "Top", &o1, None),
+            build_cache_key(&src, "Top", &o2, None),
+        );
+    }
+
     #[test]
     fn false_loops_differ() {
         let src = make_sources(&[("module Top {}", "a.veryl")]);
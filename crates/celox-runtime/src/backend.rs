@@ -56,6 +56,18 @@ pub trait SimBackend {
         (1, self.eval_comb_apply_ff_at(event))
     }
 
+    /// Evaluate combinational logic and call `after_unit` at each procedural
+    /// store boundary so the host can reapply forced values. Backends built
+    /// without force support run the whole pass and call `after_unit` once.
+    fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), SimulatorErrorCode> {
+        self.eval_comb()?;
+        after_unit(self);
+        Ok(())
+    }
+
     /// Evaluate FF domain without applying (for cascaded clocks).
     fn eval_only_ff_at(&mut self, event: Self::Event) -> Result<(), SimulatorErrorCode>;
 
@@ -93,6 +105,14 @@ pub trait SimBackend {
     fn id_to_event_slice(&self) -> &[Self::Event];
     fn num_events(&self) -> usize;
 
+    // ── instancing ──────────────────────────────────────────────
+    /// Create an independent backend that shares this backend's compiled
+    /// code and starts from a copy of its state memory. Runtime event
+    /// buffers are not copied; the fork starts with an empty buffer.
+    fn fork(&self) -> Self
+    where
+        Self: Sized;
+
     // ── trigger bits (for Simulation edge detection) ────────────
     fn clear_triggered_bits(&mut self);
     fn mark_triggered_bit(&mut self, id: usize);
@@ -0,0 +1,359 @@
+//! Statement and branch coverage.
+//!
+//! A design built with branch coverage carries one hit counter per arm of
+//! every `if`, `if_reset` and `case` in its `always_comb` and `always_ff`
+//! blocks. [`BranchCoverage`] is a snapshot of those counters keyed by source
+//! location and instance scope. Snapshots of separate runs merge by summing
+//! the counts of matching arms, and can be exported as LCOV tracefiles or
+//! Cobertura XML, where each branching statement is also a covered line.
+
+use celox_design::{CoverageArmKind, CoverageProcess};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+/// Hit count of one arm of one branching statement in one instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchHits {
+    /// Hierarchical name of the module instance.
+    pub scope: String,
+    pub file: String,
+    /// 1-based line of the branching statement.
+    pub line: u32,
+    /// 1-based column of the branching statement.
+    pub column: u32,
+    pub process: CoverageProcess,
+    pub arm: CoverageArmKind,
+    /// Number of evaluations that took this arm. A combinational block may
+    /// be evaluated more than once per step, so only `always_ff` counts are
+    /// exact numbers of executions.
+    pub hits: u64,
+}
+
+impl BranchHits {
+    fn key(&self) -> (&str, &str, u32, u32, CoverageArmKind) {
+        (&self.scope, &self.file, self.line, self.column, self.arm)
+    }
+}
+
+/// Branch hit counts of a design, in scope and source order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchCoverage {
+    branches: Vec<BranchHits>,
+}
+
+/// Counts of one branching statement, summed over all instances.
+struct Statement {
+    line: u32,
+    arms: Vec<(CoverageArmKind, u64)>,
+}
+
+impl Statement {
+    fn hits(&self) -> u64 {
+        self.arms.iter().map(|(_, hits)| hits).sum()
+    }
+
+    fn covered_arms(&self) -> usize {
+        self.arms.iter().filter(|(_, hits)| *hits > 0).count()
+    }
+}
+
+impl BranchCoverage {
+    pub fn new(branches: Vec<BranchHits>) -> Self {
+        Self { branches }
+    }
+
+    pub fn branches(&self) -> &[BranchHits] {
+        &self.branches
+    }
+
+    /// The arms of the statement at `line` of `file` in instance `scope`.
+    pub fn at(&self, scope: &str, file: &str, line: u32) -> Vec<&BranchHits> {
+        self.branches
+            .iter()
+            .filter(|branch| branch.scope == scope && branch.line == line && branch.file == file)
+            .collect()
+    }
+
+    pub fn total_branches(&self) -> usize {
+        self.branches.len()
+    }
+
+    /// Number of arms taken at least once.
+    pub fn covered_branches(&self) -> usize {
+        self.branches
+            .iter()
+            .filter(|branch| branch.hits > 0)
+            .count()
+    }
+
+    /// Add the counts of `other`, e.g. from another test run. Arms present
+    /// only in `other` are appended.
+    pub fn merge(&mut self, other: &BranchCoverage) {
+        let index = self
+            .branches
+            .iter()
+            .enumerate()
+            .map(|(index, branch)| {
+                let (scope, file, line, column, arm) = branch.key();
+                (
+                    (scope.to_string(), file.to_string(), line, column, arm),
+                    index,
+                )
+            })
+            .collect::<fxhash::FxHashMap<_, _>>();
+        for branch in &other.branches {
+            let (scope, file, line, column, arm) = branch.key();
+            match index.get(&(scope.to_string(), file.to_string(), line, column, arm)) {
+                Some(&index) => {
+                    let hits = &mut self.branches[index].hits;
+                    *hits = hits.saturating_add(branch.hits);
+                }
+                None => self.branches.push(branch.clone()),
+            }
+        }
+    }
+
+    /// Write the counts as JSON, readable again by [`Self::read_json`].
+    pub fn write_json(&self, writer: impl Write) -> std::io::Result<()> {
+        serde_json::to_writer_pretty(writer, self).map_err(std::io::Error::other)
+    }
+
+    /// Read counts written by [`Self::write_json`].
+    pub fn read_json(reader: impl Read) -> std::io::Result<Self> {
+        serde_json::from_reader(reader).map_err(std::io::Error::other)
+    }
+
+    /// Statements grouped by file, with instances folded together.
+    fn statements(&self) -> BTreeMap<&str, BTreeMap<(u32, u32), Statement>> {
+        let mut files = BTreeMap::<&str, BTreeMap<(u32, u32), Statement>>::new();
+        for branch in &self.branches {
+            let statement = files
+                .entry(&branch.file)
+                .or_default()
+                .entry((branch.line, branch.column))
+                .or_insert_with(|| Statement {
+                    line: branch.line,
+                    arms: Vec::new(),
+                });
+            match statement
+                .arms
+                .iter_mut()
+                .find(|(arm, _)| *arm == branch.arm)
+            {
+                Some((_, hits)) => *hits = hits.saturating_add(branch.hits),
+                None => statement.arms.push((branch.arm, branch.hits)),
+            }
+        }
+        files
+    }
+
+    /// Write an LCOV tracefile. Each branching statement is a line whose
+    /// count is the sum of its arms, and each arm is a branch.
+    pub fn write_lcov(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, "TN:")?;
+        for (file, statements) in self.statements() {
+            writeln!(writer, "SF:{file}")?;
+            let mut lines = BTreeMap::<u32, u64>::new();
+            let (mut found, mut hit) = (0, 0);
+            for (block, statement) in statements.values().enumerate() {
+                let executed = statement.hits();
+                let count = lines.entry(statement.line).or_default();
+                *count = count.saturating_add(executed);
+                for (branch, (_, hits)) in statement.arms.iter().enumerate() {
+                    if executed == 0 {
+                        writeln!(writer, "BRDA:{},{block},{branch},-", statement.line)?;
+                    } else {
+                        writeln!(writer, "BRDA:{},{block},{branch},{hits}", statement.line)?;
+                    }
+                }
+                found += statement.arms.len();
+                hit += statement.covered_arms();
+            }
+            writeln!(writer, "BRF:{found}")?;
+            writeln!(writer, "BRH:{hit}")?;
+            for (line, count) in &lines {
+                writeln!(writer, "DA:{line},{count}")?;
+            }
+            writeln!(writer, "LF:{}", lines.len())?;
+            writeln!(
+                writer,
+                "LH:{}",
+                lines.values().filter(|count| **count > 0).count()
+            )?;
+            writeln!(writer, "end_of_record")?;
+        }
+        Ok(())
+    }
+
+    /// Write a Cobertura XML report with one class per source file.
+    pub fn write_cobertura(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let files = self.statements();
+        let mut classes = Vec::new();
+        let (mut lines_valid, mut lines_covered) = (0, 0);
+        let (mut branches_valid, mut branches_covered) = (0, 0);
+        for (file, statements) in &files {
+            // line -> (hits, arms, covered arms)
+            let mut lines = BTreeMap::<u32, (u64, usize, usize)>::new();
+            for statement in statements.values() {
+                let line = lines.entry(statement.line).or_default();
+                line.0 = line.0.saturating_add(statement.hits());
+                line.1 += statement.arms.len();
+                line.2 += statement.covered_arms();
+            }
+            let valid = lines.len();
+            let covered = lines.values().filter(|line| line.0 > 0).count();
+            let arms = lines.values().map(|line| line.1).sum::<usize>();
+            let covered_arms = lines.values().map(|line| line.2).sum::<usize>();
+            lines_valid += valid;
+            lines_covered += covered;
+            branches_valid += arms;
+            branches_covered += covered_arms;
+            classes.push((*file, lines, rate(covered, valid), rate(covered_arms, arms)));
+        }
+
+        writeln!(writer, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            writer,
+            r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+        )?;
+        writeln!(
+            writer,
+            r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{lines_covered}" lines-valid="{lines_valid}" branches-covered="{branches_covered}" branches-valid="{branches_valid}" complexity="0" version="{}" timestamp="{}">"#,
+            rate(lines_covered, lines_valid),
+            rate(branches_covered, branches_valid),
+            env!("CARGO_PKG_VERSION"),
+            chrono::Utc::now().timestamp(),
+        )?;
+        writeln!(writer, "  <sources>")?;
+        writeln!(writer, "    <source>.</source>")?;
+        writeln!(writer, "  </sources>")?;
+        writeln!(writer, "  <packages>")?;
+        writeln!(
+            writer,
+            r#"    <package name="design" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            rate(lines_covered, lines_valid),
+            rate(branches_covered, branches_valid),
+        )?;
+        writeln!(writer, "      <classes>")?;
+        for (file, lines, line_rate, branch_rate) in classes {
+            let name = std::path::Path::new(file)
+                .file_stem()
+                .map_or(file.into(), |stem| stem.to_string_lossy());
+            writeln!(
+                writer,
+                r#"        <class name="{}" filename="{}" line-rate="{line_rate}" branch-rate="{branch_rate}" complexity="0">"#,
+                xml_escape(&name),
+                xml_escape(file),
+            )?;
+            writeln!(writer, "          <methods/>")?;
+            writeln!(writer, "          <lines>")?;
+            for (line, (hits, arms, covered)) in lines {
+                writeln!(
+                    writer,
+                    r#"            <line number="{line}" hits="{hits}" branch="true" condition-coverage="{}% ({covered}/{arms})"/>"#,
+                    covered * 100 / arms.max(1),
+                )?;
+            }
+            writeln!(writer, "          </lines>")?;
+            writeln!(writer, "        </class>")?;
+        }
+        writeln!(writer, "      </classes>")?;
+        writeln!(writer, "    </package>")?;
+        writeln!(writer, "  </packages>")?;
+        writeln!(writer, "</coverage>")
+    }
+}
+
+fn rate(covered: usize, valid: usize) -> String {
+    if valid == 0 {
+        "1".to_string()
+    } else {
+        format!("{:.4}", covered as f64 / valid as f64)
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hits(scope: &str, line: u32, arm: CoverageArmKind, hits: u64) -> BranchHits {
+        BranchHits {
+            scope: scope.to_string(),
+            file: "top.veryl".to_string(),
+            line,
+            column: 9,
+            process: CoverageProcess::Ff,
+            arm,
+            hits,
+        }
+    }
+
+    #[test]
+    fn merge_sums_matching_arms() {
+        let mut first = BranchCoverage::new(vec![
+            hits("Top", 3, CoverageArmKind::Then, 2),
+            hits("Top", 3, CoverageArmKind::Else, 0),
+        ]);
+        let second = BranchCoverage::new(vec![
+            hits("Top", 3, CoverageArmKind::Else, 5),
+            hits("Top.u", 3, CoverageArmKind::Then, 1),
+        ]);
+        first.merge(&second);
+        assert_eq!(first.total_branches(), 3);
+        assert_eq!(first.covered_branches(), 3);
+        assert_eq!(first.at("Top", "top.veryl", 3)[1].hits, 5);
+    }
+
+    #[test]
+    fn lcov_folds_instances_into_source_lines() {
+        let coverage = BranchCoverage::new(vec![
+            hits("Top.a", 3, CoverageArmKind::Then, 2),
+            hits("Top.a", 3, CoverageArmKind::Else, 0),
+            hits("Top.b", 3, CoverageArmKind::Then, 1),
+            hits("Top.b", 3, CoverageArmKind::Else, 0),
+            hits("Top.a", 7, CoverageArmKind::CaseArm(0), 0),
+            hits("Top.a", 7, CoverageArmKind::Default, 0),
+        ]);
+        let mut lcov = Vec::new();
+        coverage.write_lcov(&mut lcov).unwrap();
+        assert_eq!(
+            String::from_utf8(lcov).unwrap(),
+            "TN:\nSF:top.veryl\nBRDA:3,0,0,3\nBRDA:3,0,1,0\nBRDA:7,1,0,-\nBRDA:7,1,1,-\n\
+             BRF:4\nBRH:1\nDA:3,3\nDA:7,0\nLF:2\nLH:1\nend_of_record\n"
+        );
+
+        let mut xml = Vec::new();
+        coverage.write_cobertura(&mut xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(xml.contains(r#"filename="top.veryl""#), "{xml}");
+        assert!(
+            xml.contains(
+                r#"<line number="3" hits="3" branch="true" condition-coverage="50% (1/2)"/>"#
+            ),
+            "{xml}"
+        );
+        assert!(
+            xml.contains(r#"branches-covered="1" branches-valid="4""#),
+            "{xml}"
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let coverage = BranchCoverage::new(vec![hits("Top", 3, CoverageArmKind::CaseArm(2), 4)]);
+        let mut json = Vec::new();
+        coverage.write_json(&mut json).unwrap();
+        assert_eq!(
+            BranchCoverage::read_json(json.as_slice()).unwrap(),
+            coverage
+        );
+    }
+}
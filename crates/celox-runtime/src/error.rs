@@ -1,3 +1,5 @@
+use crate::InterruptReason;
+
 #[derive(Debug, Clone, Eq)]
 pub enum SimulatorErrorCode {
     DetectedTrueLoop,
@@ -11,6 +13,11 @@ pub enum SimulatorErrorCode {
     },
     InternalError,
     NotAnEvent(String),
+    /// The run was cancelled or hit a limit of its [`crate::Watchdog`] at `time`.
+    Interrupted {
+        reason: InterruptReason,
+        time: u64,
+    },
 }
 
 impl PartialEq for SimulatorErrorCode {
@@ -37,6 +44,16 @@ impl PartialEq for SimulatorErrorCode {
                 },
             ) => a == b && sa == sb,
             (Self::NotAnEvent(a), Self::NotAnEvent(b)) => a == b,
+            (
+                Self::Interrupted {
+                    reason: a,
+                    time: ta,
+                },
+                Self::Interrupted {
+                    reason: b,
+                    time: tb,
+                },
+            ) => a == b && ta == tb,
             _ => false,
         }
     }
@@ -64,6 +81,7 @@ impl std::fmt::Display for SimulatorErrorCode {
                 "Signal '{}' is not an event (only clock and async reset signals can be scheduled). Use `modify()` for synchronous signals.",
                 name
             ),
+            Self::Interrupted { reason, time } => write!(f, "{reason} at time {time}"),
         }
     }
 }
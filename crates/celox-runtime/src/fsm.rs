@@ -0,0 +1,363 @@
+//! FSM state and transition coverage.
+//!
+//! [`FsmCoverage`] reads each state register from every sampled memory
+//! image. Entering a variant counts as a visit of that state, and a change
+//! from one variant to another counts as a transition. Values that match no
+//! variant, including any with X or Z bits, are not states; leaving them
+//! counts as a visit but not as a transition.
+
+use celox_design::FsmState;
+use celox_state_layout::get_byte_size;
+use serde::Serialize;
+use std::io::Write;
+
+/// A state register to track.
+#[derive(Debug, Clone)]
+pub struct FsmDesc {
+    /// Hierarchical name, the scope and the register name joined by `.`.
+    pub name: String,
+    /// Name of the enum type.
+    pub type_name: String,
+    pub states: Vec<FsmState>,
+    /// Byte offset of the register in simulation memory.
+    pub offset: usize,
+    /// Bit width of the register, at most 64.
+    pub width: usize,
+    pub is_4state: bool,
+}
+
+/// Visit and transition counts of one state register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateMachineCoverage {
+    pub name: String,
+    pub type_name: String,
+    states: Vec<String>,
+    visits: Vec<u64>,
+    /// `transitions[from][to]`; the diagonal stays zero.
+    transitions: Vec<Vec<u64>>,
+}
+
+impl StateMachineCoverage {
+    fn new(desc: &FsmDesc) -> Self {
+        let count = desc.states.len();
+        Self {
+            name: desc.name.clone(),
+            type_name: desc.type_name.clone(),
+            states: desc.states.iter().map(|state| state.name.clone()).collect(),
+            visits: vec![0; count],
+            transitions: vec![vec![0; count]; count],
+        }
+    }
+
+    /// Variant names in declaration order.
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    /// How many times `state` was entered, or `None` for an unknown name.
+    pub fn visits(&self, state: &str) -> Option<u64> {
+        Some(self.visits[self.index(state)?])
+    }
+
+    /// How many times the register went from `from` to `to`.
+    pub fn transitions(&self, from: &str, to: &str) -> Option<u64> {
+        Some(self.transitions[self.index(from)?][self.index(to)?])
+    }
+
+    /// Transitions that occurred, with their counts, in declaration order.
+    pub fn taken_transitions(&self) -> Vec<(&str, &str, u64)> {
+        self.pairs()
+            .filter(|&(from, to)| self.transitions[from][to] > 0)
+            .map(|(from, to)| {
+                (
+                    self.states[from].as_str(),
+                    self.states[to].as_str(),
+                    self.transitions[from][to],
+                )
+            })
+            .collect()
+    }
+
+    /// States that were never entered.
+    pub fn unreached_states(&self) -> Vec<&str> {
+        self.states
+            .iter()
+            .zip(&self.visits)
+            .filter(|(_, visits)| **visits == 0)
+            .map(|(state, _)| state.as_str())
+            .collect()
+    }
+
+    /// Pairs of distinct states the register never went between.
+    pub fn untaken_transitions(&self) -> Vec<(&str, &str)> {
+        self.pairs()
+            .filter(|&(from, to)| self.transitions[from][to] == 0)
+            .map(|(from, to)| (self.states[from].as_str(), self.states[to].as_str()))
+            .collect()
+    }
+
+    pub fn total_transitions(&self) -> usize {
+        self.pairs().count()
+    }
+
+    pub fn covered_transitions(&self) -> usize {
+        self.taken_transitions().len()
+    }
+
+    fn index(&self, state: &str) -> Option<usize> {
+        self.states.iter().position(|name| name == state)
+    }
+
+    fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let count = self.states.len();
+        (0..count).flat_map(move |from| {
+            (0..count)
+                .filter(move |&to| to != from)
+                .map(move |to| (from, to))
+        })
+    }
+
+    fn enter(&mut self, previous: Option<usize>, current: Option<usize>) {
+        let Some(current) = current else {
+            return;
+        };
+        self.visits[current] += 1;
+        if let Some(previous) = previous {
+            self.transitions[previous][current] += 1;
+        }
+    }
+}
+
+/// Visit and transition counts of a set of state registers.
+#[derive(Debug, Clone)]
+pub struct FsmCoverage {
+    descs: Vec<FsmDesc>,
+    machines: Vec<StateMachineCoverage>,
+    /// The last sampled value of each register, `None` while it held X or Z.
+    previous: Vec<Option<u64>>,
+}
+
+impl FsmCoverage {
+    /// Track `descs`, counting their values in `memory` as the first visits.
+    pub fn new(descs: Vec<FsmDesc>, memory: &[u8]) -> Self {
+        let mut coverage = Self {
+            machines: descs.iter().map(StateMachineCoverage::new).collect(),
+            previous: vec![None; descs.len()],
+            descs,
+        };
+        for index in 0..coverage.descs.len() {
+            let value = register_value(&coverage.descs[index], memory);
+            let state = state_index(&coverage.descs[index], value);
+            coverage.machines[index].enter(None, state);
+            coverage.previous[index] = value;
+        }
+        coverage
+    }
+
+    /// Record the values in `memory`.
+    pub fn sample(&mut self, memory: &[u8]) {
+        for (index, desc) in self.descs.iter().enumerate() {
+            let value = register_value(desc, memory);
+            if value == self.previous[index] {
+                continue;
+            }
+            let previous = state_index(desc, self.previous[index]);
+            self.machines[index].enter(previous, state_index(desc, value));
+            self.previous[index] = value;
+        }
+    }
+
+    pub fn machines(&self) -> &[StateMachineCoverage] {
+        &self.machines
+    }
+
+    /// The register with hierarchical name `name`.
+    pub fn machine(&self, name: &str) -> Option<&StateMachineCoverage> {
+        self.machines.iter().find(|machine| machine.name == name)
+    }
+
+    pub fn total_states(&self) -> usize {
+        self.machines
+            .iter()
+            .map(|machine| machine.states.len())
+            .sum()
+    }
+
+    pub fn covered_states(&self) -> usize {
+        self.total_states()
+            - self
+                .machines
+                .iter()
+                .map(|machine| machine.unreached_states().len())
+                .sum::<usize>()
+    }
+
+    pub fn total_transitions(&self) -> usize {
+        self.machines
+            .iter()
+            .map(StateMachineCoverage::total_transitions)
+            .sum()
+    }
+
+    pub fn covered_transitions(&self) -> usize {
+        self.machines
+            .iter()
+            .map(StateMachineCoverage::covered_transitions)
+            .sum()
+    }
+
+    /// Write the counts as a JSON FSM-coverage report.
+    pub fn write_json(&self, writer: impl Write) -> std::io::Result<()> {
+        #[derive(Serialize)]
+        struct Report<'a> {
+            total_states: usize,
+            covered_states: usize,
+            total_transitions: usize,
+            covered_transitions: usize,
+            machines: Vec<MachineReport<'a>>,
+        }
+        #[derive(Serialize)]
+        struct MachineReport<'a> {
+            name: &'a str,
+            #[serde(rename = "type")]
+            type_name: &'a str,
+            states: Vec<StateReport<'a>>,
+            transitions: Vec<TransitionReport<'a>>,
+            unreached_states: Vec<&'a str>,
+            untaken_transitions: Vec<(&'a str, &'a str)>,
+        }
+        #[derive(Serialize)]
+        struct StateReport<'a> {
+            name: &'a str,
+            visits: u64,
+        }
+        #[derive(Serialize)]
+        struct TransitionReport<'a> {
+            from: &'a str,
+            to: &'a str,
+            count: u64,
+        }
+
+        let report = Report {
+            total_states: self.total_states(),
+            covered_states: self.covered_states(),
+            total_transitions: self.total_transitions(),
+            covered_transitions: self.covered_transitions(),
+            machines: self
+                .machines
+                .iter()
+                .map(|machine| MachineReport {
+                    name: &machine.name,
+                    type_name: &machine.type_name,
+                    states: machine
+                        .states
+                        .iter()
+                        .zip(&machine.visits)
+                        .map(|(name, &visits)| StateReport { name, visits })
+                        .collect(),
+                    transitions: machine
+                        .taken_transitions()
+                        .into_iter()
+                        .map(|(from, to, count)| TransitionReport { from, to, count })
+                        .collect(),
+                    unreached_states: machine.unreached_states(),
+                    untaken_transitions: machine.untaken_transitions(),
+                })
+                .collect(),
+        };
+        serde_json::to_writer_pretty(writer, &report).map_err(std::io::Error::other)
+    }
+}
+
+/// The register's value, or `None` if any bit is X or Z.
+fn register_value(desc: &FsmDesc, memory: &[u8]) -> Option<u64> {
+    let size = get_byte_size(desc.width);
+    let read = |offset: usize| {
+        memory[offset..offset + size]
+            .iter()
+            .rev()
+            .fold(0u64, |value, byte| (value << 8) | u64::from(*byte))
+    };
+    let mask = if desc.width >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << desc.width) - 1
+    };
+    if desc.is_4state && read(desc.offset + size) & mask != 0 {
+        return None;
+    }
+    Some(read(desc.offset) & mask)
+}
+
+fn state_index(desc: &FsmDesc, value: Option<u64>) -> Option<usize> {
+    let value = value?;
+    desc.states.iter().position(|state| state.value == value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desc(is_4state: bool) -> FsmDesc {
+        FsmDesc {
+            name: "Top.state".to_string(),
+            type_name: "State".to_string(),
+            states: ["Idle", "Run", "Done"]
+                .into_iter()
+                .enumerate()
+                .map(|(value, name)| FsmState {
+                    name: name.to_string(),
+                    value: value as u64,
+                })
+                .collect(),
+            offset: 0,
+            width: 2,
+            is_4state,
+        }
+    }
+
+    #[test]
+    fn counts_visits_and_transitions() {
+        let mut coverage = FsmCoverage::new(vec![desc(false)], &[0]);
+        for value in [1, 1, 0, 1, 3, 0] {
+            coverage.sample(&[value]);
+        }
+        let machine = coverage.machine("Top.state").unwrap();
+        assert_eq!(machine.visits("Idle"), Some(3));
+        assert_eq!(machine.visits("Run"), Some(2));
+        assert_eq!(machine.transitions("Idle", "Run"), Some(2));
+        assert_eq!(machine.transitions("Run", "Idle"), Some(1));
+        // 1 -> 3 -> 0 passes through an unnamed value.
+        assert_eq!(machine.transitions("Run", "Done"), Some(0));
+        assert_eq!(machine.unreached_states(), ["Done"]);
+        assert_eq!(
+            machine.untaken_transitions(),
+            [
+                ("Idle", "Done"),
+                ("Run", "Done"),
+                ("Done", "Idle"),
+                ("Done", "Run")
+            ]
+        );
+        assert_eq!(coverage.covered_states(), 2);
+        assert_eq!(coverage.total_transitions(), 6);
+        assert_eq!(coverage.covered_transitions(), 2);
+
+        let mut json = Vec::new();
+        coverage.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["machines"][0]["type"], "State");
+        assert_eq!(json["machines"][0]["unreached_states"][0], "Done");
+    }
+
+    #[test]
+    fn unknown_bits_are_not_a_state() {
+        let mut coverage = FsmCoverage::new(vec![desc(true)], &[0, 0b11]);
+        coverage.sample(&[2, 0]);
+        coverage.sample(&[1, 0]);
+        let machine = coverage.machine("Top.state").unwrap();
+        assert_eq!(machine.visits("Idle"), Some(0));
+        assert_eq!(machine.visits("Done"), Some(1));
+        assert_eq!(machine.taken_transitions(), [("Done", "Run", 1)]);
+    }
+}
@@ -0,0 +1,422 @@
+use crate::timescale::{Timescale, TimescaleError};
+use crate::vcd::{VcdExternalSignalDesc, VcdSignalDesc, VcdWriter};
+use crate::waveform::DumpGate;
+use celox_state_layout::get_byte_size;
+use fst_writer::{
+    FstBodyWriter, FstFileType, FstInfo, FstScopeType, FstSignalId, FstSignalType, FstVarDirection,
+    FstVarType, FstWriteError,
+};
+use num_bigint::BigUint;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// Buffered value changes are compressed into a new block past this size.
+const FLUSH_AT: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Copy)]
+enum FstWriterSource {
+    Memory { offset: usize, is_4state: bool },
+    External { index: usize },
+}
+
+struct FstWriterSignal {
+    id: Option<FstSignalId>,
+    scope: String,
+    name: String,
+    width: usize,
+    source: FstWriterSource,
+}
+
+/// Writes the same signals as [`VcdWriter`] in the compressed FST format.
+///
+/// The hierarchy is written on the first dump, and the file is only complete
+/// once the writer is finished, either by [`Self::finish`] or on drop.
+pub struct FstWriter {
+    path: PathBuf,
+    body: Option<FstBodyWriter<BufWriter<File>>>,
+    signals: Vec<FstWriterSignal>,
+    last_values: Vec<Option<(BigUint, BigUint)>>,
+    timestamp: Option<u64>,
+    external_count: usize,
+    timescale: Timescale,
+    gate: DumpGate,
+    finished: bool,
+}
+
+fn io_error(error: FstWriteError) -> std::io::Error {
+    match error {
+        FstWriteError::Io(error) => error,
+        other => std::io::Error::other(other),
+    }
+}
+
+impl FstWriter {
+    pub fn new<P: AsRef<Path>>(path: P, descs: &[VcdSignalDesc]) -> std::io::Result<Self> {
+        // Fail early on an unwritable path; the header is written on first dump.
+        File::create(path.as_ref())?;
+        let signals = descs
+            .iter()
+            .map(|desc| FstWriterSignal {
+                id: None,
+                scope: desc.scope.clone(),
+                name: desc.name.clone(),
+                width: desc.width,
+                source: FstWriterSource::Memory {
+                    offset: desc.offset,
+                    is_4state: desc.is_4state,
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let last_values = vec![None; signals.len()];
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            body: None,
+            signals,
+            last_values,
+            timestamp: None,
+            external_count: 0,
+            timescale: Timescale::default(),
+            gate: DumpGate::default(),
+            finished: false,
+        })
+    }
+
+    /// Start a new file at `path` that records the same signals as this
+    /// writer. See [`VcdWriter::fork_to`].
+    pub fn fork_to<P: AsRef<Path>>(&self, path: P) -> std::io::Result<Self> {
+        File::create(path.as_ref())?;
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            body: None,
+            signals: self
+                .signals
+                .iter()
+                .map(|signal| FstWriterSignal {
+                    id: None,
+                    scope: signal.scope.clone(),
+                    name: signal.name.clone(),
+                    width: signal.width,
+                    source: signal.source,
+                })
+                .collect(),
+            last_values: vec![None; self.last_values.len()],
+            timestamp: None,
+            external_count: self.external_count,
+            timescale: self.timescale,
+            gate: self.gate.fresh(),
+            finished: false,
+        })
+    }
+
+    /// Sets the timescale written in the header. Must be called before the
+    /// first dump.
+    pub fn set_timescale(&mut self, timescale: Timescale) -> Result<(), TimescaleError> {
+        if self.body.is_some() && timescale != self.timescale {
+            return Err(TimescaleError::WaveformStarted);
+        }
+        self.timescale = timescale;
+        Ok(())
+    }
+
+    /// Resume or stop recording. See [`VcdWriter::set_enabled`].
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.gate.set_enabled(enabled);
+    }
+
+    /// Record only dumps within `[start, end]`. See [`VcdWriter::set_window`].
+    pub fn set_window(&mut self, window: Option<(u64, u64)>) {
+        self.gate.set_window(window);
+    }
+
+    /// Adds externally supplied signals before the first dump. See
+    /// [`VcdWriter::add_external_signals`].
+    pub fn add_external_signals(&mut self, descs: &[VcdExternalSignalDesc]) -> std::io::Result<()> {
+        if descs.is_empty() {
+            return Ok(());
+        }
+        if self.external_count != 0 {
+            let existing = self
+                .signals
+                .iter()
+                .filter(|signal| matches!(signal.source, FstWriterSource::External { .. }))
+                .zip(descs)
+                .all(|(signal, desc)| {
+                    signal.scope == desc.scope
+                        && signal.name == desc.name
+                        && signal.width == desc.width
+                });
+            if existing && self.external_count == descs.len() {
+                return Ok(());
+            }
+        }
+        if self.body.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "cannot add external FST signals after the first dump",
+            ));
+        }
+        for desc in descs {
+            let index = self.external_count;
+            self.external_count += 1;
+            self.signals.push(FstWriterSignal {
+                id: None,
+                scope: desc.scope.clone(),
+                name: desc.name.clone(),
+                width: desc.width,
+                source: FstWriterSource::External { index },
+            });
+            self.last_values.push(None);
+        }
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        if self.body.is_some() {
+            return Ok(());
+        }
+        let info = FstInfo {
+            start_time: 0,
+            timescale_exponent: self.timescale.exponent(),
+            version: "celox".to_string(),
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            file_type: FstFileType::Verilog,
+        };
+        let mut header = fst_writer::open_fst(&self.path, &info).map_err(io_error)?;
+
+        let mut scope_order = Vec::<String>::new();
+        let mut scope_groups = Vec::<Vec<usize>>::new();
+        let mut scope_idx = fxhash::FxHashMap::<String, usize>::default();
+        for (signal_index, signal) in self.signals.iter().enumerate() {
+            if let Some(index) = scope_idx.get(&signal.scope).copied() {
+                scope_groups[index].push(signal_index);
+            } else {
+                let index = scope_order.len();
+                scope_idx.insert(signal.scope.clone(), index);
+                scope_order.push(signal.scope.clone());
+                scope_groups.push(vec![signal_index]);
+            }
+        }
+        for (scope, group) in scope_order.iter().zip(scope_groups) {
+            header
+                .scope(scope, "", FstScopeType::Module)
+                .map_err(io_error)?;
+            for signal_index in group {
+                let signal = &mut self.signals[signal_index];
+                let id = header
+                    .var(
+                        &signal.name,
+                        FstSignalType::bit_vec(signal.width as u32),
+                        FstVarType::Wire,
+                        FstVarDirection::Implicit,
+                        None,
+                    )
+                    .map_err(io_error)?;
+                signal.id = Some(id);
+            }
+            header.up_scope().map_err(io_error)?;
+        }
+        self.body = Some(header.finish().map_err(io_error)?);
+        Ok(())
+    }
+
+    /// Dump all changed signals at the given timestamp.
+    ///
+    /// `memory` is the raw JIT memory (stable region or full buffer).
+    pub fn dump(&mut self, timestamp: u64, memory: &[u8]) -> std::io::Result<()> {
+        self.dump_with_external(timestamp, memory, &[])
+    }
+
+    /// Dump memory-backed signals and external values in registration order.
+    pub fn dump_with_external(
+        &mut self,
+        timestamp: u64,
+        memory: &[u8],
+        external: &[(BigUint, BigUint)],
+    ) -> std::io::Result<()> {
+        if external.len() != self.external_count {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "expected {} external FST values, got {}",
+                    self.external_count,
+                    external.len()
+                ),
+            ));
+        }
+        if !self.gate.records(timestamp) {
+            if self.body.is_some() && !self.gate.blanked {
+                self.write_unknown(timestamp)?;
+                self.gate.blanked = true;
+            }
+            return Ok(());
+        }
+        self.gate.blanked = false;
+        self.write_header()?;
+        self.time_change(timestamp)?;
+        let body = self.body.as_mut().expect("header written");
+
+        let mut bits = Vec::new();
+        for (i, sig) in self.signals.iter().enumerate() {
+            let (current_val, current_mask) = match sig.source {
+                FstWriterSource::Memory { offset, is_4state } => {
+                    let value = VcdWriter::read_value(memory, offset, sig.width);
+                    let mask = if is_4state {
+                        let byte_size = get_byte_size(sig.width);
+                        VcdWriter::read_value(memory, offset + byte_size, sig.width)
+                    } else {
+                        BigUint::from(0u32)
+                    };
+                    (value, mask)
+                }
+                FstWriterSource::External { index } => {
+                    let (value, mask) = &external[index];
+                    (
+                        VcdWriter::mask_to_width(value.clone(), sig.width),
+                        VcdWriter::mask_to_width(mask.clone(), sig.width),
+                    )
+                }
+            };
+
+            let changed = match &self.last_values[i] {
+                Some((pv, pm)) => pv != &current_val || pm != &current_mask,
+                None => true,
+            };
+            if changed {
+                bits.clear();
+                bits.extend((0..sig.width as u64).rev().map(|bit| {
+                    match (current_mask.bit(bit), current_val.bit(bit)) {
+                        (false, false) => b'0',
+                        (false, true) => b'1',
+                        (true, false) => b'z',
+                        (true, true) => b'x',
+                    }
+                }));
+                let id = sig.id.expect("header assigns every signal an id");
+                body.signal_change(id, &bits).map_err(io_error)?;
+                self.last_values[i] = Some((current_val, current_mask));
+            }
+        }
+        if body.size() >= FLUSH_AT {
+            body.flush().map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    fn time_change(&mut self, timestamp: u64) -> std::io::Result<()> {
+        let body = self.body.as_mut().expect("header written");
+        // FST time only moves forward; earlier timestamps, as after
+        // restoring a checkpoint, are recorded at the latest time.
+        if self.timestamp.is_none_or(|last| timestamp > last) {
+            body.time_change(timestamp).map_err(io_error)?;
+            self.timestamp = Some(timestamp);
+        }
+        Ok(())
+    }
+
+    /// Write every signal as X and forget the last values, so the next
+    /// recorded dump writes everything again.
+    fn write_unknown(&mut self, timestamp: u64) -> std::io::Result<()> {
+        self.time_change(timestamp)?;
+        let body = self.body.as_mut().expect("header written");
+        for signal in &self.signals {
+            let id = signal.id.expect("header assigns every signal an id");
+            body.signal_change(id, &vec![b'x'; signal.width])
+                .map_err(io_error)?;
+        }
+        self.last_values.fill(None);
+        Ok(())
+    }
+
+    /// Write the remaining value changes and complete the file header.
+    pub fn finish(mut self) -> std::io::Result<()> {
+        self.finish_in_place()
+    }
+
+    fn finish_in_place(&mut self) -> std::io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        self.write_header()?;
+        match self.body.take() {
+            Some(body) => body.finish().map_err(io_error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for FstWriter {
+    fn drop(&mut self) {
+        let _ = self.finish_in_place();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fst_reader::{FstFilter, FstHierarchyEntry, FstReader, FstSignalValue};
+
+    #[test]
+    fn round_trips_four_state_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("round-trip.fst");
+        let descs = [
+            VcdSignalDesc {
+                scope: "top".into(),
+                name: "count".into(),
+                offset: 0,
+                width: 8,
+                is_4state: false,
+            },
+            VcdSignalDesc {
+                scope: "top".into(),
+                name: "nibble".into(),
+                offset: 1,
+                width: 4,
+                is_4state: true,
+            },
+        ];
+        let mut writer = FstWriter::new(&path, &descs).unwrap();
+        writer.set_timescale("10ps".parse().unwrap()).unwrap();
+        writer.dump(0, &[3, 0b0101, 0b0011]).unwrap();
+        writer.dump(5, &[3, 0b0101, 0b0011]).unwrap();
+        writer.dump(10, &[4, 0b1010, 0]).unwrap();
+        writer.finish().unwrap();
+
+        let file = std::io::BufReader::new(File::open(&path).unwrap());
+        let mut reader = FstReader::open(file).unwrap();
+        assert_eq!(reader.get_header().timescale_exponent, -11);
+        let mut names = Vec::new();
+        reader
+            .read_hierarchy(|entry| {
+                if let FstHierarchyEntry::Var { name, length, .. } = entry {
+                    names.push((name, length));
+                }
+            })
+            .unwrap();
+        assert_eq!(names, [("count".to_string(), 8), ("nibble".to_string(), 4)]);
+
+        let mut changes = Vec::new();
+        reader
+            .read_signals(&FstFilter::all(), |time, handle, value| {
+                if let FstSignalValue::String(bits) = value {
+                    let bits = String::from_utf8(bits.to_vec()).unwrap();
+                    changes.push((time, handle.get_index(), bits));
+                }
+            })
+            .unwrap();
+        changes.sort();
+        assert_eq!(
+            changes,
+            [
+                (0, 0, "00000011".to_string()),
+                (0, 1, "01zx".to_string()),
+                (10, 0, "00000100".to_string()),
+                (10, 1, "1010".to_string()),
+            ]
+        );
+    }
+}
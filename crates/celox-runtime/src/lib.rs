@@ -1,23 +1,50 @@
 //! Backend-independent executable simulation contracts and utilities.
 
 pub mod backend;
+mod coverage;
 mod error;
 mod event_buffer;
+mod fsm;
+mod fst;
+mod memory_file;
 mod reflection;
 pub mod scheduler;
 mod simulation;
 mod testbench;
+mod timescale;
+mod toggle;
 mod vcd;
+mod watchdog;
+mod waveform;
+mod waveform_diff;
+mod waveform_reader;
 
+pub use coverage::{BranchCoverage, BranchHits};
 pub use error::SimulatorErrorCode;
 pub use event_buffer::RuntimeEventBuffer;
+pub use fsm::{FsmCoverage, FsmDesc, StateMachineCoverage};
+pub use fst::FstWriter;
+pub use memory_file::{
+    MemoryFileError, MemoryFileFormat, MemoryWord, format_memory_file, parse_memory_file,
+    read_memory_file, write_memory_file,
+};
 pub use reflection::{
     DesignReflection, ReflectionScope, ReflectionScopeId, ReflectionSignal, ReflectionSignalId,
     SignalDirection,
 };
-pub use simulation::{EventInfo, SimulationExecutor, SimulationState};
+pub use scheduler::ClockSpec;
+pub use simulation::{
+    EventInfo, ScheduledEventSnapshot, ScheduledValueSnapshot, SimulationExecutor, SimulationState,
+    SimulationStateSnapshot,
+};
 pub use testbench::bind_testbench_program;
+pub use timescale::{Timescale, TimescaleError};
+pub use toggle::{BitToggles, SignalToggles, ToggleCoverage};
 pub use vcd::{VcdExternalSignalDesc, VcdSignalDesc, VcdWriter};
+pub use watchdog::{CancelToken, InterruptReason, RunLimits, Watchdog};
+pub use waveform::{DumpFilter, WaveformFormat, WaveformWriter};
+pub use waveform_diff::{SignalDivergence, WaveformDiff, WaveformDiffOptions};
+pub use waveform_reader::{Waveform, WaveformReadError, WaveformSignal};
 
 pub type AbsoluteAddr = celox_design::StateAddr;
 pub type MemoryLayout = celox_state_layout::MemoryLayout<AbsoluteAddr>;
@@ -0,0 +1,331 @@
+//! Memory image files in the `$readmemh` / `$readmemb` text format.
+//!
+//! A file is a whitespace-separated list of words in hexadecimal or binary,
+//! with `//` and `/* */` comments, `_` digit separators, `x`/`z` digits, and
+//! `@address` directives (always hexadecimal) that move the next word to a
+//! new element index. [`format_memory_file`] writes the same format back, as
+//! `$writememh` / `$writememb` do.
+
+use std::path::Path;
+
+use num_bigint::BigUint;
+
+/// Radix of the words in a memory file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryFileFormat {
+    /// `$readmemh` format.
+    Hex,
+    /// `$readmemb` format.
+    Binary,
+}
+
+impl MemoryFileFormat {
+    fn radix(self) -> u32 {
+        match self {
+            Self::Hex => 16,
+            Self::Binary => 2,
+        }
+    }
+
+    fn bits_per_digit(self) -> usize {
+        match self {
+            Self::Hex => 4,
+            Self::Binary => 1,
+        }
+    }
+}
+
+/// One word of a memory file and the element index it is loaded into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryWord {
+    pub address: usize,
+    pub value: BigUint,
+    /// Bits given as `x` or `z`.
+    pub mask: BigUint,
+}
+
+/// Failure while reading a memory file.
+#[derive(Debug)]
+pub enum MemoryFileError {
+    Io(std::io::Error),
+    /// A word or `@address` directive is malformed.
+    Syntax {
+        line: usize,
+        token: String,
+    },
+    /// A word would be loaded past the end of the memory.
+    OutOfRange {
+        address: usize,
+        depth: usize,
+    },
+}
+
+impl std::fmt::Display for MemoryFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Syntax { line, token } => write!(f, "line {line}: invalid token '{token}'"),
+            Self::OutOfRange { address, depth } => write!(
+                f,
+                "address {address} is outside a memory of {depth} elements"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MemoryFileError {}
+
+impl From<std::io::Error> for MemoryFileError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Parse the words of a memory file for a memory of `depth` elements of
+/// `width` bits. Words wider than `width` are truncated.
+pub fn parse_memory_file(
+    content: &str,
+    format: MemoryFileFormat,
+    width: usize,
+    depth: usize,
+) -> Result<Vec<MemoryWord>, MemoryFileError> {
+    let keep = (BigUint::from(1u8) << width) - 1u8;
+    let mut words = Vec::new();
+    let mut address = 0usize;
+    for (line, token) in tokens(content) {
+        let syntax = || MemoryFileError::Syntax {
+            line,
+            token: token.clone(),
+        };
+        if let Some(directive) = token.strip_prefix('@') {
+            address = usize::from_str_radix(directive, 16).map_err(|_| syntax())?;
+            continue;
+        }
+        if address >= depth {
+            return Err(MemoryFileError::OutOfRange { address, depth });
+        }
+        let (value, mask) = parse_word(&token, format).ok_or_else(syntax)?;
+        words.push(MemoryWord {
+            address,
+            value: value & &keep,
+            mask: mask & &keep,
+        });
+        address += 1;
+    }
+    Ok(words)
+}
+
+/// Read and parse the memory file at `path`. See [`parse_memory_file`].
+pub fn read_memory_file(
+    path: impl AsRef<Path>,
+    format: MemoryFileFormat,
+    width: usize,
+    depth: usize,
+) -> Result<Vec<MemoryWord>, MemoryFileError> {
+    let content = std::fs::read_to_string(path)?;
+    parse_memory_file(&content, format, width, depth)
+}
+
+/// Render `words` one per line, each padded to the digits of a `width`-bit
+/// element. An `@address` line precedes every word that does not directly
+/// follow the previous one, including a first word past address 0.
+pub fn format_memory_file(words: &[MemoryWord], format: MemoryFileFormat, width: usize) -> String {
+    let mut out = String::new();
+    let mut next = 0usize;
+    for word in words {
+        if word.address != next {
+            out.push_str(&format!("@{:x}\n", word.address));
+        }
+        out.push_str(&format_word(word, format, width));
+        out.push('\n');
+        next = word.address + 1;
+    }
+    out
+}
+
+/// Write `words` to the file at `path`. See [`format_memory_file`].
+pub fn write_memory_file(
+    path: impl AsRef<Path>,
+    words: &[MemoryWord],
+    format: MemoryFileFormat,
+    width: usize,
+) -> Result<(), MemoryFileError> {
+    std::fs::write(path, format_memory_file(words, format, width))?;
+    Ok(())
+}
+
+/// Tokens with their 1-based line numbers, comments and `_` removed.
+fn tokens(content: &str) -> Vec<(usize, String)> {
+    let mut tokens = Vec::new();
+    let mut in_block_comment = false;
+    for (index, line) in content.lines().enumerate() {
+        let mut rest = line;
+        let mut text = String::new();
+        while !rest.is_empty() {
+            if in_block_comment {
+                match rest.find("*/") {
+                    Some(end) => {
+                        rest = &rest[end + 2..];
+                        in_block_comment = false;
+                        text.push(' ');
+                    }
+                    None => rest = "",
+                }
+            } else if let Some(start) = rest
+                .find('/')
+                .filter(|&start| matches!(rest.as_bytes().get(start + 1), Some(b'/') | Some(b'*')))
+            {
+                text.push_str(&rest[..start]);
+                if rest.as_bytes()[start + 1] == b'/' {
+                    rest = "";
+                } else {
+                    rest = &rest[start + 2..];
+                    in_block_comment = true;
+                }
+            } else {
+                text.push_str(rest);
+                rest = "";
+            }
+        }
+        tokens.extend(
+            text.split_whitespace()
+                .map(|token| token.replace('_', ""))
+                .filter(|token| !token.is_empty())
+                .map(|token| (index + 1, token)),
+        );
+    }
+    tokens
+}
+
+fn parse_word(token: &str, format: MemoryFileFormat) -> Option<(BigUint, BigUint)> {
+    let bits = format.bits_per_digit();
+    let digit_mask = BigUint::from((1u8 << bits) - 1);
+    let mut value = BigUint::default();
+    let mut mask = BigUint::default();
+    for ch in token.chars() {
+        value <<= bits;
+        mask <<= bits;
+        match ch {
+            'x' | 'X' | '?' => mask |= &digit_mask,
+            'z' | 'Z' => {
+                value |= &digit_mask;
+                mask |= &digit_mask;
+            }
+            _ => value |= BigUint::from(ch.to_digit(format.radix())?),
+        }
+    }
+    Some((value, mask))
+}
+
+/// One word as digits, most significant first. A digit whose bits are all
+/// unknown prints as `z` if they are all high-impedance and `x` otherwise; a
+/// partly unknown digit prints as `x`.
+fn format_word(word: &MemoryWord, format: MemoryFileFormat, width: usize) -> String {
+    let bits = format.bits_per_digit();
+    let digit_mask = (1u64 << bits) - 1;
+    let digit = |value: &BigUint, index: usize| {
+        let shift = index * bits;
+        ((value >> shift) & BigUint::from(digit_mask))
+            .iter_u64_digits()
+            .next()
+            .unwrap_or(0)
+    };
+    (0..width.div_ceil(bits).max(1))
+        .rev()
+        .map(|index| {
+            let mask = digit(&word.mask, index);
+            let value = digit(&word.value, index);
+            if mask == 0 {
+                std::char::from_digit(value as u32, format.radix()).unwrap()
+            } else if mask == digit_mask && value == digit_mask {
+                'z'
+            } else {
+                'x'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_comments_addresses_and_unknown_digits() {
+        let content = "// header\n01 a_b /* skip\n ff */ @4\n1x\n";
+        let words = parse_memory_file(content, MemoryFileFormat::Hex, 8, 8).unwrap();
+        assert_eq!(
+            words,
+            vec![
+                MemoryWord {
+                    address: 0,
+                    value: BigUint::from(0x01u8),
+                    mask: BigUint::default(),
+                },
+                MemoryWord {
+                    address: 1,
+                    value: BigUint::from(0xabu8),
+                    mask: BigUint::default(),
+                },
+                MemoryWord {
+                    address: 4,
+                    value: BigUint::from(0x10u8),
+                    mask: BigUint::from(0x0fu8),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn binary_words_are_truncated_to_the_element_width() {
+        let words = parse_memory_file("1_0110 11", MemoryFileFormat::Binary, 4, 2).unwrap();
+        assert_eq!(words[0].value, BigUint::from(0b0110u8));
+        assert_eq!(words[1].value, BigUint::from(0b11u8));
+    }
+
+    #[test]
+    fn formatted_words_parse_back() {
+        let words = vec![
+            MemoryWord {
+                address: 1,
+                value: BigUint::from(0x0au8),
+                mask: BigUint::default(),
+            },
+            MemoryWord {
+                address: 2,
+                value: BigUint::from(0x1f0u16),
+                mask: BigUint::from(0x00fu16),
+            },
+            MemoryWord {
+                address: 7,
+                value: BigUint::from(0x0f0u16),
+                mask: BigUint::from(0x0f0u16),
+            },
+        ];
+        let text = format_memory_file(&words, MemoryFileFormat::Hex, 9);
+        assert_eq!(text, "@1\n00a\n1fx\n@7\n0z0\n");
+        assert_eq!(
+            parse_memory_file(&text, MemoryFileFormat::Hex, 9, 8).unwrap(),
+            words
+        );
+        assert_eq!(
+            format_memory_file(&words[..1], MemoryFileFormat::Binary, 3),
+            "@1\n010\n"
+        );
+    }
+
+    #[test]
+    fn rejects_words_past_the_end_and_bad_digits() {
+        assert!(matches!(
+            parse_memory_file("@2 00", MemoryFileFormat::Hex, 8, 2),
+            Err(MemoryFileError::OutOfRange {
+                address: 2,
+                depth: 2
+            })
+        ));
+        assert!(matches!(
+            parse_memory_file("00\n12", MemoryFileFormat::Binary, 8, 4),
+            Err(MemoryFileError::Syntax { line: 2, .. })
+        ));
+    }
+}
@@ -2,14 +2,106 @@ use crate::{
     SignalRef,
     backend::{EventHandle, SimBackend},
 };
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 use std::collections::BinaryHeap;
 
-#[derive(Debug, Clone)]
+/// Waveform of a periodic clock.
+///
+/// Built with [`ClockSpec::new`] and then, optionally, [`ClockSpec::high_time`]
+/// and [`ClockSpec::phase`]. The default is a 50% duty cycle with the first
+/// rising edge at time 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSpec {
+    period: u64,
+    high_time: u64,
+    phase: u64,
+}
+
+impl ClockSpec {
+    /// A square wave of `period` ticks. Odd periods spend the extra tick low.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is less than 2.
+    pub fn new(period: u64) -> Self {
+        assert!(period >= 2, "clock period must be at least 2 ticks");
+        Self {
+            period,
+            high_time: period / 2,
+            phase: 0,
+        }
+    }
+
+    /// Ticks the clock stays high in each period.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < high_time < period`.
+    pub fn high_time(mut self, high_time: u64) -> Self {
+        assert!(
+            high_time > 0 && high_time < self.period,
+            "clock high time must be between 0 and the period (exclusive)"
+        );
+        self.high_time = high_time;
+        self
+    }
+
+    /// Time of the first rising edge.
+    pub fn phase(mut self, phase: u64) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+}
+
+/// A registered periodic clock. Rising edges fall on
+/// `phase + k * (high_time + low_time)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ClockDef {
-    pub period: u64,
+    pub high_time: u64,
+    pub low_time: u64,
+    pub phase: u64,
+    /// A paused clock holds its level and has no pending edges.
+    pub paused: bool,
+}
+
+impl ClockDef {
+    pub fn period(&self) -> u64 {
+        self.high_time + self.low_time
+    }
+
+    /// Time of the next edge strictly after `now` that moves the clock away
+    /// from its current level: a falling edge if `high`, else a rising edge.
+    pub fn next_edge_after(&self, now: u64, high: bool) -> u64 {
+        let base = if high {
+            self.phase + self.high_time
+        } else {
+            self.phase
+        };
+        if now < base {
+            return base;
+        }
+        let period = self.period();
+        base + ((now - base) / period + 1) * period
+    }
 }
 
-#[derive(Debug, Clone)]
+impl From<ClockSpec> for ClockDef {
+    fn from(spec: ClockSpec) -> Self {
+        Self {
+            high_time: spec.high_time,
+            low_time: spec.period - spec.high_time,
+            phase: spec.phase,
+            paused: false,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub struct SimEvent<B: SimBackend> {
     pub time: u64,
     pub event_ref: B::Event,
@@ -17,6 +109,17 @@ pub struct SimEvent<B: SimBackend> {
     pub next_val: u8,
 }
 
+impl<B: SimBackend> Clone for SimEvent<B> {
+    fn clone(&self) -> Self {
+        Self {
+            time: self.time,
+            event_ref: self.event_ref,
+            signal: self.signal,
+            next_val: self.next_val,
+        }
+    }
+}
+
 impl<B: SimBackend> PartialEq for SimEvent<B> {
     fn eq(&self, other: &Self) -> bool {
         self.time == other.time
@@ -49,10 +152,51 @@ impl<B: SimBackend> Ord for SimEvent<B> {
     }
 }
 
+/// A pending write of a data signal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledValue {
+    pub time: u64,
+    /// Insertion order; drives at the same time are applied first-in first-out.
+    pub seq: u64,
+    pub signal: SignalRef,
+    pub value: BigUint,
+    pub mask: BigUint,
+}
+
+impl PartialOrd for ScheduledValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScheduledValue {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Earliest time, then earliest insertion, is the greatest element.
+        other
+            .time
+            .cmp(&self.time)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
 pub struct Scheduler<B: SimBackend> {
     pub time: u64,
     pub clocks: Vec<Option<ClockDef>>,
     pub event_queue: BinaryHeap<SimEvent<B>>,
+    pub value_queue: BinaryHeap<ScheduledValue>,
+    pub next_value_seq: u64,
+}
+
+impl<B: SimBackend> Clone for Scheduler<B> {
+    fn clone(&self) -> Self {
+        Self {
+            time: self.time,
+            clocks: self.clocks.clone(),
+            event_queue: self.event_queue.clone(),
+            value_queue: self.value_queue.clone(),
+            next_value_seq: self.next_value_seq,
+        }
+    }
 }
 
 impl<B: SimBackend> Scheduler<B> {
@@ -61,18 +205,47 @@ impl<B: SimBackend> Scheduler<B> {
             time: 0,
             clocks: Vec::new(),
             event_queue: BinaryHeap::new(),
+            value_queue: BinaryHeap::new(),
+            next_value_seq: 0,
         }
     }
 
+    /// Earliest pending event or data-signal write.
     pub fn next_event_time(&self) -> Option<u64> {
-        self.event_queue.peek().map(|e| e.time)
+        let event = self.event_queue.peek().map(|e| e.time);
+        let value = self.value_queue.peek().map(|v| v.time);
+        match (event, value) {
+            (Some(event), Some(value)) => Some(event.min(value)),
+            (event, value) => event.or(value),
+        }
     }
 
     pub fn push(&mut self, event: SimEvent<B>) {
         self.event_queue.push(event);
     }
 
-    pub fn pop_all_at_next_time(&mut self) -> Option<(u64, Vec<SimEvent<B>>)> {
+    /// Drop every pending event for `event_id`.
+    pub fn cancel_events(&mut self, event_id: usize) {
+        self.event_queue
+            .retain(|event| event.event_ref.id() != event_id);
+    }
+
+    /// Queue a data-signal write behind every write already queued for `time`.
+    pub fn push_value(&mut self, time: u64, signal: SignalRef, value: BigUint, mask: BigUint) {
+        let seq = self.next_value_seq;
+        self.next_value_seq += 1;
+        self.value_queue.push(ScheduledValue {
+            time,
+            seq,
+            signal,
+            value,
+            mask,
+        });
+    }
+
+    /// Pop every event and data-signal write at the earliest pending time.
+    /// Writes are returned in insertion order.
+    pub fn pop_all_at_next_time(&mut self) -> Option<(u64, Vec<SimEvent<B>>, Vec<ScheduledValue>)> {
         let next_time = self.next_event_time()?;
         let mut events = Vec::new();
         while let Some(ev) = self.event_queue.peek() {
@@ -82,7 +255,15 @@ impl<B: SimBackend> Scheduler<B> {
                 break;
             }
         }
-        Some((next_time, events))
+        let mut values = Vec::new();
+        while let Some(value) = self.value_queue.peek() {
+            if value.time == next_time {
+                values.push(self.value_queue.pop().unwrap());
+            } else {
+                break;
+            }
+        }
+        Some((next_time, events, values))
     }
 }
 
@@ -1,11 +1,13 @@
 use bit_set::BitSet;
 use celox_design::DomainKind;
 use fxhash::FxHashMap;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     SignalRef, SimulatorErrorCode,
     backend::{EventHandle, SimBackend},
-    scheduler::{ClockDef, Scheduler, SimEvent},
+    scheduler::{ClockDef, ClockSpec, Scheduler, SimEvent},
 };
 
 /// Backend execution hooks needed by the timed simulation engine.
@@ -51,6 +53,16 @@ pub trait SimulationExecutor {
         Ok(())
     }
 
+    /// Called once per cascade round of a time step, numbered from 1,
+    /// so the facade can stop a run that is cancelled or over its limits.
+    fn check_cascade_round(
+        &mut self,
+        _round: u32,
+        _timestamp: u64,
+    ) -> Result<(), SimulatorErrorCode> {
+        Ok(())
+    }
+
     /// Called after the state for a simulation timestamp has stabilized.
     fn finish_timed_step(&mut self, _timestamp: u64) {}
 }
@@ -84,6 +96,40 @@ impl<B: SimBackend> std::fmt::Debug for EventInfo<B> {
     }
 }
 
+/// One pending scheduler event, identified by event ID instead of a
+/// backend-specific handle so it can outlive the backend that produced it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledEventSnapshot {
+    pub time: u64,
+    pub event_id: usize,
+    pub signal: SignalRef,
+    pub next_val: u8,
+}
+
+/// One pending data-signal write. Values are little-endian bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledValueSnapshot {
+    pub time: u64,
+    pub signal: SignalRef,
+    pub value: Vec<u8>,
+    pub mask: Vec<u8>,
+}
+
+/// Serializable image of the scheduler queue, periodic clocks, current time,
+/// and edge-detection history of a [`SimulationState`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationStateSnapshot {
+    pub time: u64,
+    /// Periodic clock indexed by event ID.
+    pub clocks: Vec<Option<ClockDef>>,
+    /// Pending events in dispatch order.
+    pub pending: Vec<ScheduledEventSnapshot>,
+    /// Event IDs whose signal was nonzero after the last completed step.
+    pub nonzero_events: Vec<usize>,
+    /// Pending data-signal writes in application order.
+    pub pending_values: Vec<ScheduledValueSnapshot>,
+}
+
 /// Backend-independent state and execution rules for timed simulation.
 pub struct SimulationState<B: SimBackend> {
     scheduler: Scheduler<B>,
@@ -94,6 +140,19 @@ pub struct SimulationState<B: SimBackend> {
     signal_to_id: FxHashMap<SignalRef, usize>,
 }
 
+impl<B: SimBackend> Clone for SimulationState<B> {
+    fn clone(&self) -> Self {
+        Self {
+            scheduler: self.scheduler.clone(),
+            last_clock_values: self.last_clock_values.clone(),
+            topo_signals: self.topo_signals.clone(),
+            domain_kinds: self.domain_kinds.clone(),
+            event_info: self.event_info.clone(),
+            signal_to_id: self.signal_to_id.clone(),
+        }
+    }
+}
+
 impl<B: SimBackend> SimulationState<B> {
     /// Rebase edge detection after state was advanced outside this scheduler.
     pub fn synchronize_event_values(&mut self, backend: &B) {
@@ -162,6 +221,8 @@ impl<B: SimBackend> SimulationState<B> {
         }
     }
 
+    /// Register a clock that toggles every `period / 2` ticks, starting with
+    /// a rising edge at `initial_delay`.
     pub fn add_clock(
         &mut self,
         event: B::Event,
@@ -169,17 +230,86 @@ impl<B: SimBackend> SimulationState<B> {
         period: u64,
         initial_delay: u64,
     ) {
+        let clock = ClockDef {
+            high_time: period / 2,
+            low_time: period / 2,
+            phase: initial_delay,
+            paused: false,
+        };
+        self.insert_clock(event, signal, clock);
+    }
+
+    /// Register a clock with the waveform described by `spec`.
+    pub fn add_clock_with(&mut self, event: B::Event, signal: SignalRef, spec: ClockSpec) {
+        self.insert_clock(event, signal, spec.into());
+    }
+
+    fn insert_clock(&mut self, event: B::Event, signal: SignalRef, clock: ClockDef) {
         let event_id = event.id();
         if event_id >= self.scheduler.clocks.len() {
             self.scheduler.clocks.resize(event_id + 1, None);
         }
-        self.scheduler.clocks[event_id] = Some(ClockDef { period });
+        if self.scheduler.clocks[event_id].is_some() {
+            // Replacing a clock drops the edges of the old waveform.
+            self.scheduler.cancel_events(event_id);
+        }
         self.scheduler.push(SimEvent {
-            time: initial_delay,
+            time: clock.phase,
             event_ref: event,
             signal,
             next_val: 1,
         });
+        self.scheduler.clocks[event_id] = Some(clock);
+    }
+
+    /// Stop generating edges for the clock on `event`. The clock holds its
+    /// current level. Returns `false` if `event` has no clock.
+    pub fn pause_clock(&mut self, event: B::Event) -> bool {
+        let event_id = event.id();
+        let Some(Some(clock)) = self.scheduler.clocks.get_mut(event_id) else {
+            return false;
+        };
+        clock.paused = true;
+        self.scheduler.cancel_events(event_id);
+        true
+    }
+
+    /// Restart a paused clock on its original edge grid: the first edge
+    /// after the current time that changes the clock's level is scheduled.
+    /// Returns `false` if `event` has no clock.
+    pub fn resume_clock(&mut self, backend: &B, event: B::Event, signal: SignalRef) -> bool {
+        let event_id = event.id();
+        let now = self.scheduler.time;
+        let Some(Some(clock)) = self.scheduler.clocks.get_mut(event_id) else {
+            return false;
+        };
+        if !clock.paused {
+            return true;
+        }
+        clock.paused = false;
+        let high = backend.get_as::<u8>(signal) != 0;
+        let time = clock.next_edge_after(now, high);
+        self.scheduler.push(SimEvent {
+            time,
+            event_ref: event,
+            signal,
+            next_val: u8::from(!high),
+        });
+        true
+    }
+
+    /// Unregister the clock on `event` and drop its pending edges. Returns
+    /// `false` if `event` has no clock.
+    pub fn remove_clock(&mut self, event: B::Event) -> bool {
+        let event_id = event.id();
+        let Some(slot) = self.scheduler.clocks.get_mut(event_id) else {
+            return false;
+        };
+        if slot.take().is_none() {
+            return false;
+        }
+        self.scheduler.cancel_events(event_id);
+        true
     }
 
     pub fn schedule(&mut self, event: B::Event, signal: SignalRef, time: u64, value: u8) {
@@ -191,18 +321,95 @@ impl<B: SimBackend> SimulationState<B> {
         });
     }
 
+    /// Queue a write of `signal` at `time`.
+    ///
+    /// Writes to data signals land in a region after every clock and reset
+    /// edge at the same time has been processed, so flip-flops clocked at
+    /// `time` still sample the previous value, exactly as if the write were
+    /// made with `modify` right after `run_until(time)`. Writes at the same
+    /// time are applied in the order they were queued, and any edge they
+    /// cause on a derived clock is processed before the step completes.
+    /// Event signals are queued like [`Self::schedule`] with a nonzero test
+    /// on `value`.
+    pub fn schedule_value(
+        &mut self,
+        backend: &B,
+        signal: SignalRef,
+        time: u64,
+        value: BigUint,
+        mask: BigUint,
+    ) {
+        if let Some(&id) = self.signal_to_id.get(&signal) {
+            let event = backend.id_to_event_slice()[id];
+            let next_val = u8::from(value != BigUint::ZERO);
+            self.schedule(event, signal, time, next_val);
+            return;
+        }
+        self.scheduler.push_value(time, signal, value, mask);
+    }
+
     pub fn step<E>(&mut self, executor: &mut E) -> Result<Option<u64>, SimulatorErrorCode>
     where
         E: SimulationExecutor<Backend = B>,
     {
-        let (current_time, events_to_process) = match self.scheduler.pop_all_at_next_time() {
-            Some(events) => events,
-            None => return Ok(None),
-        };
+        let (current_time, events_to_process, values_to_apply) =
+            match self.scheduler.pop_all_at_next_time() {
+                Some(pending) => pending,
+                None => return Ok(None),
+            };
         self.scheduler.time = current_time;
 
-        let num_events = executor.backend().num_events();
+        // Queue the next clock edges first so an interrupted step leaves the
+        // clocks running.
         for event in &events_to_process {
+            let event_id = event.event_ref.id();
+            if let Some(Some(clock)) = self.scheduler.clocks.get(event_id) {
+                if clock.paused {
+                    continue;
+                }
+                let half = if event.next_val != 0 {
+                    clock.high_time
+                } else {
+                    clock.low_time
+                };
+                self.scheduler.push(SimEvent {
+                    time: current_time + half,
+                    event_ref: event.event_ref,
+                    signal: event.signal,
+                    next_val: 1 - event.next_val,
+                });
+            }
+        }
+
+        if !events_to_process.is_empty() {
+            self.process_events(executor, current_time, &events_to_process)?;
+        }
+        if !values_to_apply.is_empty() {
+            for value in values_to_apply {
+                executor
+                    .backend_mut()
+                    .set_four_state(value.signal, value.value, value.mask);
+            }
+            self.process_events(executor, current_time, &[])?;
+        }
+
+        executor.finish_timed_step(current_time);
+        Ok(Some(current_time))
+    }
+
+    /// Apply `events_to_process`, then run every sequential domain they or
+    /// the following combinational settles trigger until the state is stable.
+    fn process_events<E>(
+        &mut self,
+        executor: &mut E,
+        current_time: u64,
+        events_to_process: &[SimEvent<B>],
+    ) -> Result<(), SimulatorErrorCode>
+    where
+        E: SimulationExecutor<Backend = B>,
+    {
+        let num_events = executor.backend().num_events();
+        for event in events_to_process {
             executor.backend_mut().set(event.signal, event.next_val);
         }
 
@@ -212,7 +419,7 @@ impl<B: SimBackend> SimulationState<B> {
         let mut has_scheduled_event_signal = false;
         executor.backend_mut().clear_triggered_bits();
 
-        for event in &events_to_process {
+        for event in events_to_process {
             if let Some(&id) = self.signal_to_id.get(&event.signal) {
                 has_scheduled_event_signal = true;
                 let was_nonzero = self.last_clock_values.contains(id);
@@ -251,6 +458,7 @@ impl<B: SimBackend> SimulationState<B> {
         }
 
         let mut comb_already_done = false;
+        let mut round = 0u32;
         loop {
             let mut any_new_outer_loop_trigger = false;
             let mut newly_triggered = Vec::new();
@@ -322,6 +530,8 @@ impl<B: SimBackend> SimulationState<B> {
             if newly_triggered.is_empty() && !any_new_outer_loop_trigger {
                 break;
             }
+            round = round.saturating_add(1);
+            executor.check_cascade_round(round, current_time)?;
 
             for id in &newly_triggered {
                 if let Some(event) = self.event_info[*id].apply_event {
@@ -355,27 +565,96 @@ impl<B: SimBackend> SimulationState<B> {
                 self.last_clock_values.remove(*id);
             }
         }
-
-        for event in &events_to_process {
-            let event_id = event.event_ref.id();
-            if let Some(Some(clock)) = self.scheduler.clocks.get(event_id) {
-                self.scheduler.push(SimEvent {
-                    time: current_time + clock.period / 2,
-                    event_ref: event.event_ref,
-                    signal: event.signal,
-                    next_val: 1 - event.next_val,
-                });
-            }
-        }
-
-        executor.finish_timed_step(current_time);
-        Ok(Some(current_time))
+        Ok(())
     }
 
     pub fn time(&self) -> u64 {
         self.scheduler.time
     }
 
+    /// Capture the scheduler state without disturbing the pending queue.
+    pub fn snapshot(&self) -> SimulationStateSnapshot {
+        let mut queued = self.scheduler.event_queue.iter().collect::<Vec<_>>();
+        // The heap orders the earliest event as the greatest element.
+        queued.sort_by(|a, b| b.cmp(a));
+        let pending = queued
+            .into_iter()
+            .map(|event| ScheduledEventSnapshot {
+                time: event.time,
+                event_id: event.event_ref.id(),
+                signal: event.signal,
+                next_val: event.next_val,
+            })
+            .collect();
+        let mut queued_values = self.scheduler.value_queue.iter().collect::<Vec<_>>();
+        queued_values.sort_by(|a, b| b.cmp(a));
+        let pending_values = queued_values
+            .into_iter()
+            .map(|value| ScheduledValueSnapshot {
+                time: value.time,
+                signal: value.signal,
+                value: value.value.to_bytes_le(),
+                mask: value.mask.to_bytes_le(),
+            })
+            .collect();
+        SimulationStateSnapshot {
+            time: self.scheduler.time,
+            clocks: self.scheduler.clocks.clone(),
+            pending,
+            nonzero_events: self.last_clock_values.iter().collect(),
+            pending_values,
+        }
+    }
+
+    /// Replace the scheduler state with a snapshot taken from a simulation of
+    /// the same design. Event IDs are resolved against `backend`.
+    pub fn restore(
+        &mut self,
+        snapshot: &SimulationStateSnapshot,
+        backend: &B,
+    ) -> Result<(), String> {
+        let events = backend.id_to_event_slice();
+        let mut event_queue = std::collections::BinaryHeap::with_capacity(snapshot.pending.len());
+        for pending in &snapshot.pending {
+            let Some(event_ref) = events.get(pending.event_id).copied() else {
+                return Err(format!(
+                    "snapshot references unknown event id {}",
+                    pending.event_id
+                ));
+            };
+            event_queue.push(SimEvent {
+                time: pending.time,
+                event_ref,
+                signal: pending.signal,
+                next_val: pending.next_val,
+            });
+        }
+        if let Some(id) = snapshot
+            .nonzero_events
+            .iter()
+            .copied()
+            .find(|id| *id >= events.len())
+        {
+            return Err(format!("snapshot references unknown event id {id}"));
+        }
+
+        self.scheduler.time = snapshot.time;
+        self.scheduler.clocks = snapshot.clocks.clone();
+        self.scheduler.event_queue = event_queue;
+        self.scheduler.value_queue.clear();
+        self.scheduler.next_value_seq = 0;
+        for pending in &snapshot.pending_values {
+            self.scheduler.push_value(
+                pending.time,
+                pending.signal,
+                BigUint::from_bytes_le(&pending.value),
+                BigUint::from_bytes_le(&pending.mask),
+            );
+        }
+        self.last_clock_values = snapshot.nonzero_events.iter().copied().collect();
+        Ok(())
+    }
+
     pub fn set_time(&mut self, time: u64) {
         self.scheduler.time = time;
     }
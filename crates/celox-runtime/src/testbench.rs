@@ -333,6 +333,19 @@ fn bind_statement<B: SimBackend>(
             ret_signed,
             ret_strict,
         }),
+        GenericTestbenchStatement::WriteMemory {
+            memory,
+            path,
+            binary,
+            start,
+            end,
+        } => Some(GenericTestbenchStatement::WriteMemory {
+            memory: backend.resolve_signal(&memory.address),
+            path,
+            binary,
+            start,
+            end,
+        }),
         GenericTestbenchStatement::Break => Some(GenericTestbenchStatement::Break),
         GenericTestbenchStatement::Finish => Some(GenericTestbenchStatement::Finish),
     }
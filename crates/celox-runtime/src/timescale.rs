@@ -0,0 +1,248 @@
+//! Physical time units for simulation ticks.
+//!
+//! A [`Timescale`] fixes how long one simulation tick is. Durations such as
+//! `"10ns"` or `"2.5ns"` are converted into whole ticks; a duration that is
+//! not a whole number of ticks is an error rather than being rounded, so
+//! clock ratios stay exact.
+
+use serde::{Deserialize, Serialize};
+
+const UNITS: [(&str, u128); 6] = [
+    ("s", 1_000_000_000_000_000),
+    ("ms", 1_000_000_000_000),
+    ("us", 1_000_000_000),
+    ("ns", 1_000_000),
+    ("ps", 1_000),
+    ("fs", 1),
+];
+
+/// Failure while parsing or converting a physical time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimescaleError {
+    /// The text is not a number followed by `s`, `ms`, `us`, `ns`, `ps`, or `fs`.
+    Invalid(String),
+    /// A timescale must be 1, 10, or 100 of a unit.
+    Magnitude(String),
+    /// The duration is not a whole number of ticks.
+    NotMultiple {
+        duration: String,
+        timescale: Timescale,
+    },
+    /// The duration does not fit in 64-bit ticks.
+    Overflow(String),
+    /// Physical units were used on a simulation without a timescale.
+    Unset,
+    /// The waveform header was already written with another timescale.
+    WaveformStarted,
+}
+
+impl std::fmt::Display for TimescaleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(text) => write!(
+                f,
+                "invalid time '{text}'; expected a number followed by s, ms, us, ns, ps, or fs"
+            ),
+            Self::Magnitude(text) => {
+                write!(
+                    f,
+                    "invalid timescale '{text}'; expected 1, 10, or 100 of a unit"
+                )
+            }
+            Self::NotMultiple {
+                duration,
+                timescale,
+            } => write!(f, "'{duration}' is not a whole number of {timescale} ticks"),
+            Self::Overflow(text) => write!(f, "'{text}' is too long to represent in ticks"),
+            Self::Unset => write!(f, "no timescale is set for this simulation"),
+            Self::WaveformStarted => {
+                write!(
+                    f,
+                    "cannot change the timescale after the waveform has started"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimescaleError {}
+
+/// The length of one simulation tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Timescale {
+    femtoseconds: u64,
+}
+
+impl Default for Timescale {
+    /// One nanosecond, the unit written to waveforms when none is set.
+    fn default() -> Self {
+        Self {
+            femtoseconds: 1_000_000,
+        }
+    }
+}
+
+impl Timescale {
+    /// Length of one tick in femtoseconds.
+    pub fn femtoseconds(&self) -> u64 {
+        self.femtoseconds
+    }
+
+    /// Power of ten of one tick in seconds, e.g. `-11` for `10ps`.
+    pub fn exponent(&self) -> i8 {
+        self.femtoseconds.ilog10() as i8 - 15
+    }
+
+    /// The tick of `10^exponent` seconds, if it is between 1fs and 1s.
+    pub fn from_exponent(exponent: i8) -> Option<Self> {
+        let power = u32::try_from(i32::from(exponent) + 15).ok()?;
+        (power <= 15).then(|| Self {
+            femtoseconds: 10u64.pow(power),
+        })
+    }
+
+    /// Convert a duration such as `"10ns"` or `"2.5ns"` into ticks.
+    pub fn ticks(&self, duration: &str) -> Result<u64, TimescaleError> {
+        let femtoseconds = parse_femtoseconds(duration)?;
+        let tick = u128::from(self.femtoseconds);
+        if femtoseconds % tick != 0 {
+            return Err(TimescaleError::NotMultiple {
+                duration: duration.to_string(),
+                timescale: *self,
+            });
+        }
+        u64::try_from(femtoseconds / tick)
+            .map_err(|_| TimescaleError::Overflow(duration.to_string()))
+    }
+
+    /// Express `ticks` in `unit` (`"s"`, `"ms"`, `"us"`, `"ns"`, `"ps"`, or `"fs"`).
+    pub fn to_unit(&self, ticks: u64, unit: &str) -> Result<f64, TimescaleError> {
+        let scale = unit_scale(unit).ok_or_else(|| TimescaleError::Invalid(unit.to_string()))?;
+        let femtoseconds = u128::from(ticks) * u128::from(self.femtoseconds);
+        Ok(femtoseconds as f64 / scale as f64)
+    }
+}
+
+impl std::str::FromStr for Timescale {
+    type Err = TimescaleError;
+
+    /// Parse a timescale such as `"1ns"`, `"10ps"`, or `"100fs"`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (number, scale) = split_unit(text)?;
+        let magnitude = match number.trim() {
+            "1" => 1,
+            "10" => 10,
+            "100" => 100,
+            _ => return Err(TimescaleError::Magnitude(text.to_string())),
+        };
+        let femtoseconds = u64::try_from(magnitude * scale)
+            .map_err(|_| TimescaleError::Overflow(text.to_string()))?;
+        Ok(Self { femtoseconds })
+    }
+}
+
+impl std::fmt::Display for Timescale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let femtoseconds = u128::from(self.femtoseconds);
+        let (unit, scale) = UNITS
+            .iter()
+            .copied()
+            .find(|(_, scale)| femtoseconds % scale == 0)
+            .expect("femtoseconds divide every tick");
+        write!(f, "{}{}", femtoseconds / scale, unit)
+    }
+}
+
+fn unit_scale(unit: &str) -> Option<u128> {
+    UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, scale)| *scale)
+}
+
+fn split_unit(text: &str) -> Result<(&str, u128), TimescaleError> {
+    let trimmed = text.trim();
+    let split = trimmed
+        .find(|c: char| c.is_ascii_alphabetic())
+        .ok_or_else(|| TimescaleError::Invalid(text.to_string()))?;
+    let (number, unit) = trimmed.split_at(split);
+    let scale = unit_scale(unit).ok_or_else(|| TimescaleError::Invalid(text.to_string()))?;
+    Ok((number, scale))
+}
+
+/// Parse `"<integer>[.<fraction>]<unit>"` exactly into femtoseconds.
+fn parse_femtoseconds(text: &str) -> Result<u128, TimescaleError> {
+    let invalid = || TimescaleError::Invalid(text.to_string());
+    let (number, scale) = split_unit(text)?;
+    let number = number.trim();
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let overflow = || TimescaleError::Overflow(text.to_string());
+    let mut femtoseconds = if whole.is_empty() {
+        0
+    } else {
+        whole
+            .parse::<u128>()
+            .map_err(|_| overflow())?
+            .checked_mul(scale)
+            .ok_or_else(overflow)?
+    };
+    let mut place = scale;
+    for digit in fraction.chars() {
+        if place % 10 != 0 {
+            // Finer than a femtosecond: only trailing zeros are exact.
+            if digit != '0' {
+                return Err(TimescaleError::NotMultiple {
+                    duration: text.to_string(),
+                    timescale: Timescale { femtoseconds: 1 },
+                });
+            }
+            continue;
+        }
+        place /= 10;
+        femtoseconds += u128::from(digit.to_digit(10).unwrap()) * place;
+    }
+    Ok(femtoseconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_durations_to_ticks() {
+        let timescale: Timescale = "10ps".parse().unwrap();
+        assert_eq!(timescale.ticks("10ns").unwrap(), 1000);
+        assert_eq!(timescale.ticks("2500ps").unwrap(), 250);
+        assert_eq!(timescale.ticks("2.5ns").unwrap(), 250);
+        assert_eq!(timescale.to_string(), "10ps");
+        assert_eq!(timescale.exponent(), -11);
+        assert_eq!(timescale.to_unit(250, "ns").unwrap(), 2.5);
+        assert!(matches!(
+            timescale.ticks("5ps"),
+            Err(TimescaleError::NotMultiple { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_timescales() {
+        assert!(matches!(
+            "3ns".parse::<Timescale>(),
+            Err(TimescaleError::Magnitude(_))
+        ));
+        assert!(matches!(
+            "1 lightyear".parse::<Timescale>(),
+            Err(TimescaleError::Invalid(_))
+        ));
+        assert_eq!("100fs".parse::<Timescale>().unwrap().femtoseconds(), 100);
+    }
+}
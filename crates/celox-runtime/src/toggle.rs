@@ -0,0 +1,403 @@
+//! Toggle coverage and switching activity.
+//!
+//! [`ToggleCoverage`] compares each sampled memory image with the previous
+//! one bit by bit. A bit that goes from 0 to 1 counts as a rise and from 1 to
+//! 0 as a fall; changes to or from X and Z are not toggles. Alongside the
+//! counts it accumulates how long each bit held 0, 1, X and Z, which is the
+//! switching activity a SAIF file carries to power-estimation tools.
+
+use crate::timescale::Timescale;
+use crate::vcd::VcdSignalDesc;
+use celox_state_layout::get_byte_size;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Transition counts of one bit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BitToggles {
+    pub rise: u64,
+    pub fall: u64,
+}
+
+impl BitToggles {
+    /// Whether the bit has both risen and fallen.
+    pub fn is_covered(&self) -> bool {
+        self.rise > 0 && self.fall > 0
+    }
+}
+
+/// Transition counts of every bit of one signal, least significant first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignalToggles {
+    /// Hierarchical name, the scope and the signal name joined by `.`.
+    pub name: String,
+    pub width: usize,
+    pub bits: Vec<BitToggles>,
+}
+
+impl SignalToggles {
+    pub fn covered_bits(&self) -> usize {
+        self.bits.iter().filter(|bit| bit.is_covered()).count()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BitState {
+    Zero,
+    One,
+    X,
+    Z,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BitActivity {
+    state: BitState,
+    since: u64,
+    /// Time spent at 0, 1, X and Z before `since`.
+    durations: [u64; 4],
+}
+
+impl BitActivity {
+    fn duration(&self, state: BitState, now: u64) -> u64 {
+        let held = if self.state == state {
+            now.saturating_sub(self.since)
+        } else {
+            0
+        };
+        self.durations[state as usize] + held
+    }
+}
+
+/// Per-bit toggle counts and state durations of a set of signals.
+#[derive(Debug, Clone)]
+pub struct ToggleCoverage {
+    descs: Vec<VcdSignalDesc>,
+    toggles: Vec<SignalToggles>,
+    activity: Vec<Vec<BitActivity>>,
+    /// The last sampled bytes of each signal, value plane then mask plane.
+    previous: Vec<Vec<u8>>,
+    start: u64,
+    time: u64,
+}
+
+impl ToggleCoverage {
+    /// Track `descs`, taking their values in `memory` at `time` as the
+    /// starting point. Each desc's `scope` is the hierarchical scope name.
+    pub fn new(descs: Vec<VcdSignalDesc>, time: u64, memory: &[u8]) -> Self {
+        let mut coverage = Self {
+            toggles: Vec::new(),
+            activity: Vec::new(),
+            previous: Vec::new(),
+            descs,
+            start: time,
+            time,
+        };
+        coverage.clear(time, memory);
+        coverage
+    }
+
+    /// Drop every count and start over from the values in `memory`.
+    pub fn clear(&mut self, time: u64, memory: &[u8]) {
+        self.start = time;
+        self.time = time;
+        self.toggles = self
+            .descs
+            .iter()
+            .map(|desc| SignalToggles {
+                name: format!("{}.{}", desc.scope, desc.name),
+                width: desc.width,
+                bits: vec![BitToggles::default(); desc.width],
+            })
+            .collect();
+        self.previous = self
+            .descs
+            .iter()
+            .map(|desc| signal_bytes(desc, memory).to_vec())
+            .collect();
+        self.activity = self
+            .descs
+            .iter()
+            .zip(&self.previous)
+            .map(|(desc, bytes)| {
+                (0..desc.width)
+                    .map(|bit| BitActivity {
+                        state: bit_state(desc, bytes, bit),
+                        since: time,
+                        durations: [0; 4],
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+
+    /// Record the values in `memory` at `time`.
+    pub fn sample(&mut self, time: u64, memory: &[u8]) {
+        self.time = self.time.max(time);
+        for (index, desc) in self.descs.iter().enumerate() {
+            let current = signal_bytes(desc, memory);
+            if current == self.previous[index].as_slice() {
+                continue;
+            }
+            let toggles = &mut self.toggles[index].bits;
+            for (bit, activity) in self.activity[index].iter_mut().enumerate() {
+                let state = bit_state(desc, current, bit);
+                if state == activity.state {
+                    continue;
+                }
+                match (activity.state, state) {
+                    (BitState::Zero, BitState::One) => toggles[bit].rise += 1,
+                    (BitState::One, BitState::Zero) => toggles[bit].fall += 1,
+                    _ => {}
+                }
+                activity.durations[activity.state as usize] += time.saturating_sub(activity.since);
+                activity.state = state;
+                activity.since = time;
+            }
+            self.previous[index].copy_from_slice(current);
+        }
+    }
+
+    pub fn signals(&self) -> &[SignalToggles] {
+        &self.toggles
+    }
+
+    /// The signal with hierarchical name `name`.
+    pub fn signal(&self, name: &str) -> Option<&SignalToggles> {
+        self.toggles.iter().find(|signal| signal.name == name)
+    }
+
+    pub fn total_bits(&self) -> usize {
+        self.toggles.iter().map(|signal| signal.width).sum()
+    }
+
+    /// Number of bits that have both risen and fallen.
+    pub fn covered_bits(&self) -> usize {
+        self.toggles.iter().map(SignalToggles::covered_bits).sum()
+    }
+
+    /// Time covered by the counts, from the first to the last sample.
+    pub fn duration(&self) -> u64 {
+        self.time - self.start
+    }
+
+    /// Write the counts as a JSON toggle-coverage report.
+    pub fn write_json(&self, writer: impl Write) -> std::io::Result<()> {
+        #[derive(Serialize)]
+        struct Report<'a> {
+            duration: u64,
+            total_bits: usize,
+            covered_bits: usize,
+            signals: Vec<SignalReport<'a>>,
+        }
+        #[derive(Serialize)]
+        struct SignalReport<'a> {
+            name: &'a str,
+            width: usize,
+            covered_bits: usize,
+            bits: &'a [BitToggles],
+        }
+
+        let report = Report {
+            duration: self.duration(),
+            total_bits: self.total_bits(),
+            covered_bits: self.covered_bits(),
+            signals: self
+                .toggles
+                .iter()
+                .map(|signal| SignalReport {
+                    name: &signal.name,
+                    width: signal.width,
+                    covered_bits: signal.covered_bits(),
+                    bits: &signal.bits,
+                })
+                .collect(),
+        };
+        serde_json::to_writer_pretty(writer, &report).map_err(std::io::Error::other)
+    }
+
+    /// Write the switching activity as a backward SAIF file. Durations are
+    /// in units of `timescale`, which is also written to the header.
+    pub fn write_saif(
+        &self,
+        mut writer: impl Write,
+        timescale: Option<Timescale>,
+    ) -> std::io::Result<()> {
+        writeln!(writer, "(SAIFILE")?;
+        writeln!(writer, "(SAIFVERSION \"2.0\")")?;
+        writeln!(writer, "(DIRECTION \"backward\")")?;
+        writeln!(writer, "(DESIGN )")?;
+        writeln!(
+            writer,
+            "(DATE \"{}\")",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+        )?;
+        writeln!(writer, "(VENDOR \"Celox\")")?;
+        writeln!(writer, "(PROGRAM_NAME \"celox\")")?;
+        writeln!(writer, "(VERSION \"{}\")", env!("CARGO_PKG_VERSION"))?;
+        writeln!(writer, "(DIVIDER / )")?;
+        if let Some(timescale) = timescale {
+            let text = timescale.to_string();
+            let unit = text.trim_start_matches(|c: char| c.is_ascii_digit());
+            writeln!(
+                writer,
+                "(TIMESCALE {} {unit})",
+                &text[..text.len() - unit.len()]
+            )?;
+        }
+        writeln!(writer, "(DURATION {})", self.duration())?;
+
+        let mut scopes = BTreeMap::<Vec<&str>, Vec<usize>>::new();
+        for (index, desc) in self.descs.iter().enumerate() {
+            scopes
+                .entry(desc.scope.split('.').collect())
+                .or_default()
+                .push(index);
+        }
+        let mut open = Vec::<&str>::new();
+        for (path, signals) in &scopes {
+            let common = open
+                .iter()
+                .zip(path)
+                .take_while(|(open, segment)| open == segment)
+                .count();
+            while open.len() > common {
+                open.pop();
+                writeln!(writer, "{})", indent(open.len()))?;
+            }
+            for segment in &path[common..] {
+                writeln!(
+                    writer,
+                    "{}(INSTANCE {}",
+                    indent(open.len()),
+                    saif_name(segment)
+                )?;
+                open.push(segment);
+            }
+            let depth = open.len();
+            writeln!(writer, "{}(NET", indent(depth))?;
+            for &index in signals {
+                self.write_saif_nets(&mut writer, index, depth + 1)?;
+            }
+            writeln!(writer, "{})", indent(depth))?;
+        }
+        while !open.is_empty() {
+            open.pop();
+            writeln!(writer, "{})", indent(open.len()))?;
+        }
+        writeln!(writer, ")")
+    }
+
+    fn write_saif_nets(
+        &self,
+        writer: &mut impl Write,
+        index: usize,
+        depth: usize,
+    ) -> std::io::Result<()> {
+        let desc = &self.descs[index];
+        let name = saif_name(&desc.name);
+        for (bit, activity) in self.activity[index].iter().enumerate() {
+            let toggles = self.toggles[index].bits[bit];
+            let duration = |state| activity.duration(state, self.time);
+            write!(writer, "{}(", indent(depth))?;
+            if desc.width == 1 {
+                write!(writer, "{name}")?;
+            } else {
+                write!(writer, "{name}\\[{bit}\\]")?;
+            }
+            write!(
+                writer,
+                " (T0 {}) (T1 {}) (TX {})",
+                duration(BitState::Zero),
+                duration(BitState::One),
+                duration(BitState::X)
+            )?;
+            if desc.is_4state {
+                write!(writer, " (TZ {})", duration(BitState::Z))?;
+            }
+            writeln!(writer, " (TC {}) (IG 0))", toggles.rise + toggles.fall)?;
+        }
+        Ok(())
+    }
+}
+
+fn signal_bytes<'a>(desc: &VcdSignalDesc, memory: &'a [u8]) -> &'a [u8] {
+    let planes = if desc.is_4state { 2 } else { 1 };
+    &memory[desc.offset..desc.offset + get_byte_size(desc.width) * planes]
+}
+
+fn bit_state(desc: &VcdSignalDesc, bytes: &[u8], bit: usize) -> BitState {
+    let read = |index: usize| bytes[index / 8] >> (index % 8) & 1 == 1;
+    let value = read(bit);
+    let mask = desc.is_4state && read(get_byte_size(desc.width) * 8 + bit);
+    match (mask, value) {
+        (false, false) => BitState::Zero,
+        (false, true) => BitState::One,
+        (true, true) => BitState::X,
+        (true, false) => BitState::Z,
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+/// Escape the characters SAIF reserves in identifiers.
+fn saif_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '[' | ']' | '/' | '(' | ')' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desc(scope: &str, name: &str, offset: usize, width: usize) -> VcdSignalDesc {
+        VcdSignalDesc {
+            scope: scope.to_string(),
+            name: name.to_string(),
+            offset,
+            width,
+            is_4state: false,
+        }
+    }
+
+    #[test]
+    fn counts_rises_falls_and_durations() {
+        let descs = vec![desc("Top", "a", 0, 2), desc("Top.u_sub", "b", 1, 1)];
+        let mut coverage = ToggleCoverage::new(descs, 0, &[0b00, 0]);
+        coverage.sample(10, &[0b01, 1]);
+        coverage.sample(20, &[0b10, 0]);
+        coverage.sample(25, &[0b10, 0]);
+
+        let a = coverage.signal("Top.a").unwrap();
+        assert_eq!(a.bits[0], BitToggles { rise: 1, fall: 1 });
+        assert_eq!(a.bits[1], BitToggles { rise: 1, fall: 0 });
+        assert_eq!(coverage.covered_bits(), 2);
+        assert_eq!(coverage.total_bits(), 3);
+        assert_eq!(coverage.duration(), 25);
+
+        let mut saif = Vec::new();
+        coverage
+            .write_saif(&mut saif, Some("1ns".parse().unwrap()))
+            .unwrap();
+        let saif = String::from_utf8(saif).unwrap();
+        assert!(saif.contains("(TIMESCALE 1 ns)"), "{saif}");
+        assert!(saif.contains("(DURATION 25)"), "{saif}");
+        assert!(
+            saif.contains("a\\[0\\] (T0 15) (T1 10) (TX 0) (TC 2) (IG 0))"),
+            "{saif}"
+        );
+        assert!(
+            saif.contains("(INSTANCE Top\n  (NET\n    (a\\[0\\]"),
+            "{saif}"
+        );
+        assert!(saif.contains("  (INSTANCE u_sub\n"), "{saif}");
+    }
+}
@@ -1,3 +1,5 @@
+use crate::timescale::{Timescale, TimescaleError};
+use crate::waveform::DumpGate;
 use celox_state_layout::get_byte_size;
 use num_bigint::BigUint;
 use std::fs::File;
@@ -53,6 +55,8 @@ pub struct VcdWriter {
     timestamp: u64,
     header_written: bool,
     external_count: usize,
+    timescale: Timescale,
+    gate: DumpGate,
 }
 
 impl VcdWriter {
@@ -82,9 +86,61 @@ impl VcdWriter {
             timestamp: 0,
             header_written: false,
             external_count: 0,
+            timescale: Timescale::default(),
+            gate: DumpGate::default(),
         })
     }
 
+    /// Start a new file at `path` that records the same signals as this
+    /// writer. The first dump into the new file writes every value, so a
+    /// forked simulation's waveform is self-contained.
+    pub fn fork_to<P: AsRef<Path>>(&self, path: P) -> std::io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            signals: self
+                .signals
+                .iter()
+                .map(|signal| VcdWriterSignal {
+                    vcd_id: String::new(),
+                    scope: signal.scope.clone(),
+                    name: signal.name.clone(),
+                    width: signal.width,
+                    source: signal.source,
+                })
+                .collect(),
+            last_values: vec![None; self.last_values.len()],
+            timestamp: 0,
+            header_written: false,
+            external_count: self.external_count,
+            timescale: self.timescale,
+            gate: self.gate.fresh(),
+        })
+    }
+
+    /// Sets the `$timescale` written in the header. Must be called before
+    /// the first dump.
+    pub fn set_timescale(&mut self, timescale: Timescale) -> Result<(), TimescaleError> {
+        if self.header_written && timescale != self.timescale {
+            return Err(TimescaleError::WaveformStarted);
+        }
+        self.timescale = timescale;
+        Ok(())
+    }
+
+    /// Resume or stop recording. While stopped, dumps are ignored; the first
+    /// one writes every signal as X (`$dumpoff`) so viewers show the gap.
+    /// The first dump after resuming writes every current value.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.gate.set_enabled(enabled);
+    }
+
+    /// Record only dumps whose timestamp lies within `[start, end]`, or every
+    /// dump for `None`. Gaps are marked as with [`Self::set_enabled`].
+    pub fn set_window(&mut self, window: Option<(u64, u64)>) {
+        self.gate.set_window(window);
+    }
+
     /// Adds externally supplied signals before the first dump. VCD headers
     /// cannot be extended after value changes have started.
     pub fn add_external_signals(&mut self, descs: &[VcdExternalSignalDesc]) -> std::io::Result<()> {
@@ -141,7 +197,7 @@ impl VcdWriter {
         writeln!(self.writer, "$version")?;
         writeln!(self.writer, "  celox")?;
         writeln!(self.writer, "$end")?;
-        writeln!(self.writer, "$timescale 1ns $end")?;
+        writeln!(self.writer, "$timescale {} $end", self.timescale)?;
 
         let mut scope_order = Vec::<String>::new();
         let mut scope_groups = Vec::<Vec<usize>>::new();
@@ -193,7 +249,7 @@ impl VcdWriter {
     }
 
     /// Read a value from the JIT memory at the given offset and width.
-    fn read_value(memory: &[u8], offset: usize, width: usize) -> BigUint {
+    pub(crate) fn read_value(memory: &[u8], offset: usize, width: usize) -> BigUint {
         let byte_size = get_byte_size(width);
         let slice = &memory[offset..offset + byte_size];
         let mut val = BigUint::from_bytes_le(slice);
@@ -205,7 +261,7 @@ impl VcdWriter {
         val
     }
 
-    fn mask_to_width(mut value: BigUint, width: usize) -> BigUint {
+    pub(crate) fn mask_to_width(mut value: BigUint, width: usize) -> BigUint {
         if value.bits() > width as u64 {
             value &= (BigUint::from(1u8) << width) - 1u8;
         }
@@ -236,11 +292,16 @@ impl VcdWriter {
                 ),
             ));
         }
-        self.write_header()?;
-        if timestamp > self.timestamp || timestamp == 0 {
-            writeln!(self.writer, "#{}", timestamp)?;
-            self.timestamp = timestamp;
+        if !self.gate.records(timestamp) {
+            if self.header_written && !self.gate.blanked {
+                self.write_dumpoff(timestamp)?;
+                self.gate.blanked = true;
+            }
+            return Ok(());
         }
+        self.gate.blanked = false;
+        self.write_header()?;
+        self.write_timestamp(timestamp)?;
 
         for (i, sig) in self.signals.iter().enumerate() {
             let (current_val, current_mask, is_4state) = match sig.source {
@@ -295,6 +356,35 @@ impl VcdWriter {
         Ok(())
     }
 
+    fn write_timestamp(&mut self, timestamp: u64) -> std::io::Result<()> {
+        if timestamp > self.timestamp || timestamp == 0 {
+            writeln!(self.writer, "#{}", timestamp)?;
+            self.timestamp = timestamp;
+        }
+        Ok(())
+    }
+
+    /// Write every signal as X and forget the last values, so the next
+    /// recorded dump writes everything again.
+    fn write_dumpoff(&mut self, timestamp: u64) -> std::io::Result<()> {
+        self.write_timestamp(timestamp)?;
+        writeln!(self.writer, "$dumpoff")?;
+        for signal in &self.signals {
+            let unknown = (BigUint::from(1u8) << signal.width) - 1u8;
+            Self::write_four_state_value(
+                &mut self.writer,
+                signal.width,
+                &unknown,
+                &unknown,
+                &signal.vcd_id,
+            )?;
+        }
+        writeln!(self.writer, "$end")?;
+        self.writer.flush()?;
+        self.last_values.fill(None);
+        Ok(())
+    }
+
     fn write_four_state_value(
         writer: &mut BufWriter<File>,
         width: usize,
@@ -0,0 +1,139 @@
+//! Cancellation and run-time limits for long simulation calls.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::SimulatorErrorCode;
+
+/// A flag that stops a running simulation from another thread.
+///
+/// Clones share the same flag. Once cancelled, every run that checks the
+/// token fails until [`Self::reset`] is called.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every run that observes this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Clear a previous cancellation so runs can proceed again.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+/// Limits applied to each run call, such as `run_until` or `tick_by_id_n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunLimits {
+    /// Wall-clock budget of one run call, measured from its start.
+    pub wall_clock: Option<Duration>,
+    /// Maximum number of cascade rounds in one time step. A round evaluates
+    /// every newly triggered sequential domain and settles the
+    /// combinational logic again.
+    pub max_cascade_rounds: Option<u32>,
+}
+
+/// Why a run stopped before completing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptReason {
+    /// The run's [`CancelToken`] was cancelled.
+    Cancelled,
+    /// The run exceeded [`RunLimits::wall_clock`].
+    WallClockLimit(Duration),
+    /// A time step exceeded [`RunLimits::max_cascade_rounds`].
+    CascadeLimit(u32),
+}
+
+impl std::fmt::Display for InterruptReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "Simulation cancelled"),
+            Self::WallClockLimit(limit) => write!(f, "Wall-clock limit of {limit:?} exceeded"),
+            Self::CascadeLimit(rounds) => write!(f, "Cascade limit of {rounds} rounds exceeded"),
+        }
+    }
+}
+
+/// Cancellation token and limits owned by one simulator.
+///
+/// The facade calls [`Self::arm`] when a run call starts and
+/// [`Self::check`] at points where the run can stop cleanly.
+#[derive(Debug, Clone, Default)]
+pub struct Watchdog {
+    token: CancelToken,
+    limits: RunLimits,
+    deadline: Option<Instant>,
+}
+
+impl Watchdog {
+    pub fn new(token: CancelToken, limits: RunLimits) -> Self {
+        Self {
+            token,
+            limits,
+            deadline: None,
+        }
+    }
+
+    pub fn token(&self) -> &CancelToken {
+        &self.token
+    }
+
+    pub fn limits(&self) -> RunLimits {
+        self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: RunLimits) {
+        self.limits = limits;
+        self.deadline = None;
+    }
+
+    /// Start the wall-clock budget of a new run call.
+    pub fn arm(&mut self) {
+        self.deadline = self.limits.wall_clock.map(|limit| Instant::now() + limit);
+    }
+
+    /// Fail with [`SimulatorErrorCode::Interrupted`] if the token was
+    /// cancelled or the wall-clock budget is spent.
+    pub fn check(&self, time: u64) -> Result<(), SimulatorErrorCode> {
+        if self.token.is_cancelled() {
+            return Err(SimulatorErrorCode::Interrupted {
+                reason: InterruptReason::Cancelled,
+                time,
+            });
+        }
+        if let (Some(deadline), Some(limit)) = (self.deadline, self.limits.wall_clock)
+            && Instant::now() >= deadline
+        {
+            return Err(SimulatorErrorCode::Interrupted {
+                reason: InterruptReason::WallClockLimit(limit),
+                time,
+            });
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::check`], and also fail once `round` exceeds
+    /// [`RunLimits::max_cascade_rounds`].
+    pub fn check_cascade_round(&self, round: u32, time: u64) -> Result<(), SimulatorErrorCode> {
+        if let Some(max) = self.limits.max_cascade_rounds
+            && round > max
+        {
+            return Err(SimulatorErrorCode::Interrupted {
+                reason: InterruptReason::CascadeLimit(max),
+                time,
+            });
+        }
+        self.check(time)
+    }
+}
@@ -0,0 +1,242 @@
+use crate::fst::FstWriter;
+use crate::timescale::{Timescale, TimescaleError};
+use crate::vcd::{VcdExternalSignalDesc, VcdSignalDesc, VcdWriter};
+use num_bigint::BigUint;
+use std::path::Path;
+
+/// On-disk waveform format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaveformFormat {
+    Vcd,
+    Fst,
+}
+
+impl WaveformFormat {
+    /// `.fst` selects FST; every other extension selects VCD.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("fst") => Self::Fst,
+            _ => Self::Vcd,
+        }
+    }
+}
+
+/// Selects which signals are written to a waveform and when.
+///
+/// Scopes follow `$dumpvars(depth, scope)`: a scope is a hierarchical
+/// instance path such as `"u_core.u_alu"` (`""` is the top module, whose name
+/// may also prefix the path), and `depth` limits how many levels are dumped,
+/// with `1` meaning only the scope itself and `0` every level below it. With
+/// no scopes, every instance is dumped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DumpFilter {
+    scopes: Vec<(Vec<String>, usize)>,
+    exclude_arrays: bool,
+    window: Option<(u64, u64)>,
+}
+
+impl DumpFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Dump `path` and up to `depth` levels of it (`0` for all levels).
+    pub fn scope(mut self, path: &str, depth: usize) -> Self {
+        let segments = path
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.scopes.push((segments, depth));
+        self
+    }
+
+    /// Include unpacked arrays (the default) or leave them out.
+    pub fn arrays(mut self, include: bool) -> Self {
+        self.exclude_arrays = !include;
+        self
+    }
+
+    /// Record only between `start` and `end` inclusive.
+    pub fn window(mut self, start: u64, end: u64) -> Self {
+        self.window = Some((start, end));
+        self
+    }
+
+    pub fn includes_arrays(&self) -> bool {
+        !self.exclude_arrays
+    }
+
+    pub fn time_window(&self) -> Option<(u64, u64)> {
+        self.window
+    }
+
+    /// Whether an instance at `path` below the top module named `top` is
+    /// within one of the scopes.
+    pub fn includes_instance(&self, top: &str, path: &[String]) -> bool {
+        self.scopes.is_empty()
+            || self.scopes.iter().any(|(scope, depth)| {
+                let scope = match scope.split_first() {
+                    Some((first, rest)) if first == top && path.first() != Some(first) => rest,
+                    _ => scope.as_slice(),
+                };
+                path.starts_with(scope) && (*depth == 0 || path.len() - scope.len() < *depth)
+            })
+    }
+}
+
+/// Decides which dumps a writer records: dumping can be switched off at
+/// runtime and limited to an inclusive time window.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DumpGate {
+    off: bool,
+    window: Option<(u64, u64)>,
+    /// Every signal has been written as X since recording stopped.
+    pub(crate) blanked: bool,
+}
+
+impl DumpGate {
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.off = !enabled;
+    }
+
+    pub(crate) fn set_window(&mut self, window: Option<(u64, u64)>) {
+        self.window = window;
+    }
+
+    pub(crate) fn records(&self, timestamp: u64) -> bool {
+        !self.off
+            && self
+                .window
+                .is_none_or(|(start, end)| start <= timestamp && timestamp <= end)
+    }
+
+    /// The gate for a new file: same settings, nothing written yet.
+    pub(crate) fn fresh(&self) -> Self {
+        Self {
+            blanked: false,
+            ..*self
+        }
+    }
+}
+
+/// A VCD or FST writer behind the shared dump contract.
+pub enum WaveformWriter {
+    Vcd(VcdWriter),
+    Fst(Box<FstWriter>),
+}
+
+impl WaveformWriter {
+    /// Open a writer whose format is chosen by the extension of `path`.
+    pub fn new<P: AsRef<Path>>(path: P, descs: &[VcdSignalDesc]) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        match WaveformFormat::from_path(path) {
+            WaveformFormat::Vcd => VcdWriter::new(path, descs).map(Self::Vcd),
+            WaveformFormat::Fst => {
+                FstWriter::new(path, descs).map(|writer| Self::Fst(Box::new(writer)))
+            }
+        }
+    }
+
+    pub fn format(&self) -> WaveformFormat {
+        match self {
+            Self::Vcd(_) => WaveformFormat::Vcd,
+            Self::Fst(_) => WaveformFormat::Fst,
+        }
+    }
+
+    /// Start a new file at `path` in the same format, recording the same
+    /// signals. The first dump into it writes every value.
+    pub fn fork_to<P: AsRef<Path>>(&self, path: P) -> std::io::Result<Self> {
+        match self {
+            Self::Vcd(writer) => writer.fork_to(path).map(Self::Vcd),
+            Self::Fst(writer) => writer
+                .fork_to(path)
+                .map(|writer| Self::Fst(Box::new(writer))),
+        }
+    }
+
+    pub fn set_timescale(&mut self, timescale: Timescale) -> Result<(), TimescaleError> {
+        match self {
+            Self::Vcd(writer) => writer.set_timescale(timescale),
+            Self::Fst(writer) => writer.set_timescale(timescale),
+        }
+    }
+
+    /// Resume or stop recording. See [`VcdWriter::set_enabled`].
+    pub fn set_enabled(&mut self, enabled: bool) {
+        match self {
+            Self::Vcd(writer) => writer.set_enabled(enabled),
+            Self::Fst(writer) => writer.set_enabled(enabled),
+        }
+    }
+
+    /// Record only dumps within `[start, end]`. See [`VcdWriter::set_window`].
+    pub fn set_window(&mut self, window: Option<(u64, u64)>) {
+        match self {
+            Self::Vcd(writer) => writer.set_window(window),
+            Self::Fst(writer) => writer.set_window(window),
+        }
+    }
+
+    pub fn add_external_signals(&mut self, descs: &[VcdExternalSignalDesc]) -> std::io::Result<()> {
+        match self {
+            Self::Vcd(writer) => writer.add_external_signals(descs),
+            Self::Fst(writer) => writer.add_external_signals(descs),
+        }
+    }
+
+    pub fn dump(&mut self, timestamp: u64, memory: &[u8]) -> std::io::Result<()> {
+        self.dump_with_external(timestamp, memory, &[])
+    }
+
+    pub fn dump_with_external(
+        &mut self,
+        timestamp: u64,
+        memory: &[u8],
+        external: &[(BigUint, BigUint)],
+    ) -> std::io::Result<()> {
+        match self {
+            Self::Vcd(writer) => writer.dump_with_external(timestamp, memory, external),
+            Self::Fst(writer) => writer.dump_with_external(timestamp, memory, external),
+        }
+    }
+}
+
+impl From<VcdWriter> for WaveformWriter {
+    fn from(writer: VcdWriter) -> Self {
+        Self::Vcd(writer)
+    }
+}
+
+impl From<FstWriter> for WaveformWriter {
+    fn from(writer: FstWriter) -> Self {
+        Self::Fst(Box::new(writer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(text: &str) -> Vec<String> {
+        text.split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn scopes_limit_depth_like_dumpvars() {
+        let filter = DumpFilter::new().scope("Top.u_core", 2);
+        assert!(!filter.includes_instance("Top", &path("")));
+        assert!(filter.includes_instance("Top", &path("u_core")));
+        assert!(filter.includes_instance("Top", &path("u_core.u_alu")));
+        assert!(!filter.includes_instance("Top", &path("u_core.u_alu.u_add")));
+        assert!(!filter.includes_instance("Top", &path("u_mem")));
+
+        let everything = DumpFilter::new().scope("", 0);
+        assert!(everything.includes_instance("Top", &path("u_core.u_alu.u_add")));
+        assert!(DumpFilter::new().includes_instance("Top", &path("u_mem")));
+    }
+}
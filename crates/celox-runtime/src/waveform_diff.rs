@@ -0,0 +1,267 @@
+//! Comparing two waveforms signal by signal.
+//!
+//! Signals are aligned by hierarchical name and each pair is walked through
+//! the union of its change times, so the reported divergence is the earliest
+//! time at which the two dumps disagree, regardless of how often either file
+//! repeats unchanged values. Times are compared in the finer of the two
+//! timescales.
+
+use crate::timescale::Timescale;
+use crate::waveform_reader::{Waveform, WaveformSignal};
+use std::collections::BTreeMap;
+
+/// How two waveforms are matched up and compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WaveformDiffOptions {
+    left_scope: Option<String>,
+    right_scope: Option<String>,
+    x_tolerant: bool,
+}
+
+impl WaveformDiffOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compare only the left signals under `scope`, named relative to it.
+    pub fn left_scope(mut self, scope: &str) -> Self {
+        self.left_scope = Some(scope.to_string());
+        self
+    }
+
+    /// Compare only the right signals under `scope`, named relative to it.
+    pub fn right_scope(mut self, scope: &str) -> Self {
+        self.right_scope = Some(scope.to_string());
+        self
+    }
+
+    /// Let an `x` bit on either side match any value, e.g. when comparing a
+    /// four-state dump against a two-state simulator.
+    pub fn x_tolerant(mut self, tolerant: bool) -> Self {
+        self.x_tolerant = tolerant;
+        self
+    }
+}
+
+/// The first time a signal differs between the two waveforms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalDivergence {
+    pub name: String,
+    pub time: u64,
+    /// Left value at `time`; `None` before its first change.
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+/// Result of comparing two waveforms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveformDiff {
+    /// Unit of the divergence times, when either waveform declares one.
+    pub timescale: Option<Timescale>,
+    /// Diverging signals, ordered by time and then name.
+    pub divergences: Vec<SignalDivergence>,
+    /// Signals present in only one of the waveforms.
+    pub only_left: Vec<String>,
+    pub only_right: Vec<String>,
+    /// Number of signals present in both.
+    pub compared: usize,
+}
+
+impl WaveformDiff {
+    pub fn new(left: &Waveform, right: &Waveform, options: &WaveformDiffOptions) -> Self {
+        let (timescale, left_scale, right_scale) = match (left.timescale, right.timescale) {
+            (Some(l), Some(r)) => {
+                let finer = l.femtoseconds().min(r.femtoseconds());
+                let common = if finer == l.femtoseconds() { l } else { r };
+                (
+                    Some(common),
+                    l.femtoseconds() / finer,
+                    r.femtoseconds() / finer,
+                )
+            }
+            (timescale, None) | (None, timescale) => (timescale, 1, 1),
+        };
+
+        let left_signals = scoped(left, options.left_scope.as_deref());
+        let mut right_signals = scoped(right, options.right_scope.as_deref());
+        let mut diff = Self {
+            timescale,
+            divergences: Vec::new(),
+            only_left: Vec::new(),
+            only_right: Vec::new(),
+            compared: 0,
+        };
+        for (name, l) in left_signals {
+            let Some(r) = right_signals.remove(&name) else {
+                diff.only_left.push(name);
+                continue;
+            };
+            diff.compared += 1;
+            let sides = [(l, left_scale), (r, right_scale)];
+            if let Some((time, left, right)) = first_divergence(sides, options.x_tolerant) {
+                diff.divergences.push(SignalDivergence {
+                    name,
+                    time,
+                    left: left.map(str::to_string),
+                    right: right.map(str::to_string),
+                });
+            }
+        }
+        diff.only_right = right_signals.into_keys().collect();
+        diff.divergences
+            .sort_by(|a, b| (a.time, &a.name).cmp(&(b.time, &b.name)));
+        diff
+    }
+
+    /// Whether every signal is present in both waveforms and equal throughout.
+    pub fn is_identical(&self) -> bool {
+        self.divergences.is_empty() && self.only_left.is_empty() && self.only_right.is_empty()
+    }
+}
+
+fn scoped<'a>(waveform: &'a Waveform, scope: Option<&str>) -> BTreeMap<String, &'a WaveformSignal> {
+    waveform
+        .signals
+        .iter()
+        .filter_map(|signal| {
+            let name = match scope {
+                Some(scope) => signal
+                    .name
+                    .strip_prefix(scope)
+                    .and_then(|rest| rest.strip_prefix('.'))?,
+                None => &signal.name,
+            };
+            Some((name.to_string(), signal))
+        })
+        .collect()
+}
+
+type Divergence<'a> = (u64, Option<&'a str>, Option<&'a str>);
+
+fn first_divergence<'a>(
+    sides: [(&'a WaveformSignal, u64); 2],
+    x_tolerant: bool,
+) -> Option<Divergence<'a>> {
+    let mut next = [0usize; 2];
+    let mut current = [None::<&str>; 2];
+    loop {
+        let time = sides
+            .iter()
+            .zip(next)
+            .filter_map(|((signal, scale), index)| {
+                signal.changes.get(index).map(|(time, _)| time * scale)
+            })
+            .min()?;
+        for (side, (signal, scale)) in sides.iter().enumerate() {
+            while let Some((changed, value)) = signal.changes.get(next[side]) {
+                if changed * scale != time {
+                    break;
+                }
+                current[side] = Some(value);
+                next[side] += 1;
+            }
+        }
+        if !values_match(current[0], current[1], x_tolerant) {
+            return Some((time, current[0], current[1]));
+        }
+    }
+}
+
+/// Compare two values bit by bit, widening the narrower with zeros. A value
+/// that has not been set yet reads as all `x`.
+fn values_match<'a>(left: Option<&'a str>, right: Option<&'a str>, x_tolerant: bool) -> bool {
+    let (left, right) = match (left, right) {
+        (None, None) => return true,
+        (Some(value), None) | (None, Some(value)) => {
+            return x_tolerant || value.bytes().all(|bit| bit == b'x');
+        }
+        (Some(left), Some(right)) => (left, right),
+    };
+    if left.starts_with('r') || right.starts_with('r') {
+        return left == right;
+    }
+    let width = left.len().max(right.len());
+    let bits = |value: &'a str| std::iter::repeat_n(b'0', width - value.len()).chain(value.bytes());
+    bits(left)
+        .zip(bits(right))
+        .all(|(l, r)| l == r || (x_tolerant && (l == b'x' || r == b'x')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(vcd: &str) -> Waveform {
+        Waveform::from_vcd(vcd.as_bytes()).unwrap()
+    }
+
+    const LEFT: &str = "\
+$timescale 1ns $end
+$scope module top $end
+$var wire 4 ! a $end
+$var wire 1 \" b $end
+$var wire 1 # only_left $end
+$upscope $end
+$enddefinitions $end
+#0
+bx !
+0\"
+0#
+#10
+b11 !
+1\"
+#20
+b111 !
+";
+
+    const RIGHT: &str = "\
+$timescale 100ps $end
+$scope module TOP $end
+$scope module top $end
+$var wire 4 ! a $end
+$var wire 1 \" b $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+b0 !
+0\"
+#100
+b11 !
+1\"
+#150
+b110 !
+";
+
+    #[test]
+    fn reports_first_divergence_per_signal() {
+        let options = WaveformDiffOptions::new()
+            .left_scope("top")
+            .right_scope("TOP.top");
+        let diff = WaveformDiff::new(&read(LEFT), &read(RIGHT), &options);
+        assert_eq!(diff.timescale, Some("100ps".parse().unwrap()));
+        assert_eq!(diff.compared, 2);
+        assert_eq!(diff.only_left, vec!["only_left".to_string()]);
+        assert_eq!(
+            diff.divergences,
+            vec![SignalDivergence {
+                name: "a".into(),
+                time: 0,
+                left: Some("xxxx".into()),
+                right: Some("0000".into()),
+            }]
+        );
+
+        let tolerant = WaveformDiff::new(&read(LEFT), &read(RIGHT), &options.x_tolerant(true));
+        assert_eq!(tolerant.divergences.len(), 1);
+        assert_eq!(tolerant.divergences[0].time, 150);
+        assert_eq!(tolerant.divergences[0].right.as_deref(), Some("0110"));
+    }
+
+    #[test]
+    fn identical_waveforms_do_not_differ() {
+        let diff = WaveformDiff::new(&read(LEFT), &read(LEFT), &WaveformDiffOptions::new());
+        assert!(diff.is_identical());
+        assert_eq!(diff.compared, 3);
+    }
+}
@@ -0,0 +1,379 @@
+//! Reading VCD and FST waveforms back into memory.
+//!
+//! Every variable is keyed by its hierarchical name, the enclosing scopes and
+//! the variable name joined by `.`. Values are kept as strings of `0`, `1`,
+//! `x` and `z`, most significant bit first and extended to the declared
+//! width, so dumps from different simulators compare directly.
+
+use crate::timescale::Timescale;
+use crate::waveform::WaveformFormat;
+use fst_reader::{FstFilter, FstHierarchyEntry, FstReader, FstSignalValue};
+use std::io::BufRead;
+use std::path::Path;
+
+/// Failure while reading a waveform.
+#[derive(Debug)]
+pub enum WaveformReadError {
+    Io(std::io::Error),
+    /// The VCD text is malformed.
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    /// The FST file is malformed.
+    Fst(String),
+}
+
+impl std::fmt::Display for WaveformReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{error}"),
+            Self::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            Self::Fst(message) => write!(f, "invalid FST file: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for WaveformReadError {}
+
+impl From<std::io::Error> for WaveformReadError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// One variable of a waveform and its value changes in time order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveformSignal {
+    pub name: String,
+    pub width: usize,
+    pub changes: Vec<(u64, String)>,
+}
+
+impl WaveformSignal {
+    fn new(name: String, width: usize) -> Self {
+        Self {
+            name,
+            width,
+            changes: Vec::new(),
+        }
+    }
+
+    /// The value at `time`, or `None` before the first change.
+    pub fn value_at(&self, time: u64) -> Option<&str> {
+        let index = self
+            .changes
+            .partition_point(|(changed, _)| *changed <= time);
+        index
+            .checked_sub(1)
+            .map(|index| self.changes[index].1.as_str())
+    }
+
+    fn push(&mut self, time: u64, value: String) {
+        match self.changes.last_mut() {
+            Some((last, previous)) if *last == time => *previous = value,
+            Some((_, previous)) if *previous == value => {}
+            _ => self.changes.push((time, value)),
+        }
+    }
+}
+
+/// A waveform read from a VCD or FST file.
+#[derive(Debug, Clone, Default)]
+pub struct Waveform {
+    /// Length of one time unit, when the file declares a supported one.
+    pub timescale: Option<Timescale>,
+    pub signals: Vec<WaveformSignal>,
+}
+
+impl Waveform {
+    /// Read a waveform, choosing the format by the extension of `path` as
+    /// [`crate::WaveformWriter::new`] does.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, WaveformReadError> {
+        let path = path.as_ref();
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        match WaveformFormat::from_path(path) {
+            WaveformFormat::Vcd => Self::from_vcd(file),
+            WaveformFormat::Fst => Self::from_fst(file),
+        }
+    }
+
+    /// Parse VCD text.
+    pub fn from_vcd(input: impl BufRead) -> Result<Self, WaveformReadError> {
+        VcdParser::new(input).parse()
+    }
+
+    /// Read an FST file.
+    pub fn from_fst(input: impl BufRead + std::io::Seek) -> Result<Self, WaveformReadError> {
+        let fst_error = |error: fst_reader::ReaderError| WaveformReadError::Fst(error.to_string());
+        let mut reader = FstReader::open(input).map_err(fst_error)?;
+        let timescale = Timescale::from_exponent(reader.get_header().timescale_exponent);
+
+        let mut signals = Vec::new();
+        // Aliases share a handle, so one handle can feed several signals.
+        let mut by_handle = Vec::<Vec<usize>>::new();
+        let mut scopes = Vec::<String>::new();
+        reader
+            .read_hierarchy(|entry| match entry {
+                FstHierarchyEntry::Scope { name, .. } => scopes.push(name),
+                FstHierarchyEntry::UpScope => {
+                    scopes.pop();
+                }
+                FstHierarchyEntry::Var {
+                    name,
+                    length,
+                    handle,
+                    ..
+                } => {
+                    let index = handle.get_index();
+                    if by_handle.len() <= index {
+                        by_handle.resize_with(index + 1, Vec::new);
+                    }
+                    by_handle[index].push(signals.len());
+                    signals.push(WaveformSignal::new(
+                        hierarchical_name(&scopes, &name),
+                        length as usize,
+                    ));
+                }
+                _ => {}
+            })
+            .map_err(fst_error)?;
+
+        reader
+            .read_signals(&FstFilter::all(), |time, handle, value| {
+                let value = match value {
+                    FstSignalValue::String(bits) => String::from_utf8_lossy(bits).into_owned(),
+                    FstSignalValue::Real(real) => format!("r{real}"),
+                };
+                for &signal in by_handle.get(handle.get_index()).into_iter().flatten() {
+                    let signal = &mut signals[signal];
+                    let value = normalize(&value, signal.width);
+                    signal.push(time, value);
+                }
+            })
+            .map_err(fst_error)?;
+        Ok(Self { timescale, signals })
+    }
+
+    /// The signal with hierarchical name `name`.
+    pub fn signal(&self, name: &str) -> Option<&WaveformSignal> {
+        self.signals.iter().find(|signal| signal.name == name)
+    }
+}
+
+fn hierarchical_name(scopes: &[String], name: &str) -> String {
+    scopes
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(name))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Lowercase a value and extend it to `width` bits: with `x` or `z` when that
+/// is the leading bit, otherwise with `0`. Real values are kept as written.
+fn normalize(value: &str, width: usize) -> String {
+    if value.starts_with(['r', 'R']) {
+        return value.to_string();
+    }
+    let value = value.to_ascii_lowercase();
+    if value.len() >= width {
+        return value;
+    }
+    let fill = value.chars().next().filter(|bit| matches!(bit, 'x' | 'z'));
+    let fill = fill.unwrap_or('0');
+    std::iter::repeat_n(fill, width - value.len())
+        .chain(value.chars())
+        .collect()
+}
+
+struct VcdParser<R> {
+    input: R,
+    line: usize,
+    tokens: std::collections::VecDeque<String>,
+}
+
+impl<R: BufRead> VcdParser<R> {
+    fn new(input: R) -> Self {
+        Self {
+            input,
+            line: 0,
+            tokens: std::collections::VecDeque::new(),
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<String>, WaveformReadError> {
+        let mut text = String::new();
+        while self.tokens.is_empty() {
+            text.clear();
+            if self.input.read_line(&mut text)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+            self.tokens
+                .extend(text.split_whitespace().map(str::to_string));
+        }
+        Ok(self.tokens.pop_front())
+    }
+
+    fn syntax(&self, message: impl Into<String>) -> WaveformReadError {
+        WaveformReadError::Syntax {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    /// The tokens up to the closing `$end`.
+    fn section(&mut self, keyword: &str) -> Result<Vec<String>, WaveformReadError> {
+        let mut tokens = Vec::new();
+        loop {
+            match self.next_token()? {
+                Some(token) if token == "$end" => return Ok(tokens),
+                Some(token) => tokens.push(token),
+                None => return Err(self.syntax(format!("{keyword} is missing $end"))),
+            }
+        }
+    }
+
+    fn parse(mut self) -> Result<Waveform, WaveformReadError> {
+        let mut waveform = Waveform::default();
+        let mut scopes = Vec::<String>::new();
+        let mut by_id = fxhash::FxHashMap::<String, Vec<usize>>::default();
+        let mut time = 0;
+
+        while let Some(token) = self.next_token()? {
+            match token.as_str() {
+                "$scope" => {
+                    let section = self.section("$scope")?;
+                    let name = section
+                        .get(1)
+                        .ok_or_else(|| self.syntax("$scope without a name"))?;
+                    scopes.push(name.clone());
+                }
+                "$upscope" => {
+                    self.section("$upscope")?;
+                    scopes.pop();
+                }
+                "$var" => {
+                    let section = self.section("$var")?;
+                    let [_, width, id, name, rest @ ..] = section.as_slice() else {
+                        return Err(self.syntax("$var needs a type, width, id and name"));
+                    };
+                    let width = width
+                        .parse::<usize>()
+                        .map_err(|_| self.syntax(format!("invalid $var width '{width}'")))?;
+                    // A single-bit select such as `data [3]` names one bit.
+                    let name = match rest {
+                        [select] if width == 1 && !select.contains(':') => {
+                            format!("{name}{select}")
+                        }
+                        _ => name.clone(),
+                    };
+                    by_id
+                        .entry(id.clone())
+                        .or_default()
+                        .push(waveform.signals.len());
+                    waveform.signals.push(WaveformSignal::new(
+                        hierarchical_name(&scopes, &name),
+                        width,
+                    ));
+                }
+                "$timescale" => {
+                    let section = self.section("$timescale")?;
+                    waveform.timescale = section.concat().parse().ok();
+                }
+                // Value changes inside these are read as ordinary changes.
+                "$dumpvars" | "$dumpall" | "$dumpon" | "$dumpoff" | "$end" => {}
+                keyword if keyword.starts_with('$') => {
+                    self.section(keyword)?;
+                }
+                _ => {
+                    if let Some(timestamp) = token.strip_prefix('#') {
+                        time = timestamp
+                            .parse()
+                            .map_err(|_| self.syntax(format!("invalid time '{token}'")))?;
+                        continue;
+                    }
+                    let (value, id) = match token.as_bytes()[0] {
+                        b'b' | b'B' | b'r' | b'R' => {
+                            let id = self
+                                .next_token()?
+                                .ok_or_else(|| self.syntax("value change without an id"))?;
+                            let value = if token.starts_with(['r', 'R']) {
+                                token.clone()
+                            } else {
+                                token[1..].to_string()
+                            };
+                            (value, id)
+                        }
+                        b'0' | b'1' | b'x' | b'X' | b'z' | b'Z' => {
+                            (token[..1].to_string(), token[1..].to_string())
+                        }
+                        _ => return Err(self.syntax(format!("unexpected '{token}'"))),
+                    };
+                    let signals = by_id
+                        .get(&id)
+                        .ok_or_else(|| self.syntax(format!("unknown id '{id}'")))?;
+                    for &signal in signals {
+                        let signal = &mut waveform.signals[signal];
+                        let value = normalize(&value, signal.width);
+                        signal.push(time, value);
+                    }
+                }
+            }
+        }
+        Ok(waveform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VCD: &str = "\
+$timescale 10ps $end
+$scope module top $end
+$var wire 1 ! clk $end
+$scope module u_sub $end
+$var wire 4 \" data [3:0] $end
+$var wire 4 \" alias $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+$dumpvars
+0!
+bx \"
+$end
+#5
+1!
+b1 \"
+#10
+0!
+b1 \"
+";
+
+    #[test]
+    fn reads_vcd_changes_by_hierarchical_name() {
+        let waveform = Waveform::from_vcd(VCD.as_bytes()).unwrap();
+        assert_eq!(waveform.timescale, Some("10ps".parse().unwrap()));
+        let data = waveform.signal("top.u_sub.data").unwrap();
+        assert_eq!(data.width, 4);
+        assert_eq!(
+            data.changes,
+            vec![(0, "xxxx".to_string()), (5, "0001".to_string())]
+        );
+        assert_eq!(data.value_at(7), Some("0001"));
+        assert_eq!(
+            waveform.signal("top.u_sub.alias").unwrap().changes,
+            data.changes
+        );
+        assert_eq!(waveform.signal("top.clk").unwrap().value_at(9), Some("1"));
+    }
+
+    #[test]
+    fn rejects_undeclared_ids() {
+        let error = Waveform::from_vcd("#0\n1?\n".as_bytes()).unwrap_err();
+        assert!(matches!(error, WaveformReadError::Syntax { line: 2, .. }));
+    }
+}
@@ -174,4 +174,51 @@ mod tests {
         assert!(!eu.register_map.contains_key(&dead_value));
         assert!(!eu.register_map.contains_key(&dead_parameter));
     }
+
+    #[test]
+    fn removes_dead_cones_in_textual_sir() {
+        let parse =
+            |text| celox_sir::parse::parse_execution_unit::<RegionedAbsoluteAddr>(text).unwrap();
+        let mut eu = parse(
+            "
+            ExecutionUnit {
+              entry: b0
+              registers: {
+                r0: logic<8>
+                r1: logic<8>
+                r2: logic<8>
+                r3: logic<8>
+              }
+            b0:
+              r0 = Load(addr=RegionedAbsoluteAddr(region=0, inst0, state0), offset=0, bits=8)
+              r1 = r0 Add r0 // only feeds the dead parameter
+              Jump(b1 [r0, r1])
+            b1:
+              params: [r2, r3]
+              Store(addr=RegionedAbsoluteAddr(region=0, inst0, state1), offset=0, src_reg = 2, bits=8, triggers=[], comb_capture_sites=[])
+              Return
+            }",
+        );
+
+        DeadCodeEliminationPass.run(&mut eu, &PassOptions::default());
+
+        let expected = parse(
+            "
+            ExecutionUnit {
+              entry: b0
+              registers: {
+                r0: logic<8>
+                r2: logic<8>
+              }
+            b0:
+              r0 = Load(addr=RegionedAbsoluteAddr(region=0, inst0, state0), offset=0, bits=8)
+              Jump(b1 [r0])
+            b1:
+              params: [r2]
+              Store(addr=RegionedAbsoluteAddr(region=0, inst0, state1), offset=0, src_reg = 2, bits=8, triggers=[], comb_capture_sites=[])
+              Return
+            }",
+        );
+        assert_eq!(eu, expected, "{eu}");
+    }
 }
@@ -10,6 +10,7 @@ pub use celox_design::{BinaryOp, DomainKind, TriggerIdWithKind, UnaryOp};
 
 pub mod builder;
 pub mod cfg;
+pub mod parse;
 mod serde_helpers;
 pub mod transform;
 pub mod verify;
@@ -0,0 +1,892 @@
+//! Reader for the textual SIR written by the `Display` implementations.
+//!
+//! [`parse_execution_unit`] accepts exactly what [`ExecutionUnit`] prints, so
+//! a unit dumped from a trace can be pasted into a test and read back. Blank
+//! lines and `//` comments are ignored, which keeps hand-written units easy to
+//! annotate. Addresses are delegated to [`ParseAddr`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+
+use crate::{
+    BasicBlock, BinaryOp, BlockId, DomainKind, ExecutionUnit, HashMap, RegisterId, RegisterType,
+    SIRInstruction, SIROffset, SIRSwitchCase, SIRTerminator, SIRValue, TriggerIdWithKind, UnaryOp,
+};
+use celox_design::{
+    AbsoluteAddrBase, InstanceId, RegionedAbsoluteAddrBase, RegionedVarAddrBase, StateObjectId,
+};
+
+/// Address types that can be read back from their `Display` form.
+pub trait ParseAddr: Sized {
+    fn parse_addr(text: &str) -> Result<Self, String>;
+}
+
+impl ParseAddr for usize {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        text.parse()
+            .map_err(|_| format!("invalid address `{text}`"))
+    }
+}
+
+impl ParseAddr for u32 {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        text.parse()
+            .map_err(|_| format!("invalid address `{text}`"))
+    }
+}
+
+impl ParseAddr for InstanceId {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        prefixed_number(text, "inst").map(InstanceId)
+    }
+}
+
+impl ParseAddr for StateObjectId {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        prefixed_number(text, "state").map(StateObjectId::from_raw)
+    }
+}
+
+impl<V: ParseAddr> ParseAddr for AbsoluteAddrBase<V> {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        let [instance_id, var_id] = call_args(text, "AbsoluteAddr")?;
+        Ok(Self {
+            instance_id: InstanceId::parse_addr(instance_id)?,
+            var_id: V::parse_addr(var_id)?,
+        })
+    }
+}
+
+impl<V: ParseAddr> ParseAddr for RegionedAbsoluteAddrBase<V> {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        let [region, instance_id, var_id] = call_args(text, "RegionedAbsoluteAddr")?;
+        Ok(Self {
+            region: region_arg(region)?,
+            instance_id: InstanceId::parse_addr(instance_id)?,
+            var_id: V::parse_addr(var_id)?,
+        })
+    }
+}
+
+impl<V: ParseAddr> ParseAddr for RegionedVarAddrBase<V> {
+    fn parse_addr(text: &str) -> Result<Self, String> {
+        let [region, var_id] = call_args(text, "RegionedVarAddr")?;
+        Ok(Self {
+            region: region_arg(region)?,
+            var_id: V::parse_addr(var_id)?,
+        })
+    }
+}
+
+fn prefixed_number<T: FromStr>(text: &str, prefix: &str) -> Result<T, String> {
+    text.strip_prefix(prefix)
+        .and_then(|digits| digits.parse().ok())
+        .ok_or_else(|| format!("expected `{prefix}<n>`, found `{text}`"))
+}
+
+/// Split `name(a, b, ...)` into exactly `N` top-level arguments.
+fn call_args<'a, const N: usize>(text: &'a str, name: &str) -> Result<[&'a str; N], String> {
+    let inner = text
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('('))
+        .and_then(|rest| rest.strip_suffix(')'))
+        .ok_or_else(|| format!("expected `{name}(...)`, found `{text}`"))?;
+    let mut args = Vec::with_capacity(N);
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, ch) in inner.char_indices() {
+        match ch {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                args.push(inner[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(inner[start..].trim());
+    args.try_into()
+        .map_err(|args: Vec<_>| format!("`{name}` takes {N} arguments, found {}", args.len()))
+}
+
+fn region_arg(text: &str) -> Result<u32, String> {
+    text.strip_prefix("region=")
+        .and_then(|region| region.parse().ok())
+        .ok_or_else(|| format!("expected `region=<n>`, found `{text}`"))
+}
+
+/// Failure to read textual SIR, with the 1-based line it occurred on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SirParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SirParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SIR parse error at line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for SirParseError {}
+
+/// Read a whole execution unit in the form printed by its `Display`.
+pub fn parse_execution_unit<A: ParseAddr>(text: &str) -> Result<ExecutionUnit<A>, SirParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.split_once("//").map_or(line, |(code, _)| code);
+            (index + 1, line.trim())
+        })
+        .filter(|(_, line)| !line.is_empty());
+    let mut last_line = 0;
+    let mut next = |expected: &str| {
+        let next = lines.next();
+        if let Some((number, _)) = next {
+            last_line = number;
+        }
+        next.ok_or_else(|| SirParseError {
+            line: last_line,
+            message: format!("unexpected end of input, expected {expected}"),
+        })
+    };
+    let at = |line: usize| move |message: String| SirParseError { line, message };
+
+    let (number, line) = next("`ExecutionUnit {`")?;
+    exact(line, "ExecutionUnit {").map_err(at(number))?;
+    let (number, line) = next("`entry: b<n>`")?;
+    let entry_block_id = line_with(line, |c| {
+        c.expect("entry")?;
+        c.expect(":")?;
+        c.block()
+    })
+    .map_err(at(number))?;
+    let (number, line) = next("`registers: {`")?;
+    exact(line, "registers: {").map_err(at(number))?;
+
+    let mut register_map = HashMap::default();
+    loop {
+        let (number, line) = next("a register or `}`")?;
+        if line == "}" {
+            break;
+        }
+        let (register, ty) = line_with(line, |c| {
+            let register = c.register()?;
+            c.expect(":")?;
+            let signed = c.eat("signed");
+            let kind = c.word();
+            c.expect("<")?;
+            let width = c.number()?;
+            c.expect(">")?;
+            let ty = match (kind, signed) {
+                ("logic", false) => RegisterType::Logic { width },
+                ("bit", _) => RegisterType::Bit { width, signed },
+                _ => return Err(format!("unknown register type `{kind}`")),
+            };
+            Ok((register, ty))
+        })
+        .map_err(at(number))?;
+        if register_map.insert(register, ty).is_some() {
+            return Err(at(number)(format!("register {register} declared twice")));
+        }
+    }
+
+    let mut blocks = HashMap::default();
+    loop {
+        let (number, line) = next("a block or `}`")?;
+        if line == "}" {
+            break;
+        }
+        let id = line_with(line, |c| {
+            let id = c.block()?;
+            c.expect(":")?;
+            Ok(id)
+        })
+        .map_err(at(number))?;
+        let mut block = BasicBlock {
+            id,
+            params: Vec::new(),
+            instructions: Vec::new(),
+            terminator: SIRTerminator::Return,
+        };
+        let mut first = true;
+        loop {
+            let (number, line) = next("an instruction or terminator")?;
+            if first && line.starts_with("params:") {
+                block.params = line_with(line, |c| {
+                    c.expect("params")?;
+                    c.expect(":")?;
+                    c.registers()
+                })
+                .map_err(at(number))?;
+                first = false;
+                continue;
+            }
+            first = false;
+            if is_terminator(line) {
+                block.terminator = line_with(line, Cursor::terminator).map_err(at(number))?;
+                break;
+            }
+            block
+                .instructions
+                .push(line_with(line, Cursor::instruction).map_err(at(number))?);
+        }
+        if blocks.insert(id, block).is_some() {
+            return Err(at(number)(format!("block b{} defined twice", id.0)));
+        }
+    }
+    if let Some((number, line)) = lines.next() {
+        return Err(at(number)(format!("unexpected `{line}` after the unit")));
+    }
+    if !blocks.contains_key(&entry_block_id) {
+        return Err(at(last_line)(format!(
+            "entry block b{} is not defined",
+            entry_block_id.0
+        )));
+    }
+    Ok(ExecutionUnit {
+        entry_block_id,
+        blocks,
+        register_map,
+    })
+}
+
+/// Read one instruction in the form printed by its `Display`.
+pub fn parse_instruction<A: ParseAddr>(text: &str) -> Result<SIRInstruction<A>, SirParseError> {
+    line_with(text.trim(), Cursor::instruction)
+        .map_err(|message| SirParseError { line: 1, message })
+}
+
+/// Read one terminator in the form printed by its `Display`.
+pub fn parse_terminator(text: &str) -> Result<SIRTerminator, SirParseError> {
+    line_with(text.trim(), Cursor::terminator).map_err(|message| SirParseError { line: 1, message })
+}
+
+fn exact(line: &str, expected: &str) -> Result<(), String> {
+    if line.split_whitespace().eq(expected.split_whitespace()) {
+        Ok(())
+    } else {
+        Err(format!("expected `{expected}`, found `{line}`"))
+    }
+}
+
+fn is_terminator(line: &str) -> bool {
+    let head = line
+        .split(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+        .next()
+        .unwrap_or_default();
+    matches!(head, "Jump" | "Branch" | "Switch" | "Return" | "Error")
+}
+
+/// Run `parse` over a whole line and reject trailing text.
+fn line_with<'a, T>(
+    line: &'a str,
+    parse: impl FnOnce(&mut Cursor<'a>) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut cursor = Cursor { text: line, pos: 0 };
+    let value = parse(&mut cursor)?;
+    cursor.skip_ws();
+    if cursor.rest().is_empty() {
+        Ok(value)
+    } else {
+        Err(format!("unexpected `{}`", cursor.rest()))
+    }
+}
+
+const BINARY_OPS: [BinaryOp; 29] = [
+    BinaryOp::Add,
+    BinaryOp::Sub,
+    BinaryOp::Mul,
+    BinaryOp::DivU,
+    BinaryOp::DivS,
+    BinaryOp::RemU,
+    BinaryOp::RemS,
+    BinaryOp::And,
+    BinaryOp::Or,
+    BinaryOp::Xor,
+    BinaryOp::Shl,
+    BinaryOp::Shr,
+    BinaryOp::Sar,
+    BinaryOp::Eq,
+    BinaryOp::Ne,
+    BinaryOp::EqCase,
+    BinaryOp::NeCase,
+    BinaryOp::LtU,
+    BinaryOp::LtS,
+    BinaryOp::LeU,
+    BinaryOp::LeS,
+    BinaryOp::GtU,
+    BinaryOp::GtS,
+    BinaryOp::GeU,
+    BinaryOp::GeS,
+    BinaryOp::LogicAnd,
+    BinaryOp::LogicOr,
+    BinaryOp::EqWildcard,
+    BinaryOp::NeWildcard,
+];
+
+const UNARY_OPS: [UnaryOp; 11] = [
+    UnaryOp::Ident,
+    UnaryOp::ToTwoState,
+    UnaryOp::Minus,
+    UnaryOp::BitNot,
+    UnaryOp::LogicNot,
+    UnaryOp::And,
+    UnaryOp::Or,
+    UnaryOp::Xor,
+    UnaryOp::PopCount,
+    UnaryOp::CountLeadingZeros,
+    UnaryOp::CountTrailingZeros,
+];
+
+const DOMAIN_KINDS: [DomainKind; 5] = [
+    DomainKind::ClockPosedge,
+    DomainKind::ClockNegedge,
+    DomainKind::ResetAsyncHigh,
+    DomainKind::ResetAsyncLow,
+    DomainKind::Other,
+];
+
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected `{token}`, found `{}`", self.rest()))
+        }
+    }
+
+    /// `name=`, also accepting spaces around `=`.
+    fn field(&mut self, name: &str) -> Result<(), String> {
+        self.expect(name)?;
+        self.expect("=")
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.skip_ws();
+        let rest = self.rest();
+        let len = rest
+            .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn number<T: FromStr>(&mut self) -> Result<T, String> {
+        let negative = self.eat("-");
+        let digits = self.word();
+        let text = if negative {
+            format!("-{digits}")
+        } else {
+            digits.to_string()
+        };
+        text.parse()
+            .map_err(|_| format!("expected a number, found `{text}`"))
+    }
+
+    fn biguint(&mut self) -> Result<BigUint, String> {
+        let word = self.word();
+        let parsed = match word.strip_prefix("0x") {
+            Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+            None => BigUint::parse_bytes(word.as_bytes(), 10),
+        };
+        parsed.ok_or_else(|| format!("expected a number, found `{word}`"))
+    }
+
+    fn register(&mut self) -> Result<RegisterId, String> {
+        let word = self.word();
+        prefixed_number(word, "r").map(RegisterId)
+    }
+
+    fn block(&mut self) -> Result<BlockId, String> {
+        let word = self.word();
+        prefixed_number(word, "b").map(BlockId)
+    }
+
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        self.expect("[")?;
+        let mut items = Vec::new();
+        if self.eat("]") {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat("]") {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    fn registers(&mut self) -> Result<Vec<RegisterId>, String> {
+        self.list(Self::register)
+    }
+
+    fn triggers(&mut self) -> Result<Vec<TriggerIdWithKind>, String> {
+        self.list(|c| {
+            c.expect("TriggerIdWithKind")?;
+            c.expect("{")?;
+            c.expect("kind")?;
+            c.expect(":")?;
+            let word = c.word();
+            let kind = DOMAIN_KINDS
+                .into_iter()
+                .find(|kind| format!("{kind:?}") == word)
+                .ok_or_else(|| format!("unknown domain kind `{word}`"))?;
+            c.expect(",")?;
+            c.expect("id")?;
+            c.expect(":")?;
+            let id = c.number()?;
+            c.expect("}")?;
+            Ok(TriggerIdWithKind { kind, id })
+        })
+    }
+
+    /// An address, which extends to the next `,` or `)` outside brackets.
+    fn addr<A: ParseAddr>(&mut self) -> Result<A, String> {
+        self.skip_ws();
+        let rest = self.rest();
+        let mut depth = 0usize;
+        let end = rest
+            .char_indices()
+            .find(|&(_, ch)| match ch {
+                '(' | '[' => {
+                    depth += 1;
+                    false
+                }
+                ')' | ']' if depth > 0 => {
+                    depth -= 1;
+                    false
+                }
+                ',' | ')' | ']' => depth == 0,
+                _ => false,
+            })
+            .map_or(rest.len(), |(index, _)| index);
+        self.pos += end;
+        A::parse_addr(rest[..end].trim())
+    }
+
+    fn offset(&mut self) -> Result<SIROffset, String> {
+        self.skip_ws();
+        if self.rest().starts_with('r') {
+            return self.register().map(SIROffset::Dynamic);
+        }
+        let word = self.word();
+        match word {
+            "element" => {
+                self.expect("(")?;
+                let index = self.register()?;
+                self.expect(",")?;
+                self.field("width")?;
+                let element_width = self.number()?;
+                self.expect(",")?;
+                self.field("bit")?;
+                let bit_offset = self.number()?;
+                let dynamic_bit_offset = if self.eat("+") {
+                    Some(self.register()?)
+                } else {
+                    None
+                };
+                self.expect(")")?;
+                Ok(SIROffset::Element {
+                    index,
+                    element_width,
+                    bit_offset,
+                    dynamic_bit_offset,
+                })
+            }
+            "packed_elements" => {
+                self.expect("(")?;
+                self.field("bit")?;
+                let bit_offset = self.number()?;
+                self.expect(",")?;
+                self.field("element_width")?;
+                let element_width = self.number()?;
+                self.expect(")")?;
+                Ok(SIROffset::PackedElements {
+                    bit_offset,
+                    element_width,
+                })
+            }
+            _ => word
+                .parse()
+                .map(SIROffset::Static)
+                .map_err(|_| format!("expected an offset, found `{word}`")),
+        }
+    }
+
+    fn instruction<A: ParseAddr>(&mut self) -> Result<SIRInstruction<A>, String> {
+        let head = self.word();
+        match head {
+            "Store" => {
+                self.expect("(")?;
+                self.field("addr")?;
+                let addr = self.addr()?;
+                self.expect(",")?;
+                self.field("offset")?;
+                let offset = self.offset()?;
+                self.expect(",")?;
+                self.field("src_reg")?;
+                let src = RegisterId(self.number()?);
+                self.expect(",")?;
+                self.field("bits")?;
+                let bits = self.number()?;
+                self.expect(",")?;
+                self.field("triggers")?;
+                let triggers = self.triggers()?;
+                self.expect(",")?;
+                self.field("comb_capture_sites")?;
+                let sites = self.list(Self::number)?;
+                self.expect(")")?;
+                Ok(SIRInstruction::Store(
+                    addr, offset, bits, src, triggers, sites,
+                ))
+            }
+            "Commit" => {
+                self.expect("(")?;
+                self.field("src")?;
+                let src = self.addr()?;
+                self.expect(",")?;
+                self.field("dst")?;
+                let dst = self.addr()?;
+                self.expect(",")?;
+                self.field("offset")?;
+                let offset = self.offset()?;
+                self.expect(",")?;
+                self.field("bits")?;
+                let bits = self.number()?;
+                self.expect(",")?;
+                self.field("triggers")?;
+                let triggers = self.triggers()?;
+                self.expect(")")?;
+                Ok(SIRInstruction::Commit(src, dst, offset, bits, triggers))
+            }
+            "RuntimeEvent" => {
+                self.expect("(")?;
+                self.field("site")?;
+                let site_id = self.number()?;
+                self.expect(",")?;
+                self.field("args")?;
+                let args = self.registers()?;
+                self.expect(")")?;
+                Ok(SIRInstruction::RuntimeEvent { site_id, args })
+            }
+            "CombCaptureEvent" => {
+                self.expect("(")?;
+                self.field("site")?;
+                let site_id = self.number()?;
+                self.expect(",")?;
+                self.field("args")?;
+                let args = self.registers()?;
+                let mut fatal_error_code = None;
+                let mut consume_enabled = false;
+                if self.eat(",") {
+                    match self.word() {
+                        "fatal_error" => {
+                            self.expect("=")?;
+                            fatal_error_code = Some(self.number()?);
+                        }
+                        "consume_enabled" => {
+                            self.expect("=")?;
+                            self.expect("true")?;
+                            consume_enabled = true;
+                        }
+                        other => return Err(format!("unknown CombCaptureEvent field `{other}`")),
+                    }
+                }
+                self.expect(")")?;
+                Ok(SIRInstruction::CombCaptureEvent {
+                    site_id,
+                    args,
+                    fatal_error_code,
+                    consume_enabled,
+                })
+            }
+            "CombCaptureEnableIfChanged" => {
+                self.expect("(")?;
+                self.field("old")?;
+                let old = self.register()?;
+                self.expect(",")?;
+                self.field("new")?;
+                let new = self.register()?;
+                self.expect(",")?;
+                self.field("sites")?;
+                let sites = self.list(Self::number)?;
+                self.expect(")")?;
+                Ok(SIRInstruction::CombCaptureEnableIfChanged { old, new, sites })
+            }
+            _ => {
+                let dst = prefixed_number(head, "r").map(RegisterId)?;
+                self.expect("=")?;
+                self.value(dst)
+            }
+        }
+    }
+
+    /// The right-hand side of `dst = ...`.
+    fn value<A: ParseAddr>(&mut self, dst: RegisterId) -> Result<SIRInstruction<A>, String> {
+        let head = self.word();
+        match head {
+            "SIRValue" => {
+                self.expect("(")?;
+                let payload = self.biguint()?;
+                let mask = if self.eat(",") {
+                    self.field("mask")?;
+                    self.biguint()?
+                } else {
+                    BigUint::default()
+                };
+                self.expect(")")?;
+                Ok(SIRInstruction::Imm(
+                    dst,
+                    SIRValue::new_four_state(payload, mask),
+                ))
+            }
+            "Load" => {
+                self.expect("(")?;
+                self.field("addr")?;
+                let addr = self.addr()?;
+                self.expect(",")?;
+                self.field("offset")?;
+                let offset = self.offset()?;
+                self.expect(",")?;
+                self.field("bits")?;
+                let bits = self.number()?;
+                self.expect(")")?;
+                Ok(SIRInstruction::Load(dst, addr, offset, bits))
+            }
+            "Concat" => {
+                self.expect("(")?;
+                let args = self.registers()?;
+                self.expect(")")?;
+                Ok(SIRInstruction::Concat(dst, args))
+            }
+            "Slice" => {
+                self.expect("(")?;
+                let src = self.register()?;
+                self.expect(",")?;
+                self.field("offset")?;
+                let offset = self.number()?;
+                self.expect(",")?;
+                self.field("width")?;
+                let width = self.number()?;
+                self.expect(")")?;
+                Ok(SIRInstruction::Slice(dst, src, offset, width))
+            }
+            "Mux" => {
+                self.expect("(")?;
+                self.field("cond")?;
+                let cond = self.register()?;
+                self.expect(",")?;
+                self.field("then")?;
+                let then_value = self.register()?;
+                self.expect(",")?;
+                self.field("else")?;
+                let else_value = self.register()?;
+                self.expect(")")?;
+                Ok(SIRInstruction::Mux(dst, cond, then_value, else_value))
+            }
+            _ => {
+                if let Ok(lhs) = prefixed_number(head, "r").map(RegisterId) {
+                    let word = self.word();
+                    let op = BINARY_OPS
+                        .into_iter()
+                        .find(|op| op.to_string() == word)
+                        .ok_or_else(|| format!("unknown binary operator `{word}`"))?;
+                    let rhs = self.register()?;
+                    return Ok(SIRInstruction::Binary(dst, lhs, op, rhs));
+                }
+                let op = UNARY_OPS
+                    .into_iter()
+                    .find(|op| op.to_string() == head)
+                    .ok_or_else(|| format!("unknown instruction `{head}`"))?;
+                let src = self.register()?;
+                Ok(SIRInstruction::Unary(dst, op, src))
+            }
+        }
+    }
+
+    /// A branch target, `b<n>` optionally followed by `[r.., ..]`.
+    fn target(&mut self) -> Result<(BlockId, Vec<RegisterId>), String> {
+        let block = self.block()?;
+        self.skip_ws();
+        let args = if self.rest().starts_with('[') {
+            self.registers()?
+        } else {
+            Vec::new()
+        };
+        Ok((block, args))
+    }
+
+    fn terminator(&mut self) -> Result<SIRTerminator, String> {
+        let head = self.word();
+        match head {
+            "Jump" => {
+                self.expect("(")?;
+                let (block, args) = self.target()?;
+                self.expect(")")?;
+                Ok(SIRTerminator::Jump(block, args))
+            }
+            "Branch" => {
+                self.expect("(")?;
+                let cond = self.register()?;
+                self.expect("?")?;
+                let true_block = self.target()?;
+                self.expect(":")?;
+                let false_block = self.target()?;
+                self.expect(")")?;
+                Ok(SIRTerminator::Branch {
+                    cond,
+                    true_block,
+                    false_block,
+                })
+            }
+            "Switch" => {
+                self.expect("(")?;
+                let selector = self.register()?;
+                self.expect(";")?;
+                let mut cases = Vec::new();
+                if !self.eat(";") {
+                    loop {
+                        let value = self.biguint()?;
+                        self.expect("=>")?;
+                        let target = self.block()?;
+                        cases.push(SIRSwitchCase { value, target });
+                        if self.eat(";") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                self.expect("default")?;
+                self.expect("=>")?;
+                let default = self.block()?;
+                self.expect(")")?;
+                Ok(SIRTerminator::Switch {
+                    selector,
+                    cases,
+                    default,
+                })
+            }
+            "Return" => Ok(SIRTerminator::Return),
+            "Error" => {
+                self.expect("(")?;
+                let code = self.number()?;
+                self.expect(")")?;
+                Ok(SIRTerminator::Error(code))
+            }
+            _ => Err(format!("unknown terminator `{head}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use celox_design::RegionedStateAddr;
+
+    const UNIT: &str = "
+ExecutionUnit {
+  entry: b0
+  registers: {
+    r0: logic<8>
+    r1: bit<8>
+    r2: signed bit<16>
+    r3: bit<1>
+  }
+b0:
+  r0 = SIRValue(0x5, mask=0x2)
+  r1 = Load(addr=3, offset=element(r3, width=8, bit=0+r3), bits=8)
+  r2 = Concat([r0, r1])
+  r3 = r1 LtU r0 // compare
+  r1 = Mux(cond=r3, then=r1, else=r0)
+  r1 = BitNot r1
+  r1 = Slice(r2, offset=4, width=8)
+  Store(addr=7, offset=r1, src_reg = 1, bits=8, triggers=[TriggerIdWithKind { kind: ClockPosedge, id: 2 }], comb_capture_sites=[1, 4])
+  Commit(src=7, dst=9, offset=packed_elements(bit=8, element_width=4), bits=16, triggers=[])
+  RuntimeEvent(site=3, args=[r0, r1])
+  CombCaptureEvent(site=1, args=[], fatal_error=-7)
+  CombCaptureEvent(site=2, args=[r2], consume_enabled=true)
+  CombCaptureEnableIfChanged(old=r0, new=r1, sites=[5])
+  Branch(r3 ? b1 [r1] : b2)
+b1:
+  params: [r1]
+  Switch(r1; 0x0 => b2, 0x3 => b3; default => b2)
+b2:
+  Jump(b3)
+b3:
+  Error(-1)
+}
+";
+
+    #[test]
+    fn round_trips_the_display_form() {
+        let unit = parse_execution_unit::<usize>(UNIT).unwrap();
+        assert_eq!(unit.blocks.len(), 4);
+        assert_eq!(
+            unit.register_map[&RegisterId(2)],
+            RegisterType::Bit {
+                width: 16,
+                signed: true
+            }
+        );
+        assert_eq!(unit.blocks[&BlockId(1)].params, vec![RegisterId(1)]);
+        let printed = unit.to_string();
+        assert_eq!(parse_execution_unit::<usize>(&printed).unwrap(), unit);
+    }
+
+    #[test]
+    fn parses_design_addresses() {
+        let text =
+            "r0 = Load(addr=RegionedAbsoluteAddr(region=1, inst2, state5), offset=0, bits=4)";
+        let instruction = parse_instruction::<RegionedStateAddr>(text).unwrap();
+        let SIRInstruction::Load(_, addr, SIROffset::Static(0), 4) = &instruction else {
+            panic!("unexpected {instruction:?}");
+        };
+        assert_eq!(
+            (addr.region, addr.instance_id, addr.var_id),
+            (1, InstanceId(2), StateObjectId::from_raw(5))
+        );
+        assert_eq!(instruction.to_string(), text);
+    }
+
+    #[test]
+    fn reports_the_failing_line() {
+        let text = UNIT.replace("r3 = r1 LtU r0", "r3 = r1 Frob r0");
+        let error = parse_execution_unit::<usize>(&text).unwrap_err();
+        assert_eq!(error.line, 14);
+        assert!(error.message.contains("Frob"), "{error}");
+        assert!(parse_terminator("Jump(b1").is_err());
+    }
+}
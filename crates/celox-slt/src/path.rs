@@ -25,13 +25,16 @@ pub enum LogicPathTarget<A: Hash + Eq + Clone> {
         fatal_error_code: Option<i64>,
         consume_enabled: bool,
     },
+    /// Branch-coverage counter incremented whenever the 1-bit `expr` guard
+    /// is true. The counter is not a dependency of any other path.
+    Coverage(VarAtomBase<A>),
 }
 
 impl<A: Hash + Eq + Clone> LogicPathTarget<A> {
     pub fn var(&self) -> Option<&VarAtomBase<A>> {
         match self {
             LogicPathTarget::Var(var) => Some(var),
-            LogicPathTarget::CombCaptureEvent { .. } => None,
+            LogicPathTarget::CombCaptureEvent { .. } | LogicPathTarget::Coverage(_) => None,
         }
     }
 }
@@ -43,6 +46,7 @@ impl<A: fmt::Display + Hash + Eq + Clone> fmt::Display for LogicPathTarget<A> {
             LogicPathTarget::CombCaptureEvent { site_id, .. } => {
                 write!(f, "capture_event({site_id})")
             }
+            LogicPathTarget::Coverage(counter) => write!(f, "coverage({counter})"),
         }
     }
 }
@@ -130,6 +134,11 @@ impl<A: fmt::Debug + fmt::Display + Hash + Eq + Clone> LogicPath<A> {
                 fatal_error_code: *fatal_error_code,
                 consume_enabled: *consume_enabled,
             },
+            LogicPathTarget::Coverage(counter) => LogicPathTarget::Coverage(VarAtomBase::new(
+                f(&counter.id),
+                counter.access.lsb,
+                counter.access.msb,
+            )),
         };
         let local_inputs = self
             .local_inputs
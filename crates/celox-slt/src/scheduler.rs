@@ -1012,6 +1012,43 @@ fn emit_logic_path_store_with_result<Addr: Clone + Eq + Ord + Hash + Debug + Cop
                 emit(builder, lower_cache);
             }
         }
+        LogicPathTarget::Coverage(counter) => {
+            debug_assert!(prepared_result.is_none());
+            let hit = lower_logic_path_expr(lowerer, builder, path, arena, lower_cache);
+            // An unknown guard selects neither arm, so it is not a hit.
+            let known_hit = builder.alloc_bit(1, false);
+            builder.emit(SIRInstruction::Unary(known_hit, UnaryOp::ToTwoState, hit));
+            let width = 1 + counter.access.msb - counter.access.lsb;
+            let one = builder.alloc_bit(width, false);
+            let zero = builder.alloc_bit(width, false);
+            builder.emit(SIRInstruction::Imm(one, SIRValue::new(1u64)));
+            builder.emit(SIRInstruction::Imm(zero, SIRValue::new(0u64)));
+            let increment = builder.alloc_bit(width, false);
+            builder.emit(SIRInstruction::Mux(increment, known_hit, one, zero));
+            let offset = SIROffset::Static(counter.access.lsb);
+            let count = builder.alloc_bit(width, false);
+            builder.emit(SIRInstruction::Load(
+                count,
+                counter.id,
+                offset.clone(),
+                width,
+            ));
+            let next = builder.alloc_bit(width, false);
+            builder.emit(SIRInstruction::Binary(
+                next,
+                count,
+                BinaryOp::Add,
+                increment,
+            ));
+            builder.emit(SIRInstruction::Store(
+                counter.id,
+                offset,
+                width,
+                next,
+                Vec::new(),
+                Vec::new(),
+            ));
+        }
     }
 }
 
@@ -1659,7 +1696,7 @@ fn build_fold_group_schedule_index<Addr: Clone + Eq + Ord + Hash + Copy>(
             .map(|(_, node)| *node)
             .chain(path.pre_lower_nodes.iter().copied())
             .chain(match &path.target {
-                LogicPathTarget::Var(_) => Vec::new(),
+                LogicPathTarget::Var(_) | LogicPathTarget::Coverage(_) => Vec::new(),
                 LogicPathTarget::CombCaptureEvent {
                     guard,
                     args,
@@ -2638,6 +2675,115 @@ fn form_scheduled_guard_regions<A: Clone + Eq + Hash>(
     result
 }
 
+/// Reorder scheduled work into dependency levels for multi-threaded
+/// evaluation.
+///
+/// No item depends on another item of the same level, so every level is cut
+/// into at most `partitions` contiguous buckets which a backend may evaluate
+/// concurrently. The stable sort keeps the original topological order inside
+/// a level. Items which read or write a variable written by an earlier item,
+/// or write a variable read by one, land on a later level even when the
+/// dependency graph has no edge between them (for instance an observer
+/// snapshot before a later writeback). Comb-capture events snapshot their
+/// arguments through the shared lowering cache, so schedules containing them
+/// are left unpartitioned. The returned flags mark the items which start a
+/// new bucket.
+fn partition_scheduled_work_by_level<Addr: Clone + Eq + Hash>(
+    work: Vec<ScheduledWork>,
+    input: &[LogicPath<Addr>],
+    users: &[Vec<usize>],
+    partitions: usize,
+) -> (Vec<ScheduledWork>, Vec<bool>) {
+    let has_capture = input.iter().any(|path| {
+        matches!(path.target, LogicPathTarget::CombCaptureEvent { .. })
+            || !path.comb_capture_enable_sites.is_empty()
+    });
+    if partitions <= 1 || has_capture {
+        let starts = vec![false; work.len()];
+        return (work, starts);
+    }
+    let path_count = input.len();
+    let mut ready_level = vec![0usize; users.len()];
+    let mut last_read = HashMap::<Addr, usize>::default();
+    let mut last_write = HashMap::<Addr, usize>::default();
+    let mut levelled = Vec::with_capacity(work.len());
+    for item in work {
+        let ff_node;
+        let paths = match &item {
+            ScheduledWork::CombPath(path) => std::slice::from_ref(path),
+            ScheduledWork::CombScc(paths) | ScheduledWork::GuardedComb { paths, .. } => {
+                paths.as_slice()
+            }
+            ScheduledWork::Ff(index) => {
+                ff_node = path_count + index;
+                std::slice::from_ref(&ff_node)
+            }
+        };
+        let mut level = paths
+            .iter()
+            .map(|path| ready_level[*path])
+            .max()
+            .unwrap_or(0);
+        let mut reads = Vec::new();
+        let mut writes = Vec::new();
+        for path in paths.iter().filter_map(|&path| input.get(path)) {
+            reads.extend(
+                path.sources
+                    .iter()
+                    .chain(&path.previous_sources)
+                    .chain(&path.address_sources)
+                    .map(|source| source.id.clone())
+                    .chain(path.local_inputs.iter().map(|(id, _)| id.clone())),
+            );
+            if let LogicPathTarget::Var(target) = &path.target {
+                writes.push(target.id.clone());
+            }
+        }
+        for id in reads.iter().chain(&writes) {
+            if let Some(written) = last_write.get(id) {
+                level = level.max(written + 1);
+            }
+        }
+        for id in &writes {
+            if let Some(read) = last_read.get(id) {
+                level = level.max(read + 1);
+            }
+        }
+        for id in reads {
+            let entry = last_read.entry(id).or_default();
+            *entry = (*entry).max(level);
+        }
+        for id in writes {
+            let entry = last_write.entry(id).or_default();
+            *entry = (*entry).max(level);
+        }
+        let members = paths.iter().copied().collect::<HashSet<_>>();
+        for &path in paths {
+            for &user in &users[path] {
+                if !members.contains(&user) {
+                    ready_level[user] = ready_level[user].max(level + 1);
+                }
+            }
+        }
+        levelled.push((level, item));
+    }
+    levelled.sort_by_key(|(level, _)| *level);
+
+    let mut starts = Vec::with_capacity(levelled.len());
+    let mut level_start = 0;
+    while level_start < levelled.len() {
+        let level = levelled[level_start].0;
+        let level_len = levelled[level_start..]
+            .iter()
+            .take_while(|(other, _)| *other == level)
+            .count();
+        let bucket_len = level_len.div_ceil(partitions);
+        starts.extend((0..level_len).map(|index| index % bucket_len == 0));
+        level_start += level_len;
+    }
+    (levelled.into_iter().map(|(_, item)| item).collect(), starts)
+}
+
 /// Lower a consecutive set of exact grouped-fold paths.  The packed fold
 /// results are computed atomically, then each projection is created and stored
 /// in topological order.  Ordinary paths bypass this buffer entirely.
@@ -2841,8 +2987,10 @@ fn stable_topological_sccs(
 }
 
 fn logic_path_is_scheduling_barrier<Addr: Clone + Eq + Hash>(path: &LogicPath<Addr>) -> bool {
-    matches!(path.target, LogicPathTarget::CombCaptureEvent { .. })
-        || !path.comb_capture_enable_sites.is_empty()
+    matches!(
+        path.target,
+        LogicPathTarget::CombCaptureEvent { .. } | LogicPathTarget::Coverage(_)
+    ) || !path.comb_capture_enable_sites.is_empty()
 }
 
 fn cached_logic_path_roots<Addr: Clone + Eq + Hash>(path: &LogicPath<Addr>) -> Vec<NodeId> {
@@ -3215,6 +3363,7 @@ fn sort_impl<Addr: Clone + Eq + Ord + Hash + Debug + Copy + Display, E>(
     var_widths: &HashMap<Addr, usize>,
     unpacked_element_widths: &HashMap<Addr, usize>,
     first_runtime_error_code: i64,
+    comb_partitions: usize,
     mut ff: Option<&mut dyn ClockFfLowering<Addr, Error = E>>,
 ) -> Result<ScheduleResult<Addr>, ClockSortError<Addr, E>> {
     let (input, ff_plan) = if let Some(ff_lowering) = ff.as_deref() {
@@ -3366,6 +3515,14 @@ fn sort_impl<Addr: Clone + Eq + Ord + Hash + Debug + Copy + Display, E>(
     drop(dependencies.predecessors);
     drop(values);
     let scheduled_work = form_scheduled_guard_regions(scheduled_work, &input, arena, four_state);
+    // Fused comb+FF functions stay one sequential unit; only the ordinary
+    // combinational schedule is split for parallel evaluation.
+    let (scheduled_work, unit_starts) = partition_scheduled_work_by_level(
+        scheduled_work,
+        &input,
+        &adj,
+        if ff.is_none() { comb_partitions } else { 1 },
+    );
 
     let mut builder = SIRBuilder::new();
     if let Some(ff_lowering) = ff.as_deref_mut() {
@@ -3419,7 +3576,27 @@ fn sort_impl<Addr: Clone + Eq + Ord + Hash + Debug + Copy + Display, E>(
 
     // 4. Lower each scheduled component, selecting static unrolling or
     // dynamic convergence for cyclic SCCs.
-    for work in scheduled_work {
+    for (work, starts_unit) in scheduled_work.into_iter().zip(unit_starts) {
+        if starts_unit {
+            flush_pending_fold_paths(
+                &mut pending_fold_indices,
+                &input,
+                &fold_group_schedule_index,
+                &lowerer,
+                &mut builder,
+                arena,
+                &mut lower_cache,
+                &mut dep_memo,
+                &mut inverse_dep_memo,
+                unpacked_element_widths,
+                four_state,
+            );
+            pending_fold_roots.clear();
+            if let Some(eu) = builder.flush_eu() {
+                result_eus.push(eu);
+                lower_cache.clear();
+            }
+        }
         let singleton;
         let scc = match &work {
             ScheduledWork::CombPath(path) => {
@@ -3877,9 +4054,15 @@ pub fn sort<Addr: Clone + Eq + Ord + Hash + Debug + Copy + Display>(
         var_widths,
         &HashMap::default(),
         first_runtime_error_code,
+        1,
     )
 }
 
+/// Schedule combinational logic paths into execution units.
+///
+/// With `comb_partitions > 1` the units are cut at dependency-level
+/// boundaries, and every level is split into at most that many independent
+/// units, so a backend may evaluate the units of one level concurrently.
 pub fn sort_with_unpacked_element_widths<Addr: Clone + Eq + Ord + Hash + Debug + Copy + Display>(
     input: Vec<LogicPath<Addr>>,
     arena: &SLTNodeArena<Addr>,
@@ -3889,6 +4072,7 @@ pub fn sort_with_unpacked_element_widths<Addr: Clone + Eq + Ord + Hash + Debug +
     var_widths: &HashMap<Addr, usize>,
     unpacked_element_widths: &HashMap<Addr, usize>,
     first_runtime_error_code: i64,
+    comb_partitions: usize,
 ) -> Result<ScheduleResult<Addr>, SchedulerError<Addr>> {
     match sort_impl::<Addr, std::convert::Infallible>(
         input,
@@ -3899,6 +4083,7 @@ pub fn sort_with_unpacked_element_widths<Addr: Clone + Eq + Ord + Hash + Debug +
         var_widths,
         unpacked_element_widths,
         first_runtime_error_code,
+        comb_partitions,
         None,
     ) {
         Ok(result) => Ok(result),
@@ -3929,6 +4114,7 @@ pub fn sort_clock<Addr: Clone + Eq + Ord + Hash + Debug + Copy + Display, E>(
         var_widths,
         unpacked_element_widths,
         first_runtime_error_code,
+        1,
         Some(ff),
     )
 }
@@ -3942,7 +4128,7 @@ mod tests {
         NormalizedIndexExpr, add_acyclic_ff_write_order_edges, best_weighted_fold_family,
         build_fold_group_schedule_index, build_logic_path_memory_ssa, collect_node_input_deps,
         direct_ff_write_ranges, plan_ff_comb_schedule, prepare_atomic_fold_group_results, sort,
-        stable_topological_sccs,
+        sort_with_unpacked_element_widths, stable_topological_sccs,
     };
     use crate::{HashMap, HashSet};
     use crate::{
@@ -4449,6 +4635,46 @@ mod tests {
         assert_eq!(stores, vec![10, 11, 12, 20, 21, 22]);
     }
 
+    #[test]
+    fn comb_partitions_split_dependency_levels_into_units() {
+        let mut arena = SLTNodeArena::new();
+        let paths = vec![
+            simple_path(&mut arena, 10, None),
+            simple_path(&mut arena, 20, None),
+            simple_path(&mut arena, 11, Some(10)),
+            simple_path(&mut arena, 21, Some(20)),
+            simple_path(&mut arena, 30, None),
+        ];
+        let result = sort_with_unpacked_element_widths(
+            paths,
+            &arena,
+            &crate::HashSet::default(),
+            &crate::HashMap::default(),
+            false,
+            &crate::HashMap::default(),
+            &crate::HashMap::default(),
+            1,
+            2,
+        )
+        .unwrap();
+        let units = result
+            .execution_units
+            .iter()
+            .map(|unit| {
+                unit.blocks[&unit.entry_block_id]
+                    .instructions
+                    .iter()
+                    .filter_map(|instruction| match instruction {
+                        SIRInstruction::Store(address, ..) => Some(*address),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(units, vec![vec![10, 20], vec![30], vec![11], vec![21]]);
+    }
+
     #[test]
     fn previous_value_use_is_an_order_edge_not_a_forwarded_value() {
         let mut arena = SLTNodeArena::new();
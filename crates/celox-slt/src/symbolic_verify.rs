@@ -200,6 +200,13 @@ where
                 ));
             }
         }
+        if matches!(path.target, LogicPathTarget::Coverage(_)) && expression_width != 1 {
+            return Err(fail(
+                "ROOT.COVERAGE_GUARD_WIDTH",
+                path.expr,
+                format!("coverage guard width {expression_width} is not 1"),
+            ));
+        }
         for &node in &path.pre_lower_nodes {
             require(node, "logic-path pre-lower value")?;
         }
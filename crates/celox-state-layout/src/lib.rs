@@ -52,13 +52,13 @@ pub struct SparseWorkingLayout {
     pub summary_word_count: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MemoryLayoutMode {
     Packed,
     ElementStrided,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UnpackedArrayLayout {
     pub element_width: usize,
     pub element_count: usize,
@@ -402,6 +402,38 @@ where
         self
     }
 
+    /// Order-independent hash of every semantic offset in this layout.
+    ///
+    /// Backend scratch is excluded, so two backends that agree on the state
+    /// ABI produce the same fingerprint. Runtime state captured under one
+    /// layout is only meaningful when restored under a matching fingerprint.
+    pub fn fingerprint(&self) -> u64 {
+        fn sorted<A: Copy + Ord, V: Copy>(map: &HashMap<A, V>) -> Vec<(A, V)> {
+            let mut entries = map
+                .iter()
+                .map(|(&key, &value)| (key, value))
+                .collect::<Vec<_>>();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            entries
+        }
+        fxhash::hash64(&(
+            (self.four_state, self.mode),
+            sorted(&self.offsets),
+            sorted(&self.widths),
+            sorted(&self.is_4states),
+            sorted(&self.unpacked_arrays),
+            sorted(&self.working_offsets),
+            sorted(&self.sparse_offsets),
+            (
+                self.total_size,
+                self.working_base_offset,
+                self.sparse_base_offset,
+                self.triggered_bits_offset,
+                self.triggered_bits_total_size,
+            ),
+        ))
+    }
+
     pub fn plane_size(&self, address: &A) -> usize {
         self.unpacked_arrays
             .get(address)
@@ -623,6 +655,46 @@ mod tests {
         assert_eq!(expanded.triggered_bits_offset, base.triggered_bits_offset);
     }
 
+    #[test]
+    fn fingerprint_tracks_offsets_but_not_backend_scratch() {
+        struct WidthLayoutSource(usize);
+
+        impl LayoutSource<u32> for WidthLayoutSource {
+            fn layout_input(&self, _mode: MemoryLayoutMode) -> LayoutInput<u32> {
+                LayoutInput {
+                    state_objects: vec![
+                        StateObjectLayout {
+                            address: 1,
+                            width: self.0,
+                            is_4state: false,
+                        },
+                        StateObjectLayout {
+                            address: 2,
+                            width: 8,
+                            is_4state: false,
+                        },
+                    ],
+                    working_addresses: Vec::new(),
+                    sparse_addresses: Vec::new(),
+                    unpacked_arrays: HashMap::default(),
+                    requirements: LayoutRequirements::default(),
+                    ff_referenced_addresses: HashSet::default(),
+                    num_events: 1,
+                    runtime_event_sites: Vec::new(),
+                }
+            }
+        }
+
+        let narrow = MemoryLayout::build(&WidthLayoutSource(8), false, MemoryLayoutMode::Packed);
+        let wide = MemoryLayout::build(&WidthLayoutSource(64), false, MemoryLayoutMode::Packed);
+
+        assert_eq!(
+            narrow.fingerprint(),
+            narrow.clone().with_backend_scratch(32).fingerprint()
+        );
+        assert_ne!(narrow.fingerprint(), wide.fingerprint());
+    }
+
     #[test]
     #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
     fn state_header_fields_do_not_overlap() {
@@ -217,6 +217,15 @@ pub enum TestbenchStatement<Event, Signal, Expression, Argument, Target = Signal
         ret_signed: bool,
         ret_strict: bool,
     },
+    /// `$writememh` / `$writememb` of an unpacked array.
+    WriteMemory {
+        memory: Signal,
+        path: String,
+        binary: bool,
+        start: Option<usize>,
+        /// Last element written, inclusive.
+        end: Option<usize>,
+    },
     Break,
     Finish,
 }
@@ -8,7 +8,9 @@ use std::{
     process::{Command, ExitCode},
 };
 
-use celox::{NativeProgramImage, NativeProgramInstance};
+use celox::{
+    NativeProgramImage, NativeProgramInstance, Waveform, WaveformDiff, WaveformDiffOptions,
+};
 use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
@@ -26,6 +28,8 @@ struct Cli {
 enum CliCommand {
     /// Build native executables for VPI testbenches.
     Vpi(VpiArgs),
+    /// Inspect and compare VCD or FST waveforms.
+    Wave(WaveArgs),
 }
 
 #[derive(Args)]
@@ -54,6 +58,39 @@ struct BuildArgs {
     output: PathBuf,
 }
 
+#[derive(Args)]
+struct WaveArgs {
+    #[command(subcommand)]
+    command: WaveCommand,
+}
+
+#[derive(Subcommand)]
+enum WaveCommand {
+    /// Report the first time each signal differs between two waveforms.
+    Diff(DiffArgs),
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// First waveform (`.vcd` or `.fst`).
+    left: PathBuf,
+
+    /// Second waveform (`.vcd` or `.fst`).
+    right: PathBuf,
+
+    /// Compare only signals under this scope of the first waveform.
+    #[arg(long, value_name = "SCOPE")]
+    left_scope: Option<String>,
+
+    /// Compare only signals under this scope of the second waveform.
+    #[arg(long, value_name = "SCOPE")]
+    right_scope: Option<String>,
+
+    /// Let X bits on either side match any value.
+    #[arg(long)]
+    x_tolerant: bool,
+}
+
 #[derive(Parser)]
 #[command(
     name = "celox simulation",
@@ -116,6 +153,54 @@ fn build(arguments: BuildArgs) -> Result<(), String> {
     Ok(())
 }
 
+fn wave_diff(arguments: DiffArgs) -> Result<(), String> {
+    let read = |path: &Path| {
+        Waveform::read(path).map_err(|error| format!("failed to read {}: {error}", path.display()))
+    };
+    let left = read(&arguments.left)?;
+    let right = read(&arguments.right)?;
+    let mut options = WaveformDiffOptions::new().x_tolerant(arguments.x_tolerant);
+    if let Some(scope) = &arguments.left_scope {
+        options = options.left_scope(scope);
+    }
+    if let Some(scope) = &arguments.right_scope {
+        options = options.right_scope(scope);
+    }
+    let diff = WaveformDiff::new(&left, &right, &options);
+
+    for name in &diff.only_left {
+        println!("only in {}: {name}", arguments.left.display());
+    }
+    for name in &diff.only_right {
+        println!("only in {}: {name}", arguments.right.display());
+    }
+    if let Some(timescale) = diff.timescale.filter(|_| !diff.divergences.is_empty()) {
+        println!("times in units of {timescale}");
+    }
+    let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    for divergence in &diff.divergences {
+        println!(
+            "{} {}: {} != {}",
+            divergence.time,
+            divergence.name,
+            value(&divergence.left),
+            value(&divergence.right)
+        );
+    }
+
+    if diff.is_identical() {
+        println!("{} signals match", diff.compared);
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} signals differ, {} unmatched",
+            diff.divergences.len(),
+            diff.compared,
+            diff.only_left.len() + diff.only_right.len()
+        ))
+    }
+}
+
 fn environment_path(name: &str) -> Option<PathBuf> {
     env::var_os(name).map(PathBuf::from)
 }
@@ -317,6 +402,9 @@ fn run() -> Result<(), String> {
             CliCommand::Vpi(arguments) => match arguments.command {
                 VpiCommand::Build(arguments) => build(arguments),
             },
+            CliCommand::Wave(arguments) => match arguments.command {
+                WaveCommand::Diff(arguments) => wave_diff(arguments),
+            },
         },
     }
 }
@@ -339,11 +427,40 @@ mod tests {
     fn vpi_build_arguments_have_a_default_output() {
         let cli = Cli::try_parse_from(["celox", "vpi", "build", "top.veryl", "--top", "Top"])
             .expect("valid VPI build command");
-        let CliCommand::Vpi(arguments) = cli.command;
+        let CliCommand::Vpi(arguments) = cli.command else {
+            panic!("expected the vpi command");
+        };
         let VpiCommand::Build(arguments) = arguments.command;
         assert_eq!(arguments.output, Path::new("celox.out"));
     }
 
+    #[test]
+    fn wave_diff_compares_two_dumps() {
+        let temporary = tempfile::tempdir().unwrap();
+        let header =
+            "$scope module top $end\n$var wire 2 ! a $end\n$upscope $end\n$enddefinitions $end\n";
+        let left = temporary.path().join("left.vcd");
+        let right = temporary.path().join("right.vcd");
+        std::fs::write(&left, format!("{header}#0\nbxx !\n#5\nb10 !\n")).unwrap();
+        std::fs::write(&right, format!("{header}#0\nb01 !\n#5\nb10 !\n")).unwrap();
+
+        let diff = |extra: &[&str]| {
+            let mut command = vec!["celox", "wave", "diff"];
+            command.extend([left.to_str().unwrap(), right.to_str().unwrap()]);
+            command.extend(extra);
+            let CliCommand::Wave(arguments) = Cli::try_parse_from(command).unwrap().command else {
+                panic!("expected the wave command");
+            };
+            let WaveCommand::Diff(arguments) = arguments.command;
+            wave_diff(arguments)
+        };
+        assert_eq!(
+            diff(&[]),
+            Err("1 of 1 signals differ, 0 unmatched".to_string())
+        );
+        assert_eq!(diff(&["--x-tolerant"]), Ok(()));
+    }
+
     #[test]
     fn simulation_arguments_accept_the_cocotb_entry_points() {
         let arguments = SimulationArgs::try_parse_from([
@@ -8,7 +8,8 @@ const COUNTER: &str = include_str!("../testdata/veryl/top_n1000.veryl");
 const AXI_LITE_REG_FILE: &str = include_str!("../tests/fixtures/bitslice/axi_lite_reg_file.veryl");
 
 fn compile_default(source: &str, top: &str, parameter: Option<(&str, u64)>) {
-    let mut builder = Simulator::builder(black_box(source), top);
+    // Every iteration compiles the same design; measure a cold build.
+    let mut builder = Simulator::builder(black_box(source), top).module_cache(false);
     if let Some((name, value)) = parameter {
         builder = builder.param(name, value);
     }
@@ -39,6 +40,7 @@ fn benchmark_compilation(c: &mut Criterion) {
     c.bench_function("compile/cranelift/counter_n1000", |b| {
         b.iter(|| {
             let simulator = Simulator::builder(black_box(COUNTER), "Top")
+                .module_cache(false)
                 .param("N", 1_000)
                 .build_cranelift()
                 .expect("benchmark design should compile with Cranelift");
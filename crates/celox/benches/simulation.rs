@@ -114,7 +114,10 @@ static GRAY_COUNTER_SRC: LazyLock<String> = LazyLock::new(|| {
 fn benchmark_counter(c: &mut Criterion) {
     c.bench_function("simulation_build_top_n1000", |b| {
         b.iter(|| {
-            let _sim = Simulator::builder(CODE, "Top").build().unwrap();
+            let _sim = Simulator::builder(CODE, "Top")
+                .module_cache(false)
+                .build()
+                .unwrap();
         })
     });
 
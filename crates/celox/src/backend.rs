@@ -1,3 +1,7 @@
+#[cfg(feature = "host-runtime")]
+pub(crate) mod comb_split;
+#[cfg(feature = "host-runtime")]
+pub mod interpreter;
 pub(crate) mod memory_layout;
 #[cfg(all(
     feature = "host-runtime",
@@ -0,0 +1,1382 @@
+//! Reference interpreter backend for simulation.
+//!
+//! Executes SIR execution units directly over the same [`MemoryLayout`] the
+//! compiled backends use, one instruction at a time and with every register
+//! held as a value/mask pair of [`BigUint`]s. Nothing is compiled, so the
+//! backend is slow but has no host requirements; its purpose is to be the
+//! executable definition of SIR that the compiled backends are checked
+//! against.
+
+use std::sync::Arc;
+
+use celox_design::{BinaryOp, DomainKind, TriggerIdWithKind, UnaryOp};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, ToPrimitive, Zero};
+
+use crate::{
+    HashMap, SimulatorOptions,
+    backend::{
+        MemoryLayout, SimulatorErrorCode, get_byte_size,
+        memory_layout::{
+            RUNTIME_EVENT_HEADER_SIZE, RUNTIME_EVENT_SLOT_ARG_COUNT_OFFSET,
+            RUNTIME_EVENT_SLOT_PAYLOAD_OFFSET, RUNTIME_EVENT_SLOT_SEQ_OFFSET,
+            RUNTIME_EVENT_SLOT_SITE_OFFSET, RUNTIME_EVENT_WRITING,
+        },
+    },
+    ir::{
+        AbsoluteAddr, ExecutionUnit, RegionedAbsoluteAddr, RegisterId, RegisterType,
+        SIRInstruction, SIROffset, SIRTerminator, SPARSE_WORKING_REGION, STABLE_REGION, SignalRef,
+    },
+};
+
+type Unit = ExecutionUnit<RegionedAbsoluteAddr>;
+/// Units run for one event, as one list per entry point. Each list sees the
+/// trigger values captured when it starts.
+type UnitMap = HashMap<AbsoluteAddr, Vec<Vec<Unit>>>;
+
+/// Handle to the flip-flop units of one event (clock/async-reset).
+#[derive(Debug, Clone, Copy)]
+pub struct InterpreterEventRef {
+    pub addr: AbsoluteAddr,
+    pub id: usize,
+}
+
+impl super::traits::EventHandle for InterpreterEventRef {
+    fn id(&self) -> usize {
+        self.id
+    }
+    fn addr(&self) -> AbsoluteAddr {
+        self.addr
+    }
+}
+
+impl super::traits::SimBackend for InterpreterBackend {
+    type Event = InterpreterEventRef;
+
+    fn eval_comb(&mut self) -> Result<(), SimulatorErrorCode> {
+        InterpreterBackend::eval_comb(self)
+    }
+    fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), SimulatorErrorCode> {
+        InterpreterBackend::eval_comb_units_with(self, after_unit)
+    }
+    fn eval_apply_ff_at(&mut self, event: InterpreterEventRef) -> Result<(), SimulatorErrorCode> {
+        InterpreterBackend::eval_apply_ff_at(self, &event)
+    }
+    fn eval_only_ff_at(&mut self, event: InterpreterEventRef) -> Result<(), SimulatorErrorCode> {
+        InterpreterBackend::eval_only_ff_at(self, &event)
+    }
+    fn apply_ff_at(&mut self, event: InterpreterEventRef) -> Result<(), SimulatorErrorCode> {
+        InterpreterBackend::apply_ff_at(self, &event)
+    }
+    fn resolve_signal(&self, addr: &AbsoluteAddr) -> SignalRef {
+        InterpreterBackend::resolve_signal(self, addr)
+    }
+    fn resolve_event(&self, addr: &AbsoluteAddr) -> InterpreterEventRef {
+        InterpreterBackend::resolve_event(self, addr)
+    }
+    fn resolve_event_opt(&self, addr: &AbsoluteAddr) -> Option<InterpreterEventRef> {
+        InterpreterBackend::resolve_event_opt(self, addr)
+    }
+    fn resolve_eval_only_event(&self, addr: &AbsoluteAddr) -> Option<InterpreterEventRef> {
+        InterpreterBackend::resolve_eval_only_event(self, addr)
+    }
+    fn resolve_apply_event(&self, addr: &AbsoluteAddr) -> Option<InterpreterEventRef> {
+        InterpreterBackend::resolve_apply_event(self, addr)
+    }
+    fn set<T: Copy>(&mut self, signal: SignalRef, value: T) {
+        InterpreterBackend::set(self, signal, value)
+    }
+    fn set_wide(&mut self, signal: SignalRef, value: BigUint) {
+        InterpreterBackend::set_wide(self, signal, value)
+    }
+    fn set_four_state(&mut self, signal: SignalRef, value: BigUint, mask: BigUint) {
+        InterpreterBackend::set_four_state(self, signal, value, mask)
+    }
+    fn get(&self, signal: SignalRef) -> BigUint {
+        InterpreterBackend::get(self, signal)
+    }
+    fn get_as<T: Default + Copy>(&self, signal: SignalRef) -> T {
+        InterpreterBackend::get_as(self, signal)
+    }
+    fn get_four_state(&self, signal: SignalRef) -> (BigUint, BigUint) {
+        InterpreterBackend::get_four_state(self, signal)
+    }
+    fn memory_as_ptr(&self) -> (*const u8, usize) {
+        InterpreterBackend::memory_as_ptr(self)
+    }
+    fn memory_as_mut_ptr(&mut self) -> (*mut u8, usize) {
+        InterpreterBackend::memory_as_mut_ptr(self)
+    }
+    fn runtime_event_buffer_as_ptr(&self) -> (*const u8, usize) {
+        InterpreterBackend::runtime_event_buffer_as_ptr(self)
+    }
+    fn set_comb_capture_event_enabled(&mut self, active_sites: &[bool]) {
+        InterpreterBackend::set_comb_capture_event_enabled(self, active_sites)
+    }
+    fn stable_region_size(&self) -> usize {
+        InterpreterBackend::stable_region_size(self)
+    }
+    fn layout(&self) -> &super::MemoryLayout {
+        InterpreterBackend::layout(self)
+    }
+    fn id_to_addr_slice(&self) -> &[AbsoluteAddr] {
+        InterpreterBackend::id_to_addr_slice(self)
+    }
+    fn id_to_event_slice(&self) -> &[InterpreterEventRef] {
+        InterpreterBackend::id_to_event_slice(self)
+    }
+    fn num_events(&self) -> usize {
+        InterpreterBackend::num_events(self)
+    }
+    fn fork(&self) -> Self {
+        InterpreterBackend::fork(self)
+    }
+    fn clear_triggered_bits(&mut self) {
+        InterpreterBackend::clear_triggered_bits(self)
+    }
+    fn mark_triggered_bit(&mut self, id: usize) {
+        InterpreterBackend::mark_triggered_bit(self, id)
+    }
+    fn get_triggered_bits(&self) -> bit_set::BitSet {
+        InterpreterBackend::get_triggered_bits(self)
+    }
+}
+
+/// Execution units shared by every instance forked from one backend.
+struct InterpreterProgram {
+    comb: Vec<Unit>,
+    /// Per-store combinational units, split only with force support or
+    /// profiling.
+    comb_units: Vec<Unit>,
+    event: UnitMap,
+    eval_only: UnitMap,
+    apply: UnitMap,
+}
+
+pub struct InterpreterBackend {
+    program: Arc<InterpreterProgram>,
+    /// Simulation state followed by the runtime event buffer and the
+    /// comb-capture enable bytes. Kept as words so the event buffer can be
+    /// read with aligned `u64` loads.
+    memory: Vec<u64>,
+    runtime_event_base: usize,
+    comb_capture_enabled_base: usize,
+    event_map: HashMap<AbsoluteAddr, InterpreterEventRef>,
+    eval_only_event_map: HashMap<AbsoluteAddr, InterpreterEventRef>,
+    apply_event_map: HashMap<AbsoluteAddr, InterpreterEventRef>,
+    id_to_addr: Vec<AbsoluteAddr>,
+    id_to_event: Vec<InterpreterEventRef>,
+    layout: MemoryLayout,
+    options: SimulatorOptions,
+}
+
+impl InterpreterBackend {
+    pub fn new(
+        laid_out: &crate::ir::LaidOutProgram,
+        options: &SimulatorOptions,
+    ) -> Result<Self, crate::SimulatorError> {
+        let sir = laid_out;
+        let layout = laid_out.layout().clone();
+
+        let mut event = UnitMap::default();
+        let mut eval_only = UnitMap::default();
+        let mut apply = UnitMap::default();
+        let mut event_map = HashMap::default();
+        let mut eval_only_event_map = HashMap::default();
+        let mut apply_event_map = HashMap::default();
+        let mut id_to_addr = Vec::new();
+        let mut addr_to_id = HashMap::default();
+
+        let mut collect_ffs =
+            |ff_map: &HashMap<AbsoluteAddr, Vec<Unit>>,
+             units_map: &mut UnitMap,
+             emap: &mut HashMap<AbsoluteAddr, InterpreterEventRef>| {
+                for (clock, units) in ff_map {
+                    let canonical = sir.design.events.canonical(*clock);
+                    let id = *addr_to_id.entry(canonical).or_insert_with(|| {
+                        id_to_addr.push(canonical);
+                        id_to_addr.len() - 1
+                    });
+                    units_map.entry(canonical).or_default().push(units.clone());
+                    emap.insert(
+                        canonical,
+                        InterpreterEventRef {
+                            addr: canonical,
+                            id,
+                        },
+                    );
+                }
+            };
+        collect_ffs(&sir.sir.eval_apply_ffs, &mut event, &mut event_map);
+        collect_ffs(
+            &sir.sir.eval_only_ffs,
+            &mut eval_only,
+            &mut eval_only_event_map,
+        );
+        collect_ffs(&sir.sir.apply_ffs, &mut apply, &mut apply_event_map);
+
+        // Insert clock domain aliases
+        for (alias, canonical) in &sir.design.events.aliases {
+            if let Some(ev) = event_map.get(canonical).cloned() {
+                event_map.insert(*alias, ev);
+            }
+            if let Some(ev) = eval_only_event_map.get(canonical).cloned() {
+                eval_only_event_map.insert(*alias, ev);
+            }
+            if let Some(ev) = apply_event_map.get(canonical).cloned() {
+                apply_event_map.insert(*alias, ev);
+            }
+        }
+
+        let runtime_event_base = layout.merged_total_size.next_multiple_of(8);
+        let comb_capture_enabled_base = runtime_event_base + layout.runtime_event_buffer_size;
+        let comb_capture_enabled_size = layout.runtime_event_site_layouts.len().max(1);
+        let memory_size = comb_capture_enabled_base + comb_capture_enabled_size;
+        let mut memory = vec![0u64; memory_size.div_ceil(8)];
+
+        // Initialize 4-state regions to X (v=1, m=1)
+        if options.four_state {
+            let is_4state = |addr: &AbsoluteAddr| {
+                sir.design
+                    .state_objects
+                    .get(addr)
+                    .is_some_and(|metadata| metadata.is_4state)
+            };
+            let stable = layout.offsets.iter().map(|(addr, &offset)| (addr, offset));
+            let working = layout
+                .working_offsets
+                .iter()
+                .map(|(addr, &offset)| (addr, layout.working_base_offset + offset));
+            let bytes = bytes_mut(&mut memory);
+            for (addr, offset) in stable.chain(working) {
+                if is_4state(addr) {
+                    let size = get_byte_size(layout.widths[addr]);
+                    bytes[offset..offset + 2 * size].fill(0xFF);
+                }
+            }
+        }
+
+        let id_to_event = id_to_addr.iter().map(|addr| event_map[addr]).collect();
+
+        Ok(Self {
+            program: Arc::new(InterpreterProgram {
+                comb: sir.sir.eval_comb.clone(),
+                comb_units: crate::backend::comb_split::comb_entry_units(sir, options),
+                event,
+                eval_only,
+                apply,
+            }),
+            memory,
+            runtime_event_base,
+            comb_capture_enabled_base,
+            event_map,
+            eval_only_event_map,
+            apply_event_map,
+            id_to_addr,
+            id_to_event,
+            layout,
+            options: options.clone(),
+        })
+    }
+
+    /// Create an independent instance that shares the execution units and
+    /// starts from a copy of this backend's memory.
+    pub fn fork(&self) -> Self {
+        Self {
+            program: Arc::clone(&self.program),
+            memory: self.memory.clone(),
+            runtime_event_base: self.runtime_event_base,
+            comb_capture_enabled_base: self.comb_capture_enabled_base,
+            event_map: self.event_map.clone(),
+            eval_only_event_map: self.eval_only_event_map.clone(),
+            apply_event_map: self.apply_event_map.clone(),
+            id_to_addr: self.id_to_addr.clone(),
+            id_to_event: self.id_to_event.clone(),
+            layout: self.layout.clone(),
+            options: self.options.clone(),
+        }
+    }
+
+    /// Run one entry point: a list of units sharing captured trigger values.
+    fn run_units(&mut self, units: &[Unit]) -> Result<(), SimulatorErrorCode> {
+        let mut interpreter = Interpreter {
+            mem: bytes_mut(&mut self.memory),
+            layout: &self.layout,
+            four_state: self.options.four_state,
+            emit_triggers: self.options.emit_triggers,
+            runtime_event_base: self.runtime_event_base,
+            comb_capture_enabled_base: self.comb_capture_enabled_base,
+            trigger_old_values: HashMap::default(),
+        };
+        match interpreter.run(units) {
+            Ok(()) | Err(0) => Ok(()),
+            Err(code) if code > 0 => Err(SimulatorErrorCode::DetectedTrueLoopCode(code)),
+            Err(_) => Err(SimulatorErrorCode::InternalError),
+        }
+    }
+
+    fn run_event(
+        &mut self,
+        select: impl Fn(&InterpreterProgram) -> &UnitMap,
+        event: &InterpreterEventRef,
+    ) -> Result<(), SimulatorErrorCode> {
+        let program = Arc::clone(&self.program);
+        if let Some(entries) = select(&program).get(&event.addr) {
+            for units in entries {
+                self.run_units(units)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn eval_comb(&mut self) -> Result<(), SimulatorErrorCode> {
+        let program = Arc::clone(&self.program);
+        self.run_units(&program.comb)
+    }
+
+    /// Evaluate combinational logic, calling `after_unit` after each
+    /// per-store unit when the backend was built with force support.
+    pub fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), SimulatorErrorCode> {
+        let program = Arc::clone(&self.program);
+        if program.comb_units.is_empty() {
+            self.eval_comb()?;
+            after_unit(self);
+            return Ok(());
+        }
+        for unit in &program.comb_units {
+            self.run_units(std::slice::from_ref(unit))?;
+            after_unit(self);
+        }
+        Ok(())
+    }
+
+    pub fn eval_apply_ff_at(
+        &mut self,
+        event: &InterpreterEventRef,
+    ) -> Result<(), SimulatorErrorCode> {
+        self.run_event(|program| &program.event, event)
+    }
+
+    pub fn eval_only_ff_at(
+        &mut self,
+        event: &InterpreterEventRef,
+    ) -> Result<(), SimulatorErrorCode> {
+        self.run_event(|program| &program.eval_only, event)
+    }
+
+    pub fn apply_ff_at(&mut self, event: &InterpreterEventRef) -> Result<(), SimulatorErrorCode> {
+        self.run_event(|program| &program.apply, event)
+    }
+
+    pub fn resolve_signal(&self, addr: &AbsoluteAddr) -> SignalRef {
+        SignalRef {
+            offset: self.layout.offsets[addr],
+            width: self.layout.widths[addr],
+            is_4state: self.layout.is_4states[addr],
+            array_layout: None,
+        }
+    }
+
+    pub fn resolve_event(&self, addr: &AbsoluteAddr) -> InterpreterEventRef {
+        self.event_map[addr]
+    }
+
+    pub fn resolve_event_opt(&self, addr: &AbsoluteAddr) -> Option<InterpreterEventRef> {
+        self.event_map.get(addr).cloned()
+    }
+
+    pub fn resolve_eval_only_event(&self, addr: &AbsoluteAddr) -> Option<InterpreterEventRef> {
+        self.eval_only_event_map.get(addr).cloned()
+    }
+
+    pub fn resolve_apply_event(&self, addr: &AbsoluteAddr) -> Option<InterpreterEventRef> {
+        self.apply_event_map.get(addr).cloned()
+    }
+
+    pub fn set<T: Copy>(&mut self, signal: SignalRef, value: T) {
+        let allocated_size = get_byte_size(signal.width);
+        let provided_size = std::mem::size_of::<T>();
+        assert!(provided_size <= allocated_size);
+
+        let bytes =
+            unsafe { std::slice::from_raw_parts(&value as *const T as *const u8, provided_size) };
+        let clear_mask = self.options.four_state && signal.is_4state;
+        let mem = bytes_mut(&mut self.memory);
+        mem[signal.offset..signal.offset + allocated_size].fill(0);
+        mem[signal.offset..signal.offset + provided_size].copy_from_slice(bytes);
+        if clear_mask {
+            mem[signal.offset + allocated_size..signal.offset + 2 * allocated_size].fill(0);
+        }
+    }
+
+    pub fn set_wide(&mut self, signal: SignalRef, value: BigUint) {
+        self.set_four_state(signal, value, BigUint::ZERO);
+    }
+
+    pub fn set_four_state(&mut self, signal: SignalRef, value: BigUint, mask: BigUint) {
+        let allocated_size = get_byte_size(signal.width);
+        let has_mask = self.options.four_state && signal.is_4state;
+        let mem = bytes_mut(&mut self.memory);
+        let mut v_bytes = value.to_bytes_le();
+        v_bytes.resize(allocated_size, 0);
+        mem[signal.offset..signal.offset + allocated_size]
+            .copy_from_slice(&v_bytes[..allocated_size]);
+        if has_mask {
+            let mut m_bytes = mask.to_bytes_le();
+            m_bytes.resize(allocated_size, 0);
+            mem[signal.offset + allocated_size..signal.offset + 2 * allocated_size]
+                .copy_from_slice(&m_bytes[..allocated_size]);
+        }
+    }
+
+    pub fn get(&self, signal: SignalRef) -> BigUint {
+        read_bits(bytes(&self.memory), signal.offset, 0, signal.width)
+    }
+
+    pub fn get_as<T: Default + Copy>(&self, signal: SignalRef) -> T {
+        let byte_size = get_byte_size(signal.width);
+        let provided_size = std::mem::size_of::<T>();
+        assert!(byte_size <= provided_size);
+
+        let slice = &bytes(&self.memory)[signal.offset..signal.offset + byte_size];
+        let mut val = T::default();
+        unsafe {
+            let val_ptr = &mut val as *mut T as *mut u8;
+            std::ptr::copy_nonoverlapping(slice.as_ptr(), val_ptr, byte_size);
+        }
+        val
+    }
+
+    pub fn get_four_state(&self, signal: SignalRef) -> (BigUint, BigUint) {
+        let mem = bytes(&self.memory);
+        let value = read_bits(mem, signal.offset, 0, signal.width);
+        let mask = if self.options.four_state && signal.is_4state {
+            let byte_size = get_byte_size(signal.width);
+            read_bits(mem, signal.offset + byte_size, 0, signal.width)
+        } else {
+            BigUint::ZERO
+        };
+        (value, mask)
+    }
+
+    pub fn memory_as_ptr(&self) -> (*const u8, usize) {
+        (
+            self.memory.as_ptr() as *const u8,
+            self.layout.merged_total_size,
+        )
+    }
+
+    pub fn memory_as_mut_ptr(&mut self) -> (*mut u8, usize) {
+        (
+            self.memory.as_mut_ptr() as *mut u8,
+            self.layout.merged_total_size,
+        )
+    }
+
+    pub fn runtime_event_buffer_as_ptr(&self) -> (*const u8, usize) {
+        (
+            bytes(&self.memory)[self.runtime_event_base..].as_ptr(),
+            self.layout.runtime_event_buffer_size,
+        )
+    }
+
+    pub fn set_comb_capture_event_enabled(&mut self, active_sites: &[bool]) {
+        let enabled_base = self.comb_capture_enabled_base;
+        let enabled_len = self.layout.runtime_event_site_layouts.len().max(1);
+        let enabled = &mut bytes_mut(&mut self.memory)[enabled_base..enabled_base + enabled_len];
+        enabled.fill(0);
+        for (byte, &active) in enabled.iter_mut().zip(active_sites) {
+            *byte = active as u8;
+        }
+    }
+
+    pub fn stable_region_size(&self) -> usize {
+        self.layout.total_size
+    }
+
+    pub fn layout(&self) -> &MemoryLayout {
+        &self.layout
+    }
+
+    pub fn id_to_addr_slice(&self) -> &[AbsoluteAddr] {
+        &self.id_to_addr
+    }
+
+    pub fn id_to_event_slice(&self) -> &[InterpreterEventRef] {
+        &self.id_to_event
+    }
+
+    pub fn num_events(&self) -> usize {
+        self.event_map
+            .values()
+            .chain(self.eval_only_event_map.values())
+            .chain(self.apply_event_map.values())
+            .map(|event| event.id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn clear_triggered_bits(&mut self) {
+        let offset = self.layout.triggered_bits_offset;
+        let size = self.layout.triggered_bits_total_size;
+        bytes_mut(&mut self.memory)[offset..offset + size].fill(0);
+    }
+
+    pub fn mark_triggered_bit(&mut self, id: usize) {
+        let offset = self.layout.triggered_bits_offset + id / 8;
+        bytes_mut(&mut self.memory)[offset] |= 1 << (id % 8);
+    }
+
+    pub fn get_triggered_bits(&self) -> bit_set::BitSet {
+        let mut bits = bit_set::BitSet::with_capacity(self.num_events());
+        let offset = self.layout.triggered_bits_offset;
+        let size = self.layout.triggered_bits_total_size;
+        for (i, &byte) in bytes(&self.memory)[offset..offset + size]
+            .iter()
+            .enumerate()
+        {
+            for j in 0..8 {
+                if byte & (1 << j) != 0 {
+                    bits.insert(i * 8 + j);
+                }
+            }
+        }
+        bits
+    }
+}
+
+fn bytes(memory: &[u64]) -> &[u8] {
+    // SAFETY: `u8` has no alignment requirement and every bit pattern is valid.
+    unsafe { std::slice::from_raw_parts(memory.as_ptr() as *const u8, memory.len() * 8) }
+}
+
+fn bytes_mut(memory: &mut [u64]) -> &mut [u8] {
+    // SAFETY: as for `bytes`; the returned slice holds the only borrow.
+    unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr() as *mut u8, memory.len() * 8) }
+}
+
+/// Value and X/Z mask of a register. In two-state mode the mask is zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Value {
+    value: BigUint,
+    mask: BigUint,
+}
+
+impl Value {
+    fn two_state(value: BigUint) -> Self {
+        Self {
+            value,
+            mask: BigUint::ZERO,
+        }
+    }
+
+    fn bool(value: bool) -> Self {
+        Self::two_state(BigUint::from(value))
+    }
+
+    /// Whether any bit is a known one.
+    fn known_one(&self) -> bool {
+        !(&self.value & not(&self.mask, self.value.bits() as usize)).is_zero()
+    }
+
+    fn has_unknown(&self) -> bool {
+        !self.mask.is_zero()
+    }
+
+    /// Encode every X/Z bit as value one, the form arithmetic results take.
+    fn normalized(mut self) -> Self {
+        self.value |= &self.mask;
+        self
+    }
+}
+
+/// Register file of one unit. Every write is truncated to the register width.
+struct Registers<'u> {
+    types: &'u HashMap<RegisterId, RegisterType>,
+    values: HashMap<RegisterId, Value>,
+    four_state: bool,
+}
+
+impl Registers<'_> {
+    fn width(&self, reg: &RegisterId) -> usize {
+        self.types.get(reg).map_or(0, RegisterType::width)
+    }
+
+    fn get(&self, reg: &RegisterId) -> Value {
+        self.values.get(reg).cloned().unwrap_or_default()
+    }
+
+    fn set(&mut self, reg: RegisterId, value: Value) {
+        let width_mask = ones(self.width(&reg));
+        let value = Value {
+            value: value.value & &width_mask,
+            mask: if self.four_state {
+                value.mask & width_mask
+            } else {
+                BigUint::ZERO
+            },
+        };
+        self.values.insert(reg, value);
+    }
+}
+
+fn ones(width: usize) -> BigUint {
+    (BigUint::one() << width) - 1u32
+}
+
+fn not(value: &BigUint, width: usize) -> BigUint {
+    value ^ ones(width)
+}
+
+/// Interpret the low `width` bits of `value` as two's complement.
+fn signed(value: &BigUint, width: usize) -> BigInt {
+    if width > 0 && value.bit(width as u64 - 1) {
+        BigInt::from(value.clone()) - (BigInt::one() << width)
+    } else {
+        BigInt::from(value.clone())
+    }
+}
+
+/// Reduce `value` modulo `2^width`.
+fn wrap(value: BigInt, width: usize) -> BigUint {
+    let modulus = BigInt::one() << width;
+    let reduced = ((value % &modulus) + &modulus) % modulus;
+    reduced.to_biguint().expect("non-negative after reduction")
+}
+
+fn shift_amount(value: &BigUint) -> usize {
+    value.to_usize().unwrap_or(usize::MAX)
+}
+
+fn binary(
+    op: BinaryOp,
+    l: &Value,
+    l_width: usize,
+    r: &Value,
+    r_width: usize,
+    d_width: usize,
+) -> Value {
+    let (lv, rv) = (&l.value, &r.value);
+    let (lm, rm) = (&l.mask, &r.mask);
+    let operand_width = l_width.max(r_width);
+    let full = ones(d_width);
+    let any_unknown = l.has_unknown() || r.has_unknown();
+
+    if matches!(op, BinaryOp::EqCase | BinaryOp::NeCase) {
+        let equal = lv == rv && lm == rm;
+        return Value::bool(equal == (op == BinaryOp::EqCase));
+    }
+    if matches!(op, BinaryOp::EqWildcard | BinaryOp::NeWildcard) {
+        let compare = not(rm, operand_width);
+        let equal = (lv & &compare) == (rv & &compare);
+        let mismatch = (lv ^ rv) & &compare & not(lm, operand_width);
+        let mask = mismatch.is_zero() && !(lm & &compare).is_zero();
+        return Value {
+            value: BigUint::from(equal == (op == BinaryOp::EqWildcard)),
+            mask: BigUint::from(mask),
+        }
+        .normalized();
+    }
+
+    let value = match op {
+        BinaryOp::Add => lv + rv,
+        BinaryOp::Sub => wrap(BigInt::from(lv.clone()) - BigInt::from(rv.clone()), d_width),
+        BinaryOp::Mul => lv * rv,
+        BinaryOp::DivU | BinaryOp::RemU => {
+            if rv.is_zero() {
+                BigUint::ZERO
+            } else if op == BinaryOp::DivU {
+                lv / rv
+            } else {
+                lv % rv
+            }
+        }
+        BinaryOp::DivS | BinaryOp::RemS => {
+            let (ls, rs) = (signed(lv, l_width), signed(rv, r_width));
+            if rs.is_zero() {
+                BigUint::ZERO
+            } else if op == BinaryOp::DivS {
+                wrap(ls / rs, d_width)
+            } else {
+                wrap(ls % rs, d_width)
+            }
+        }
+        BinaryOp::And => lv & rv,
+        BinaryOp::Or => lv | rv,
+        BinaryOp::Xor => lv ^ rv,
+        BinaryOp::Shl => {
+            let amount = shift_amount(rv);
+            if amount >= d_width {
+                BigUint::ZERO
+            } else {
+                lv << amount
+            }
+        }
+        BinaryOp::Shr => {
+            let amount = shift_amount(rv);
+            if amount >= l_width {
+                BigUint::ZERO
+            } else {
+                lv >> amount
+            }
+        }
+        BinaryOp::Sar => {
+            let amount = shift_amount(rv).min(l_width);
+            wrap(signed(lv, l_width) >> amount, d_width)
+        }
+        BinaryOp::Eq => BigUint::from(lv == rv),
+        BinaryOp::Ne => BigUint::from(lv != rv),
+        BinaryOp::LtU => BigUint::from(lv < rv),
+        BinaryOp::LeU => BigUint::from(lv <= rv),
+        BinaryOp::GtU => BigUint::from(lv > rv),
+        BinaryOp::GeU => BigUint::from(lv >= rv),
+        BinaryOp::LtS => BigUint::from(signed(lv, l_width) < signed(rv, r_width)),
+        BinaryOp::LeS => BigUint::from(signed(lv, l_width) <= signed(rv, r_width)),
+        BinaryOp::GtS => BigUint::from(signed(lv, l_width) > signed(rv, r_width)),
+        BinaryOp::GeS => BigUint::from(signed(lv, l_width) >= signed(rv, r_width)),
+        BinaryOp::LogicAnd => BigUint::from(!lv.is_zero() && !rv.is_zero()),
+        BinaryOp::LogicOr => BigUint::from(!lv.is_zero() || !rv.is_zero()),
+        BinaryOp::EqCase | BinaryOp::NeCase | BinaryOp::EqWildcard | BinaryOp::NeWildcard => {
+            unreachable!("handled above")
+        }
+    };
+
+    let mask = match op {
+        // A known zero on either side decides an AND bit, a known one an OR bit.
+        BinaryOp::And => (lm & rm) | (lm & rv) | (rm & lv),
+        BinaryOp::Or => (lm & rm) | (lm & not(rv, operand_width)) | (rm & not(lv, operand_width)),
+        BinaryOp::Xor => lm | rm,
+        BinaryOp::LogicAnd => {
+            let known_false = (lv | lm).is_zero() || (rv | rm).is_zero();
+            if !known_false && any_unknown {
+                full
+            } else {
+                BigUint::ZERO
+            }
+        }
+        BinaryOp::LogicOr => {
+            let known_true = l.known_one() || r.known_one();
+            if !known_true && any_unknown {
+                full
+            } else {
+                BigUint::ZERO
+            }
+        }
+        BinaryOp::Shl | BinaryOp::Shr | BinaryOp::Sar if rm.is_zero() => {
+            let amount = shift_amount(rv);
+            match op {
+                BinaryOp::Shl if amount < d_width => lm << amount,
+                BinaryOp::Shr if amount < l_width => lm >> amount,
+                BinaryOp::Sar => wrap(signed(lm, l_width) >> amount.min(l_width), d_width),
+                _ => BigUint::ZERO,
+            }
+        }
+        _ if any_unknown => full,
+        _ => BigUint::ZERO,
+    };
+
+    Value { value, mask }.normalized()
+}
+
+fn unary(op: UnaryOp, s: &Value, s_width: usize, d_width: usize) -> Value {
+    let (v, m) = (&s.value, &s.mask);
+    let full_if_unknown = || {
+        if s.has_unknown() {
+            ones(d_width)
+        } else {
+            BigUint::ZERO
+        }
+    };
+    let result = match op {
+        UnaryOp::Ident => return s.clone(),
+        UnaryOp::ToTwoState => return Value::two_state(v & not(m, s_width)),
+        UnaryOp::Minus => Value {
+            value: wrap(-BigInt::from(v.clone()), d_width),
+            mask: full_if_unknown(),
+        },
+        UnaryOp::BitNot => Value {
+            value: not(v, d_width),
+            mask: m.clone(),
+        },
+        UnaryOp::LogicNot | UnaryOp::Or => {
+            let known_one = s.known_one();
+            let value = (!v.is_zero()) == (op == UnaryOp::Or);
+            Value {
+                value: BigUint::from(value),
+                mask: BigUint::from(!known_one && s.has_unknown()),
+            }
+        }
+        UnaryOp::And => {
+            let known_zero = !not(&(v | m), s_width).is_zero();
+            Value {
+                value: BigUint::from(*v == ones(s_width)),
+                mask: BigUint::from(!known_zero && s.has_unknown()),
+            }
+        }
+        UnaryOp::Xor => Value {
+            value: BigUint::from(v.count_ones() % 2 == 1),
+            mask: BigUint::from(s.has_unknown()),
+        },
+        UnaryOp::PopCount => Value {
+            value: BigUint::from(v.count_ones()),
+            mask: full_if_unknown(),
+        },
+        UnaryOp::CountLeadingZeros => Value {
+            value: BigUint::from(s_width as u64 - v.bits()),
+            mask: full_if_unknown(),
+        },
+        UnaryOp::CountTrailingZeros => Value {
+            value: BigUint::from(v.trailing_zeros().unwrap_or(s_width as u64)),
+            mask: full_if_unknown(),
+        },
+    };
+    result.normalized()
+}
+
+fn mux(cond: &Value, then_value: Value, else_value: Value) -> Value {
+    if cond.known_one() {
+        then_value
+    } else if !cond.has_unknown() {
+        else_value
+    } else {
+        // Bits on which the arms agree survive an unknown condition.
+        let diff = (&then_value.value ^ &else_value.value) | (&then_value.mask ^ &else_value.mask);
+        Value {
+            value: then_value.value | &diff,
+            mask: then_value.mask | diff,
+        }
+    }
+}
+
+/// Read `width` bits starting `bit` bits after `byte_base`. Bytes past the
+/// end of memory read as zero.
+fn read_bits(mem: &[u8], byte_base: usize, bit: usize, width: usize) -> BigUint {
+    if width == 0 {
+        return BigUint::ZERO;
+    }
+    let start = byte_base.saturating_add(bit / 8);
+    let shift = bit % 8;
+    let bytes = (0..(shift + width).div_ceil(8))
+        .map(|i| {
+            start
+                .checked_add(i)
+                .and_then(|index| mem.get(index))
+                .copied()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    (BigUint::from_bytes_le(&bytes) >> shift) & ones(width)
+}
+
+/// Write the low `width` bits of `value` at the position `read_bits` reads.
+/// Bytes past the end of memory are dropped.
+fn write_bits(mem: &mut [u8], byte_base: usize, bit: usize, width: usize, value: &BigUint) {
+    if width == 0 {
+        return;
+    }
+    let start = byte_base.saturating_add(bit / 8);
+    let shift = bit % 8;
+    let len = (shift + width).div_ceil(8);
+    let mut data = ((value & ones(width)) << shift).to_bytes_le();
+    data.resize(len, 0);
+    let mut keep = (ones(width) << shift).to_bytes_le();
+    keep.resize(len, 0);
+    for i in 0..len {
+        if let Some(byte) = start.checked_add(i).and_then(|index| mem.get_mut(index)) {
+            *byte = (*byte & !keep[i]) | data[i];
+        }
+    }
+}
+
+fn read_u64(mem: &[u8], offset: usize) -> u64 {
+    read_bits(mem, offset, 0, 64).to_u64().unwrap_or(0)
+}
+
+fn write_u64(mem: &mut [u8], offset: usize, value: u64) {
+    write_bits(mem, offset, 0, 64, &BigUint::from(value));
+}
+
+/// Executes one entry point over the backend memory.
+struct Interpreter<'a> {
+    mem: &'a mut [u8],
+    layout: &'a MemoryLayout,
+    four_state: bool,
+    emit_triggers: bool,
+    runtime_event_base: usize,
+    comb_capture_enabled_base: usize,
+    /// First word of every trigger source, read when the entry point starts.
+    trigger_old_values: HashMap<(AbsoluteAddr, u32), u64>,
+}
+
+impl Interpreter<'_> {
+    /// Run `units` in order. `Err` carries the code of an `Error` terminator
+    /// or a fatal runtime event.
+    fn run(&mut self, units: &[Unit]) -> Result<(), i64> {
+        if self.emit_triggers {
+            for instruction in units
+                .iter()
+                .flat_map(|unit| unit.blocks.values())
+                .flat_map(|block| &block.instructions)
+            {
+                let addr = match instruction {
+                    SIRInstruction::Store(addr, _, _, _, triggers, _)
+                    | SIRInstruction::Commit(_, addr, _, _, triggers)
+                        if !triggers.is_empty() =>
+                    {
+                        addr
+                    }
+                    _ => continue,
+                };
+                let old = read_u64(self.mem, self.byte_offset(addr));
+                self.trigger_old_values
+                    .insert((addr.absolute_addr(), addr.region), old);
+            }
+        }
+        for unit in units {
+            self.run_unit(unit)?;
+        }
+        Ok(())
+    }
+
+    fn run_unit(&mut self, unit: &Unit) -> Result<(), i64> {
+        let mut regs = Registers {
+            types: &unit.register_map,
+            values: HashMap::default(),
+            four_state: self.four_state,
+        };
+        let mut block_id = unit.entry_block_id;
+        let mut args = Vec::new();
+        loop {
+            let block = &unit.blocks[&block_id];
+            for (&param, value) in block.params.iter().zip(args.drain(..)) {
+                regs.set(param, value);
+            }
+            for instruction in &block.instructions {
+                self.step(&mut regs, instruction)?;
+            }
+            let (target, passed) = match &block.terminator {
+                SIRTerminator::Jump(target, passed) => (*target, passed.as_slice()),
+                SIRTerminator::Branch {
+                    cond,
+                    true_block,
+                    false_block,
+                } => {
+                    let (target, passed) = if regs.get(cond).value.is_zero() {
+                        false_block
+                    } else {
+                        true_block
+                    };
+                    (*target, passed.as_slice())
+                }
+                SIRTerminator::Switch {
+                    selector,
+                    cases,
+                    default,
+                } => {
+                    let selector = regs.get(selector).value;
+                    let target = cases
+                        .iter()
+                        .find(|case| case.value == selector)
+                        .map_or(*default, |case| case.target);
+                    (target, [].as_slice())
+                }
+                SIRTerminator::Return => return Ok(()),
+                SIRTerminator::Error(code) => return Err(*code),
+            };
+            args = passed.iter().map(|reg| regs.get(reg)).collect();
+            block_id = target;
+        }
+    }
+
+    fn step(
+        &mut self,
+        regs: &mut Registers<'_>,
+        instruction: &SIRInstruction<RegionedAbsoluteAddr>,
+    ) -> Result<(), i64> {
+        match instruction {
+            SIRInstruction::Imm(dst, value) => {
+                regs.set(
+                    *dst,
+                    Value {
+                        value: value.payload.clone(),
+                        mask: value.mask.clone(),
+                    },
+                );
+            }
+            SIRInstruction::Binary(dst, lhs, op, rhs) => {
+                let value = binary(
+                    *op,
+                    &regs.get(lhs),
+                    regs.width(lhs),
+                    &regs.get(rhs),
+                    regs.width(rhs),
+                    regs.width(dst),
+                );
+                regs.set(*dst, value);
+            }
+            SIRInstruction::Unary(dst, op, src) => {
+                let value = unary(*op, &regs.get(src), regs.width(src), regs.width(dst));
+                regs.set(*dst, value);
+            }
+            SIRInstruction::Load(dst, addr, offset, op_width) => {
+                let value = match self.bit_offset(regs, offset) {
+                    Some(bit) => self.load(addr, bit, *op_width),
+                    None => Value::default(),
+                };
+                regs.set(*dst, value);
+            }
+            SIRInstruction::Store(addr, offset, op_width, src, triggers, comb_capture_sites) => {
+                self.store(
+                    regs,
+                    addr,
+                    offset,
+                    *op_width,
+                    &regs.get(src),
+                    triggers,
+                    comb_capture_sites,
+                );
+            }
+            SIRInstruction::Commit(src, dst, offset, op_width, triggers) => {
+                if src.region == SPARSE_WORKING_REGION {
+                    self.sparse_commit(src, dst);
+                } else if let Some(bit) = self.bit_offset(regs, offset) {
+                    let value = self.load(src, bit, *op_width);
+                    self.write(dst, bit, *op_width, &value);
+                    self.detect_triggers(dst, bit, *op_width, triggers);
+                }
+            }
+            SIRInstruction::Concat(dst, parts) => {
+                let mut value = Value::default();
+                for part in parts {
+                    let width = regs.width(part);
+                    let part = regs.get(part);
+                    value.value = (value.value << width) | part.value;
+                    value.mask = (value.mask << width) | part.mask;
+                }
+                regs.set(*dst, value);
+            }
+            SIRInstruction::Slice(dst, src, bit_offset, width) => {
+                let src = regs.get(src);
+                regs.set(
+                    *dst,
+                    Value {
+                        value: (src.value >> *bit_offset) & ones(*width),
+                        mask: (src.mask >> *bit_offset) & ones(*width),
+                    },
+                );
+            }
+            SIRInstruction::Mux(dst, cond, then_value, else_value) => {
+                let value = mux(&regs.get(cond), regs.get(then_value), regs.get(else_value));
+                regs.set(*dst, value);
+            }
+            SIRInstruction::RuntimeEvent { site_id, args } => {
+                let args = args.iter().map(|arg| regs.get(arg)).collect::<Vec<_>>();
+                self.write_runtime_event(*site_id, &args);
+            }
+            SIRInstruction::CombCaptureEvent {
+                site_id,
+                args,
+                fatal_error_code,
+                consume_enabled,
+            } => {
+                let enabled = self.comb_capture_enabled_base + *site_id as usize;
+                if self.mem[enabled] != 0 {
+                    let args = args.iter().map(|arg| regs.get(arg)).collect::<Vec<_>>();
+                    self.write_runtime_event(*site_id, &args);
+                    if *consume_enabled {
+                        self.mem[enabled] = 0;
+                    }
+                    if let Some(code) = fatal_error_code {
+                        return Err(*code);
+                    }
+                }
+            }
+            SIRInstruction::CombCaptureEnableIfChanged { old, new, sites } => {
+                if regs.get(old) != regs.get(new) {
+                    self.enable_comb_capture_sites(sites);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn byte_offset(&self, addr: &RegionedAbsoluteAddr) -> usize {
+        let abs = addr.absolute_addr();
+        match addr.region {
+            STABLE_REGION => self.layout.offsets[&abs],
+            SPARSE_WORKING_REGION => {
+                self.layout.sparse_base_offset + self.layout.sparse_offsets[&abs]
+            }
+            _ => self.layout.working_base_offset + self.layout.working_offsets[&abs],
+        }
+    }
+
+    /// Byte distance from the value plane to the mask plane, if `abs` has one.
+    fn mask_plane(&self, abs: &AbsoluteAddr) -> Option<usize> {
+        (self.four_state && self.layout.is_4states[abs])
+            .then(|| get_byte_size(self.layout.widths[abs]))
+    }
+
+    /// Logical bit offset of an access, or `None` if it does not fit a `usize`.
+    fn bit_offset(&self, regs: &Registers<'_>, offset: &SIROffset) -> Option<usize> {
+        let reg_value = |reg: &RegisterId| regs.get(reg).value.to_usize();
+        match offset {
+            SIROffset::Static(bit)
+            | SIROffset::PackedElements {
+                bit_offset: bit, ..
+            } => Some(*bit),
+            SIROffset::Dynamic(reg) => reg_value(reg),
+            SIROffset::Element {
+                index,
+                element_width,
+                bit_offset,
+                dynamic_bit_offset,
+            } => reg_value(index)?
+                .checked_mul(*element_width)?
+                .checked_add(*bit_offset)?
+                .checked_add(match dynamic_bit_offset {
+                    Some(reg) => reg_value(reg)?,
+                    None => 0,
+                }),
+        }
+    }
+
+    fn load(&self, addr: &RegionedAbsoluteAddr, bit: usize, width: usize) -> Value {
+        let base = self.byte_offset(addr);
+        Value {
+            value: read_bits(self.mem, base, bit, width),
+            mask: match self.mask_plane(&addr.absolute_addr()) {
+                Some(plane) => read_bits(self.mem, base + plane, bit, width),
+                None => BigUint::ZERO,
+            },
+        }
+    }
+
+    fn write(&mut self, addr: &RegionedAbsoluteAddr, bit: usize, width: usize, value: &Value) {
+        let base = self.byte_offset(addr);
+        write_bits(self.mem, base, bit, width, &value.value);
+        if let Some(plane) = self.mask_plane(&addr.absolute_addr()) {
+            write_bits(self.mem, base + plane, bit, width, &value.mask);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn store(
+        &mut self,
+        regs: &Registers<'_>,
+        addr: &RegionedAbsoluteAddr,
+        offset: &SIROffset,
+        op_width: usize,
+        value: &Value,
+        triggers: &[TriggerIdWithKind],
+        comb_capture_sites: &[u32],
+    ) {
+        // width=0: identity Store optimized away by alias; triggers only.
+        if op_width == 0 {
+            self.detect_triggers(addr, 0, 0, triggers);
+            return;
+        }
+        let Some(bit) = self.bit_offset(regs, offset) else {
+            return;
+        };
+        if addr.region == SPARSE_WORKING_REGION {
+            self.prepare_sparse_store(&addr.absolute_addr(), bit, op_width);
+        }
+        let before = (!comb_capture_sites.is_empty()).then(|| self.load(addr, bit, op_width));
+        self.write(addr, bit, op_width, value);
+        if before.is_some_and(|before| before != self.load(addr, bit, op_width)) {
+            self.enable_comb_capture_sites(comb_capture_sites);
+        }
+        self.detect_triggers(addr, bit, op_width, triggers);
+    }
+
+    /// Copy the stable chunks a sparse store touches into the working copy
+    /// the first time each one is written, and mark them dirty.
+    fn prepare_sparse_store(&mut self, abs: &AbsoluteAddr, bit: usize, op_width: usize) {
+        let sparse = &self.layout.sparse_layouts[abs];
+        let byte_size = get_byte_size(self.layout.widths[abs]);
+        let stable_base = self.layout.offsets[abs];
+        let sparse_base = self.layout.sparse_base_offset + self.layout.sparse_offsets[abs];
+        let planes = if self.mask_plane(abs).is_some() { 2 } else { 1 };
+        let last_chunk = bit.saturating_add(op_width - 1) >> 6;
+        for chunk in (bit >> 6)..=last_chunk.min(sparse.chunk_count.saturating_sub(1)) {
+            let dirty_word = chunk >> 6;
+            let dirty_addr = sparse.dirty_words_offset + dirty_word * 8;
+            let dirty_bits = read_u64(self.mem, dirty_addr);
+            let dirty_mask = 1u64 << (chunk & 63);
+            if dirty_bits & dirty_mask == 0 {
+                let len = byte_size.saturating_sub(chunk * 8).min(8);
+                for plane in 0..planes {
+                    let delta = plane * byte_size + chunk * 8;
+                    self.mem.copy_within(
+                        stable_base + delta..stable_base + delta + len,
+                        sparse_base + delta,
+                    );
+                }
+            }
+            write_u64(self.mem, dirty_addr, dirty_bits | dirty_mask);
+            let summary_addr = sparse.summary_words_offset + (dirty_word >> 6) * 8;
+            let summary_bits = read_u64(self.mem, summary_addr);
+            write_u64(
+                self.mem,
+                summary_addr,
+                summary_bits | (1 << (dirty_word & 63)),
+            );
+        }
+    }
+
+    /// Copy every dirty chunk of a sparse working copy to the stable region
+    /// and clear the dirty tracking.
+    fn sparse_commit(&mut self, src: &RegionedAbsoluteAddr, dst: &RegionedAbsoluteAddr) {
+        debug_assert_eq!(dst.region, STABLE_REGION);
+        let abs = src.absolute_addr();
+        let sparse = &self.layout.sparse_layouts[&abs];
+        let byte_size = get_byte_size(self.layout.widths[&abs]);
+        let src_base = self.byte_offset(src);
+        let dst_base = self.byte_offset(dst);
+        let planes = if self.mask_plane(&abs).is_some() {
+            2
+        } else {
+            1
+        };
+        for summary_index in 0..sparse.summary_word_count {
+            let summary_addr = sparse.summary_words_offset + summary_index * 8;
+            let summary_bits = read_u64(self.mem, summary_addr);
+            write_u64(self.mem, summary_addr, 0);
+            for summary_bit in set_bits(summary_bits) {
+                let word_index = summary_index * 64 + summary_bit;
+                let dirty_addr = sparse.dirty_words_offset + word_index * 8;
+                let dirty_bits = read_u64(self.mem, dirty_addr);
+                write_u64(self.mem, dirty_addr, 0);
+                for dirty_bit in set_bits(dirty_bits) {
+                    let chunk = word_index * 64 + dirty_bit;
+                    let len = byte_size.saturating_sub(chunk * 8).min(8);
+                    for plane in 0..planes {
+                        let delta = plane * byte_size + chunk * 8;
+                        self.mem.copy_within(
+                            src_base + delta..src_base + delta + len,
+                            dst_base + delta,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Set the triggered bit of every trigger whose edge the last write to
+    /// `addr` produced, compared against the value captured at entry.
+    fn detect_triggers(
+        &mut self,
+        addr: &RegionedAbsoluteAddr,
+        bit: usize,
+        op_width: usize,
+        triggers: &[TriggerIdWithKind],
+    ) {
+        if !self.emit_triggers || triggers.is_empty() {
+            return;
+        }
+        let abs = addr.absolute_addr();
+        let old_word = self
+            .trigger_old_values
+            .get(&(abs, addr.region))
+            .copied()
+            .unwrap_or(0);
+        let fired = |trigger: &TriggerIdWithKind, old: u64, new: u64| match trigger.kind {
+            DomainKind::ClockPosedge => old == 0 && new == 1,
+            DomainKind::ClockNegedge => old == 1 && new == 0,
+            DomainKind::ResetAsyncHigh => new == 1,
+            DomainKind::ResetAsyncLow => new == 0,
+            DomainKind::Other => old != new,
+        };
+        let fired = if op_width == 0 {
+            // An aliased store compares the first byte of the stable value.
+            let new = u64::from(self.mem[self.layout.offsets[&abs]]);
+            let changed = new != old_word & 0xFF;
+            triggers.iter().filter(|_| changed).collect::<Vec<_>>()
+        } else {
+            let width = op_width.min(64);
+            let old = if bit < 64 { old_word >> bit } else { 0 } & (u64::MAX >> (64 - width));
+            let new = read_bits(self.mem, self.byte_offset(addr), bit, width)
+                .to_u64()
+                .unwrap_or(0);
+            triggers
+                .iter()
+                .filter(|trigger| fired(trigger, old, new))
+                .collect()
+        };
+        for trigger in fired {
+            self.mem[self.layout.triggered_bits_offset + trigger.id / 8] |= 1 << (trigger.id % 8);
+        }
+    }
+
+    fn enable_comb_capture_sites(&mut self, sites: &[u32]) {
+        for &site in sites {
+            self.mem[self.comb_capture_enabled_base + site as usize] = 1;
+        }
+    }
+
+    /// Append one event to the runtime event ring buffer.
+    fn write_runtime_event(&mut self, site_id: u32, args: &[Value]) {
+        let base = self.runtime_event_base;
+        let seq = read_u64(self.mem, base);
+        let capacity = self.layout.runtime_event_capacity.max(1);
+        let slot = base
+            + RUNTIME_EVENT_HEADER_SIZE
+            + (seq as usize % capacity) * self.layout.runtime_event_slot_size;
+        write_u64(
+            self.mem,
+            slot + RUNTIME_EVENT_SLOT_SEQ_OFFSET,
+            RUNTIME_EVENT_WRITING,
+        );
+        write_u64(
+            self.mem,
+            slot + RUNTIME_EVENT_SLOT_SITE_OFFSET,
+            u64::from(site_id),
+        );
+        write_u64(
+            self.mem,
+            slot + RUNTIME_EVENT_SLOT_ARG_COUNT_OFFSET,
+            args.len() as u64,
+        );
+        if let Some(site_layout) = self.layout.runtime_event_site_layouts.get(site_id as usize) {
+            let payload = slot + RUNTIME_EVENT_SLOT_PAYLOAD_OFFSET;
+            for (arg, arg_layout) in args.iter().zip(&site_layout.args) {
+                let words = arg_layout.word_count * 64;
+                write_bits(
+                    self.mem,
+                    payload + arg_layout.value_word_offset * 8,
+                    0,
+                    words,
+                    &arg.value,
+                );
+                write_bits(
+                    self.mem,
+                    payload + arg_layout.mask_word_offset * 8,
+                    0,
+                    words,
+                    &arg.mask,
+                );
+            }
+        }
+        write_u64(self.mem, slot + RUNTIME_EVENT_SLOT_SEQ_OFFSET, seq);
+        write_u64(self.mem, base, seq + 1);
+    }
+}
+
+fn set_bits(mut word: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        (word != 0).then(|| {
+            let bit = word.trailing_zeros() as usize;
+            word &= word - 1;
+            bit
+        })
+    })
+}
+
+// Tests: crates/celox/tests/interpreter_backend.rs
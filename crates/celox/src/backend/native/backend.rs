@@ -18,15 +18,14 @@ use celox_testbench::TestbenchProgram;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
-use crate::ir::{
-    AbsoluteAddr, BlockId, ExecutionUnit, LaidOutProgram, RegionedAbsoluteAddr, RegisterId,
-    SIRInstruction, SIROffset, SIRTerminator, SignalArrayLayout, SignalRef,
-};
+use crate::ir::{AbsoluteAddr, LaidOutProgram, SignalArrayLayout, SignalRef};
 use crate::{CodegenError, HashMap, HashSet, SimulatorError, SimulatorOptions};
 
 use super::super::RuntimeEventBuffer;
 use super::super::traits::SimulatorErrorCode;
 use super::super::{MemoryLayout, get_byte_size};
+use super::lanes::{LaneArena, NativeMemory};
+use super::parallel::{self, NativeStage, WorkerPool};
 #[cfg(any(
     feature = "x86_64-codegen",
     all(target_arch = "x86_64", not(feature = "arm64-codegen"))
@@ -156,6 +155,8 @@ impl super::super::EventHandle for NativeEventRef {
 pub struct SharedNativeCode {
     comb_func: NativeSimFunc,
     comb_unit_funcs: Vec<NativeSimFunc>,
+    parallel_comb: Vec<NativeStage>,
+    parallel_apply_ffs: HashMap<usize, Vec<NativeStage>>,
     /// Keep the combined executable image alive so every entry pointer remains
     /// valid. The image contains all native functions and their trailing
     /// constant/literal data.
@@ -236,6 +237,23 @@ impl SharedNativeCode {
             .copied()
             .map(|offset| native_function_at(&jit_image, offset))
             .collect::<Result<Vec<_>, _>>()?;
+        let materialize_stages = |stages: &[Vec<usize>]| {
+            stages
+                .iter()
+                .map(|stage| {
+                    stage
+                        .iter()
+                        .map(|&offset| native_function_at(&jit_image, offset))
+                        .collect::<Result<NativeStage, _>>()
+                })
+                .collect::<Result<Vec<_>, SimulatorError>>()
+        };
+        let parallel_comb = materialize_stages(&program_image.parallel_comb_offsets)?;
+        let parallel_apply_ffs = program_image
+            .parallel_apply_ff_offsets
+            .iter()
+            .map(|(&id, stages)| Ok((id, materialize_stages(stages)?)))
+            .collect::<Result<HashMap<_, _>, SimulatorError>>()?;
         let event_map = materialize_map(&program_image.event_map)?;
         let eval_only_event_map = materialize_map(&program_image.eval_only_event_map)?;
         let apply_event_map = materialize_map(&program_image.apply_event_map)?;
@@ -249,6 +267,8 @@ impl SharedNativeCode {
         Ok(Self {
             comb_func,
             comb_unit_funcs,
+            parallel_comb,
+            parallel_apply_ffs,
             _jit_image: jit_image,
             event_map,
             eval_only_event_map,
@@ -323,6 +343,9 @@ struct NativeRuntimeOptions {
     native_tick_loop: bool,
     native_force_support: bool,
     perf_map: bool,
+    /// Worker threads for the staged entries; `0` in older images means one.
+    #[serde(default)]
+    threads: usize,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -332,6 +355,14 @@ pub(crate) struct NativeRuntimeSchema {
     pub(crate) comb_observers: Vec<RuntimeCombObserver<AbsoluteAddr>>,
     pub(crate) testbench_read_roots: HashSet<AbsoluteAddr>,
     pub(crate) rtl_writes: HashSet<celox_design::VarAtomBase<AbsoluteAddr>>,
+    #[serde(default)]
+    pub(crate) coverage_points: Vec<celox_design::CoveragePoint<AbsoluteAddr>>,
+    #[serde(default)]
+    pub(crate) fsm_registers: Vec<celox_design::FsmRegister<AbsoluteAddr>>,
+    #[serde(default)]
+    pub(crate) final_memory_dumps: Vec<celox_design::MemoryDump<AbsoluteAddr>>,
+    #[serde(default)]
+    pub(crate) registers: HashSet<AbsoluteAddr>,
 }
 
 /// Pointer-free native compiler artifact which can be attached to the
@@ -343,6 +374,13 @@ pub struct NativeProgramImage {
     symbols: Vec<NativeCodeSymbol>,
     comb_offset: usize,
     comb_unit_offsets: Vec<usize>,
+    /// Stages of concurrently executable `eval_comb` partitions.
+    #[serde(default)]
+    parallel_comb_offsets: Vec<Vec<usize>>,
+    /// Stages of concurrently executable `eval_apply_ff` partitions, keyed by
+    /// event id.
+    #[serde(default)]
+    parallel_apply_ff_offsets: HashMap<usize, Vec<Vec<usize>>>,
     required_native_features: u8,
     event_map: HashMap<AbsoluteAddr, NativeEventImageRef>,
     eval_only_event_map: HashMap<AbsoluteAddr, NativeEventImageRef>,
@@ -407,6 +445,10 @@ impl NativeProgramImage {
                 comb_observers: self.runtime_schema.comb_observers.clone(),
                 testbench_read_roots: self.runtime_schema.testbench_read_roots.clone(),
                 rtl_writes: self.runtime_schema.rtl_writes.clone(),
+                coverage_points: self.runtime_schema.coverage_points.clone(),
+                fsm_registers: self.runtime_schema.fsm_registers.clone(),
+                final_memory_dumps: self.runtime_schema.final_memory_dumps.clone(),
+                registers: self.runtime_schema.registers.clone(),
             },
             testbench: self.testbench.clone(),
         }
@@ -453,6 +495,15 @@ impl NativeProgramImage {
         {
             return Err("a combinational unit offset does not name an image entry".into());
         }
+        if self
+            .parallel_comb_offsets
+            .iter()
+            .chain(self.parallel_apply_ff_offsets.values().flatten())
+            .flatten()
+            .any(|offset| !entry_offsets.contains(offset))
+        {
+            return Err("a parallel stage offset does not name an image entry".into());
+        }
         if self.required_native_features & !KNOWN_NATIVE_FEATURES != 0 {
             return Err("native image contains unknown feature requirements".into());
         }
@@ -1169,424 +1220,205 @@ fn format_native_codegen_trace(
     }
 }
 
-fn offset_registers(offset: &SIROffset, registers: &mut Vec<RegisterId>) {
-    match offset {
-        SIROffset::Dynamic(register) => registers.push(*register),
-        SIROffset::Element {
-            index,
-            dynamic_bit_offset,
-            ..
-        } => {
-            registers.push(*index);
-            registers.extend(dynamic_bit_offset);
-        }
-        SIROffset::Static(_) | SIROffset::PackedElements { .. } => {}
-    }
+/// Staged partitions compiled for multi-threaded evaluation.
+#[derive(Default)]
+struct ParallelProgram {
+    comb: Vec<Vec<CompiledNativeFunction>>,
+    /// Keyed by `eval_apply_ff` compile-task index.
+    apply_ffs: HashMap<usize, Vec<Vec<CompiledNativeFunction>>>,
 }
 
-fn instruction_registers<A>(instruction: &SIRInstruction<A>) -> Vec<RegisterId> {
-    let mut registers = Vec::new();
-    match instruction {
-        SIRInstruction::Imm(..) => {}
-        SIRInstruction::Binary(_, lhs, _, rhs) => registers.extend([*lhs, *rhs]),
-        SIRInstruction::Unary(_, _, source) | SIRInstruction::Slice(_, source, _, _) => {
-            registers.push(*source);
-        }
-        SIRInstruction::Load(_, _, offset, _) => offset_registers(offset, &mut registers),
-        SIRInstruction::Store(_, offset, _, source, _, _) => {
-            registers.push(*source);
-            offset_registers(offset, &mut registers);
-        }
-        SIRInstruction::Commit(..) => {}
-        SIRInstruction::Concat(_, sources) => registers.extend(sources),
-        SIRInstruction::Mux(_, condition, then_value, else_value) => {
-            registers.extend([*condition, *then_value, *else_value]);
-        }
-        SIRInstruction::RuntimeEvent { args, .. }
-        | SIRInstruction::CombCaptureEvent { args, .. } => registers.extend(args),
-        SIRInstruction::CombCaptureEnableIfChanged { old, new, .. } => {
-            registers.extend([*old, *new]);
-        }
+impl ParallelProgram {
+    fn functions(&self) -> impl Iterator<Item = &CompiledNativeFunction> {
+        self.comb
+            .iter()
+            .chain(self.apply_ffs.values().flatten())
+            .flatten()
     }
-    registers
 }
 
-fn comb_block_execution_order<A>(unit: &ExecutionUnit<A>) -> Vec<BlockId> {
-    fn visit<A>(
-        unit: &ExecutionUnit<A>,
-        block_id: BlockId,
-        visited: &mut HashSet<BlockId>,
-        postorder: &mut Vec<BlockId>,
-    ) {
-        if !visited.insert(block_id) {
-            return;
-        }
-        for successor in celox_sir::cfg::terminator_successors(&unit.blocks[&block_id].terminator) {
-            visit(unit, successor, visited, postorder);
-        }
-        postorder.push(block_id);
-    }
-
-    let mut visited = HashSet::default();
-    let mut postorder = Vec::with_capacity(unit.blocks.len());
-    visit(unit, unit.entry_block_id, &mut visited, &mut postorder);
-    postorder.reverse();
-    postorder
+struct ParallelCompileJob<'a> {
+    task: Option<usize>,
+    stage: usize,
+    slot: usize,
+    units: Vec<&'a crate::ir::ExecutionUnit<crate::ir::RegionedAbsoluteAddr>>,
 }
 
-fn is_comb_runtime_effect(instruction: &SIRInstruction<RegionedAbsoluteAddr>) -> bool {
-    matches!(
-        instruction,
-        SIRInstruction::RuntimeEvent { .. }
-            | SIRInstruction::CombCaptureEvent { .. }
-            | SIRInstruction::CombCaptureEnableIfChanged { .. }
-    )
-}
+/// Compile the stages planned by [`parallel::plan_stages`] for `eval_comb`
+/// and every `eval_apply_ff` task.
+///
+/// Partitions of one stage run concurrently, so partition slot `p` gets its
+/// own spill/scratch arena behind the arenas of slots `0..p`. Arena sizes are
+/// only known after register allocation: every partition is compiled once at
+/// offset zero, and the partitions of slots `1..` are compiled again at their
+/// final offset.
+fn compile_parallel_program(
+    sir: &LaidOutProgram,
+    layout: &MemoryLayout,
+    options: &SimulatorOptions,
+    compile_tasks: &[NativeCompileTask<'_>],
+) -> Result<ParallelProgram, SimulatorError> {
+    const MAX_PARALLEL_NATIVE_FUNCTIONS: usize = 4;
 
-fn interleave_comb_runtime_effects(
-    unit: &ExecutionUnit<RegionedAbsoluteAddr>,
-    ordered_stores: &[(BlockId, usize)],
-    store_units: Vec<ExecutionUnit<RegionedAbsoluteAddr>>,
-) -> Vec<ExecutionUnit<RegionedAbsoluteAddr>> {
-    let ordered_sites = comb_block_execution_order(unit)
-        .into_iter()
-        .flat_map(|block_id| {
-            (0..unit.blocks[&block_id].instructions.len()).map(move |index| (block_id, index))
-        })
-        .collect::<Vec<_>>();
-    let positions = ordered_sites
-        .iter()
-        .enumerate()
-        .map(|(position, &site)| (site, position))
-        .collect::<HashMap<_, _>>();
-    let mut effect_groups = vec![Vec::new(); ordered_stores.len() + 1];
-    for site in ordered_sites {
-        if !is_comb_runtime_effect(&unit.blocks[&site.0].instructions[site.1]) {
-            continue;
-        }
-        let boundary = ordered_stores
+    let threads = options.threads;
+    let mut groups = vec![(None, sir.sir.eval_comb.iter().collect::<Vec<_>>())];
+    groups.extend(
+        compile_tasks
             .iter()
-            .filter(|store| positions[store] < positions[&site])
-            .count();
-        effect_groups[boundary].push(site);
-    }
-    if effect_groups.iter().all(Vec::is_empty) {
-        return store_units;
-    }
-
-    let mut result = Vec::with_capacity(store_units.len() + effect_groups.len());
-    let mut stores = store_units.into_iter();
-    for (boundary, group) in effect_groups.into_iter().enumerate() {
-        if !group.is_empty() {
-            let group = group.into_iter().collect::<HashSet<_>>();
-            let mut events = unit.clone();
-            for (block_id, block) in &mut events.blocks {
-                block.instructions = std::mem::take(&mut block.instructions)
-                    .into_iter()
-                    .enumerate()
-                    .filter_map(|(index, instruction)| {
-                        let site = (*block_id, index);
-                        if matches!(
-                            instruction,
-                            SIRInstruction::Store(..) | SIRInstruction::Commit(..)
-                        ) {
-                            None
-                        } else if is_comb_runtime_effect(&instruction) {
-                            group.contains(&site).then_some(instruction)
-                        } else {
-                            Some(instruction)
-                        }
-                    })
-                    .collect();
+            .enumerate()
+            .filter(|(_, task)| task.label == "eval_apply_ff")
+            .map(|(task_id, task)| (Some(task_id), task.units.clone())),
+    );
+    let mut jobs = Vec::new();
+    for (task, units) in groups {
+        let Some(stages) = parallel::plan_stages(&units, layout, threads) else {
+            continue;
+        };
+        for (stage, partitions) in stages.into_iter().enumerate() {
+            for (slot, partition) in partitions.into_iter().enumerate() {
+                jobs.push(ParallelCompileJob {
+                    task,
+                    stage,
+                    slot,
+                    units: partition.into_iter().map(|unit| units[unit]).collect(),
+                });
             }
-            result.push(events);
-        }
-        if boundary < ordered_stores.len() {
-            result.push(stores.next().unwrap());
         }
     }
-    result
-}
-
-fn split_comb_execution_unit(
-    unit: &ExecutionUnit<RegionedAbsoluteAddr>,
-) -> Vec<ExecutionUnit<RegionedAbsoluteAddr>> {
-    if unit.blocks.len() != 1 {
-        let definitions = unit
-            .blocks
-            .iter()
-            .flat_map(|(block_id, block)| {
-                block
-                    .instructions
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(index, instruction)| {
-                        instruction
-                            .defined_register()
-                            .map(|register| (register, (*block_id, index)))
-                    })
-            })
-            .collect::<HashMap<_, _>>();
-        let store_sites = comb_block_execution_order(unit)
-            .into_iter()
-            .flat_map(|block_id| {
-                let block = &unit.blocks[&block_id];
-                block
-                    .instructions
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, instruction)| {
-                        matches!(
-                            instruction,
-                            SIRInstruction::Store(..) | SIRInstruction::Commit(..)
-                        )
-                    })
-                    .map(move |(index, _)| (block_id, index))
-            })
-            .collect::<Vec<_>>();
-        if store_sites.is_empty() {
-            return vec![unit.clone()];
-        }
+    if jobs.is_empty() {
+        return Ok(ParallelProgram::default());
+    }
 
-        let instruction_at = |site: (BlockId, usize)| &unit.blocks[&site.0].instructions[site.1];
-        let register_dependencies = store_sites
-            .iter()
-            .copied()
-            .map(|store_site| {
-                let mut dependencies = HashSet::default();
-                let mut pending = instruction_registers(instruction_at(store_site));
-                while let Some(register) = pending.pop() {
-                    if !dependencies.insert(register) {
-                        continue;
-                    }
-                    if let Some(&definition) = definitions.get(&register) {
-                        pending.extend(instruction_registers(instruction_at(definition)));
-                    }
-                }
-                (store_site, dependencies)
-            })
-            .collect::<HashMap<_, _>>();
-        let store_source = |site| match instruction_at(site) {
-            SIRInstruction::Store(_, _, _, source, _, _) => Some(*source),
-            _ => None,
+    let compile = |job: &ParallelCompileJob<'_>, arena_offset: usize| {
+        let x86_options = crate::backend::X86BackendOptions {
+            arena_offset,
+            ..options.x86_options.clone()
         };
-        let mut remaining = store_sites;
-        let mut ordered_stores = Vec::with_capacity(remaining.len());
-        while !remaining.is_empty() {
-            let next = remaining
-                .iter()
-                .position(|candidate| {
-                    let candidate_source = store_source(*candidate);
-                    !remaining.iter().any(|predecessor| {
-                        if predecessor == candidate {
-                            return false;
+        compile_unit_refs(
+            &job.units,
+            layout,
+            options.four_state,
+            if job.task.is_some() {
+                "eval_apply_ff"
+            } else {
+                "eval_comb"
+            },
+            None,
+            &x86_options,
+            false,
+            &options.optimize_options.diagnostics,
+        )
+    };
+    let compile_all = |jobs: &[&ParallelCompileJob<'_>], offsets: &[usize]| {
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            let handles = (0..jobs.len().min(MAX_PARALLEL_NATIVE_FUNCTIONS))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut compiled = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(job) = jobs.get(index) else {
+                                break;
+                            };
+                            compiled.push((index, compile(job, offsets[job.slot])?));
                         }
-                        store_source(*predecessor).is_some_and(|source| {
-                            Some(source) != candidate_source
-                                && register_dependencies[candidate].contains(&source)
-                        })
+                        Ok::<_, SimulatorError>(compiled)
                     })
                 })
-                .unwrap_or(0);
-            ordered_stores.push(remaining.remove(next));
-        }
-
-        let split = ordered_stores
-            .iter()
-            .enumerate()
-            .map(|(order, &target)| {
-                let reloads = ordered_stores[..order]
-                    .iter()
-                    .filter_map(|&prior_site| {
-                        let SIRInstruction::Store(address, offset, bits, source, _, _) =
-                            instruction_at(prior_site)
-                        else {
-                            return None;
-                        };
-                        (register_dependencies[&target].contains(source)
-                            && store_source(target) != Some(*source)
-                            && unit.register_map[source].width() == *bits)
-                            .then(|| (*source, (*address, offset.clone(), *bits)))
-                    })
-                    .collect::<HashMap<_, _>>();
-                let mut extracted = unit.clone();
-                for (block_id, block) in &mut extracted.blocks {
-                    block.instructions = std::mem::take(&mut block.instructions)
-                        .into_iter()
-                        .enumerate()
-                        .filter_map(|(index, instruction)| {
-                            let site = (*block_id, index);
-                            match instruction {
-                                SIRInstruction::Store(..) | SIRInstruction::Commit(..) => {
-                                    (site == target).then_some(instruction)
-                                }
-                                SIRInstruction::RuntimeEvent { .. }
-                                | SIRInstruction::CombCaptureEvent { .. }
-                                | SIRInstruction::CombCaptureEnableIfChanged { .. } => None,
-                                _ => {
-                                    if let Some(register) = instruction.defined_register()
-                                        && let Some((address, offset, bits)) =
-                                            reloads.get(&register)
-                                    {
-                                        Some(SIRInstruction::Load(
-                                            register,
-                                            *address,
-                                            offset.clone(),
-                                            *bits,
-                                        ))
-                                    } else {
-                                        Some(instruction)
-                                    }
-                                }
-                            }
-                        })
-                        .collect();
-                }
-                extracted
-            })
-            .collect::<Vec<_>>();
-
-        return interleave_comb_runtime_effects(unit, &ordered_stores, split);
-    }
-    let block = &unit.blocks[&unit.entry_block_id];
-    if !block.params.is_empty() || block.terminator != SIRTerminator::Return {
-        return vec![unit.clone()];
-    }
-
-    let definitions = block
-        .instructions
-        .iter()
-        .enumerate()
-        .filter_map(|(index, instruction)| {
-            instruction
-                .defined_register()
-                .map(|register| (register, index))
+                .collect::<Vec<_>>();
+            let mut compiled = Vec::with_capacity(jobs.len());
+            for handle in handles {
+                compiled.extend(handle.join().map_err(|_| {
+                    codegen_message("native parallel-stage compile thread panicked")
+                })??);
+            }
+            compiled.sort_unstable_by_key(|(index, _)| *index);
+            Ok::<_, SimulatorError>(
+                compiled
+                    .into_iter()
+                    .map(|(_, code)| code)
+                    .collect::<Vec<_>>(),
+            )
         })
-        .collect::<HashMap<_, _>>();
-    let store_indices = block
-        .instructions
+    };
+
+    let align16 = |size: usize| size.div_ceil(16) * 16;
+    let arena_base = align16(
+        layout
+            .merged_total_size
+            .checked_add(layout.triggered_bits_total_size)
+            .expect("native semantic-memory size overflow"),
+    );
+    let all_jobs = jobs.iter().collect::<Vec<_>>();
+    let mut compiled = compile_all(&all_jobs, &vec![0; threads])?;
+    let mut slot_sizes = vec![0usize; threads];
+    for (job, code) in jobs.iter().zip(&compiled) {
+        let arena = code.required_state_size.saturating_sub(arena_base);
+        slot_sizes[job.slot] = slot_sizes[job.slot].max(arena);
+    }
+    let mut slot_offsets = vec![0usize; threads];
+    for slot in 1..threads {
+        slot_offsets[slot] = align16(slot_offsets[slot - 1] + slot_sizes[slot - 1]);
+    }
+    let relocated = jobs
         .iter()
         .enumerate()
-        .filter_map(|(index, instruction)| {
-            matches!(
-                instruction,
-                SIRInstruction::Store(..) | SIRInstruction::Commit(..)
-            )
-            .then_some(index)
-        })
+        .filter(|(_, job)| job.slot != 0)
         .collect::<Vec<_>>();
-    if store_indices.is_empty() {
-        return vec![unit.clone()];
+    let relocated_jobs = relocated.iter().map(|(_, job)| *job).collect::<Vec<_>>();
+    for ((index, job), code) in relocated
+        .iter()
+        .zip(compile_all(&relocated_jobs, &slot_offsets)?)
+    {
+        if code.required_state_size > arena_base + slot_offsets[job.slot] + slot_sizes[job.slot] {
+            return Err(codegen_message(
+                "native parallel-stage arena grew when it was relocated",
+            ));
+        }
+        compiled[*index] = code;
     }
 
-    let register_dependencies = store_indices
-        .iter()
-        .copied()
-        .map(|store_index| {
-            let mut dependencies = HashSet::default();
-            let mut pending = instruction_registers(&block.instructions[store_index]);
-            while let Some(register) = pending.pop() {
-                if !dependencies.insert(register) {
-                    continue;
-                }
-                if let Some(&definition) = definitions.get(&register) {
-                    pending.extend(instruction_registers(&block.instructions[definition]));
-                }
-            }
-            (store_index, dependencies)
-        })
-        .collect::<HashMap<_, _>>();
-    let store_source = |index| match &block.instructions[index] {
-        SIRInstruction::Store(_, _, _, source, _, _) => Some(*source),
-        _ => None,
-    };
-    let mut remaining = store_indices.clone();
-    let mut ordered_stores = Vec::with_capacity(remaining.len());
-    while !remaining.is_empty() {
-        let next = remaining
-            .iter()
-            .position(|candidate| {
-                let candidate_source = store_source(*candidate);
-                !remaining.iter().any(|predecessor| {
-                    if predecessor == candidate {
-                        return false;
-                    }
-                    store_source(*predecessor).is_some_and(|source| {
-                        Some(source) != candidate_source
-                            && register_dependencies[candidate].contains(&source)
-                    })
-                })
-            })
-            .unwrap_or(0);
-        ordered_stores.push(remaining.remove(next));
+    let mut program = ParallelProgram::default();
+    for (job, code) in jobs.iter().zip(compiled) {
+        let stages = match job.task {
+            Some(task) => program.apply_ffs.entry(task).or_default(),
+            None => &mut program.comb,
+        };
+        if stages.len() <= job.stage {
+            stages.resize_with(job.stage + 1, Vec::new);
+        }
+        stages[job.stage].push(code);
     }
+    Ok(program)
+}
 
-    let split = ordered_stores
+fn append_parallel_stages(
+    image: &mut Vec<u8>,
+    entries: &mut Vec<NativeCodeEntry>,
+    image_symbols: &mut Vec<NativeCodeSymbol>,
+    name: &str,
+    stages: &[Vec<CompiledNativeFunction>],
+) -> Result<Vec<Vec<usize>>, SimulatorError> {
+    stages
         .iter()
         .enumerate()
-        .map(|(order, &store_index)| {
-            let reloads = ordered_stores[..order]
+        .map(|(stage, partitions)| {
+            partitions
                 .iter()
-                .filter_map(|&prior_index| {
-                    let SIRInstruction::Store(address, offset, bits, source, _, _) =
-                        &block.instructions[prior_index]
-                    else {
-                        return None;
-                    };
-                    (register_dependencies[&store_index].contains(source)
-                        && store_source(store_index) != Some(*source)
-                        && unit.register_map[source].width() == *bits)
-                        .then(|| (*source, (*address, offset.clone(), *bits)))
-                })
-                .collect::<HashMap<_, _>>();
-            let mut prefix = HashSet::<usize>::default();
-            let mut pending = instruction_registers(&block.instructions[store_index]);
-            while let Some(register) = pending.pop() {
-                let Some(&definition) = definitions.get(&register) else {
-                    continue;
-                };
-                if !prefix.insert(definition) {
-                    continue;
-                }
-                if let Some((_, offset, _)) = reloads.get(&register) {
-                    offset_registers(offset, &mut pending);
-                    continue;
-                }
-                pending.extend(instruction_registers(&block.instructions[definition]));
-            }
-            let mut prefix = prefix.into_iter().collect::<Vec<_>>();
-            prefix.sort_unstable();
-            let mut instructions = prefix
-                .into_iter()
-                .map(|index| {
-                    let instruction = &block.instructions[index];
-                    if let Some(register) = instruction.defined_register()
-                        && let Some((address, offset, bits)) = reloads.get(&register)
-                    {
-                        return SIRInstruction::Load(register, *address, offset.clone(), *bits);
-                    }
-                    instruction.clone()
+                .enumerate()
+                .map(|(partition, compiled)| {
+                    append_native_code(
+                        image,
+                        entries,
+                        image_symbols,
+                        format!("{name}.stage[{stage}][{partition}]"),
+                        compiled,
+                    )
                 })
-                .collect::<Vec<_>>();
-            instructions.push(block.instructions[store_index].clone());
-            let split_block = celox_sir::BasicBlock {
-                id: BlockId(0),
-                params: Vec::new(),
-                instructions,
-                terminator: SIRTerminator::Return,
-            };
-            ExecutionUnit {
-                entry_block_id: BlockId(0),
-                blocks: [(BlockId(0), split_block)].into_iter().collect(),
-                register_map: unit.register_map.clone(),
-            }
+                .collect()
         })
-        .collect::<Vec<_>>();
-    let ordered_store_sites = ordered_stores
-        .iter()
-        .map(|&index| (unit.entry_block_id, index))
-        .collect::<Vec<_>>();
-    interleave_comb_runtime_effects(unit, &ordered_store_sites, split)
+        .collect()
 }
 
 fn compile_program(
@@ -1657,25 +1489,11 @@ fn compile_program(
         }
         Ok::<_, SimulatorError>((comb_jit, compiled_ff_codes))
     })?;
-    // A foreign-interface image can request per-unit entries so force/release
-    // can reapply overrides between procedural store boundaries. Ordinary
-    // images do not compile or retain this duplicate combinational code.
-    let force_store_boundaries = options.optimize_options.opt_level() == crate::OptLevel::O0;
-    let comb_runtime_units = if options.native_force_support {
-        sir.sir
-            .eval_comb
-            .iter()
-            .flat_map(|unit| {
-                if force_store_boundaries {
-                    split_comb_execution_unit(unit)
-                } else {
-                    vec![unit.clone()]
-                }
-            })
-            .collect::<Vec<_>>()
-    } else {
-        Vec::new()
-    };
+    // A foreign-interface or profiling image can request per-unit entries so
+    // force/release can reapply overrides between procedural store boundaries
+    // and the profiler can time each unit. Ordinary images do not compile or
+    // retain this duplicate combinational code.
+    let comb_runtime_units = crate::backend::comb_split::comb_entry_units(laid_out, options);
     let comb_unit_jits = comb_runtime_units
         .iter()
         .enumerate()
@@ -1692,6 +1510,17 @@ fn compile_program(
             )
         })
         .collect::<Result<Vec<_>, _>>()?;
+    // Only the x86 emitter can relocate a function's arena, which concurrent
+    // partitions need; other targets keep sequential evaluation.
+    let parallel_program = if options.threads > 1
+        && cfg!(any(
+            feature = "x86_64-codegen",
+            all(target_arch = "x86_64", not(feature = "arm64-codegen"))
+        )) {
+        compile_parallel_program(sir, layout, options, &compile_tasks)?
+    } else {
+        ParallelProgram::default()
+    };
     let codegen_trace = capture_trace
         .then(|| format_native_codegen_trace(&comb_jit, &compiled_ff_codes, &compile_tasks));
     let semantic_memory_size = layout
@@ -1709,6 +1538,11 @@ fn compile_program(
                 .iter()
                 .map(|compiled| compiled.required_state_size),
         )
+        .chain(
+            parallel_program
+                .functions()
+                .map(|compiled| compiled.required_state_size),
+        )
         .fold(semantic_memory_size, usize::max);
     let required_native_features = std::iter::once(comb_jit.required_native_features)
         .chain(
@@ -1721,6 +1555,11 @@ fn compile_program(
                 .iter()
                 .map(|compiled| compiled.required_native_features),
         )
+        .chain(
+            parallel_program
+                .functions()
+                .map(|compiled| compiled.required_native_features),
+        )
         .fold(0, |features, required| features | required);
     let mut packed_image = Vec::new();
     let mut code_entries = Vec::with_capacity(1 + compiled_ff_codes.len());
@@ -1742,6 +1581,32 @@ fn compile_program(
             compiled,
         )?);
     }
+    let parallel_comb_offsets = append_parallel_stages(
+        &mut packed_image,
+        &mut code_entries,
+        &mut image_symbols,
+        "eval_comb",
+        &parallel_program.comb,
+    )?;
+    let mut parallel_tasks = parallel_program
+        .apply_ffs
+        .keys()
+        .copied()
+        .collect::<Vec<_>>();
+    parallel_tasks.sort_unstable();
+    let mut parallel_task_offsets = HashMap::default();
+    for task_id in parallel_tasks {
+        parallel_task_offsets.insert(
+            task_id,
+            append_parallel_stages(
+                &mut packed_image,
+                &mut code_entries,
+                &mut image_symbols,
+                &format!("eval_apply_ff.task[{task_id}]"),
+                &parallel_program.apply_ffs[&task_id],
+            )?,
+        );
+    }
     let mut compiled_ff_keys = compiled_ff_codes.keys().copied().collect::<Vec<_>>();
     compiled_ff_keys.sort_unstable();
     let mut task_offsets = HashMap::default();
@@ -1837,6 +1702,19 @@ fn compile_program(
         &mut id_to_addr,
         &mut id_to_event,
     )?;
+    // An event's staged entries replace the sequential function it is bound
+    // to, so they must come from the same compile task.
+    let parallel_apply_ff_offsets = sir
+        .sir
+        .eval_apply_ffs
+        .keys()
+        .filter_map(|addr| {
+            let task_id = task_bindings[&("eval_apply_ff", *addr)];
+            let event = event_map.get(addr)?;
+            let stages = parallel_task_offsets.get(&task_id)?;
+            (event.func_offset == task_offsets[&task_id]).then(|| (event.id, stages.clone()))
+        })
+        .collect::<HashMap<_, _>>();
     compile_ff_group(
         &sir.sir.eval_only_ffs,
         "eval_only_ff",
@@ -1886,6 +1764,8 @@ fn compile_program(
             symbols: image_symbols,
             comb_offset,
             comb_unit_offsets,
+            parallel_comb_offsets,
+            parallel_apply_ff_offsets,
             required_native_features,
             event_map,
             eval_only_event_map,
@@ -1901,6 +1781,10 @@ fn compile_program(
                 comb_observers: sir.runtime().runtime_schema.comb_observers.clone(),
                 testbench_read_roots: sir.runtime().runtime_schema.testbench_read_roots.clone(),
                 rtl_writes: sir.runtime().runtime_schema.rtl_writes.clone(),
+                coverage_points: sir.runtime().runtime_schema.coverage_points.clone(),
+                fsm_registers: sir.runtime().runtime_schema.fsm_registers.clone(),
+                final_memory_dumps: sir.runtime().runtime_schema.final_memory_dumps.clone(),
+                registers: sir.runtime().runtime_schema.registers.clone(),
             },
             layout: layout.clone(),
             native_memory_size,
@@ -1909,6 +1793,7 @@ fn compile_program(
                 native_tick_loop: options.x86_options.native_tick_loop,
                 native_force_support: options.native_force_support,
                 perf_map: options.x86_options.diagnostics.perf_map,
+                threads: options.threads,
             },
             four_state_inits,
         },
@@ -1922,10 +1807,13 @@ fn compile_program(
 
 pub struct NativeBackend {
     compiled: Arc<SharedNativeCode>,
-    memory: Vec<u64>,
+    memory: NativeMemory,
     runtime_event_buffer: Arc<RuntimeEventBuffer>,
     comb_capture_enabled: Vec<u8>,
     execution_timing: Option<NativeExecutionTiming>,
+    /// Present when the image has staged entries and more than one thread
+    /// was requested.
+    pool: Option<WorkerPool>,
 }
 
 fn write_bits_to_memory_from(
@@ -2000,21 +1888,6 @@ fn write_initial_run_to_plane(
 }
 
 impl NativeBackend {
-    pub(crate) fn eval_comb_units_with(
-        &mut self,
-        mut after_unit: impl FnMut(&mut Self),
-    ) -> Result<(), SimulatorErrorCode> {
-        let funcs = self.compiled.comb_unit_funcs.clone();
-        if funcs.is_empty() {
-            return self.eval_comb();
-        }
-        for func in funcs {
-            self.call_func_timed(func)?;
-            after_unit(self);
-        }
-        Ok(())
-    }
-
     /// Compile a pointer-free native image without attaching it to executable
     /// memory. A precompiled runtime can load the result with
     /// [`SharedNativeCode::from_image`].
@@ -2098,17 +1971,68 @@ impl NativeBackend {
 
         let mut backend = Self {
             compiled: shared,
-            memory,
+            memory: NativeMemory::Owned(memory),
             runtime_event_buffer,
             comb_capture_enabled,
             execution_timing: None,
+            pool: None,
         };
+        backend.pool = backend.new_pool();
         backend.install_event_buffers();
         let compiled = Arc::clone(&backend.compiled);
         backend.apply_initial_values(&compiled.program_image.design.initial_state);
         backend
     }
 
+    /// Create an independent instance with a copy of this backend's memory.
+    /// Execution timing is not inherited.
+    pub fn fork(&self) -> Self {
+        let mut backend = Self {
+            compiled: Arc::clone(&self.compiled),
+            memory: NativeMemory::Owned(self.memory.to_vec()),
+            runtime_event_buffer: Arc::new(RuntimeEventBuffer::new(
+                self.compiled.layout.runtime_event_buffer_size,
+            )),
+            comb_capture_enabled: self.comb_capture_enabled.clone(),
+            execution_timing: None,
+            pool: None,
+        };
+        backend.pool = backend.new_pool();
+        backend.install_event_buffers();
+        backend
+    }
+
+    /// Like [`Self::fork`], but the copy lives in window `lane` of `arena`.
+    /// Batch lanes evaluate one after another, so they run without a pool.
+    pub(crate) fn fork_into_lane(&self, arena: &Arc<LaneArena>, lane: usize) -> Self {
+        let mut memory = arena.window(lane, self.memory.len());
+        memory.copy_from_slice(&self.memory);
+        let mut backend = Self {
+            compiled: Arc::clone(&self.compiled),
+            memory,
+            runtime_event_buffer: Arc::new(RuntimeEventBuffer::new(
+                self.compiled.layout.runtime_event_buffer_size,
+            )),
+            comb_capture_enabled: self.comb_capture_enabled.clone(),
+            execution_timing: None,
+            pool: None,
+        };
+        backend.install_event_buffers();
+        backend
+    }
+
+    /// Number of 64-bit words in this instance's state memory.
+    pub(crate) fn memory_words(&self) -> usize {
+        self.memory.len()
+    }
+
+    fn new_pool(&self) -> Option<WorkerPool> {
+        let threads = self.compiled.options.threads;
+        let staged =
+            !self.compiled.parallel_comb.is_empty() || !self.compiled.parallel_apply_ffs.is_empty();
+        (threads > 1 && staged).then(|| WorkerPool::new(threads))
+    }
+
     fn apply_initial_values(&mut self, initial_state: &[InitialStateValue<AbsoluteAddr>]) {
         for init in initial_state {
             let signal = self.resolve_signal(&init.address);
@@ -2324,6 +2248,35 @@ impl NativeBackend {
         result
     }
 
+    /// Run staged entries on the worker pool, timed like a single call.
+    fn call_stages_timed(&mut self, stages: &[NativeStage]) -> Result<(), SimulatorErrorCode> {
+        let pool = self
+            .pool
+            .as_ref()
+            .expect("staged entries are only selected with a worker pool");
+        let start = self.execution_timing.is_some().then(Instant::now);
+        let ret = pool.run(self.memory.as_mut_ptr() as *mut u8, stages);
+        if let (Some(start), Some(timing)) = (start, self.execution_timing.as_mut()) {
+            timing.elapsed = timing.elapsed.saturating_add(start.elapsed());
+            timing.calls = timing.calls.saturating_add(1);
+        }
+        match ret {
+            0 => Ok(()),
+            code if code > 0 => Err(SimulatorErrorCode::DetectedTrueLoopCode(code)),
+            _ => Err(SimulatorErrorCode::InternalError),
+        }
+    }
+
+    fn parallel_comb(&self) -> Option<Arc<SharedNativeCode>> {
+        (self.pool.is_some() && !self.compiled.parallel_comb.is_empty())
+            .then(|| Arc::clone(&self.compiled))
+    }
+
+    fn parallel_apply_ff(&self, event: &NativeEventRef) -> Option<Arc<SharedNativeCode>> {
+        (self.pool.is_some() && self.compiled.parallel_apply_ffs.contains_key(&event.id))
+            .then(|| Arc::clone(&self.compiled))
+    }
+
     fn call_func_many(
         memory: &mut [u64],
         func: NativeSimFunc,
@@ -2372,15 +2325,42 @@ impl super::super::SimBackend for NativeBackend {
     type Event = NativeEventRef;
 
     fn eval_comb(&mut self) -> Result<(), SimulatorErrorCode> {
+        if let Some(compiled) = self.parallel_comb() {
+            return self.call_stages_timed(&compiled.parallel_comb);
+        }
         let func = self.compiled.comb_func;
         self.call_func_timed(func)
     }
 
+    fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), SimulatorErrorCode> {
+        let funcs = self.compiled.comb_unit_funcs.clone();
+        if funcs.is_empty() {
+            self.eval_comb()?;
+            after_unit(self);
+            return Ok(());
+        }
+        for func in funcs {
+            self.call_func_timed(func)?;
+            after_unit(self);
+        }
+        Ok(())
+    }
+
     fn eval_apply_ff_at(&mut self, event: NativeEventRef) -> Result<(), SimulatorErrorCode> {
+        if let Some(compiled) = self.parallel_apply_ff(&event) {
+            return self.call_stages_timed(&compiled.parallel_apply_ffs[&event.id]);
+        }
         self.call_func_timed(event.func)
     }
 
     fn eval_comb_apply_ff_at(&mut self, event: NativeEventRef) -> Result<(), SimulatorErrorCode> {
+        if self.parallel_comb().is_some() || self.parallel_apply_ff(&event).is_some() {
+            self.eval_comb()?;
+            return self.eval_apply_ff_at(event);
+        }
         self.call_func_timed(event.comb_apply_func)
     }
 
@@ -2389,6 +2369,12 @@ impl super::super::SimBackend for NativeBackend {
         event: NativeEventRef,
         count: u64,
     ) -> (u64, Result<(), SimulatorErrorCode>) {
+        if self.parallel_comb().is_some() || self.parallel_apply_ff(&event).is_some() {
+            if count == 0 {
+                return (0, Ok(()));
+            }
+            return (1, self.eval_comb_apply_ff_at(event));
+        }
         if self.compiled.options.native_tick_loop {
             self.call_func_many_timed(event.comb_apply_func, count)
         } else if count == 0 {
@@ -2595,6 +2581,10 @@ impl super::super::SimBackend for NativeBackend {
         self.compiled.id_to_event.len()
     }
 
+    fn fork(&self) -> Self {
+        NativeBackend::fork(self)
+    }
+
     fn clear_triggered_bits(&mut self) {
         let offset = self.compiled.layout.triggered_bits_offset;
         let size = self.compiled.layout.triggered_bits_total_size;
@@ -0,0 +1,110 @@
+//! State memory shared by the lanes of a batch simulation.
+//!
+//! A batch allocates one contiguous arena and hands each lane a disjoint,
+//! cache-line aligned window of it. Compiled functions only see a state base
+//! pointer, so the same machine code evaluates every lane.
+
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+/// Words per cache line; lane windows start on a line boundary.
+const LINE_WORDS: usize = 8;
+
+/// One contiguous allocation holding the state of every lane.
+pub(crate) struct LaneArena {
+    base: NonNull<u64>,
+    words: usize,
+}
+
+// Safety: the arena is only reachable through `NativeMemory::Lane` windows,
+// which never overlap and are each owned by exactly one backend.
+unsafe impl Send for LaneArena {}
+unsafe impl Sync for LaneArena {}
+
+impl LaneArena {
+    /// Words between the starts of two neighbouring lanes.
+    pub(crate) fn lane_stride(lane_words: usize) -> usize {
+        lane_words.div_ceil(LINE_WORDS) * LINE_WORDS
+    }
+
+    /// Allocate `lanes` zeroed windows of at least `lane_words` words each.
+    pub(crate) fn new(lanes: usize, lane_words: usize) -> Arc<Self> {
+        let words = Self::lane_stride(lane_words) * lanes;
+        // Over-allocate one line so the first window can start on a line.
+        let storage = vec![0u64; words + LINE_WORDS].into_boxed_slice();
+        let len = storage.len();
+        let raw = Box::into_raw(storage) as *mut u64;
+        let base = NonNull::new(raw).expect("boxed slices are never null");
+        let arena = Arc::new(Self { base, words: len });
+        debug_assert!(arena.aligned_start() + words <= len);
+        arena
+    }
+
+    fn aligned_start(&self) -> usize {
+        let misalignment = (self.base.as_ptr() as usize / 8) % LINE_WORDS;
+        (LINE_WORDS - misalignment) % LINE_WORDS
+    }
+
+    /// Take the window of `lane` as the state memory of one backend.
+    pub(crate) fn window(self: &Arc<Self>, lane: usize, lane_words: usize) -> NativeMemory {
+        let start = self.aligned_start() + lane * Self::lane_stride(lane_words);
+        assert!(
+            start + lane_words <= self.words,
+            "lane {lane} is outside the batch arena"
+        );
+        NativeMemory::Lane {
+            arena: Arc::clone(self),
+            start,
+            len: lane_words,
+        }
+    }
+}
+
+impl Drop for LaneArena {
+    fn drop(&mut self) {
+        // Safety: `base` and `words` came from `Box::into_raw` in `new`.
+        unsafe {
+            drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                self.base.as_ptr(),
+                self.words,
+            )));
+        }
+    }
+}
+
+/// State memory of one native backend instance.
+pub(crate) enum NativeMemory {
+    Owned(Vec<u64>),
+    /// A window of a batch arena. Windows are created once per lane and are
+    /// not cloned, so no two backends alias the same words.
+    Lane {
+        arena: Arc<LaneArena>,
+        start: usize,
+        len: usize,
+    },
+}
+
+impl Deref for NativeMemory {
+    type Target = [u64];
+
+    fn deref(&self) -> &[u64] {
+        match self {
+            Self::Owned(words) => words,
+            Self::Lane { arena, start, len } => unsafe {
+                std::slice::from_raw_parts(arena.base.as_ptr().add(*start), *len)
+            },
+        }
+    }
+}
+
+impl DerefMut for NativeMemory {
+    fn deref_mut(&mut self) -> &mut [u64] {
+        match self {
+            Self::Owned(words) => words,
+            Self::Lane { arena, start, len } => unsafe {
+                std::slice::from_raw_parts_mut(arena.base.as_ptr().add(*start), *len)
+            },
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub mod backend;
 mod image_file;
+mod lanes;
+mod parallel;
 mod runtime_image;
 pub use backend::{
     NativeBackend, NativeCodeEntry, NativeExecutionTiming, NativeProgramImage, SharedNativeCode,
@@ -11,4 +13,5 @@ pub use backend::{
 pub use celox_backend_arm64::{jit_mem, scalar as emit};
 pub use celox_backend_x86::native::*;
 pub use image_file::{AppendedNativeImage, NativeImageArchitecture, NativeImageContainerError};
+pub(crate) use lanes::LaneArena;
 pub use runtime_image::{NativeProgramInstance, NativeProgramLoadError, NativeSignalIdentity};
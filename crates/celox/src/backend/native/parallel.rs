@@ -0,0 +1,362 @@
+//! Multi-threaded evaluation of independent execution units.
+//!
+//! [`plan_stages`] groups the units of one sequential program into stages.
+//! Units in one stage touch disjoint cache lines of the simulation state and
+//! have no runtime side effects in common, so they may run in any order or
+//! concurrently. Stages still run in order; [`WorkerPool`] executes one stage
+//! at a time and returns only after every unit of the stage has finished.
+
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+use crate::HashMap;
+use crate::ir::{
+    ExecutionUnit, RegionedAbsoluteAddr, SIRInstruction, SPARSE_WORKING_REGION, STABLE_REGION,
+};
+
+use super::super::MemoryLayout;
+use super::backend::NativeSimFunc;
+
+/// Conflict granularity. Distinct lines also keep concurrent units from
+/// false-sharing state.
+const CACHE_LINE_BYTES: usize = 64;
+
+/// Pseudo cache lines for state which has no per-variable address.
+const RUNTIME_EFFECT_LINE: usize = usize::MAX;
+const TRIGGERED_BITS_LINE: usize = usize::MAX - 1;
+const SPARSE_METADATA_LINE: usize = usize::MAX - 2;
+
+#[derive(Default)]
+struct Footprint {
+    reads: Vec<usize>,
+    writes: Vec<usize>,
+    cost: usize,
+}
+
+impl Footprint {
+    fn object_lines(
+        lines: &mut Vec<usize>,
+        layout: &MemoryLayout,
+        address: &RegionedAbsoluteAddr,
+    ) -> bool {
+        let absolute = address.absolute_addr();
+        let base = match address.region {
+            STABLE_REGION => layout.offsets.get(&absolute).copied(),
+            SPARSE_WORKING_REGION => layout
+                .sparse_offsets
+                .get(&absolute)
+                .map(|offset| layout.sparse_base_offset + offset),
+            _ => layout
+                .working_offsets
+                .get(&absolute)
+                .map(|offset| layout.working_base_offset + offset),
+        };
+        let (Some(base), true) = (base, layout.widths.contains_key(&absolute)) else {
+            return false;
+        };
+        let planes = if layout.four_state { 2 } else { 1 };
+        let end = base + layout.plane_size(&absolute).max(1) * planes;
+        lines.extend(base / CACHE_LINE_BYTES..end.div_ceil(CACHE_LINE_BYTES));
+        true
+    }
+
+    fn of_unit(unit: &ExecutionUnit<RegionedAbsoluteAddr>, layout: &MemoryLayout) -> Self {
+        let mut footprint = Self::default();
+        let mut unknown = false;
+        for block in unit.blocks.values() {
+            footprint.cost += block.instructions.len() + 1;
+            for instruction in &block.instructions {
+                match instruction {
+                    SIRInstruction::Load(_, address, _, _) => {
+                        unknown |= !Self::object_lines(&mut footprint.reads, layout, address);
+                        if address.region == SPARSE_WORKING_REGION {
+                            footprint.reads.push(SPARSE_METADATA_LINE);
+                        }
+                    }
+                    SIRInstruction::Store(address, _, _, _, triggers, capture_sites) => {
+                        unknown |= !Self::object_lines(&mut footprint.writes, layout, address);
+                        if address.region == SPARSE_WORKING_REGION {
+                            footprint.writes.push(SPARSE_METADATA_LINE);
+                        }
+                        if !triggers.is_empty() {
+                            footprint.writes.push(TRIGGERED_BITS_LINE);
+                        }
+                        if !capture_sites.is_empty() {
+                            footprint.writes.push(RUNTIME_EFFECT_LINE);
+                        }
+                    }
+                    SIRInstruction::Commit(source, destination, _, _, triggers) => {
+                        unknown |= !Self::object_lines(&mut footprint.reads, layout, source);
+                        unknown |= !Self::object_lines(&mut footprint.writes, layout, destination);
+                        if source.region == SPARSE_WORKING_REGION
+                            || destination.region == SPARSE_WORKING_REGION
+                        {
+                            footprint.writes.push(SPARSE_METADATA_LINE);
+                        }
+                        if !triggers.is_empty() {
+                            footprint.writes.push(TRIGGERED_BITS_LINE);
+                        }
+                    }
+                    SIRInstruction::RuntimeEvent { .. }
+                    | SIRInstruction::CombCaptureEvent { .. }
+                    | SIRInstruction::CombCaptureEnableIfChanged { .. } => {
+                        footprint.writes.push(RUNTIME_EFFECT_LINE);
+                    }
+                    SIRInstruction::Imm(..)
+                    | SIRInstruction::Binary(..)
+                    | SIRInstruction::Unary(..)
+                    | SIRInstruction::Concat(..)
+                    | SIRInstruction::Slice(..)
+                    | SIRInstruction::Mux(..) => {}
+                }
+            }
+        }
+        if unknown {
+            // An address without a physical placement cannot be proven
+            // disjoint from anything; the unit gets a stage of its own.
+            footprint.writes.push(RUNTIME_EFFECT_LINE);
+            footprint.reads.push(RUNTIME_EFFECT_LINE);
+        }
+        for lines in [&mut footprint.reads, &mut footprint.writes] {
+            lines.sort_unstable();
+            lines.dedup();
+        }
+        footprint
+    }
+}
+
+/// Group `units`, given in sequential execution order, into stages of at most
+/// `threads` partitions each.
+///
+/// Every unit is placed one level after the latest earlier unit it conflicts
+/// with (read-after-write, write-after-read or write-after-write on a cache
+/// line), so running the levels in order and the units of a level in any order
+/// is equivalent to the sequential order. Consecutive levels with a single
+/// partition are fused into one stage. The result is `None` when no stage has
+/// two partitions, in which case the ordinary sequential function is as fast.
+pub(crate) fn plan_stages(
+    units: &[&ExecutionUnit<RegionedAbsoluteAddr>],
+    layout: &MemoryLayout,
+    threads: usize,
+) -> Option<Vec<Vec<Vec<usize>>>> {
+    if threads < 2 || units.len() < 2 {
+        return None;
+    }
+    let footprints = units
+        .iter()
+        .map(|unit| Footprint::of_unit(unit, layout))
+        .collect::<Vec<_>>();
+    let mut last_write = HashMap::<usize, usize>::default();
+    let mut last_read = HashMap::<usize, usize>::default();
+    let mut levels: Vec<Vec<usize>> = Vec::new();
+    for (index, footprint) in footprints.iter().enumerate() {
+        let after_writes = footprint
+            .reads
+            .iter()
+            .chain(&footprint.writes)
+            .filter_map(|line| last_write.get(line));
+        let after_reads = footprint
+            .writes
+            .iter()
+            .filter_map(|line| last_read.get(line));
+        let level = after_writes
+            .chain(after_reads)
+            .map(|level| level + 1)
+            .max()
+            .unwrap_or(0);
+        for &line in &footprint.reads {
+            let entry = last_read.entry(line).or_default();
+            *entry = (*entry).max(level);
+        }
+        for &line in &footprint.writes {
+            let entry = last_write.entry(line).or_default();
+            *entry = (*entry).max(level);
+        }
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+        levels[level].push(index);
+    }
+
+    let mut stages: Vec<Vec<Vec<usize>>> = Vec::new();
+    for level in levels {
+        let partitions = balance(&level, &footprints, threads);
+        match (stages.last_mut(), partitions.len()) {
+            (Some(previous), 1) if previous.len() == 1 => {
+                previous[0].extend(partitions.into_iter().flatten());
+            }
+            _ => stages.push(partitions),
+        }
+    }
+    stages.iter().any(|stage| stage.len() > 1).then_some(stages)
+}
+
+/// Longest-processing-time assignment of one level's units to partitions.
+fn balance(level: &[usize], footprints: &[Footprint], threads: usize) -> Vec<Vec<usize>> {
+    let mut by_cost = level.to_vec();
+    by_cost.sort_by_key(|&unit| std::cmp::Reverse(footprints[unit].cost));
+    let mut partitions = vec![(0usize, Vec::new()); threads.min(level.len())];
+    for unit in by_cost {
+        let lightest = partitions
+            .iter_mut()
+            .min_by_key(|(cost, _)| *cost)
+            .expect("a level has at least one unit");
+        lightest.0 += footprints[unit].cost;
+        lightest.1.push(unit);
+    }
+    partitions
+        .into_iter()
+        .map(|(_, mut units)| {
+            units.sort_unstable();
+            units
+        })
+        .collect()
+}
+
+/// One stage's entry points, executed concurrently on one state buffer.
+pub(crate) type NativeStage = Arc<[NativeSimFunc]>;
+
+struct StageJob {
+    memory: usize,
+    funcs: NativeStage,
+    next: AtomicUsize,
+    pending: AtomicUsize,
+    result: AtomicI64,
+}
+
+impl StageJob {
+    /// Claim and run entries until none are left.
+    fn work(&self) {
+        loop {
+            let index = self.next.fetch_add(1, Ordering::Relaxed);
+            let Some(&func) = self.funcs.get(index) else {
+                break;
+            };
+            // Safety: `memory` points at a live state buffer sized for every
+            // entry of the stage, and the stage's entries were planned to
+            // touch disjoint state and were emitted with disjoint arenas.
+            let code = unsafe { func(self.memory as *mut u8) };
+            if code != 0 {
+                let _ = self
+                    .result
+                    .compare_exchange(0, code, Ordering::Relaxed, Ordering::Relaxed);
+            }
+            self.pending.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+struct PoolShared {
+    job: Mutex<Option<Arc<StageJob>>>,
+    generation: AtomicU64,
+    shutdown: AtomicBool,
+    wake_lock: Mutex<()>,
+    wake: Condvar,
+}
+
+/// Persistent helper threads; the calling thread takes part in every stage.
+pub(crate) struct WorkerPool {
+    shared: Arc<PoolShared>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+/// Spin iterations before an idle worker blocks on the condition variable.
+const IDLE_SPINS: usize = 1 << 14;
+
+impl WorkerPool {
+    pub(crate) fn new(threads: usize) -> Self {
+        let shared = Arc::new(PoolShared {
+            job: Mutex::new(None),
+            generation: AtomicU64::new(0),
+            shutdown: AtomicBool::new(false),
+            wake_lock: Mutex::new(()),
+            wake: Condvar::new(),
+        });
+        let workers = (1..threads)
+            .map(|index| {
+                let shared = Arc::clone(&shared);
+                std::thread::Builder::new()
+                    .name(format!("celox-worker-{index}"))
+                    .spawn(move || worker_loop(&shared))
+                    .expect("failed to spawn a simulation worker thread")
+            })
+            .collect();
+        Self { shared, workers }
+    }
+
+    /// Run `stages` in order on `memory` and return the first non-zero entry
+    /// result, which stops the remaining stages.
+    pub(crate) fn run(&self, memory: *mut u8, stages: &[NativeStage]) -> i64 {
+        for funcs in stages {
+            let code = if funcs.len() == 1 {
+                // Safety: as in `StageJob::work`.
+                unsafe { funcs[0](memory) }
+            } else {
+                self.run_stage(memory, funcs)
+            };
+            if code != 0 {
+                return code;
+            }
+        }
+        0
+    }
+
+    fn run_stage(&self, memory: *mut u8, funcs: &NativeStage) -> i64 {
+        let job = Arc::new(StageJob {
+            memory: memory as usize,
+            funcs: Arc::clone(funcs),
+            next: AtomicUsize::new(0),
+            pending: AtomicUsize::new(funcs.len()),
+            result: AtomicI64::new(0),
+        });
+        *self.shared.job.lock().expect("worker pool poisoned") = Some(Arc::clone(&job));
+        self.shared.generation.fetch_add(1, Ordering::Release);
+        drop(self.shared.wake_lock.lock().expect("worker pool poisoned"));
+        self.shared.wake.notify_all();
+        job.work();
+        while job.pending.load(Ordering::Acquire) != 0 {
+            std::hint::spin_loop();
+        }
+        job.result.load(Ordering::Relaxed)
+    }
+}
+
+fn worker_loop(shared: &PoolShared) {
+    let mut seen = 0;
+    loop {
+        let mut spins = 0;
+        while shared.generation.load(Ordering::Acquire) == seen && spins < IDLE_SPINS {
+            std::hint::spin_loop();
+            spins += 1;
+        }
+        {
+            let mut guard = shared.wake_lock.lock().expect("worker pool poisoned");
+            while shared.generation.load(Ordering::Acquire) == seen
+                && !shared.shutdown.load(Ordering::Acquire)
+            {
+                guard = shared.wake.wait(guard).expect("worker pool poisoned");
+            }
+        }
+        if shared.shutdown.load(Ordering::Acquire) {
+            return;
+        }
+        seen = shared.generation.load(Ordering::Acquire);
+        let job = shared.job.lock().expect("worker pool poisoned").clone();
+        if let Some(job) = job {
+            job.work();
+        }
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::Release);
+        self.shared.generation.fetch_add(1, Ordering::Release);
+        drop(self.shared.wake_lock.lock().expect("worker pool poisoned"));
+        self.shared.wake.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
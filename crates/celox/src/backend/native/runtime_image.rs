@@ -422,7 +422,7 @@ impl NativeProgramInstance {
                 .set_four_state(*signal, value.clone(), mask.clone());
         }
         self.backend
-            .eval_comb_units_with(|backend| {
+            .eval_comb_units_with(&mut |backend| {
                 for (signal, value, mask) in &overrides {
                     backend.set_four_state(*signal, value.clone(), mask.clone());
                 }
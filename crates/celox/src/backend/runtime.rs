@@ -56,6 +56,8 @@ impl super::EventHandle for EventRef {
 pub struct SharedJitCode {
     _engine: JitEngine,
     pub(crate) comb_func: SimFunc,
+    /// Per-store combinational entry points, compiled only with force support.
+    comb_unit_funcs: Vec<SimFunc>,
     pub(crate) event_map: HashMap<AbsoluteAddr, EventRef>,
     pub(crate) eval_only_event_map: HashMap<AbsoluteAddr, EventRef>,
     pub(crate) apply_event_map: HashMap<AbsoluteAddr, EventRef>,
@@ -378,6 +380,18 @@ impl JitBackend {
 
         let comb_code_ptr = res.map_err(SimulatorError::from)?;
 
+        // Force support reapplies overrides between procedural store
+        // boundaries and the profiler times each unit, so both need one entry
+        // point per unit.
+        let mut comb_unit_funcs = Vec::new();
+        for unit in crate::backend::comb_split::comb_entry_units(sir, &options) {
+            let ptr = engine
+                .compile_units(std::slice::from_ref(&unit), None, None, None)
+                .map_err(SimulatorError::from)?;
+            let func: SimFunc = unsafe { std::mem::transmute(ptr) };
+            comb_unit_funcs.push(func);
+        }
+
         let mut next_id = 0;
         let mut addr_to_id = HashMap::default();
         let mut id_to_addr = Vec::new();
@@ -570,6 +584,7 @@ impl JitBackend {
         Ok(SharedJitCode {
             _engine: engine,
             comb_func,
+            comb_unit_funcs,
             event_map,
             eval_only_event_map,
             apply_event_map,
@@ -638,6 +653,21 @@ impl JitBackend {
         }
     }
 
+    /// Create an independent instance with a copy of this backend's memory.
+    pub fn fork(&self) -> Self {
+        let mut backend = Self {
+            shared: Arc::clone(&self.shared),
+            memory: self.memory.clone(),
+            runtime_event_buffer: Arc::new(RuntimeEventBuffer::new(
+                self.shared.layout.runtime_event_buffer_size,
+            )),
+            comb_capture_enabled: self.comb_capture_enabled.clone(),
+            comb_func: self.comb_func,
+        };
+        backend.install_event_buffers();
+        backend
+    }
+
     /// Returns the shared compiled code, allowing it to be reused for
     /// creating additional simulator instances without recompilation.
     pub fn shared_code(&self) -> Arc<SharedJitCode> {
@@ -1014,6 +1044,23 @@ impl super::SimBackend for JitBackend {
         self.eval_comb()
     }
 
+    fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), SimulatorErrorCode> {
+        if self.shared.comb_unit_funcs.is_empty() {
+            self.eval_comb()?;
+            after_unit(self);
+            return Ok(());
+        }
+        let shared = Arc::clone(&self.shared);
+        for &func in &shared.comb_unit_funcs {
+            self.run_sim_func(func)?;
+            after_unit(self);
+        }
+        Ok(())
+    }
+
     fn eval_apply_ff_at(&mut self, event: EventRef) -> Result<(), SimulatorErrorCode> {
         self.eval_apply_ff_at(event)
     }
@@ -1114,6 +1161,10 @@ impl super::SimBackend for JitBackend {
         self.num_events()
     }
 
+    fn fork(&self) -> Self {
+        JitBackend::fork(self)
+    }
+
     fn clear_triggered_bits(&mut self) {
         self.clear_triggered_bits()
     }
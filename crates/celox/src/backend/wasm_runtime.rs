@@ -4,6 +4,8 @@
 //! executes the generated modules with Wasmtime. Provides the same interface
 //! as [`JitBackend`] so that [`Simulator`] can use either backend.
 
+use std::sync::Arc;
+
 use num_bigint::BigUint;
 use wasmtime::{Engine, Linker, Memory, Module, Store, TypedFunc};
 
@@ -58,6 +60,12 @@ impl super::traits::SimBackend for WasmBackend {
     fn eval_comb(&mut self) -> Result<(), super::SimulatorErrorCode> {
         WasmBackend::eval_comb(self)
     }
+    fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), super::SimulatorErrorCode> {
+        WasmBackend::eval_comb_units_with(self, after_unit)
+    }
     fn eval_apply_ff_at(&mut self, event: WasmEventRef) -> Result<(), super::SimulatorErrorCode> {
         WasmBackend::eval_apply_ff_at(self, &event)
     }
@@ -127,6 +135,9 @@ impl super::traits::SimBackend for WasmBackend {
     fn num_events(&self) -> usize {
         WasmBackend::num_events(self)
     }
+    fn fork(&self) -> Self {
+        WasmBackend::fork(self).expect("re-instantiating already validated wasm modules")
+    }
     fn clear_triggered_bits(&mut self) {
         WasmBackend::clear_triggered_bits(self)
     }
@@ -139,10 +150,84 @@ impl super::traits::SimBackend for WasmBackend {
 }
 
 /// The runtime WASM backend.
+/// Compiled modules shared by every instance forked from one backend.
+struct WasmModules {
+    engine: Engine,
+    comb: Module,
+    /// Per-store combinational modules, compiled only with force support.
+    comb_units: Vec<Module>,
+    event: Vec<(AbsoluteAddr, Module)>,
+    eval_only: Vec<(AbsoluteAddr, Module)>,
+    apply: Vec<(AbsoluteAddr, Module)>,
+}
+
+type WasmFuncMap = HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>>;
+
+struct WasmFuncs {
+    comb: TypedFunc<(), i64>,
+    comb_units: Vec<TypedFunc<(), i64>>,
+    event: WasmFuncMap,
+    eval_only: WasmFuncMap,
+    apply: WasmFuncMap,
+}
+
+impl WasmModules {
+    /// Instantiate every module against `memory` inside `store`.
+    fn instantiate(
+        &self,
+        store: &mut Store<()>,
+        memory: &Memory,
+    ) -> Result<WasmFuncs, crate::SimulatorError> {
+        let instantiate_all = |store: &mut Store<()>,
+                               modules: &[(AbsoluteAddr, Module)]|
+         -> Result<WasmFuncMap, crate::SimulatorError> {
+            let mut funcs = WasmFuncMap::default();
+            for (addr, module) in modules {
+                let func = instantiate_module(&self.engine, store, module, memory)?;
+                funcs.entry(*addr).or_default().push(func);
+            }
+            Ok(funcs)
+        };
+        Ok(WasmFuncs {
+            comb: instantiate_module(&self.engine, store, &self.comb, memory)?,
+            comb_units: self
+                .comb_units
+                .iter()
+                .map(|module| instantiate_module(&self.engine, store, module, memory))
+                .collect::<Result<_, _>>()?,
+            event: instantiate_all(store, &self.event)?,
+            eval_only: instantiate_all(store, &self.eval_only)?,
+            apply: instantiate_all(store, &self.apply)?,
+        })
+    }
+}
+
+/// Instantiate a WASM module with the shared memory.
+fn instantiate_module(
+    engine: &Engine,
+    store: &mut Store<()>,
+    module: &Module,
+    memory: &Memory,
+) -> Result<TypedFunc<(), i64>, crate::SimulatorError> {
+    let mut linker = Linker::new(engine);
+    linker
+        .define(&mut *store, "env", "memory", *memory)
+        .map_err(|source| wasm_codegen_error("linking", source))?;
+    let instance = linker
+        .instantiate(&mut *store, module)
+        .map_err(|source| wasm_codegen_error("instantiation", source))?;
+    let func = instance
+        .get_typed_func::<(), i64>(&mut *store, "run")
+        .map_err(|source| wasm_codegen_error("entry-point lookup", source))?;
+    Ok(func)
+}
+
 pub struct WasmBackend {
+    modules: Arc<WasmModules>,
     store: Store<()>,
     memory: Memory,
     comb_func: TypedFunc<(), i64>,
+    comb_unit_funcs: Vec<TypedFunc<(), i64>>,
     event_funcs: HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>>,
     eval_only_funcs: HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>>,
     apply_funcs: HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>>,
@@ -174,6 +259,23 @@ impl WasmBackend {
         let comb_module = Module::new(&engine, &comb_wasm.bytes)
             .map_err(|source| wasm_codegen_error("eval_comb compilation", source))?;
 
+        // Force support reapplies overrides between procedural store
+        // boundaries and the profiler times each unit, so both need one
+        // module per unit.
+        let mut comb_unit_modules = Vec::new();
+        for unit in crate::backend::comb_split::comb_entry_units(sir, options) {
+            let wasm = wasm_codegen::compile_units(
+                std::slice::from_ref(&unit),
+                &layout,
+                options.four_state,
+                options.emit_triggers,
+            );
+            comb_unit_modules.push(
+                Module::new(&engine, &wasm.bytes)
+                    .map_err(|source| wasm_codegen_error("eval_comb unit compilation", source))?,
+            );
+        }
+
         // Compile event functions
         let mut event_modules = Vec::new();
         let mut eval_only_modules = Vec::new();
@@ -328,55 +430,28 @@ impl WasmBackend {
             }
         }
 
-        // Helper: instantiate a WASM module with the shared memory.
-        fn instantiate_module(
-            engine: &Engine,
-            store: &mut Store<()>,
-            module: &Module,
-            memory: &Memory,
-        ) -> Result<TypedFunc<(), i64>, crate::SimulatorError> {
-            let mut linker = Linker::new(engine);
-            linker
-                .define(&mut *store, "env", "memory", *memory)
-                .map_err(|source| wasm_codegen_error("linking", source))?;
-            let instance = linker
-                .instantiate(&mut *store, module)
-                .map_err(|source| wasm_codegen_error("instantiation", source))?;
-            let func = instance
-                .get_typed_func::<(), i64>(&mut *store, "run")
-                .map_err(|source| wasm_codegen_error("entry-point lookup", source))?;
-            Ok(func)
-        }
-
-        let comb_func = instantiate_module(&engine, &mut store, &comb_module, &memory)?;
-
-        let mut event_funcs: HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>> = HashMap::default();
-        for (addr, module) in &event_modules {
-            let func = instantiate_module(&engine, &mut store, module, &memory)?;
-            event_funcs.entry(*addr).or_default().push(func);
-        }
-        let mut eval_only_funcs: HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>> =
-            HashMap::default();
-        for (addr, module) in &eval_only_modules {
-            let func = instantiate_module(&engine, &mut store, module, &memory)?;
-            eval_only_funcs.entry(*addr).or_default().push(func);
-        }
-        let mut apply_funcs: HashMap<AbsoluteAddr, Vec<TypedFunc<(), i64>>> = HashMap::default();
-        for (addr, module) in &apply_modules {
-            let func = instantiate_module(&engine, &mut store, module, &memory)?;
-            apply_funcs.entry(*addr).or_default().push(func);
-        }
+        let modules = Arc::new(WasmModules {
+            engine,
+            comb: comb_module,
+            comb_units: comb_unit_modules,
+            event: event_modules,
+            eval_only: eval_only_modules,
+            apply: apply_modules,
+        });
+        let funcs = modules.instantiate(&mut store, &memory)?;
 
         let id_to_event: Vec<WasmEventRef> =
             id_to_addr.iter().map(|addr| event_map[addr]).collect();
 
         Ok(Self {
+            modules,
             store,
             memory,
-            comb_func,
-            event_funcs,
-            eval_only_funcs,
-            apply_funcs,
+            comb_func: funcs.comb,
+            comb_unit_funcs: funcs.comb_units,
+            event_funcs: funcs.event,
+            eval_only_funcs: funcs.eval_only,
+            apply_funcs: funcs.apply,
             event_map,
             eval_only_event_map,
             apply_event_map,
@@ -387,6 +462,34 @@ impl WasmBackend {
         })
     }
 
+    /// Create an independent instance that reuses the compiled modules and
+    /// starts from a copy of this backend's linear memory.
+    pub fn fork(&self) -> Result<Self, crate::SimulatorError> {
+        let mut store = Store::new(&self.modules.engine, ());
+        let memory = Memory::new(&mut store, self.memory.ty(&self.store))
+            .map_err(|source| wasm_codegen_error("memory creation", source))?;
+        let source = self.memory.data(&self.store);
+        memory.data_mut(&mut store)[..source.len()].copy_from_slice(source);
+        let funcs = self.modules.instantiate(&mut store, &memory)?;
+        Ok(Self {
+            modules: Arc::clone(&self.modules),
+            store,
+            memory,
+            comb_func: funcs.comb,
+            comb_unit_funcs: funcs.comb_units,
+            event_funcs: funcs.event,
+            eval_only_funcs: funcs.eval_only,
+            apply_funcs: funcs.apply,
+            event_map: self.event_map.clone(),
+            eval_only_event_map: self.eval_only_event_map.clone(),
+            apply_event_map: self.apply_event_map.clone(),
+            id_to_addr: self.id_to_addr.clone(),
+            id_to_event: self.id_to_event.clone(),
+            layout: self.layout.clone(),
+            options: self.options.clone(),
+        })
+    }
+
     fn run_func(&mut self, func: &TypedFunc<(), i64>) -> Result<(), SimulatorErrorCode> {
         let res = func.call(&mut self.store, ()).unwrap_or(2);
         match res {
@@ -402,6 +505,24 @@ impl WasmBackend {
         self.run_func(&func)
     }
 
+    /// Evaluate combinational logic, calling `after_unit` after each
+    /// per-store module when the backend was built with force support.
+    pub fn eval_comb_units_with(
+        &mut self,
+        after_unit: &mut dyn FnMut(&mut Self),
+    ) -> Result<(), SimulatorErrorCode> {
+        if self.comb_unit_funcs.is_empty() {
+            self.eval_comb()?;
+            after_unit(self);
+            return Ok(());
+        }
+        for func in self.comb_unit_funcs.clone() {
+            self.run_func(&func)?;
+            after_unit(self);
+        }
+        Ok(())
+    }
+
     pub fn eval_apply_ff_at(&mut self, event: &WasmEventRef) -> Result<(), SimulatorErrorCode> {
         if let Some(funcs) = self.event_funcs.get(&event.addr).cloned() {
             for func in funcs {
@@ -0,0 +1,131 @@
+//! Lane-parallel simulation of independent stimulus sets.
+//!
+//! A [`BatchSimulator`] holds `N` lanes of one compiled design. The state of
+//! every lane lives in a single contiguous allocation, one cache-line aligned
+//! window per lane, and all lanes run the same machine code with a different
+//! state base pointer. Each lane keeps its own inputs, dirty tracking, forces,
+//! and watchers, so lanes can be driven and clocked independently; the batch
+//! calls evaluate every lane in turn.
+
+use crate::backend::native::{LaneArena, NativeBackend};
+use crate::{ForkError, RuntimeErrorCode, SignalRef, Simulator};
+use num_bigint::BigUint;
+
+/// A runtime error raised while evaluating one lane of a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaneError {
+    pub lane: usize,
+    pub error: RuntimeErrorCode,
+}
+
+impl std::fmt::Display for LaneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "lane {}: {:?}", self.lane, self.error)
+    }
+}
+
+impl std::error::Error for LaneError {}
+
+/// `N` copies of one simulator whose state is laid out lane by lane in a
+/// single allocation.
+pub struct BatchSimulator {
+    lanes: Vec<Simulator<NativeBackend>>,
+}
+
+impl BatchSimulator {
+    /// Copy the current state of `sim` into `lanes` lanes.
+    pub fn from_simulator(sim: &Simulator<NativeBackend>, lanes: usize) -> Result<Self, ForkError> {
+        let arena = LaneArena::new(lanes, sim.memory_words());
+        let lanes = (0..lanes)
+            .map(|lane| sim.fork_into_lane(&arena, lane))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { lanes })
+    }
+
+    /// Number of lanes.
+    pub fn lanes(&self) -> usize {
+        self.lanes.len()
+    }
+
+    /// Words between the state of two neighbouring lanes.
+    pub fn lane_stride(&self) -> usize {
+        self.lanes
+            .first()
+            .map_or(0, |lane| LaneArena::lane_stride(lane.memory_words()))
+    }
+
+    /// The simulator of one lane, for anything not covered by the batch API.
+    pub fn lane(&self, lane: usize) -> &Simulator<NativeBackend> {
+        &self.lanes[lane]
+    }
+
+    pub fn lane_mut(&mut self, lane: usize) -> &mut Simulator<NativeBackend> {
+        &mut self.lanes[lane]
+    }
+
+    pub fn signal(&self, path: &str) -> SignalRef {
+        self.lanes[0].signal(path)
+    }
+
+    pub fn event(&self, port: &str) -> crate::NativeEventRef {
+        self.lanes[0].event(port)
+    }
+
+    /// Sets a signal value in one lane.
+    pub fn set<T: Copy>(&mut self, lane: usize, signal: SignalRef, val: T) {
+        self.lanes[lane].set(signal, val);
+    }
+
+    pub fn set_wide(&mut self, lane: usize, signal: SignalRef, val: BigUint) {
+        self.lanes[lane].set_wide(signal, val);
+    }
+
+    /// Reads a signal value from one lane, evaluating its combinational logic
+    /// first if its inputs changed.
+    pub fn get(&mut self, lane: usize, signal: SignalRef) -> BigUint {
+        self.lanes[lane].get(signal)
+    }
+
+    pub fn get_as<T: Default + Copy>(&mut self, lane: usize, signal: SignalRef) -> T {
+        self.lanes[lane].get_as(signal)
+    }
+
+    /// Evaluates combinational logic in every lane.
+    pub fn eval_comb(&mut self) -> Result<(), LaneError> {
+        self.each_lane(|sim| sim.eval_comb())
+    }
+
+    /// Ticks `event` in every lane.
+    pub fn tick(&mut self, event: crate::NativeEventRef) -> Result<(), LaneError> {
+        self.each_lane(|sim| sim.tick(event))
+    }
+
+    /// Ticks `event` in the lanes whose entry in `mask` is `true`; the other
+    /// lanes keep their state. Lanes past the end of `mask` are not ticked.
+    pub fn tick_lanes(
+        &mut self,
+        event: crate::NativeEventRef,
+        mask: &[bool],
+    ) -> Result<(), LaneError> {
+        for (lane, (sim, _)) in self
+            .lanes
+            .iter_mut()
+            .zip(mask)
+            .enumerate()
+            .filter(|(_, (_, active))| **active)
+        {
+            sim.tick(event).map_err(|error| LaneError { lane, error })?;
+        }
+        Ok(())
+    }
+
+    fn each_lane(
+        &mut self,
+        mut f: impl FnMut(&mut Simulator<NativeBackend>) -> Result<(), RuntimeErrorCode>,
+    ) -> Result<(), LaneError> {
+        for (lane, sim) in self.lanes.iter_mut().enumerate() {
+            f(sim).map_err(|error| LaneError { lane, error })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! Versioned simulator checkpoints.
+//!
+//! A checkpoint captures the stable and working state regions, the
+//! combinational-observer history, and (for [`crate::Simulation`]) the
+//! scheduler queue and current time. It is tied to the memory layout and the
+//! elaborated design it was taken from, so it can only be restored into a
+//! simulator built from the same design with the same layout options.
+//!
+//! Injected component state is owned by user handlers and is not captured.
+
+use std::path::Path;
+
+use celox_runtime::SimulationStateSnapshot;
+use serde::{Deserialize, Serialize};
+
+const CHECKPOINT_MAGIC: &[u8; 8] = b"CELOXCKP";
+const CHECKPOINT_VERSION: u16 = 3;
+const HEADER_SIZE: usize = 8 + 2 + 8;
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Failure while encoding, decoding, or restoring a checkpoint.
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("checkpoint I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to encode or decode checkpoint payload: {0}")]
+    Postcard(#[from] postcard::Error),
+    #[error("checkpoint header is missing")]
+    MissingHeader,
+    #[error("checkpoint version {0} is unsupported")]
+    UnsupportedVersion(u16),
+    #[error("checkpoint payload checksum does not match")]
+    ChecksumMismatch,
+    #[error(
+        "checkpoint was taken with memory layout {found:#018x}, but this simulator uses {expected:#018x}"
+    )]
+    LayoutMismatch { expected: u64, found: u64 },
+    #[error(
+        "checkpoint was taken from design {found:#018x}, but this simulator runs {expected:#018x}"
+    )]
+    DesignMismatch { expected: u64, found: u64 },
+    #[error("checkpoint state region has {found} bytes, expected {expected}")]
+    StateSizeMismatch { expected: usize, found: usize },
+    #[error("checkpoint was taken from a Simulator and has no scheduler state")]
+    MissingSimulationState,
+    #[error("checkpoint scheduler state is invalid: {0}")]
+    InvalidSimulationState(String),
+}
+
+/// Snapshot of a running simulator that can be restored in-process or written
+/// to disk and restored by a later run of the same design.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatorCheckpoint {
+    pub(crate) layout_fingerprint: u64,
+    pub(crate) design_fingerprint: u64,
+    /// Bytes from the end of the state header up to the triggered bits.
+    pub(crate) state: Vec<u8>,
+    /// Little-endian `(value, mask)` pairs per combinational observer.
+    pub(crate) comb_observers: Vec<Vec<(Vec<u8>, Vec<u8>)>>,
+    pub(crate) comb_observer_initial_eval: bool,
+    pub(crate) dirty: bool,
+    pub(crate) simulation: Option<SimulationStateSnapshot>,
+}
+
+impl SimulatorCheckpoint {
+    /// Simulation time at which the checkpoint was taken, if it came from a
+    /// [`crate::Simulation`].
+    pub fn time(&self) -> Option<u64> {
+        self.simulation.as_ref().map(|simulation| simulation.time)
+    }
+
+    /// Serialize this checkpoint as a versioned binary container.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CheckpointError> {
+        let payload = postcard::to_allocvec(self)?;
+        let mut output = Vec::with_capacity(HEADER_SIZE + payload.len());
+        output.extend_from_slice(CHECKPOINT_MAGIC);
+        output.extend_from_slice(&CHECKPOINT_VERSION.to_le_bytes());
+        output.extend_from_slice(&checksum(&payload).to_le_bytes());
+        output.extend_from_slice(&payload);
+        Ok(output)
+    }
+
+    /// Decode a container produced by [`Self::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != CHECKPOINT_MAGIC {
+            return Err(CheckpointError::MissingHeader);
+        }
+        let version = u16::from_le_bytes(bytes[8..10].try_into().unwrap());
+        if version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(version));
+        }
+        let expected_checksum = u64::from_le_bytes(bytes[10..18].try_into().unwrap());
+        let payload = &bytes[HEADER_SIZE..];
+        if checksum(payload) != expected_checksum {
+            return Err(CheckpointError::ChecksumMismatch);
+        }
+        Ok(postcard::from_bytes(payload)?)
+    }
+
+    /// Write this checkpoint to `path`.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Read a checkpoint previously written with [`Self::write`].
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
@@ -38,6 +38,7 @@ pub fn register_static_component_manifest(name: &str, json: &str) {
     loader::register_static_manifest(name, json);
 }
 
+#[derive(Clone)]
 struct LiveInput {
     port: u32,
     expr: CompiledExpr,
@@ -45,6 +46,7 @@ struct LiveInput {
     width: usize,
 }
 
+#[derive(Clone)]
 struct LiveOutput {
     port: u32,
     name: String,
@@ -52,6 +54,7 @@ struct LiveOutput {
     rtl_driven: bool,
 }
 
+#[derive(Clone)]
 struct LiveEvent {
     event_id: usize,
     port: u32,
@@ -80,6 +83,37 @@ impl ComponentRuntime {
     pub(crate) fn set_injected(&mut self, injected: InjectedComponents) {
         self.injected = injected;
     }
+
+    /// Duplicate every live instance for a forked simulator. Fails with the
+    /// name of the first instance whose state cannot be copied.
+    pub(crate) fn try_fork(&self) -> Result<Self, String> {
+        let components = self
+            .components
+            .iter()
+            .map(|component| {
+                let (Some(instance), Some(host)) =
+                    (component.instance.try_fork(), component.host.try_fork())
+                else {
+                    return Err(component.name.clone());
+                };
+                Ok(LiveComponent {
+                    name: component.name.clone(),
+                    instance,
+                    host,
+                    inputs: component.inputs.clone(),
+                    outputs: component.outputs.clone(),
+                    events: component.events.clone(),
+                    fire_count: component.fire_count,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            components,
+            last_trace_values: self.last_trace_values.clone(),
+            active_reset_event: self.active_reset_event,
+            injected: self.injected.clone(),
+        })
+    }
 }
 
 pub(crate) struct ComponentWrite {
@@ -36,6 +36,7 @@ pub enum PortRole {
     Reset,
 }
 
+#[derive(Clone)]
 struct HostPort {
     name: String,
     dir: PortDir,
@@ -51,6 +52,7 @@ struct HostPort {
 }
 
 /// A component-internal signal registered for waveform dumping.
+#[derive(Clone)]
 pub struct HostTraceVar {
     pub name: String,
     pub width: u32,
@@ -145,6 +147,36 @@ impl HostContext {
         Self::default()
     }
 
+    /// Copy the staging buffers and bookkeeping for a forked simulator.
+    /// Open host-mediated files cannot be shared, so a context holding one
+    /// is not forkable.
+    pub fn try_fork(&self) -> Option<Self> {
+        if self.files.iter().any(Option::is_some) {
+            return None;
+        }
+        Some(Self {
+            ports: self.ports.clone(),
+            params: self.params.clone(),
+            failures: self.failures.clone(),
+            finish_requested: self.finish_requested,
+            logs: self.logs.clone(),
+            cycle: self.cycle,
+            time: self.time,
+            seed: self.seed,
+            fired_clock: self.fired_clock,
+            use_4state: self.use_4state,
+            label: self.label.clone(),
+            touched: self.touched.clone(),
+            resolved_role: self.resolved_role.clone(),
+            files: self.files.iter().map(|_| None).collect(),
+            touched_files: self.touched_files.clone(),
+            read_base: self.read_base.clone(),
+            write_base: self.write_base.clone(),
+            trace_vars: self.trace_vars.clone(),
+            in_create: self.in_create,
+        })
+    }
+
     pub fn add_port(&mut self, name: &str, dir: PortDir, width: u32) -> u32 {
         self.add_port_role(name, dir, PortRole::Data, width)
     }
@@ -818,6 +850,24 @@ enum InstanceInner {
 unsafe impl Send for ExternalInstance {}
 
 impl ExternalInstance {
+    /// Duplicate an instance for a forked simulator. Only injected instances
+    /// qualify: their state lives behind the shared embedding handler, while
+    /// native and Wasm component state is opaque to the host.
+    pub fn try_fork(&self) -> Option<Self> {
+        match &self.inner {
+            InstanceInner::Injected {
+                instance,
+                definition,
+            } => Some(Self {
+                inner: InstanceInner::Injected {
+                    instance: instance.clone(),
+                    definition: definition.clone(),
+                },
+            }),
+            _ => None,
+        }
+    }
+
     pub fn create_injected(
         definition: InjectedComponentDefinition,
         host: &mut HostContext,
@@ -0,0 +1,77 @@
+//! Host-side force overrides.
+//!
+//! A forced signal is written once when the force is placed and again after
+//! every point where the design could have overwritten it: after each
+//! flip-flop update and at each procedural store boundary of combinational
+//! evaluation. Inputs and registers only need the former. Holding a net that
+//! combinational logic drives needs the latter, which requires a simulator
+//! built with `force_support(true)` so the backend keeps per-unit entry
+//! points; without it the net is reapplied only once after the whole pass.
+
+use num_bigint::BigUint;
+
+use crate::backend::{SimBackend, SimulatorErrorCode};
+use crate::ir::SignalRef;
+
+/// The set of signals currently held at a forced value.
+#[derive(Debug, Clone, Default)]
+pub struct ForceSet {
+    forces: Vec<(SignalRef, BigUint, BigUint)>,
+}
+
+impl ForceSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold `signal` at `(value, mask)`, replacing any earlier force on it.
+    pub fn force(&mut self, signal: SignalRef, value: BigUint, mask: BigUint) {
+        match self
+            .forces
+            .iter_mut()
+            .find(|(forced, ..)| *forced == signal)
+        {
+            Some(entry) => *entry = (signal, value, mask),
+            None => self.forces.push((signal, value, mask)),
+        }
+    }
+
+    /// Drop the force on `signal`. Returns `false` if it was not forced.
+    pub fn release(&mut self, signal: SignalRef) -> bool {
+        let len = self.forces.len();
+        self.forces.retain(|(forced, ..)| *forced != signal);
+        self.forces.len() != len
+    }
+
+    pub fn contains(&self, signal: SignalRef) -> bool {
+        self.forces.iter().any(|(forced, ..)| *forced == signal)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forces.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.forces.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.forces.clear();
+    }
+
+    /// Write every forced value into `backend`.
+    pub fn apply<B: SimBackend>(&self, backend: &mut B) {
+        for (signal, value, mask) in &self.forces {
+            backend.set_four_state(*signal, value.clone(), mask.clone());
+        }
+    }
+
+    /// Evaluate combinational logic while holding every forced value.
+    pub fn eval_comb<B: SimBackend>(&self, backend: &mut B) -> Result<(), SimulatorErrorCode> {
+        if self.forces.is_empty() {
+            return backend.eval_comb();
+        }
+        self.apply(backend);
+        backend.eval_comb_units_with(&mut |backend| self.apply(backend))
+    }
+}
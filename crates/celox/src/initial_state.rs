@@ -0,0 +1,176 @@
+//! Starting values of registers that have no explicit initializer.
+//!
+//! A register is state written by an `always_ff` block. Before any initial
+//! value from the design is applied, every register is filled according to
+//! the policy of the deepest scope that contains its instance, so explicit
+//! initializers and `$readmemh` images still take precedence.
+
+use num_bigint::BigUint;
+use rand::{Rng as _, SeedableRng as _};
+use rand_pcg::Pcg64;
+
+/// Value a register without an explicit initializer starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InitialStatePolicy {
+    /// Unknown where the storage can hold it, i.e. four-state registers of a
+    /// four-state simulation; zero everywhere else. This is the default.
+    #[default]
+    X,
+    /// Every bit 0.
+    Zero,
+    /// Every bit 1.
+    Ones,
+    /// Pseudo-random bits. Each register draws from a generator seeded with
+    /// `seed` and its hierarchical path, so the same seed gives the same
+    /// values on every backend and across rebuilds of the design.
+    Random { seed: u64 },
+}
+
+/// A policy for the whole design, with overrides for instance subtrees.
+///
+/// Scopes are hierarchical instance paths as in [`crate::DumpFilter`]:
+/// `"u_core.u_alu"` selects that instance and everything below it, and the
+/// top module's name may prefix the path. When scopes nest, the deepest one
+/// containing an instance decides its policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct InitialStatePolicies {
+    default: InitialStatePolicy,
+    scopes: Vec<(Vec<String>, InitialStatePolicy)>,
+}
+
+impl InitialStatePolicies {
+    pub fn new(default: InitialStatePolicy) -> Self {
+        Self {
+            default,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Use `policy` for the instance at `path` and every instance below it.
+    pub fn scope(mut self, path: &str, policy: InitialStatePolicy) -> Self {
+        let segments = path
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.scopes.push((segments, policy));
+        self
+    }
+
+    pub(crate) fn set_default(&mut self, policy: InitialStatePolicy) {
+        self.default = policy;
+    }
+
+    /// Whether every register keeps the default X policy, which is what
+    /// freshly allocated state already holds.
+    pub fn is_default(&self) -> bool {
+        self.default == InitialStatePolicy::X
+            && self
+                .scopes
+                .iter()
+                .all(|(_, policy)| *policy == InitialStatePolicy::X)
+    }
+
+    /// The policy of an instance at `path` below the top module named `top`.
+    pub fn policy_for(&self, top: &str, path: &[String]) -> InitialStatePolicy {
+        self.scopes
+            .iter()
+            .filter_map(|(scope, policy)| {
+                let scope = match scope.split_first() {
+                    Some((first, rest)) if first == top && path.first() != Some(first) => rest,
+                    _ => scope.as_slice(),
+                };
+                path.starts_with(scope).then_some((scope.len(), *policy))
+            })
+            .max_by_key(|(depth, _)| *depth)
+            .map_or(self.default, |(_, policy)| policy)
+    }
+}
+
+/// Produces the starting value of one register, element by element.
+pub(crate) struct RegisterFill {
+    policy: InitialStatePolicy,
+    rng: Option<Pcg64>,
+}
+
+impl RegisterFill {
+    /// `path` is the register's full hierarchical name.
+    pub(crate) fn new(policy: InitialStatePolicy, path: &str) -> Self {
+        let rng = match policy {
+            InitialStatePolicy::Random { seed } => {
+                Some(Pcg64::seed_from_u64(fxhash::hash64(&(seed, path))))
+            }
+            _ => None,
+        };
+        Self { policy, rng }
+    }
+
+    /// The next `width`-bit value and X mask. `four_state` tells whether
+    /// the storage has a mask plane.
+    pub(crate) fn next(&mut self, width: usize, four_state: bool) -> (BigUint, BigUint) {
+        let ones = (BigUint::from(1u8) << width) - 1u8;
+        match self.policy {
+            InitialStatePolicy::X if four_state => (ones.clone(), ones),
+            InitialStatePolicy::X | InitialStatePolicy::Zero => {
+                (BigUint::default(), BigUint::default())
+            }
+            InitialStatePolicy::Ones => (ones, BigUint::default()),
+            InitialStatePolicy::Random { .. } => {
+                let rng = self.rng.as_mut().expect("random policy has a generator");
+                let mut bytes = vec![0u8; width.div_ceil(8)];
+                rng.fill_bytes(&mut bytes);
+                (BigUint::from_bytes_le(&bytes) & ones, BigUint::default())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|segment| segment.to_string()).collect()
+    }
+
+    #[test]
+    fn deepest_scope_wins() {
+        let policies = InitialStatePolicies::new(InitialStatePolicy::Zero)
+            .scope("Top.u_core", InitialStatePolicy::Ones)
+            .scope("u_core.u_alu", InitialStatePolicy::Random { seed: 1 });
+        assert_eq!(policies.policy_for("Top", &[]), InitialStatePolicy::Zero);
+        assert_eq!(
+            policies.policy_for("Top", &path(&["u_core"])),
+            InitialStatePolicy::Ones
+        );
+        assert_eq!(
+            policies.policy_for("Top", &path(&["u_core", "u_alu", "u_add"])),
+            InitialStatePolicy::Random { seed: 1 }
+        );
+        assert_eq!(
+            policies.policy_for("Top", &path(&["u_core2"])),
+            InitialStatePolicy::Zero
+        );
+        assert!(!policies.is_default());
+        assert!(InitialStatePolicies::default().is_default());
+    }
+
+    #[test]
+    fn random_fill_is_reproducible_per_register() {
+        let policy = InitialStatePolicy::Random { seed: 7 };
+        let a = RegisterFill::new(policy, "u_core.state").next(70, false);
+        let b = RegisterFill::new(policy, "u_core.state").next(70, false);
+        let c = RegisterFill::new(policy, "u_core.count").next(70, false);
+        assert_eq!(a, b);
+        assert_ne!(a.0, c.0);
+        assert!(a.0.bits() <= 70);
+        assert_eq!(
+            RegisterFill::new(InitialStatePolicy::X, "r").next(4, true),
+            (BigUint::from(15u8), BigUint::from(15u8))
+        );
+        assert_eq!(
+            RegisterFill::new(InitialStatePolicy::X, "r").next(4, false),
+            (BigUint::default(), BigUint::default())
+        );
+    }
+}
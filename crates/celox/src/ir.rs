@@ -14,14 +14,7 @@ pub(crate) use celox_design::{
 pub use celox_frontend_core::shared::{
     FrontendLookup, InstancePath, SourceAddr, SourceVarId, VariableInfo, VariableKind,
 };
-#[cfg(all(
-    feature = "host-runtime",
-    any(
-        target_arch = "x86_64",
-        feature = "arm64-codegen",
-        target_arch = "aarch64"
-    )
-))]
+#[cfg(feature = "host-runtime")]
 use celox_runtime::{
     DesignReflection, ReflectionScope, ReflectionScopeId, ReflectionSignal, ReflectionSignalId,
     SignalDirection,
@@ -90,6 +83,9 @@ pub struct RuntimeDesign {
     instances: HashMap<InstanceId, RuntimeInstance>,
     instance_ids: HashMap<InstancePath, InstanceId>,
     variables: HashMap<AbsoluteAddr, RuntimeVariable>,
+    /// Branch-coverage counters: the only state objects without a variable.
+    #[serde(default)]
+    coverage_counters: crate::HashSet<AbsoluteAddr>,
 }
 
 impl std::ops::Deref for RuntimeDesign {
@@ -104,12 +100,14 @@ impl RuntimeDesign {
     fn from_projection(
         semantic: celox_design::ElaboratedDesign<AbsoluteAddr>,
         frontend: FrontendLookup,
+        coverage_counters: crate::HashSet<AbsoluteAddr>,
     ) -> Result<Self, DesignProjectionError> {
         let expected_count = frontend
             .instance_module
             .values()
             .map(|module_id| frontend.module_variables[module_id].len())
-            .sum::<usize>();
+            .sum::<usize>()
+            + coverage_counters.len();
         if semantic.state_objects.len() != expected_count {
             return Err(DesignProjectionError::StateObjectCount {
                 design: semantic.state_objects.len(),
@@ -190,6 +188,7 @@ impl RuntimeDesign {
             instances,
             instance_ids: frontend.instance_ids,
             variables,
+            coverage_counters,
         };
         design
             .validate()
@@ -264,12 +263,43 @@ impl RuntimeDesign {
             .join(".")
     }
 
+    /// Stable hash of the instance hierarchy and every state object's path and
+    /// width. Runtime state captured from one design is rejected by another
+    /// design even when their physical layouts happen to coincide.
+    pub fn fingerprint(&self) -> u64 {
+        let mut instances = self
+            .instances
+            .values()
+            .map(|instance| (&instance.display_path, &instance.module_name))
+            .collect::<Vec<_>>();
+        instances.sort_unstable();
+        let mut variables = self
+            .variables
+            .keys()
+            .map(|address| {
+                let width = self.semantic.state_objects.get(address).map(|m| m.width);
+                (self.get_path(address), width)
+            })
+            .collect::<Vec<_>>();
+        variables.sort_unstable();
+        fxhash::hash64(&(instances, variables))
+    }
+
     pub(crate) fn validate(&self) -> Result<(), String> {
-        if self.variables.len() != self.semantic.state_objects.len() {
+        if self.variables.len() + self.coverage_counters.len() != self.semantic.state_objects.len()
+        {
             return Err(format!(
                 "state variable count differs: design={} runtime={}",
                 self.semantic.state_objects.len(),
-                self.variables.len()
+                self.variables.len() + self.coverage_counters.len()
+            ));
+        }
+        if let Some(counter) = self.coverage_counters.iter().find(|counter| {
+            self.variables.contains_key(counter)
+                || !self.semantic.state_objects.contains_key(counter)
+        }) {
+            return Err(format!(
+                "coverage counter {counter} is not a hidden state object"
             ));
         }
         if self.instance_ids.len() != self.instances.len() {
@@ -668,14 +698,7 @@ fn rebuild_rtl_writes(program: &mut OptimizedSir) {
 }
 
 impl RuntimeProgram {
-    #[cfg(all(
-        feature = "host-runtime",
-        any(
-            target_arch = "x86_64",
-            feature = "arm64-codegen",
-            target_arch = "aarch64"
-        )
-    ))]
+    #[cfg(feature = "host-runtime")]
     pub(crate) fn build_design_reflection(
         &self,
         layout: &crate::backend::MemoryLayout,
@@ -842,12 +865,24 @@ impl RuntimeProgram {
     pub(crate) fn from_scheduled(
         scheduled: celox_frontend_core::ScheduledRtl,
     ) -> Result<(SirProgram, Self), DesignProjectionError> {
-        let design = RuntimeDesign::from_projection(scheduled.design, scheduled.frontend_lookup)?;
+        let coverage_counters = scheduled
+            .runtime_schema
+            .coverage_points
+            .iter()
+            .map(|point| point.counter)
+            .collect();
+        let design = RuntimeDesign::from_projection(
+            scheduled.design,
+            scheduled.frontend_lookup,
+            coverage_counters,
+        )?;
+        let mut runtime_schema = scheduled.runtime_schema;
+        runtime_schema.registers = collect_ff_targets(&scheduled.sir);
         Ok((
             scheduled.sir,
             Self {
                 design,
-                runtime_schema: scheduled.runtime_schema,
+                runtime_schema,
                 testbench: None,
             },
         ))
@@ -901,6 +936,38 @@ impl RuntimeProgram {
     }
 }
 
+/// State written by flip-flop units. Before optimization every flip-flop
+/// store targets a working region, so the working-region addresses are
+/// exactly the registers.
+fn collect_ff_targets(sir: &SirProgram) -> crate::HashSet<AbsoluteAddr> {
+    let mut addrs = crate::HashSet::default();
+    for units in sir
+        .eval_apply_ffs
+        .values()
+        .chain(sir.eval_only_ffs.values())
+        .chain(sir.apply_ffs.values())
+    {
+        for eu in units {
+            for block in eu.blocks.values() {
+                for inst in &block.instructions {
+                    match inst {
+                        SIRInstruction::Store(addr, _, _, _, _, _)
+                            if addr.region != STABLE_REGION =>
+                        {
+                            addrs.insert(addr.absolute_addr());
+                        }
+                        SIRInstruction::Commit(_, dst, _, _, _) => {
+                            addrs.insert(dst.absolute_addr());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    addrs
+}
+
 impl OptimizedSir {
     /// Collect the set of `AbsoluteAddr` values that are accessed in the working
     /// region (region != STABLE). These are the only variables that need working
@@ -1008,14 +1075,7 @@ pub(crate) mod verify {
 pub use celox_slt::{GlueAddrBase, GlueBlockBase};
 
 pub use celox_frontend_core::TraceSimModule as SimModule;
-#[cfg(all(
-    feature = "host-runtime",
-    any(
-        target_arch = "x86_64",
-        feature = "arm64-codegen",
-        target_arch = "aarch64"
-    )
-))]
+#[cfg(feature = "host-runtime")]
 pub(crate) use celox_runtime::SignalArrayLayout;
 pub use celox_runtime::SignalRef;
 
@@ -1,4 +1,15 @@
 mod backend;
+#[cfg(all(
+    feature = "host-runtime",
+    any(
+        target_arch = "x86_64",
+        feature = "arm64-codegen",
+        target_arch = "aarch64"
+    )
+))]
+mod batch;
+#[cfg(feature = "host-runtime")]
+mod checkpoint;
 #[cfg(all(feature = "arm64-codegen", feature = "x86_64-codegen"))]
 compile_error!("arm64-codegen and x86_64-codegen cannot be enabled together");
 #[cfg(feature = "host-runtime")]
@@ -11,26 +22,41 @@ pub use component::{
 };
 mod debug;
 mod diagnostics;
+#[cfg(feature = "host-runtime")]
+mod force;
+#[cfg(feature = "host-runtime")]
+mod initial_state;
 mod ir;
 mod optimizer;
 mod parser;
 pub(crate) mod portable;
 #[cfg(feature = "host-runtime")]
+mod profile;
+#[cfg(feature = "host-runtime")]
+mod property;
+#[cfg(feature = "host-runtime")]
+mod replay;
+#[cfg(feature = "host-runtime")]
 mod simulation;
 mod simulator;
 #[cfg(feature = "host-runtime")]
 pub mod testbench;
 mod testbench_compile;
 pub(crate) mod timing;
+#[cfg(feature = "host-runtime")]
+mod watch;
 pub use backend::SimulatorErrorCode as RuntimeErrorCode;
 pub use backend::{
     EventHandle, LayoutRequirements, MemoryLayout, MemoryLayoutMode, SimBackend, get_byte_size,
 };
-pub use celox_design::{DomainKind, ElaboratedDesign, EventTopology, RuntimeSchema};
+pub use celox_design::{
+    CoverageArmKind, CoverageProcess, DomainKind, ElaboratedDesign, EventTopology, FsmState,
+    RuntimeSchema,
+};
 pub use celox_frontend_core::FrontendArtifactError;
 pub use celox_frontend_sdk as frontend_sdk;
 pub use celox_frontend_sdk::FrontendArtifact;
-pub use celox_frontend_veryl::{FrontendDiagnostic, LoweringPhase, ParserError};
+pub use celox_frontend_veryl::{FrontendDiagnostic, LoweringPhase, ParserError, XPropMode};
 pub use celox_runtime::{
     DesignReflection, ReflectionScope, ReflectionScopeId, ReflectionSignal, ReflectionSignalId,
     SignalDirection,
@@ -58,13 +84,23 @@ pub use veryl_metadata::{ClockType, ResetType};
 #[cfg(feature = "host-runtime")]
 mod host_api {
     use crate::SimBackend;
+    pub use crate::backend::interpreter::InterpreterBackend;
     pub use crate::backend::wasm_runtime::WasmBackend;
     pub use crate::backend::{
         CraneliftDiagnostics, CraneliftOptLevel, CraneliftOptions, EventRef, JitBackend,
         RegallocAlgorithm, SharedJitCode,
     };
+    pub use crate::checkpoint::{CheckpointError, SimulatorCheckpoint};
     pub use crate::debug::CompilationTraceResult;
     pub use crate::diagnostics::DiagnosticsOptions;
+    pub use crate::force::ForceSet;
+    pub use crate::initial_state::{InitialStatePolicies, InitialStatePolicy};
+    pub use crate::profile::{Profile, ProfileEntry, ProfilePhase, ProfileRow};
+    pub use crate::property::{
+        ClockEdge, CompareOp, Delay, Implication, Property, PropertyError, PropertyExpr,
+        PropertyFailure, PropertyId, Sequence,
+    };
+    pub use crate::replay::{ReplayError, ReplayMapping, ReplayMismatch, ReplayReport};
     pub use crate::simulation::Simulation;
     #[cfg(any(
         target_arch = "x86_64",
@@ -73,12 +109,19 @@ mod host_api {
     ))]
     pub use crate::simulator::NativeCompilation;
     pub use crate::simulator::{
-        DeadStorePolicy, InstanceHierarchy, NamedEvent, NamedSignal, RuntimeEvent,
+        DeadStorePolicy, ForkError, InstanceHierarchy, NamedEvent, NamedSignal, RuntimeEvent,
         RuntimeEventDrain, RuntimeFormatContext, Simulator, SimulatorBuilder, SimulatorOptions,
     };
     pub use crate::testbench::{AssertionResult, SourceLocation, TestResult, TestResultDetailed};
+    pub use crate::watch::{ValueChange, WatchCallback, WatchId, WatchList};
     pub use celox_macros::veryl_test;
-    pub use celox_runtime::{VcdSignalDesc, VcdWriter};
+    pub use celox_runtime::{
+        BitToggles, BranchCoverage, BranchHits, CancelToken, ClockSpec, DumpFilter, FsmCoverage,
+        FsmDesc, FstWriter, InterruptReason, MemoryFileError, MemoryFileFormat, MemoryWord,
+        RunLimits, SignalDivergence, SignalToggles, StateMachineCoverage, Timescale,
+        TimescaleError, ToggleCoverage, VcdSignalDesc, VcdWriter, Waveform, WaveformDiff,
+        WaveformDiffOptions, WaveformFormat, WaveformReadError, WaveformSignal, WaveformWriter,
+    };
 
     pub struct IOContext<'a, B: SimBackend = DefaultBackend> {
         pub(crate) backend: &'a mut B,
@@ -135,6 +178,12 @@ mod host_api {
         target_arch = "aarch64"
     ))]
     pub use crate::backend::{NativeDiagnostics, NativeDumpOptions};
+    #[cfg(any(
+        target_arch = "x86_64",
+        feature = "arm64-codegen",
+        target_arch = "aarch64"
+    ))]
+    pub use crate::batch::{BatchSimulator, LaneError};
 
     /// Default simulation backend: custom native when it matches the compilation
     /// target, and Cranelift when a host-side cross-codegen feature is enabled.
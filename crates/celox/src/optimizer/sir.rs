@@ -122,7 +122,129 @@ pub(crate) fn optimize_native_merged_chain(
         four_state,
         recover_merged_effect_regions,
         diagnostics,
+    )?;
+    split_strided_packed_element_accesses(eu, layout);
+    Ok(())
+}
+
+/// Split multi-element `PackedElements` accesses to element-strided arrays.
+///
+/// The memory layout only selects an element-strided array when every access
+/// in the per-EU SIR stays inside one element. Coalescing across the merged
+/// unit boundaries can still join neighbouring elements afterwards, and the
+/// native lowering addresses such a range as contiguous packed bits. Rewrite
+/// those accesses back into one Load/Store per element.
+#[cfg(all(
+    feature = "host-runtime",
+    any(
+        target_arch = "x86_64",
+        feature = "arm64-codegen",
+        target_arch = "aarch64"
     )
+))]
+fn split_strided_packed_element_accesses(
+    eu: &mut ExecutionUnit<RegionedAbsoluteAddr>,
+    layout: &crate::backend::MemoryLayout,
+) {
+    use crate::ir::{RegisterId, RegisterType, SIRInstruction, SIROffset};
+
+    let split = |address: &RegionedAbsoluteAddr, offset: &SIROffset, width: usize| {
+        let SIROffset::PackedElements {
+            bit_offset,
+            element_width,
+        } = *offset
+        else {
+            return None;
+        };
+        let array = layout.unpacked_arrays.get(&address.absolute_addr())?;
+        (array.element_width == element_width
+            && (1..=64).contains(&element_width)
+            && width > element_width
+            && width.is_multiple_of(element_width)
+            && !packed_range_is_physically_contiguous(layout, *address, bit_offset, width))
+        .then_some((bit_offset, element_width, width / element_width))
+    };
+    let mut next_register = eu
+        .register_map
+        .keys()
+        .map(|register| register.0 + 1)
+        .max()
+        .unwrap_or(0);
+    let register_map = &mut eu.register_map;
+    let mut allocate = |width: usize| {
+        let register = RegisterId(next_register);
+        next_register += 1;
+        register_map.insert(register, RegisterType::Logic { width });
+        register
+    };
+    for block in eu.blocks.values_mut() {
+        if !block
+            .instructions
+            .iter()
+            .any(|instruction| match instruction {
+                SIRInstruction::Load(_, address, offset, width)
+                | SIRInstruction::Store(address, offset, width, ..) => {
+                    split(address, offset, *width).is_some()
+                }
+                _ => false,
+            })
+        {
+            continue;
+        }
+        let mut instructions = Vec::with_capacity(block.instructions.len());
+        for instruction in std::mem::take(&mut block.instructions) {
+            match instruction {
+                SIRInstruction::Load(destination, address, ref offset, width)
+                    if let Some((bit_offset, element_width, count)) =
+                        split(&address, offset, width) =>
+                {
+                    let mut elements = Vec::with_capacity(count);
+                    for element in 0..count {
+                        let register = allocate(element_width);
+                        instructions.push(SIRInstruction::Load(
+                            register,
+                            address,
+                            SIROffset::Static(bit_offset + element * element_width),
+                            element_width,
+                        ));
+                        elements.push(register);
+                    }
+                    elements.reverse();
+                    instructions.push(SIRInstruction::Concat(destination, elements));
+                }
+                SIRInstruction::Store(
+                    address,
+                    ref offset,
+                    width,
+                    source,
+                    ref triggers,
+                    ref comb_capture_sites,
+                ) if let Some((bit_offset, element_width, count)) =
+                    split(&address, offset, width) =>
+                {
+                    for element in 0..count {
+                        let register = allocate(element_width);
+                        instructions.push(SIRInstruction::Slice(
+                            register,
+                            source,
+                            element * element_width,
+                            element_width,
+                        ));
+                        instructions.push(SIRInstruction::Store(
+                            address,
+                            SIROffset::Static(bit_offset + element * element_width),
+                            element_width,
+                            register,
+                            triggers.clone(),
+                            comb_capture_sites.clone(),
+                        ));
+                    }
+                }
+                instruction => instructions.push(instruction),
+            }
+        }
+        block.instructions = instructions;
+    }
 }
 
 #[cfg(all(
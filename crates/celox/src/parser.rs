@@ -510,7 +510,7 @@ pub fn parse_sv(
     sources: &[(&str, &std::path::Path)],
     top: &str,
     parameter_overrides: &[(String, u64)],
-    _config: &BuildConfig,
+    config: &BuildConfig,
     ignored_loops: &[(
         (Vec<(String, usize)>, Vec<String>),
         (Vec<(String, usize)>, Vec<String>),
@@ -539,6 +539,7 @@ pub fn parse_sv(
         ignored_loops,
         true_loops,
         four_state,
+        config.comb_partitions,
         &frontend_trace_options,
         trace.is_some().then_some(&mut frontend_trace),
     )
@@ -0,0 +1,330 @@
+//! Instrumented runtime profiler.
+//!
+//! A simulator built with [`crate::SimulatorBuilder::profile`] compiles every
+//! combinational execution unit as its own entry point and times each call,
+//! together with every flip-flop domain call. Optimized units can mix the
+//! logic of several instances, so a unit's time is split between instances
+//! in proportion to the stores it performs in each of them. Units without
+//! stores fall back to their loads.
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use crate::HashMap;
+use crate::ir::{
+    AbsoluteAddr, ExecutionUnit, InstanceId, LaidOutProgram, RegionedAbsoluteAddr, RuntimeProgram,
+    SIRInstruction,
+};
+
+/// Part of a time step an entry was measured in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfilePhase {
+    /// Combinational settle.
+    Comb,
+    /// Evaluation and update of a flip-flop domain.
+    Sequential,
+}
+
+/// Time one execution unit spent on behalf of one instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileEntry {
+    /// Dotted instance path starting with the top module name.
+    pub instance: String,
+    pub module: String,
+    /// `comb[<n>]` for a combinational unit, `always_ff(<event>)` for the
+    /// flip-flops triggered by one event.
+    pub unit: String,
+    pub phase: ProfilePhase,
+    /// This instance's share of the unit's time.
+    pub time: Duration,
+    /// Number of times the unit ran.
+    pub calls: u64,
+}
+
+/// Time aggregated over one instance or module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileRow {
+    pub name: String,
+    pub time: Duration,
+}
+
+/// Snapshot of the profiler returned by [`crate::Simulator::profile`].
+///
+/// Entries are ranked by time, longest first. `Display` renders them as a
+/// table and [`Self::write_folded`] as folded stacks for flamegraph tools.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    entries: Vec<ProfileEntry>,
+    total: Duration,
+}
+
+impl Profile {
+    pub fn entries(&self) -> &[ProfileEntry] {
+        &self.entries
+    }
+
+    /// Time spent in all profiled units.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Time per instance, excluding its children, longest first.
+    pub fn by_instance(&self) -> Vec<ProfileRow> {
+        self.aggregate(|entry| &entry.instance)
+    }
+
+    /// Time per module summed over its instances, longest first.
+    pub fn by_module(&self) -> Vec<ProfileRow> {
+        self.aggregate(|entry| &entry.module)
+    }
+
+    fn aggregate(&self, key: impl Fn(&ProfileEntry) -> &String) -> Vec<ProfileRow> {
+        let mut times: HashMap<&String, Duration> = HashMap::default();
+        for entry in &self.entries {
+            *times.entry(key(entry)).or_default() += entry.time;
+        }
+        let mut rows = times
+            .into_iter()
+            .map(|(name, time)| ProfileRow {
+                name: name.clone(),
+                time,
+            })
+            .collect::<Vec<_>>();
+        rows.sort_by(|a, b| b.time.cmp(&a.time).then_with(|| a.name.cmp(&b.name)));
+        rows
+    }
+
+    /// Write one `Top;u_core;u_alu;comb[3] <nanoseconds>` line per entry,
+    /// the folded-stack input of `flamegraph.pl` and `inferno-flamegraph`.
+    pub fn write_folded<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        for entry in &self.entries {
+            let nanos = entry.time.as_nanos();
+            if nanos == 0 {
+                continue;
+            }
+            writeln!(
+                writer,
+                "{};{} {nanos}",
+                entry.instance.replace('.', ";"),
+                entry.unit
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let instance_width = self
+            .entries
+            .iter()
+            .map(|entry| entry.instance.len())
+            .chain(std::iter::once("instance".len()))
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "{:>12} {:>7} {:>10}  {:<instance_width$}  unit",
+            "time", "share", "calls", "instance"
+        )?;
+        for entry in &self.entries {
+            let share = if self.total.is_zero() {
+                0.0
+            } else {
+                100.0 * entry.time.as_secs_f64() / self.total.as_secs_f64()
+            };
+            writeln!(
+                f,
+                "{:>12} {:>6.2}% {:>10}  {:<instance_width$}  {} ({})",
+                format!("{:.3?}", entry.time),
+                share,
+                entry.calls,
+                entry.instance,
+                entry.unit,
+                entry.module
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UnitCounter {
+    label: String,
+    phase: ProfilePhase,
+    /// Owning instances with their share of the unit, summing to one.
+    owners: Vec<(InstanceId, f64)>,
+    time: Duration,
+    calls: u64,
+}
+
+impl UnitCounter {
+    fn new<'a>(
+        label: String,
+        phase: ProfilePhase,
+        units: impl IntoIterator<Item = &'a ExecutionUnit<RegionedAbsoluteAddr>>,
+    ) -> Self {
+        Self {
+            label,
+            phase,
+            owners: unit_owners(units),
+            time: Duration::ZERO,
+            calls: 0,
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.time += elapsed;
+        self.calls += 1;
+    }
+}
+
+/// Share of each instance in `units`, by stores or else by loads.
+fn unit_owners<'a>(
+    units: impl IntoIterator<Item = &'a ExecutionUnit<RegionedAbsoluteAddr>>,
+) -> Vec<(InstanceId, f64)> {
+    let mut stores: HashMap<InstanceId, u64> = HashMap::default();
+    let mut loads: HashMap<InstanceId, u64> = HashMap::default();
+    for unit in units {
+        for instruction in unit.blocks.values().flat_map(|block| &block.instructions) {
+            match instruction {
+                SIRInstruction::Store(addr, ..) | SIRInstruction::Commit(_, addr, ..) => {
+                    *stores.entry(addr.instance_id).or_default() += 1;
+                }
+                SIRInstruction::Load(_, addr, ..) => {
+                    *loads.entry(addr.instance_id).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    let counts = if stores.is_empty() { loads } else { stores };
+    let total = counts.values().sum::<u64>() as f64;
+    let mut owners = counts
+        .into_iter()
+        .map(|(instance, count)| (instance, count as f64 / total))
+        .collect::<Vec<_>>();
+    owners.sort_by_key(|(instance, _)| *instance);
+    owners
+}
+
+/// Per-unit timers of a profiling simulator.
+#[derive(Debug, Clone)]
+pub(crate) struct Profiler {
+    /// One counter per combinational entry point, in evaluation order.
+    comb: Vec<UnitCounter>,
+    /// Flip-flop units keyed by their triggering event.
+    ff: HashMap<AbsoluteAddr, UnitCounter>,
+}
+
+impl Profiler {
+    pub(crate) fn new(program: &LaidOutProgram, options: &crate::SimulatorOptions) -> Self {
+        let comb_units = crate::backend::comb_split::comb_entry_units(program, options);
+        let comb = if comb_units.is_empty() {
+            vec![UnitCounter::new(
+                "comb".to_string(),
+                ProfilePhase::Comb,
+                &program.sir.eval_comb,
+            )]
+        } else {
+            comb_units
+                .iter()
+                .enumerate()
+                .map(|(index, unit)| {
+                    UnitCounter::new(
+                        format!("comb[{index}]"),
+                        ProfilePhase::Comb,
+                        std::iter::once(unit),
+                    )
+                })
+                .collect()
+        };
+        let mut ff_units: HashMap<AbsoluteAddr, Vec<&ExecutionUnit<RegionedAbsoluteAddr>>> =
+            HashMap::default();
+        for (addr, units) in program
+            .sir
+            .eval_apply_ffs
+            .iter()
+            .chain(&program.sir.eval_only_ffs)
+            .chain(&program.sir.apply_ffs)
+        {
+            ff_units.entry(*addr).or_default().extend(units);
+        }
+        let ff = ff_units
+            .into_iter()
+            .map(|(addr, units)| {
+                let label = format!("always_ff({})", program.get_path(&addr));
+                let counter = UnitCounter::new(label, ProfilePhase::Sequential, units);
+                (addr, counter)
+            })
+            .collect();
+        Self { comb, ff }
+    }
+
+    /// Record the `index`-th combinational unit of the running pass.
+    pub(crate) fn record_comb(&mut self, index: usize, elapsed: Duration) {
+        let last = self.comb.len() - 1;
+        self.comb[index.min(last)].record(elapsed);
+    }
+
+    pub(crate) fn record_ff(&mut self, event: AbsoluteAddr, elapsed: Duration) {
+        if let Some(counter) = self.ff.get_mut(&event) {
+            counter.record(elapsed);
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        for counter in self.comb.iter_mut().chain(self.ff.values_mut()) {
+            counter.time = Duration::ZERO;
+            counter.calls = 0;
+        }
+    }
+
+    pub(crate) fn report(&self, program: &RuntimeProgram) -> Profile {
+        let root = program.design.root_instance();
+        let top = root.map_or("", |instance| instance.module_name.as_str());
+        let mut entries = Vec::new();
+        let mut total = Duration::ZERO;
+        for counter in self.comb.iter().chain(self.ff.values()) {
+            if counter.calls == 0 {
+                continue;
+            }
+            total += counter.time;
+            let root_owner = root.map(|instance| (instance.id, 1.0));
+            let owners = if counter.owners.is_empty() {
+                root_owner.as_slice()
+            } else {
+                counter.owners.as_slice()
+            };
+            for &(id, share) in owners {
+                let instance = program.design.instance(id);
+                entries.push(ProfileEntry {
+                    instance: std::iter::once(top)
+                        .chain(
+                            instance
+                                .iter()
+                                .flat_map(|instance| &instance.display_path)
+                                .map(String::as_str),
+                        )
+                        .collect::<Vec<_>>()
+                        .join("."),
+                    module: instance
+                        .map_or(top, |instance| &instance.module_name)
+                        .to_string(),
+                    unit: counter.label.clone(),
+                    phase: counter.phase,
+                    time: counter.time.mul_f64(share),
+                    calls: counter.calls,
+                });
+            }
+        }
+        entries.sort_by(|a, b| {
+            b.time
+                .cmp(&a.time)
+                .then_with(|| a.instance.cmp(&b.instance))
+                .then_with(|| a.unit.cmp(&b.unit))
+        });
+        Profile { entries, total }
+    }
+}
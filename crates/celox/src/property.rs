@@ -0,0 +1,1075 @@
+//! Clocked temporal properties.
+//!
+//! A [`Property`] is a small subset of SystemVerilog concurrent assertions:
+//! an optional `disable iff` condition, an optional antecedent sequence with
+//! `|->` or `|=>`, and a consequent sequence. Sequences are boolean
+//! expressions over named signals joined by `##n` or `##[m:n]` delays.
+//!
+//! [`crate::Simulation`] samples every referenced signal before each time
+//! step, which corresponds to the SVA preponed region, and evaluates a
+//! property when its clock has the requested edge during the step. A new
+//! attempt starts on every such edge. Each attempt tracks the set of partial
+//! matches, so the cost per edge is proportional to the number of attempts in
+//! flight rather than to the length of the simulation.
+
+use std::fmt;
+use std::str::FromStr;
+
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+use crate::ir::SignalRef;
+
+/// Identifier returned by `assert_property` and accepted by `remove_property`.
+pub type PropertyId = u32;
+
+/// Clock edge on which a property samples its signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClockEdge {
+    #[default]
+    Posedge,
+    Negedge,
+}
+
+/// Comparison operator of a [`PropertyExpr::Compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Boolean expression evaluated on the values sampled at a clock edge.
+///
+/// A signal with any X or Z bit makes comparisons unknown, and an unknown
+/// expression counts as false, as in an SVA assertion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyExpr {
+    /// A top-level signal, true when nonzero.
+    Signal(String),
+    Const(BigUint),
+    Not(Box<PropertyExpr>),
+    And(Box<PropertyExpr>, Box<PropertyExpr>),
+    Or(Box<PropertyExpr>, Box<PropertyExpr>),
+    Compare(CompareOp, Box<PropertyExpr>, Box<PropertyExpr>),
+    /// `$rose(sig)`: the least significant bit became 1 since the previous edge.
+    Rose(String),
+    /// `$fell(sig)`: the least significant bit became 0 since the previous edge.
+    Fell(String),
+    /// `$stable(sig)`: the value did not change since the previous edge.
+    Stable(String),
+    /// `$past(sig)`: the value sampled at the previous edge.
+    Past(String),
+}
+
+impl PropertyExpr {
+    pub fn signal(name: &str) -> Self {
+        Self::Signal(name.to_string())
+    }
+
+    pub fn rose(name: &str) -> Self {
+        Self::Rose(name.to_string())
+    }
+
+    pub fn fell(name: &str) -> Self {
+        Self::Fell(name.to_string())
+    }
+
+    pub fn stable(name: &str) -> Self {
+        Self::Stable(name.to_string())
+    }
+
+    pub fn past(name: &str) -> Self {
+        Self::Past(name.to_string())
+    }
+
+    pub fn and(self, rhs: Self) -> Self {
+        Self::And(Box::new(self), Box::new(rhs))
+    }
+
+    pub fn or(self, rhs: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(rhs))
+    }
+
+    pub fn compare(self, op: CompareOp, rhs: Self) -> Self {
+        Self::Compare(op, Box::new(self), Box::new(rhs))
+    }
+
+    fn for_each_signal(&self, f: &mut impl FnMut(&str)) {
+        match self {
+            Self::Signal(name)
+            | Self::Rose(name)
+            | Self::Fell(name)
+            | Self::Stable(name)
+            | Self::Past(name) => f(name),
+            Self::Const(_) => {}
+            Self::Not(inner) => inner.for_each_signal(f),
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) | Self::Compare(_, lhs, rhs) => {
+                lhs.for_each_signal(f);
+                rhs.for_each_signal(f);
+            }
+        }
+    }
+}
+
+impl std::ops::Not for PropertyExpr {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+/// Delay before a sequence element, in clock edges. `max: None` is `$`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delay {
+    pub min: u32,
+    pub max: Option<u32>,
+}
+
+impl Delay {
+    pub const NONE: Self = Self::exact(0);
+
+    pub const fn exact(cycles: u32) -> Self {
+        Self {
+            min: cycles,
+            max: Some(cycles),
+        }
+    }
+
+    pub const fn range(min: u32, max: Option<u32>) -> Self {
+        Self { min, max }
+    }
+}
+
+/// Boolean expressions separated by delays, e.g. `a ##1 b ##[0:2] c`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    pub steps: Vec<(Delay, PropertyExpr)>,
+}
+
+impl Sequence {
+    /// A sequence that matches `expr` at its start.
+    pub fn new(expr: PropertyExpr) -> Self {
+        Self::after(Delay::NONE, expr)
+    }
+
+    /// A sequence whose first element is checked `delay` edges after its start.
+    pub fn after(delay: Delay, expr: PropertyExpr) -> Self {
+        Self {
+            steps: vec![(delay, expr)],
+        }
+    }
+
+    /// Append `expr`, checked `delay` edges after the previous element.
+    pub fn then(mut self, delay: Delay, expr: PropertyExpr) -> Self {
+        self.steps.push((delay, expr));
+        self
+    }
+}
+
+/// Implication operator between antecedent and consequent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Implication {
+    /// `|->`: the consequent starts at the edge where the antecedent matched.
+    Overlapping,
+    /// `|=>`: the consequent starts at the following edge.
+    NonOverlapping,
+}
+
+/// A clocked temporal property.
+///
+/// Build one with [`Property::new`] and [`Property::implies`], or parse SVA
+/// text such as `@(posedge clk) disable iff (rst) req |-> ##[1:4] ack`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Property {
+    pub clock: String,
+    pub edge: ClockEdge,
+    pub disable_iff: Option<PropertyExpr>,
+    pub antecedent: Option<(Sequence, Implication)>,
+    pub consequent: Sequence,
+}
+
+impl Property {
+    /// A property requiring `sequence` to match from every rising edge of `clock`.
+    pub fn new(clock: &str, sequence: Sequence) -> Self {
+        Self {
+            clock: clock.to_string(),
+            edge: ClockEdge::Posedge,
+            disable_iff: None,
+            antecedent: None,
+            consequent: sequence,
+        }
+    }
+
+    /// A property requiring `consequent` wherever `antecedent` matches.
+    pub fn implies(
+        clock: &str,
+        antecedent: Sequence,
+        implication: Implication,
+        consequent: Sequence,
+    ) -> Self {
+        Self {
+            antecedent: Some((antecedent, implication)),
+            ..Self::new(clock, consequent)
+        }
+    }
+
+    /// Sample on the falling edge of the clock instead.
+    pub fn on_negedge(mut self) -> Self {
+        self.edge = ClockEdge::Negedge;
+        self
+    }
+
+    /// Abandon every attempt in flight, and start none, on edges where `expr` holds.
+    pub fn disable_iff(mut self, expr: PropertyExpr) -> Self {
+        self.disable_iff = Some(expr);
+        self
+    }
+}
+
+impl FromStr for Property {
+    type Err = PropertyError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Parser::new(text)?.property()
+    }
+}
+
+/// Failure while parsing or registering a property.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyError {
+    /// The text is not a property in the supported SVA subset.
+    Syntax { position: usize, message: String },
+    /// The text has no `@(posedge clk)` clocking event.
+    MissingClock,
+    /// The property names a signal the design does not have.
+    UnknownSignal(String),
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax { position, message } => {
+                write!(f, "syntax error at offset {position}: {message}")
+            }
+            Self::MissingClock => write!(f, "the property has no clocking event"),
+            Self::UnknownSignal(name) => write!(f, "the design has no signal '{name}'"),
+        }
+    }
+}
+
+impl std::error::Error for PropertyError {}
+
+/// A failed property attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyFailure {
+    pub id: PropertyId,
+    /// The name the property was registered with.
+    pub name: String,
+    /// Simulation time of the clock edge where the attempt started.
+    pub start_time: u64,
+    /// Simulation time of the clock edge where the attempt failed.
+    pub time: u64,
+}
+
+impl fmt::Display for PropertyFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "property '{}' failed at time {} (attempt started at {})",
+            self.name, self.time, self.start_time
+        )
+    }
+}
+
+// ── Parser ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    SysFunc(String),
+    Number(BigUint),
+    Punct(&'static str),
+    End,
+}
+
+const PUNCTUATION: [&str; 19] = [
+    "|->", "|=>", "##", "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "(", ")", "[", "]", ":",
+    "$", "@",
+];
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+fn syntax(position: usize, message: impl Into<String>) -> PropertyError {
+    PropertyError::Syntax {
+        position,
+        message: message.into(),
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, PropertyError> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let word_end = |from: usize| {
+            let mut end = from;
+            while end < bytes.len()
+                && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_' || bytes[end] == b'.')
+            {
+                end += 1;
+            }
+            end
+        };
+        if c.is_ascii_alphabetic() || c == b'_' {
+            i = word_end(i);
+            tokens.push((start, Token::Ident(text[start..i].to_string())));
+        } else if c == b'$' && bytes.get(i + 1).is_some_and(u8::is_ascii_alphabetic) {
+            i = word_end(i + 1);
+            tokens.push((start, Token::SysFunc(text[start + 1..i].to_string())));
+        } else if c.is_ascii_digit() || c == b'\'' {
+            i = word_end(i);
+            let mut literal = &text[start..i];
+            if bytes.get(i) == Some(&b'\'') {
+                i = word_end(i + 1);
+                literal = &text[start..i];
+            }
+            tokens.push((start, Token::Number(parse_number(start, literal)?)));
+        } else if let Some(punct) = PUNCTUATION
+            .iter()
+            .find(|punct| text[i..].starts_with(**punct))
+        {
+            i += punct.len();
+            tokens.push((start, Token::Punct(punct)));
+        } else {
+            return Err(syntax(
+                start,
+                format!("unexpected character '{}'", c as char),
+            ));
+        }
+    }
+    tokens.push((text.len(), Token::End));
+    Ok(tokens)
+}
+
+/// Parse a decimal, `0x`/`0b` or Verilog-style (`8'hff`, `'b1`) literal.
+fn parse_number(position: usize, literal: &str) -> Result<BigUint, PropertyError> {
+    let invalid = || syntax(position, format!("invalid number '{literal}'"));
+    let clean = literal.replace('_', "");
+    let (radix, digits) = if let Some((_, based)) = clean.split_once('\'') {
+        let mut chars = based.chars();
+        let radix = match chars.next().map(|c| c.to_ascii_lowercase()) {
+            Some('h') => 16,
+            Some('d') => 10,
+            Some('o') => 8,
+            Some('b') => 2,
+            _ => return Err(invalid()),
+        };
+        (radix, chars.as_str().to_string())
+    } else if let Some(hex) = clean.strip_prefix("0x") {
+        (16, hex.to_string())
+    } else if let Some(bin) = clean.strip_prefix("0b") {
+        (2, bin.to_string())
+    } else {
+        (10, clean)
+    };
+    BigUint::parse_bytes(digits.as_bytes(), radix).ok_or_else(invalid)
+}
+
+impl Parser {
+    fn new(text: &str) -> Result<Self, PropertyError> {
+        Ok(Self {
+            tokens: tokenize(text)?,
+            pos: 0,
+        })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.pos].0
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.pos].1.clone();
+        if token != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Token::Punct(p) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Token::Ident(word) if word == keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), PropertyError> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(syntax(self.position(), format!("expected '{punct}'")))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, PropertyError> {
+        match self.next() {
+            Token::Ident(name) => Ok(name),
+            _ => Err(syntax(
+                self.tokens[self.pos - 1].0,
+                "expected a signal name",
+            )),
+        }
+    }
+
+    fn cycles(&mut self) -> Result<u32, PropertyError> {
+        let position = self.position();
+        match self.next() {
+            Token::Number(value) => {
+                u32::try_from(&value).map_err(|_| syntax(position, "delay is too large"))
+            }
+            _ => Err(syntax(position, "expected a cycle count")),
+        }
+    }
+
+    fn property(&mut self) -> Result<Property, PropertyError> {
+        let mut clocking = None;
+        if self.eat_punct("@") {
+            self.expect_punct("(")?;
+            let edge = if self.eat_keyword("negedge") {
+                ClockEdge::Negedge
+            } else {
+                self.eat_keyword("posedge");
+                ClockEdge::Posedge
+            };
+            clocking = Some((self.ident()?, edge));
+            self.expect_punct(")")?;
+        }
+        let mut disable_iff = None;
+        if self.eat_keyword("disable") {
+            if !self.eat_keyword("iff") {
+                return Err(syntax(self.position(), "expected 'iff'"));
+            }
+            self.expect_punct("(")?;
+            disable_iff = Some(self.expr()?);
+            self.expect_punct(")")?;
+        }
+        let first = self.sequence()?;
+        let implication = if self.eat_punct("|->") {
+            Some(Implication::Overlapping)
+        } else if self.eat_punct("|=>") {
+            Some(Implication::NonOverlapping)
+        } else {
+            None
+        };
+        let (antecedent, consequent) = match implication {
+            Some(implication) => (Some((first, implication)), self.sequence()?),
+            None => (None, first),
+        };
+        if *self.peek() != Token::End {
+            return Err(syntax(self.position(), "unexpected trailing input"));
+        }
+        let (clock, edge) = clocking.ok_or(PropertyError::MissingClock)?;
+        Ok(Property {
+            clock,
+            edge,
+            disable_iff,
+            antecedent,
+            consequent,
+        })
+    }
+
+    fn delay(&mut self) -> Result<Option<Delay>, PropertyError> {
+        if !self.eat_punct("##") {
+            return Ok(None);
+        }
+        if !self.eat_punct("[") {
+            return Ok(Some(Delay::exact(self.cycles()?)));
+        }
+        let position = self.position();
+        let min = self.cycles()?;
+        self.expect_punct(":")?;
+        let max = if self.eat_punct("$") {
+            None
+        } else {
+            Some(self.cycles()?)
+        };
+        self.expect_punct("]")?;
+        if max.is_some_and(|max| max < min) {
+            return Err(syntax(position, "delay range is empty"));
+        }
+        Ok(Some(Delay::range(min, max)))
+    }
+
+    fn sequence(&mut self) -> Result<Sequence, PropertyError> {
+        let delay = self.delay()?.unwrap_or(Delay::NONE);
+        let mut sequence = Sequence::after(delay, self.expr()?);
+        while let Some(delay) = self.delay()? {
+            sequence = sequence.then(delay, self.expr()?);
+        }
+        Ok(sequence)
+    }
+
+    fn expr(&mut self) -> Result<PropertyExpr, PropertyError> {
+        let mut lhs = self.and_expr()?;
+        while self.eat_punct("||") {
+            lhs = lhs.or(self.and_expr()?);
+        }
+        Ok(lhs)
+    }
+
+    fn and_expr(&mut self) -> Result<PropertyExpr, PropertyError> {
+        let mut lhs = self.compare_expr()?;
+        while self.eat_punct("&&") {
+            lhs = lhs.and(self.compare_expr()?);
+        }
+        Ok(lhs)
+    }
+
+    fn compare_expr(&mut self) -> Result<PropertyExpr, PropertyError> {
+        let lhs = self.unary_expr()?;
+        let op = match self.peek() {
+            Token::Punct("==") => CompareOp::Eq,
+            Token::Punct("!=") => CompareOp::Ne,
+            Token::Punct("<") => CompareOp::Lt,
+            Token::Punct("<=") => CompareOp::Le,
+            Token::Punct(">") => CompareOp::Gt,
+            Token::Punct(">=") => CompareOp::Ge,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        Ok(lhs.compare(op, self.unary_expr()?))
+    }
+
+    fn unary_expr(&mut self) -> Result<PropertyExpr, PropertyError> {
+        if self.eat_punct("!") {
+            return Ok(!self.unary_expr()?);
+        }
+        let position = self.position();
+        match self.next() {
+            Token::Punct("(") => {
+                let inner = self.expr()?;
+                self.expect_punct(")")?;
+                Ok(inner)
+            }
+            Token::Number(value) => Ok(PropertyExpr::Const(value)),
+            Token::Ident(name) => Ok(PropertyExpr::Signal(name)),
+            Token::SysFunc(func) => {
+                self.expect_punct("(")?;
+                let name = self.ident()?;
+                self.expect_punct(")")?;
+                match func.as_str() {
+                    "rose" => Ok(PropertyExpr::Rose(name)),
+                    "fell" => Ok(PropertyExpr::Fell(name)),
+                    "stable" => Ok(PropertyExpr::Stable(name)),
+                    "past" => Ok(PropertyExpr::Past(name)),
+                    _ => Err(syntax(position, format!("unsupported function '${func}'"))),
+                }
+            }
+            _ => Err(syntax(position, "expected an expression")),
+        }
+    }
+}
+
+// ── Checker ─────────────────────────────────────────────────────────
+
+/// A [`PropertyExpr`] with signal names resolved to sample slots.
+#[derive(Debug, Clone)]
+enum Node {
+    Sample(usize),
+    Const(BigUint),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Compare(CompareOp, Box<Node>, Box<Node>),
+    Rose(usize),
+    Fell(usize),
+    Stable(usize),
+    Past(usize),
+}
+
+type Samples = [(BigUint, BigUint)];
+
+fn known(sample: &(BigUint, BigUint)) -> Option<&BigUint> {
+    sample.1.is_zero().then_some(&sample.0)
+}
+
+fn lsb(sample: &(BigUint, BigUint)) -> Option<bool> {
+    (!sample.1.bit(0)).then(|| sample.0.bit(0))
+}
+
+fn truth(value: bool) -> Option<BigUint> {
+    Some(BigUint::from(value as u8))
+}
+
+impl Node {
+    /// Evaluate to a known value, or `None` when X or Z bits make it unknown.
+    fn eval(&self, now: &Samples, prev: Option<&Samples>) -> Option<BigUint> {
+        match self {
+            Self::Sample(slot) => known(&now[*slot]).cloned(),
+            Self::Const(value) => Some(value.clone()),
+            Self::Not(inner) => inner
+                .eval(now, prev)
+                .map(|v| BigUint::from(v.is_zero() as u8)),
+            Self::And(lhs, rhs) => match (lhs.eval(now, prev), rhs.eval(now, prev)) {
+                (Some(l), _) if l.is_zero() => truth(false),
+                (_, Some(r)) if r.is_zero() => truth(false),
+                (Some(_), Some(_)) => truth(true),
+                _ => None,
+            },
+            Self::Or(lhs, rhs) => match (lhs.eval(now, prev), rhs.eval(now, prev)) {
+                (Some(l), _) if !l.is_zero() => truth(true),
+                (_, Some(r)) if !r.is_zero() => truth(true),
+                (Some(_), Some(_)) => truth(false),
+                _ => None,
+            },
+            Self::Compare(op, lhs, rhs) => {
+                let (l, r) = (lhs.eval(now, prev)?, rhs.eval(now, prev)?);
+                truth(match op {
+                    CompareOp::Eq => l == r,
+                    CompareOp::Ne => l != r,
+                    CompareOp::Lt => l < r,
+                    CompareOp::Le => l <= r,
+                    CompareOp::Gt => l > r,
+                    CompareOp::Ge => l >= r,
+                })
+            }
+            // Sampled-value functions are false on the first edge, which has
+            // no previous sample to compare against.
+            Self::Rose(slot) => truth(
+                lsb(&now[*slot]) == Some(true)
+                    && prev.is_some_and(|prev| lsb(&prev[*slot]) != Some(true)),
+            ),
+            Self::Fell(slot) => truth(
+                lsb(&now[*slot]) == Some(false)
+                    && prev.is_some_and(|prev| lsb(&prev[*slot]) != Some(false)),
+            ),
+            Self::Stable(slot) => truth(prev.is_some_and(|prev| prev[*slot] == now[*slot])),
+            Self::Past(slot) => prev.and_then(|prev| known(&prev[*slot]).cloned()),
+        }
+    }
+
+    fn holds(&self, now: &Samples, prev: Option<&Samples>) -> bool {
+        self.eval(now, prev).is_some_and(|value| !value.is_zero())
+    }
+}
+
+/// A partial match: step `step` of a sequence is due between `min` and `max`
+/// edges from now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Thread {
+    step: usize,
+    min: u32,
+    max: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledSequence {
+    steps: Vec<(Delay, Node)>,
+}
+
+impl CompiledSequence {
+    fn start(&self, extra_delay: u32) -> Thread {
+        let delay = self.steps[0].0;
+        Thread {
+            step: 0,
+            min: delay.min + extra_delay,
+            max: delay.max.map(|max| max + extra_delay),
+        }
+    }
+
+    /// Consume one edge. Returns how many threads completed the sequence on
+    /// this edge and leaves the threads still pending in `threads`.
+    fn advance(&self, threads: &mut Vec<Thread>, now: &Samples, prev: Option<&Samples>) -> usize {
+        let mut matches = 0;
+        let mut pending = Vec::new();
+        let mut work = std::mem::take(threads);
+        while let Some(thread) = work.pop() {
+            if thread.min > 0 {
+                pending.push(Thread {
+                    min: thread.min - 1,
+                    max: thread.max.map(|max| max - 1),
+                    ..thread
+                });
+                continue;
+            }
+            if thread.max != Some(0) {
+                pending.push(Thread {
+                    max: thread.max.map(|max| max - 1),
+                    ..thread
+                });
+            }
+            if !self.steps[thread.step].1.holds(now, prev) {
+                continue;
+            }
+            match self.steps.get(thread.step + 1) {
+                None => matches += 1,
+                Some((delay, _)) => work.push(Thread {
+                    step: thread.step + 1,
+                    min: delay.min,
+                    max: delay.max,
+                }),
+            }
+        }
+        pending.sort_by_key(|thread| (thread.step, thread.min, thread.max));
+        pending.dedup();
+        *threads = pending;
+        matches
+    }
+}
+
+/// Threads of one attempt, tagged with the time the attempt started.
+#[derive(Debug, Clone)]
+struct Attempt {
+    start_time: u64,
+    threads: Vec<Thread>,
+}
+
+#[derive(Debug, Clone)]
+struct Checker {
+    id: PropertyId,
+    name: String,
+    clock: usize,
+    edge: ClockEdge,
+    disable_iff: Option<Node>,
+    antecedent: Option<(CompiledSequence, Implication)>,
+    consequent: CompiledSequence,
+    /// Antecedent matches still in progress.
+    triggers: Vec<Attempt>,
+    /// Consequent obligations that must match before their threads run out.
+    obligations: Vec<Attempt>,
+    prev: Option<Vec<(BigUint, BigUint)>>,
+}
+
+impl Checker {
+    fn clocked(&self, before: &Samples, clock_now: &(BigUint, BigUint)) -> bool {
+        let target = self.edge == ClockEdge::Posedge;
+        lsb(&before[self.clock]) == Some(!target) && lsb(clock_now) == Some(target)
+    }
+
+    fn on_edge(
+        &mut self,
+        time: u64,
+        now: Vec<(BigUint, BigUint)>,
+        failures: &mut Vec<PropertyFailure>,
+    ) {
+        let prev = self.prev.as_deref();
+        if self
+            .disable_iff
+            .as_ref()
+            .is_some_and(|disable| disable.holds(&now, prev))
+        {
+            self.triggers.clear();
+            self.obligations.clear();
+            self.prev = Some(now);
+            return;
+        }
+
+        match &self.antecedent {
+            Some((antecedent, implication)) => {
+                self.triggers.push(Attempt {
+                    start_time: time,
+                    threads: vec![antecedent.start(0)],
+                });
+                let extra_delay = match implication {
+                    Implication::Overlapping => 0,
+                    Implication::NonOverlapping => 1,
+                };
+                let mut fired = Vec::new();
+                self.triggers.retain_mut(|attempt| {
+                    let matches = antecedent.advance(&mut attempt.threads, &now, prev);
+                    for _ in 0..matches {
+                        fired.push(Attempt {
+                            start_time: attempt.start_time,
+                            threads: vec![self.consequent.start(extra_delay)],
+                        });
+                    }
+                    !attempt.threads.is_empty()
+                });
+                self.obligations.extend(fired);
+            }
+            None => self.obligations.push(Attempt {
+                start_time: time,
+                threads: vec![self.consequent.start(0)],
+            }),
+        }
+
+        let consequent = &self.consequent;
+        self.obligations.retain_mut(|attempt| {
+            if consequent.advance(&mut attempt.threads, &now, prev) > 0 {
+                return false;
+            }
+            if attempt.threads.is_empty() {
+                failures.push(PropertyFailure {
+                    id: self.id,
+                    name: self.name.clone(),
+                    start_time: attempt.start_time,
+                    time,
+                });
+                return false;
+            }
+            true
+        });
+        self.prev = Some(now);
+    }
+}
+
+/// Properties registered on a [`crate::Simulation`] and the failures they
+/// reported.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PropertySet {
+    next_id: PropertyId,
+    signals: Vec<SignalRef>,
+    slots: crate::HashMap<String, usize>,
+    checkers: Vec<Checker>,
+    failures: Vec<PropertyFailure>,
+}
+
+impl PropertySet {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.checkers.is_empty()
+    }
+
+    /// Register `property`, resolving its signal names with `resolve`.
+    pub(crate) fn add(
+        &mut self,
+        name: &str,
+        property: &Property,
+        mut resolve: impl FnMut(&str) -> Option<SignalRef>,
+    ) -> Result<PropertyId, PropertyError> {
+        let mut slot = |name: &str| -> Result<usize, PropertyError> {
+            if let Some(&slot) = self.slots.get(name) {
+                return Ok(slot);
+            }
+            let signal =
+                resolve(name).ok_or_else(|| PropertyError::UnknownSignal(name.to_string()))?;
+            self.signals.push(signal);
+            self.slots.insert(name.to_string(), self.signals.len() - 1);
+            Ok(self.signals.len() - 1)
+        };
+
+        let clock = slot(&property.clock)?;
+        let mut names = Vec::new();
+        let mut collect =
+            |expr: &PropertyExpr| expr.for_each_signal(&mut |name| names.push(name.to_string()));
+        property.disable_iff.iter().for_each(&mut collect);
+        for sequence in property
+            .antecedent
+            .iter()
+            .map(|(sequence, _)| sequence)
+            .chain([&property.consequent])
+        {
+            sequence.steps.iter().for_each(|(_, expr)| collect(expr));
+        }
+        for name in names {
+            slot(&name)?;
+        }
+
+        let slots = &self.slots;
+        let compile_sequence = |sequence: &Sequence| CompiledSequence {
+            steps: sequence
+                .steps
+                .iter()
+                .map(|(delay, expr)| (*delay, compile(expr, slots)))
+                .collect(),
+        };
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.checkers.push(Checker {
+            id,
+            name: name.to_string(),
+            clock,
+            edge: property.edge,
+            disable_iff: property
+                .disable_iff
+                .as_ref()
+                .map(|expr| compile(expr, slots)),
+            antecedent: property
+                .antecedent
+                .as_ref()
+                .map(|(sequence, implication)| (compile_sequence(sequence), *implication)),
+            consequent: compile_sequence(&property.consequent),
+            triggers: Vec::new(),
+            obligations: Vec::new(),
+            prev: None,
+        });
+        Ok(id)
+    }
+
+    /// Remove a property. Returns `false` if `id` was not registered.
+    pub(crate) fn remove(&mut self, id: PropertyId) -> bool {
+        let len = self.checkers.len();
+        self.checkers.retain(|checker| checker.id != id);
+        self.checkers.len() != len
+    }
+
+    /// Abandon every attempt in flight, e.g. after the state was restored.
+    pub(crate) fn reset(&mut self) {
+        for checker in &mut self.checkers {
+            checker.triggers.clear();
+            checker.obligations.clear();
+            checker.prev = None;
+        }
+    }
+
+    /// Read every referenced signal before a time step.
+    pub(crate) fn sample(
+        &self,
+        mut read: impl FnMut(SignalRef) -> (BigUint, BigUint),
+    ) -> Vec<(BigUint, BigUint)> {
+        self.signals.iter().map(|&signal| read(signal)).collect()
+    }
+
+    /// Evaluate every property whose clock edge occurred in the step that
+    /// ended at `time`, on the values `before` sampled ahead of the step.
+    pub(crate) fn on_step(
+        &mut self,
+        time: u64,
+        before: Vec<(BigUint, BigUint)>,
+        mut read: impl FnMut(SignalRef) -> (BigUint, BigUint),
+    ) {
+        for checker in &mut self.checkers {
+            let clock_now = read(self.signals[checker.clock]);
+            if checker.clocked(&before, &clock_now) {
+                checker.on_edge(time, before.clone(), &mut self.failures);
+            }
+        }
+    }
+
+    pub(crate) fn failures(&self) -> &[PropertyFailure] {
+        &self.failures
+    }
+
+    pub(crate) fn take_failures(&mut self) -> Vec<PropertyFailure> {
+        std::mem::take(&mut self.failures)
+    }
+}
+
+fn compile(expr: &PropertyExpr, slots: &crate::HashMap<String, usize>) -> Node {
+    let slot = |name: &String| slots[name.as_str()];
+    let boxed = |expr: &PropertyExpr| Box::new(compile(expr, slots));
+    match expr {
+        PropertyExpr::Signal(name) => Node::Sample(slot(name)),
+        PropertyExpr::Const(value) => Node::Const(value.clone()),
+        PropertyExpr::Not(inner) => Node::Not(boxed(inner)),
+        PropertyExpr::And(lhs, rhs) => Node::And(boxed(lhs), boxed(rhs)),
+        PropertyExpr::Or(lhs, rhs) => Node::Or(boxed(lhs), boxed(rhs)),
+        PropertyExpr::Compare(op, lhs, rhs) => Node::Compare(*op, boxed(lhs), boxed(rhs)),
+        PropertyExpr::Rose(name) => Node::Rose(slot(name)),
+        PropertyExpr::Fell(name) => Node::Fell(slot(name)),
+        PropertyExpr::Stable(name) => Node::Stable(slot(name)),
+        PropertyExpr::Past(name) => Node::Past(slot(name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signal(name: &str) -> PropertyExpr {
+        PropertyExpr::signal(name)
+    }
+
+    #[test]
+    fn parses_implication_with_delay_range() {
+        let property: Property = "@(posedge clk) disable iff (rst) req |-> ##[1:4] ack"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            property,
+            Property::implies(
+                "clk",
+                Sequence::new(signal("req")),
+                Implication::Overlapping,
+                Sequence::after(Delay::range(1, Some(4)), signal("ack")),
+            )
+            .disable_iff(signal("rst"))
+        );
+    }
+
+    #[test]
+    fn parses_sampled_functions_and_comparisons() {
+        let property: Property = "@(negedge clk) $rose(valid) |=> !valid ##[0:$] state == 8'h1f"
+            .parse()
+            .unwrap();
+        assert_eq!(property.edge, ClockEdge::Negedge);
+        assert_eq!(
+            property.antecedent,
+            Some((
+                Sequence::new(PropertyExpr::rose("valid")),
+                Implication::NonOverlapping
+            ))
+        );
+        assert_eq!(
+            property.consequent,
+            Sequence::new(!signal("valid")).then(
+                Delay::range(0, None),
+                signal("state").compare(CompareOp::Eq, PropertyExpr::Const(BigUint::from(0x1fu8)))
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_properties() {
+        assert_eq!(
+            "req |-> ack".parse::<Property>(),
+            Err(PropertyError::MissingClock)
+        );
+        assert!(matches!(
+            "@(posedge clk) req |-> ##[4:1] ack".parse::<Property>(),
+            Err(PropertyError::Syntax { .. })
+        ));
+        assert!(matches!(
+            "@(posedge clk) $onehot(req)".parse::<Property>(),
+            Err(PropertyError::Syntax { .. })
+        ));
+    }
+
+    #[test]
+    fn range_threads_match_anywhere_in_the_window() {
+        let sequence = CompiledSequence {
+            steps: vec![
+                (Delay::NONE, Node::Sample(0)),
+                (Delay::range(1, Some(2)), Node::Sample(1)),
+            ],
+        };
+        let sample = |a: u8, b: u8| {
+            vec![
+                (BigUint::from(a), BigUint::zero()),
+                (BigUint::from(b), BigUint::zero()),
+            ]
+        };
+        let mut threads = vec![sequence.start(0)];
+        assert_eq!(sequence.advance(&mut threads, &sample(1, 0), None), 0);
+        assert_eq!(sequence.advance(&mut threads, &sample(0, 0), None), 0);
+        assert_eq!(sequence.advance(&mut threads, &sample(0, 1), None), 1);
+        assert!(threads.is_empty());
+    }
+}
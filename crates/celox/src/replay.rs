@@ -0,0 +1,362 @@
+//! Driving a simulation from a recorded waveform.
+//!
+//! [`Simulation::replay_inputs`] reads a VCD or FST file, queues every
+//! recorded change of a top-level input at its recorded time, and runs the
+//! simulation to the end of the recording. Clock and reset ports are queued
+//! as events, so edges fire exactly as in a testbench that schedules them;
+//! data inputs are queued with [`Simulation::schedule_value_four_state`].
+//! With output checking enabled, every top-level output is compared with
+//! the recording at each recorded time, after that time has settled.
+
+use crate::{RuntimeErrorCode, SimBackend, Simulation, VariableKind, ir::SignalRef};
+use celox_runtime::{Timescale, Waveform, WaveformReadError, WaveformSignal};
+use num_bigint::BigUint;
+
+/// How recorded signals are matched to the design's top-level ports.
+///
+/// By default each port is matched to the recorded signal with the same name
+/// in the shallowest scope that has one, which finds `TOP.clk` in a dump
+/// whose hierarchy is `TOP.top.clk`. [`Self::scope`] names the scope
+/// explicitly and [`Self::signal`] pairs a port with any recorded signal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayMapping {
+    scope: Option<String>,
+    signals: Vec<(String, String)>,
+    check_outputs: bool,
+}
+
+impl ReplayMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match ports to the signals directly under `scope` (`""` for signals
+    /// outside any scope).
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scope = Some(scope.to_string());
+        self
+    }
+
+    /// Match `port` to the recorded signal with hierarchical name `recorded`.
+    pub fn signal(mut self, recorded: &str, port: &str) -> Self {
+        self.signals.push((recorded.to_string(), port.to_string()));
+        self
+    }
+
+    /// Compare the design's outputs against the recorded outputs.
+    pub fn check_outputs(mut self, check: bool) -> Self {
+        self.check_outputs = check;
+        self
+    }
+}
+
+/// An output that differs from the recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayMismatch {
+    pub time: u64,
+    pub port: String,
+    /// Recorded value; its `x` bits match any value.
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Outcome of [`Simulation::replay_inputs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Inputs that were driven from the recording.
+    pub inputs: Vec<String>,
+    /// Outputs that were checked against the recording.
+    pub outputs: Vec<String>,
+    /// Output mismatches, in time order.
+    pub mismatches: Vec<ReplayMismatch>,
+}
+
+impl ReplayReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Failure while replaying a waveform.
+#[derive(Debug)]
+pub enum ReplayError {
+    Read(WaveformReadError),
+    /// A mapping names a port the design does not have.
+    UnknownPort(String),
+    /// A mapping names a signal the recording does not have.
+    UnknownSignal(String),
+    /// The recorded signal and the port have different widths.
+    WidthMismatch {
+        port: String,
+        port_width: usize,
+        recorded_width: usize,
+    },
+    /// A recorded value is not a bit vector.
+    UnsupportedValue {
+        port: String,
+        value: String,
+    },
+    /// A recorded time is not a whole number of simulation ticks.
+    TimeNotRepresentable(u64),
+    Runtime(RuntimeErrorCode),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "failed to read the waveform: {error}"),
+            Self::UnknownPort(port) => write!(f, "the design has no top-level port '{port}'"),
+            Self::UnknownSignal(name) => write!(f, "the waveform has no signal '{name}'"),
+            Self::WidthMismatch {
+                port,
+                port_width,
+                recorded_width,
+            } => write!(
+                f,
+                "port '{port}' is {port_width} bits wide but the recording is {recorded_width} bits"
+            ),
+            Self::UnsupportedValue { port, value } => {
+                write!(f, "cannot drive '{port}' with recorded value '{value}'")
+            }
+            Self::TimeNotRepresentable(time) => write!(
+                f,
+                "recorded time {time} is not a whole number of simulation ticks"
+            ),
+            Self::Runtime(error) => write!(f, "{error:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<WaveformReadError> for ReplayError {
+    fn from(error: WaveformReadError) -> Self {
+        Self::Read(error)
+    }
+}
+
+impl From<RuntimeErrorCode> for ReplayError {
+    fn from(error: RuntimeErrorCode) -> Self {
+        Self::Runtime(error)
+    }
+}
+
+struct ReplayPort<'a> {
+    name: String,
+    signal: SignalRef,
+    recorded: &'a WaveformSignal,
+}
+
+impl<B: SimBackend> Simulation<B> {
+    /// Drive the top-level inputs from the waveform at `path` and run to its
+    /// last recorded time. Recorded time 0 is the current simulation time.
+    /// When both the recording and the simulation have a timescale, recorded
+    /// times are converted into simulation ticks.
+    pub fn replay_inputs(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+        mapping: &ReplayMapping,
+    ) -> Result<ReplayReport, ReplayError> {
+        let waveform = Waveform::read(path)?;
+        let scale = TimeScale::new(waveform.timescale, self.timescale);
+        let start = self.time();
+
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        for (port, recorded) in self.match_ports(&waveform, mapping)? {
+            if recorded.width != port.signal.width {
+                return Err(ReplayError::WidthMismatch {
+                    port: port.name,
+                    port_width: port.signal.width,
+                    recorded_width: recorded.width,
+                });
+            }
+            let target = ReplayPort {
+                name: port.name,
+                signal: port.signal,
+                recorded,
+            };
+            match port.info.var_kind {
+                VariableKind::Input => inputs.push(target),
+                VariableKind::Output => outputs.push(target),
+                _ => {}
+            }
+        }
+
+        for input in &inputs {
+            let addr = self
+                .simulator
+                .program
+                .get_addr(&[], &[input.name.as_str()])
+                .expect("top-level port has an address");
+            let event = self.simulator.backend.resolve_event_opt(&addr);
+            for (time, value) in &input.recorded.changes {
+                let time = start + scale.ticks(*time)?;
+                let (value, mask) =
+                    parse_bits(value).ok_or_else(|| ReplayError::UnsupportedValue {
+                        port: input.name.clone(),
+                        value: value.clone(),
+                    })?;
+                match event {
+                    Some(event) => {
+                        let level = u8::from(value.bit(0) && !mask.bit(0));
+                        self.state.schedule(event, input.signal, time, level);
+                    }
+                    None => self.schedule_value_four_state(input.signal, time, value, mask),
+                }
+            }
+        }
+
+        let mut times = inputs
+            .iter()
+            .chain(&outputs)
+            .flat_map(|port| port.recorded.changes.iter().map(|(time, _)| *time))
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        times.dedup();
+
+        let mut report = ReplayReport {
+            inputs: inputs.iter().map(|port| port.name.clone()).collect(),
+            outputs: Vec::new(),
+            mismatches: Vec::new(),
+        };
+        if !mapping.check_outputs {
+            if let Some(&last) = times.last() {
+                self.run_until(start + scale.ticks(last)?)?;
+            }
+            return Ok(report);
+        }
+        report.outputs = outputs.iter().map(|port| port.name.clone()).collect();
+        for time in times {
+            let now = start + scale.ticks(time)?;
+            self.run_until(now)?;
+            for output in &outputs {
+                let Some(expected) = output.recorded.value_at(time) else {
+                    continue;
+                };
+                let (value, mask) = self.get_four_state(output.signal);
+                let actual = format_bits(&value, &mask, output.signal.width);
+                if !bits_match(expected, &actual) {
+                    report.mismatches.push(ReplayMismatch {
+                        time: now,
+                        port: output.name.clone(),
+                        expected: expected.to_string(),
+                        actual,
+                    });
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn match_ports<'w>(
+        &self,
+        waveform: &'w Waveform,
+        mapping: &ReplayMapping,
+    ) -> Result<Vec<(crate::NamedSignal, &'w WaveformSignal)>, ReplayError> {
+        let ports = self.named_signals();
+        let mut matched = Vec::new();
+        for (recorded, port) in &mapping.signals {
+            let named = ports
+                .iter()
+                .find(|named| &named.name == port)
+                .ok_or_else(|| ReplayError::UnknownPort(port.clone()))?;
+            let signal = waveform
+                .signal(recorded)
+                .ok_or_else(|| ReplayError::UnknownSignal(recorded.clone()))?;
+            matched.push((named.clone(), signal));
+        }
+        for named in ports {
+            if mapping.signals.iter().any(|(_, port)| *port == named.name) {
+                continue;
+            }
+            let candidates = waveform.signals.iter().filter(|signal| {
+                let Some(scope) = signal
+                    .name
+                    .strip_suffix(named.name.as_str())
+                    .and_then(|scope| scope.strip_suffix('.').or(scope.is_empty().then_some("")))
+                else {
+                    return false;
+                };
+                mapping.scope.as_ref().is_none_or(|wanted| wanted == scope)
+            });
+            if let Some(signal) = candidates.min_by_key(|signal| signal.name.matches('.').count()) {
+                matched.push((named, signal));
+            }
+        }
+        Ok(matched)
+    }
+}
+
+/// Converts recorded times into simulation ticks.
+enum TimeScale {
+    Multiply(u64),
+    Divide(u64),
+}
+
+impl TimeScale {
+    fn new(recorded: Option<Timescale>, simulation: Option<Timescale>) -> Self {
+        match (recorded, simulation) {
+            (Some(recorded), Some(simulation)) => {
+                let (recorded, simulation) = (recorded.femtoseconds(), simulation.femtoseconds());
+                if recorded >= simulation {
+                    Self::Multiply(recorded / simulation)
+                } else {
+                    Self::Divide(simulation / recorded)
+                }
+            }
+            _ => Self::Multiply(1),
+        }
+    }
+
+    fn ticks(&self, time: u64) -> Result<u64, ReplayError> {
+        match *self {
+            Self::Multiply(factor) => time
+                .checked_mul(factor)
+                .ok_or(ReplayError::TimeNotRepresentable(time)),
+            Self::Divide(divisor) if time.is_multiple_of(divisor) => Ok(time / divisor),
+            Self::Divide(_) => Err(ReplayError::TimeNotRepresentable(time)),
+        }
+    }
+}
+
+/// Split a `0`/`1`/`x`/`z` string into value and mask planes: `x` sets both
+/// bits and `z` only the mask bit.
+fn parse_bits(bits: &str) -> Option<(BigUint, BigUint)> {
+    let mut value = BigUint::default();
+    let mut mask = BigUint::default();
+    for (index, bit) in bits.bytes().rev().enumerate() {
+        let index = index as u64;
+        match bit {
+            b'0' => {}
+            b'1' => value.set_bit(index, true),
+            b'x' => {
+                value.set_bit(index, true);
+                mask.set_bit(index, true);
+            }
+            b'z' => mask.set_bit(index, true),
+            _ => return None,
+        }
+    }
+    Some((value, mask))
+}
+
+fn format_bits(value: &BigUint, mask: &BigUint, width: usize) -> String {
+    (0..width as u64)
+        .rev()
+        .map(|index| match (mask.bit(index), value.bit(index)) {
+            (false, false) => '0',
+            (false, true) => '1',
+            (true, true) => 'x',
+            (true, false) => 'z',
+        })
+        .collect()
+}
+
+fn bits_match(expected: &str, actual: &str) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .bytes()
+            .zip(actual.bytes())
+            .all(|(expected, actual)| expected == b'x' || expected == actual)
+}
@@ -4,16 +4,23 @@ use crate::{
     ir::SignalRef,
     simulator::{InstanceHierarchy, NamedEvent, NamedSignal},
 };
-use celox_runtime::{EventInfo, SimulationExecutor, SimulationState};
+use celox_runtime::{
+    ClockSpec, EventInfo, SimulationExecutor, SimulationState, Timescale, TimescaleError,
+};
 
 /// A timed simulation wrapper around the core logic engine.
 ///
 /// Manages simulation time, periodic clocks, and an event queue.
 ///
+/// Time is counted in unitless ticks. After [`Self::set_timescale`], physical
+/// durations can be converted with [`Self::ticks`] and [`Self::time_in`].
+///
 /// The default type parameter uses the host's [`crate::DefaultBackend`].
 pub struct Simulation<B: SimBackend = crate::DefaultBackend> {
     pub(crate) simulator: Simulator<B>,
     pub(crate) state: SimulationState<B>,
+    pub(crate) timescale: Option<Timescale>,
+    pub(crate) properties: crate::property::PropertySet,
 }
 
 impl<B: SimBackend> std::fmt::Debug for Simulation<B> {
@@ -79,9 +86,15 @@ impl<B: SimBackend> SimulationExecutor for Simulator<B> {
         Ok(())
     }
 
+    fn check_cascade_round(&mut self, round: u32, timestamp: u64) -> Result<(), RuntimeErrorCode> {
+        self.watchdog.check_cascade_round(round, timestamp)
+    }
+
     fn finish_timed_step(&mut self, timestamp: u64) {
         self.dirty = false;
+        self.watch_time = timestamp;
         self.dump(timestamp);
+        self.notify_watchers();
     }
 }
 
@@ -189,7 +202,12 @@ pub(crate) fn simulation_state<B: SimBackend>(simulator: &Simulator<B>) -> Simul
 impl<B: SimBackend> Simulation<B> {
     pub(crate) fn new(simulator: Simulator<B>) -> Self {
         let state = simulation_state(&simulator);
-        Self { simulator, state }
+        Self {
+            simulator,
+            state,
+            timescale: None,
+            properties: Default::default(),
+        }
     }
 
     /// Returns warnings emitted during compilation.
@@ -202,6 +220,20 @@ impl<B: SimBackend> Simulation<B> {
         self.simulator.dump(timestamp);
     }
 
+    /// Resume waveform recording, writing every current value at the
+    /// current time.
+    pub fn dump_on(&mut self) {
+        self.simulator.dump_on();
+        self.dump(self.time());
+    }
+
+    /// Stop waveform recording. Signals read as X from the current time
+    /// until [`Self::dump_on`].
+    pub fn dump_off(&mut self) {
+        self.simulator.dump_off();
+        self.dump(self.time());
+    }
+
     /// Resolves a signal path into a performance-optimized [`SignalRef`].
     pub fn signal(&self, path: &str) -> SignalRef {
         self.simulator.signal(path)
@@ -230,6 +262,48 @@ impl<B: SimBackend> Simulation<B> {
         }
     }
 
+    /// Register a clock with the duty cycle and phase described by `spec`,
+    /// replacing any clock already on `port`.
+    pub fn add_clock_with(&mut self, port: &str, spec: ClockSpec) -> Result<(), RuntimeErrorCode> {
+        let (ev, signal) = self.resolve_port_event(port)?;
+        self.state.add_clock_with(ev, signal, spec);
+        Ok(())
+    }
+
+    /// Stop the clock on `port`, holding its current level until
+    /// [`Self::resume_clock`]. Does nothing if `port` has no clock.
+    pub fn pause_clock(&mut self, port: &str) -> Result<(), RuntimeErrorCode> {
+        let (ev, _) = self.resolve_port_event(port)?;
+        self.state.pause_clock(ev);
+        Ok(())
+    }
+
+    /// Restart a clock stopped by [`Self::pause_clock`]. Edges stay aligned
+    /// to the clock's original phase, so the first edge after the current
+    /// time that changes its level is the next one generated.
+    pub fn resume_clock(&mut self, port: &str) -> Result<(), RuntimeErrorCode> {
+        let (ev, signal) = self.resolve_port_event(port)?;
+        self.state.resume_clock(&self.simulator.backend, ev, signal);
+        Ok(())
+    }
+
+    /// Unregister the clock on `port` and drop its pending edges. The signal
+    /// keeps its current level.
+    pub fn remove_clock(&mut self, port: &str) -> Result<(), RuntimeErrorCode> {
+        let (ev, _) = self.resolve_port_event(port)?;
+        self.state.remove_clock(ev);
+        Ok(())
+    }
+
+    fn resolve_port_event(&self, port: &str) -> Result<(B::Event, SignalRef), RuntimeErrorCode> {
+        let signal = self.simulator.signal(port);
+        let addr = self.simulator.program.get_addr(&[], &[port]).unwrap();
+        match self.simulator.backend.resolve_event_opt(&addr) {
+            Some(ev) => Ok((ev, signal)),
+            None => Err(RuntimeErrorCode::NotAnEvent(port.to_string())),
+        }
+    }
+
     /// Schedule a one-shot event at a specific time.
     /// The signal must be registered as an event (clock or async reset) in the backend.
     pub fn schedule(&mut self, port: &str, time: u64, value: u64) -> Result<(), RuntimeErrorCode> {
@@ -245,35 +319,405 @@ impl<B: SimBackend> Simulation<B> {
         Ok(())
     }
 
+    /// Queue a write of `value` to `signal` at `time`.
+    ///
+    /// Unlike [`Self::schedule`], any signal can be driven, at any width.
+    /// Writes to data signals are applied after the clock and reset edges at
+    /// the same time, so flip-flops clocked at `time` still sample the old
+    /// value. Writes at the same time apply in the order they were queued.
+    pub fn schedule_value(&mut self, signal: SignalRef, time: u64, value: num_bigint::BigUint) {
+        self.schedule_value_four_state(signal, time, value, num_bigint::BigUint::default());
+    }
+
+    /// Like [`Self::schedule_value`], but with an explicit X/Z mask.
+    pub fn schedule_value_four_state(
+        &mut self,
+        signal: SignalRef,
+        time: u64,
+        value: num_bigint::BigUint,
+        mask: num_bigint::BigUint,
+    ) {
+        self.state
+            .schedule_value(&self.simulator.backend, signal, time, value, mask);
+    }
+
     /// Advance time to the next scheduled event and process all events at that time.
     /// Returns the new simulation time, or None if no events are scheduled.
     pub fn step(&mut self) -> Result<Option<u64>, RuntimeErrorCode> {
-        self.state.step(&mut self.simulator)
+        self.simulator.watchdog.arm();
+        self.step_watched()
+    }
+
+    /// [`Self::step`] within the wall-clock budget armed by the caller.
+    fn step_watched(&mut self) -> Result<Option<u64>, RuntimeErrorCode> {
+        self.simulator.watchdog.check(self.state.time())?;
+        if self.properties.is_empty() {
+            return self.state.step(&mut self.simulator);
+        }
+        // Inputs written through shared memory do not mark the simulator
+        // dirty, so settle explicitly before taking the preponed samples.
+        self.simulator.eval_comb_checked()?;
+        self.simulator.dirty = false;
+        let sampled = self
+            .properties
+            .sample(|signal| self.simulator.get_four_state(signal));
+        let time = self.state.step(&mut self.simulator)?;
+        if let Some(time) = time {
+            self.properties.on_step(time, sampled, |signal| {
+                self.simulator.get_four_state(signal)
+            });
+        }
+        Ok(time)
     }
 
     /// Advance time and run until `end_time` (inclusive).
+    ///
+    /// Fails with [`RuntimeErrorCode::Interrupted`] at the current time if the
+    /// [`Self::cancel_token`] fires or a limit set on the builder is exceeded.
+    /// Time steps completed before that point are kept.
     pub fn run_until(&mut self, end_time: u64) -> Result<(), RuntimeErrorCode> {
+        self.simulator.watchdog.arm();
         while let Some(next_time) = self.state.next_event_time() {
             if next_time > end_time {
                 break;
             }
-            self.step()?;
+            self.step_watched()?;
         }
         self.state.set_time(end_time);
+        self.simulator.watch_time = end_time;
         self.dump(end_time);
         Ok(())
     }
 
+    /// Returns the token that cancels [`Self::run_until`] and [`Self::step`]
+    /// from another thread. See [`Simulator::cancel_token`].
+    pub fn cancel_token(&self) -> celox_runtime::CancelToken {
+        self.simulator.cancel_token()
+    }
+
+    /// Returns the limits applied to each run call.
+    pub fn run_limits(&self) -> celox_runtime::RunLimits {
+        self.simulator.run_limits()
+    }
+
+    /// Replace the limits set on the builder.
+    pub fn set_run_limits(&mut self, limits: celox_runtime::RunLimits) {
+        self.simulator.set_run_limits(limits);
+    }
+
     /// Returns the current simulation time.
     pub fn time(&self) -> u64 {
         self.state.time()
     }
 
+    /// Set the physical length of one tick, e.g. `"1ns"` or `"10ps"`. The
+    /// timescale is also written to the VCD header, so it must be set before
+    /// the first dump.
+    pub fn set_timescale(&mut self, timescale: Timescale) -> Result<(), TimescaleError> {
+        if let Some(writer) = self.simulator.vcd_writer.as_mut() {
+            writer.set_timescale(timescale)?;
+        }
+        self.timescale = Some(timescale);
+        Ok(())
+    }
+
+    /// Returns the timescale set by [`Self::set_timescale`].
+    pub fn timescale(&self) -> Option<Timescale> {
+        self.timescale
+    }
+
+    /// Convert a duration such as `"10ns"` or `"2.5ns"` into ticks. Fails if
+    /// no timescale is set or the duration is not a whole number of ticks.
+    pub fn ticks(&self, duration: &str) -> Result<u64, TimescaleError> {
+        self.timescale.ok_or(TimescaleError::Unset)?.ticks(duration)
+    }
+
+    /// Returns the current simulation time in `unit` (`"s"`, `"ms"`, `"us"`,
+    /// `"ns"`, `"ps"`, or `"fs"`).
+    pub fn time_in(&self, unit: &str) -> Result<f64, TimescaleError> {
+        self.timescale
+            .ok_or(TimescaleError::Unset)?
+            .to_unit(self.time(), unit)
+    }
+
     /// Returns the time of the next scheduled event, if any.
     pub fn next_event_time(&self) -> Option<u64> {
         self.state.next_event_time()
     }
 
+    /// Branch this simulation into an independent instance that shares the
+    /// compiled code. Time, clocks, and pending events are copied; see
+    /// [`Simulator::fork`] for what else carries over.
+    pub fn fork(&self) -> Result<Self, crate::ForkError> {
+        Ok(Self {
+            simulator: self.simulator.fork()?,
+            state: self.state.clone(),
+            timescale: self.timescale,
+            properties: self.properties.clone(),
+        })
+    }
+
+    /// Like [`Self::fork`], but the fork continues the waveform in a new
+    /// file at `path`, in the same format.
+    pub fn fork_with_vcd(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, crate::ForkError> {
+        Ok(Self {
+            simulator: self.simulator.fork_with_vcd(path)?,
+            state: self.state.clone(),
+            timescale: self.timescale,
+            properties: self.properties.clone(),
+        })
+    }
+
+    /// Capture signal state, the scheduler queue, clocks, and current time.
+    pub fn save_state(&self) -> crate::SimulatorCheckpoint {
+        let mut checkpoint = self.simulator.save_state();
+        checkpoint.simulation = Some(self.state.snapshot());
+        checkpoint
+    }
+
+    /// Restore a checkpoint taken by [`Self::save_state`], including time and
+    /// pending events. Checkpoints taken from a bare [`Simulator`] are
+    /// rejected because they carry no scheduler state.
+    pub fn restore_state(
+        &mut self,
+        checkpoint: &crate::SimulatorCheckpoint,
+    ) -> Result<(), crate::CheckpointError> {
+        let Some(snapshot) = &checkpoint.simulation else {
+            return Err(crate::CheckpointError::MissingSimulationState);
+        };
+        self.simulator.restore_state(checkpoint)?;
+        self.simulator.watch_time = snapshot.time;
+        self.properties.reset();
+        self.state
+            .restore(snapshot, &self.simulator.backend)
+            .map_err(crate::CheckpointError::InvalidSimulationState)
+    }
+
+    /// Call `callback` whenever `signal` changes after a `step`, `modify`, or
+    /// `eval_comb`. The reported time is the simulation time. See
+    /// [`Simulator::watch`].
+    pub fn watch(
+        &mut self,
+        signal: SignalRef,
+        callback: impl FnMut(&crate::ValueChange) + Send + 'static,
+    ) -> crate::WatchId {
+        self.simulator.watch(signal, callback)
+    }
+
+    /// Remove a watch registered with [`Self::watch`].
+    pub fn unwatch(&mut self, id: crate::WatchId) {
+        self.simulator.unwatch(id);
+    }
+
+    /// Check `property`, written in SVA syntax such as
+    /// `@(posedge clk) disable iff (rst) req |-> ##[1:4] ack`, on every edge
+    /// of its clock from now on. Failed attempts are collected under `name`
+    /// and read with [`Self::property_failures`].
+    pub fn assert_property(
+        &mut self,
+        name: &str,
+        property: &str,
+    ) -> Result<crate::PropertyId, crate::PropertyError> {
+        self.add_property(name, &property.parse()?)
+    }
+
+    /// Like [`Self::assert_property`], for a property built in Rust.
+    pub fn add_property(
+        &mut self,
+        name: &str,
+        property: &crate::Property,
+    ) -> Result<crate::PropertyId, crate::PropertyError> {
+        let simulator = &self.simulator;
+        self.properties
+            .add(name, property, |path| simulator.try_signal(path).ok())
+    }
+
+    /// Stop checking a property registered with [`Self::assert_property`].
+    /// Failures it already reported are kept.
+    pub fn remove_property(&mut self, id: crate::PropertyId) {
+        self.properties.remove(id);
+    }
+
+    /// Failed property attempts, in the order they failed.
+    pub fn property_failures(&self) -> &[crate::PropertyFailure] {
+        self.properties.failures()
+    }
+
+    /// Return and clear the failures collected so far.
+    pub fn take_property_failures(&mut self) -> Vec<crate::PropertyFailure> {
+        self.properties.take_failures()
+    }
+
+    /// Start counting per-bit transitions from the current values. See
+    /// [`Simulator::enable_toggle_coverage`].
+    pub fn enable_toggle_coverage(&mut self) {
+        self.simulator.enable_toggle_coverage();
+    }
+
+    /// Stop counting transitions and return the counts so far.
+    pub fn disable_toggle_coverage(&mut self) -> Option<crate::ToggleCoverage> {
+        self.simulator.disable_toggle_coverage()
+    }
+
+    /// Transition counts, or `None` while toggle coverage is off. Durations
+    /// are in simulation ticks.
+    pub fn toggle_coverage(&self) -> Option<&crate::ToggleCoverage> {
+        self.simulator.toggle_coverage()
+    }
+
+    /// Write the switching activity to a SAIF file at `path`, in units of
+    /// the timescale set by [`Self::set_timescale`]. Fails if toggle
+    /// coverage is off.
+    pub fn write_saif(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let coverage = self.toggle_coverage().ok_or_else(|| {
+            std::io::Error::other("toggle coverage is not enabled for this simulation")
+        })?;
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        coverage.write_saif(&mut writer, self.timescale)?;
+        std::io::Write::flush(&mut writer)
+    }
+
+    /// Current branch hit counts. See [`Simulator::coverage`].
+    pub fn coverage(&mut self) -> crate::BranchCoverage {
+        self.simulator.coverage()
+    }
+
+    /// Start counting state visits and transitions. See
+    /// [`Simulator::enable_fsm_coverage`].
+    pub fn enable_fsm_coverage(&mut self) {
+        self.simulator.enable_fsm_coverage();
+    }
+
+    /// Stop counting state visits and return the counts so far.
+    pub fn disable_fsm_coverage(&mut self) -> Option<crate::FsmCoverage> {
+        self.simulator.disable_fsm_coverage()
+    }
+
+    /// State and transition counts, or `None` while FSM coverage is off.
+    pub fn fsm_coverage(&self) -> Option<&crate::FsmCoverage> {
+        self.simulator.fsm_coverage()
+    }
+
+    /// Time spent per execution unit and instance. See [`Simulator::profile`].
+    pub fn profile(&self) -> Option<crate::Profile> {
+        self.simulator.profile()
+    }
+
+    /// Clear the times collected for [`Self::profile`].
+    pub fn reset_profile(&mut self) {
+        self.simulator.reset_profile();
+    }
+
+    /// Hold `signal` at `value` until [`Self::release`] is called. See
+    /// [`Simulator::force`].
+    pub fn force(&mut self, signal: SignalRef, value: num_bigint::BigUint) {
+        self.simulator.force(signal, value);
+    }
+
+    /// Like [`Self::force`], but with an explicit X/Z mask.
+    pub fn force_four_state(
+        &mut self,
+        signal: SignalRef,
+        value: num_bigint::BigUint,
+        mask: num_bigint::BigUint,
+    ) {
+        self.simulator.force_four_state(signal, value, mask);
+    }
+
+    /// Release a force placed by [`Self::force`].
+    pub fn release(&mut self, signal: SignalRef) {
+        self.simulator.release(signal);
+    }
+
+    /// Returns `true` if `signal` is currently forced.
+    pub fn is_forced(&self, signal: SignalRef) -> bool {
+        self.simulator.is_forced(signal)
+    }
+
+    /// Write `value` once without holding it. See [`Simulator::deposit`].
+    pub fn deposit(&mut self, signal: SignalRef, value: num_bigint::BigUint) {
+        self.simulator.deposit(signal, value);
+    }
+
+    /// Like [`Self::deposit`], but with an explicit X/Z mask.
+    pub fn deposit_four_state(
+        &mut self,
+        signal: SignalRef,
+        value: num_bigint::BigUint,
+        mask: num_bigint::BigUint,
+    ) {
+        self.simulator.deposit_four_state(signal, value, mask);
+    }
+
+    /// Reads one element of an unpacked array. See
+    /// [`Simulator::read_array_element`].
+    pub fn read_array_element(&mut self, signal: SignalRef, index: usize) -> num_bigint::BigUint {
+        self.simulator.read_array_element(signal, index)
+    }
+
+    /// Writes one element of an unpacked array. See
+    /// [`Simulator::write_array_element`].
+    pub fn write_array_element(
+        &mut self,
+        signal: SignalRef,
+        index: usize,
+        value: num_bigint::BigUint,
+    ) {
+        self.simulator.write_array_element(signal, index, value);
+    }
+
+    /// Writes the leading elements of an unpacked array. See
+    /// [`Simulator::load_array`].
+    pub fn load_array(&mut self, signal: SignalRef, values: &[num_bigint::BigUint]) {
+        self.simulator.load_array(signal, values);
+    }
+
+    /// Reads every element of an unpacked array.
+    pub fn dump_array(&mut self, signal: SignalRef) -> Vec<num_bigint::BigUint> {
+        self.simulator.dump_array(signal)
+    }
+
+    /// Loads an unpacked array from a memory file. See
+    /// [`Simulator::load_memory_file`].
+    pub fn load_memory_file(
+        &mut self,
+        signal: SignalRef,
+        path: impl AsRef<std::path::Path>,
+        format: crate::MemoryFileFormat,
+    ) -> Result<usize, crate::MemoryFileError> {
+        self.simulator.load_memory_file(signal, path, format)
+    }
+
+    /// Writes part of an unpacked array to a memory file. See
+    /// [`Simulator::write_memory_file`].
+    pub fn write_memory_file(
+        &mut self,
+        signal: SignalRef,
+        path: impl AsRef<std::path::Path>,
+        format: crate::MemoryFileFormat,
+        range: impl std::ops::RangeBounds<usize>,
+    ) -> Result<usize, crate::MemoryFileError> {
+        self.simulator
+            .write_memory_file(signal, path, format, range)
+    }
+
+    /// Runs the memory dumps of the design's `final` blocks. See
+    /// [`Simulator::run_final_blocks`].
+    pub fn run_final_blocks(&mut self) -> Result<(), crate::MemoryFileError> {
+        self.simulator.run_final_blocks()
+    }
+
+    /// Retrieves the current 4-state value (value, mask) of a variable.
+    pub fn get_four_state(
+        &mut self,
+        signal: SignalRef,
+    ) -> (num_bigint::BigUint, num_bigint::BigUint) {
+        self.simulator.get_four_state(signal)
+    }
+
     /// Directly execute combinational logic evaluation.
     pub fn eval_comb(&mut self) -> Result<(), RuntimeErrorCode> {
         self.simulator.eval_comb()
@@ -333,6 +777,55 @@ impl<B: SimBackend> Simulation<B> {
         }
     }
 
+    /// Register a clock with an explicit waveform by event ID.
+    pub fn add_clock_with_by_id(
+        &mut self,
+        event_id: u32,
+        spec: ClockSpec,
+    ) -> Result<(), RuntimeErrorCode> {
+        let (ev, signal) = self.resolve_id_event(event_id)?;
+        self.state.add_clock_with(ev, signal, spec);
+        Ok(())
+    }
+
+    /// Pause a clock by event ID. See [`Self::pause_clock`].
+    pub fn pause_clock_by_id(&mut self, event_id: u32) -> Result<(), RuntimeErrorCode> {
+        let (ev, _) = self.resolve_id_event(event_id)?;
+        self.state.pause_clock(ev);
+        Ok(())
+    }
+
+    /// Resume a clock by event ID. See [`Self::resume_clock`].
+    pub fn resume_clock_by_id(&mut self, event_id: u32) -> Result<(), RuntimeErrorCode> {
+        let (ev, signal) = self.resolve_id_event(event_id)?;
+        self.state.resume_clock(&self.simulator.backend, ev, signal);
+        Ok(())
+    }
+
+    /// Remove a clock by event ID. See [`Self::remove_clock`].
+    pub fn remove_clock_by_id(&mut self, event_id: u32) -> Result<(), RuntimeErrorCode> {
+        let (ev, _) = self.resolve_id_event(event_id)?;
+        self.state.remove_clock(ev);
+        Ok(())
+    }
+
+    fn resolve_id_event(&self, event_id: u32) -> Result<(B::Event, SignalRef), RuntimeErrorCode> {
+        let not_an_event = || RuntimeErrorCode::NotAnEvent(format!("event_id={}", event_id));
+        let addr = *self
+            .simulator
+            .backend
+            .id_to_addr_slice()
+            .get(event_id as usize)
+            .ok_or_else(not_an_event)?;
+        let signal = self.simulator.backend.resolve_signal(&addr);
+        let ev = self
+            .simulator
+            .backend
+            .resolve_event_opt(&addr)
+            .ok_or_else(not_an_event)?;
+        Ok((ev, signal))
+    }
+
     /// Schedule a one-shot event by event ID.
     pub fn schedule_by_id(
         &mut self,
@@ -33,7 +33,7 @@ mod host {
     use crate::backend::native::{NativeBackend, SharedNativeCode};
     use crate::{
         IOContext, RuntimeErrorCode,
-        backend::{JitBackend, MemoryLayout, SharedJitCode, SimBackend},
+        backend::{EventHandle, JitBackend, MemoryLayout, SharedJitCode, SimBackend},
         ir::{
             InitialMemoryData, InitialMemoryWriteRun, InstancePath, RuntimeEventKind,
             RuntimeEventSite, RuntimeProgram, SignalRef, VariableInfo,
@@ -79,7 +79,7 @@ mod host {
     pub struct Simulator<B: SimBackend = crate::DefaultBackend> {
         pub(crate) backend: B,
         pub(crate) program: RuntimeProgram,
-        pub(crate) vcd_writer: Option<crate::VcdWriter>,
+        pub(crate) vcd_writer: Option<crate::WaveformWriter>,
         pub(crate) dirty: bool,
         pub(crate) warnings: Vec<CompilationWarning>,
         pub(crate) components: crate::component::ComponentRuntime,
@@ -89,9 +89,28 @@ mod host {
         comb_observer_snapshots: Vec<Vec<(BigUint, BigUint)>>,
         comb_observer_initial_eval: bool,
         pub(crate) diagnostics: crate::RuntimeDiagnostics,
+        pub(crate) watchdog: celox_runtime::Watchdog,
+        pub(crate) profiler: Option<crate::profile::Profiler>,
         tick_timing_ticks: u64,
         tick_timing_eval_apply_ns: u64,
         tick_timing_eval_comb_ns: u64,
+        watchers: crate::WatchList,
+        pub(crate) watch_time: u64,
+        forces: crate::ForceSet,
+        toggles: Option<crate::ToggleCoverage>,
+        fsm: Option<crate::FsmCoverage>,
+        /// `(element_width, element_count)` of every unpacked array, keyed by
+        /// stable-region offset.
+        array_shapes: Arc<crate::HashMap<usize, (usize, usize)>>,
+    }
+
+    /// Failure while forking a running simulator.
+    #[derive(Debug, thiserror::Error)]
+    pub enum ForkError {
+        #[error("component instance `{0}` holds state that cannot be forked")]
+        Component(String),
+        #[error("failed to create forked waveform: {0}")]
+        Io(#[from] std::io::Error),
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -222,6 +241,68 @@ mod host {
         }
     }
 
+    /// Bit positions of the elements of one unpacked array in state memory.
+    struct ArrayElements {
+        width: usize,
+        count: usize,
+        value_offset: usize,
+        mask_offset: Option<usize>,
+        /// Bits between the starts of neighbouring elements.
+        stride: usize,
+    }
+
+    impl ArrayElements {
+        fn bit_offset(&self, plane: usize, index: usize) -> usize {
+            assert!(
+                index < self.count,
+                "index {index} is outside an array of {} elements",
+                self.count
+            );
+            plane + index * self.stride
+        }
+
+        fn read(&self, mem: &[u8], index: usize) -> (BigUint, BigUint) {
+            let value = read_bits(mem, self.bit_offset(self.value_offset, index), self.width);
+            let mask = self.mask_offset.map_or_else(BigUint::default, |plane| {
+                read_bits(mem, self.bit_offset(plane, index), self.width)
+            });
+            (value, mask)
+        }
+
+        fn write(&self, mem: &mut [u8], index: usize, value: &BigUint, mask: &BigUint) {
+            self.write_plane(mem, self.bit_offset(self.value_offset, index), value);
+            if let Some(plane) = self.mask_offset {
+                self.write_plane(mem, self.bit_offset(plane, index), mask);
+            }
+        }
+
+        fn write_plane(&self, mem: &mut [u8], bit_offset: usize, value: &BigUint) {
+            let bytes = fixed_le_bytes(value, self.width);
+            if bit_offset.is_multiple_of(8) && self.width.is_multiple_of(8) {
+                let start = bit_offset / 8;
+                let len = self.width / 8;
+                mem[start..start + len].copy_from_slice(&bytes[..len]);
+            } else {
+                write_bits_to_memory(mem, bit_offset, self.width, &bytes);
+            }
+        }
+    }
+
+    fn fixed_le_bytes(value: &BigUint, width: usize) -> Vec<u8> {
+        let mut bytes = value.to_bytes_le();
+        bytes.resize(width.div_ceil(8).max(bytes.len()), 0);
+        bytes
+    }
+
+    fn read_bits(mem: &[u8], bit_offset: usize, bit_width: usize) -> BigUint {
+        let start = bit_offset / 8;
+        let end = (bit_offset + bit_width).div_ceil(8);
+        mask_width(
+            BigUint::from_bytes_le(&mem[start..end]) >> (bit_offset % 8),
+            bit_width,
+        )
+    }
+
     fn write_initial_run_to_plane(
         mem: &mut [u8],
         signal: SignalRef,
@@ -581,14 +662,83 @@ mod host {
                 comb_observer_snapshots: Vec::new(),
                 comb_observer_initial_eval: true,
                 diagnostics: crate::RuntimeDiagnostics::default(),
+                watchdog: celox_runtime::Watchdog::default(),
+                profiler: None,
                 tick_timing_ticks: 0,
                 tick_timing_eval_apply_ns: 0,
                 tick_timing_eval_comb_ns: 0,
+                watchers: crate::WatchList::new(),
+                watch_time: 0,
+                forces: crate::ForceSet::new(),
+                toggles: None,
+                fsm: None,
+                array_shapes: Arc::new(crate::HashMap::default()),
             };
+            sim.array_shapes = Arc::new(sim.collect_array_shapes());
             sim.comb_observer_snapshots = sim.snapshot_all_comb_observers();
             sim
         }
 
+        /// Branch this simulator into an independent instance.
+        ///
+        /// The fork shares the compiled code and copies only the memory image
+        /// and runtime bookkeeping, so it is cheap compared to a rebuild. The
+        /// fork does not write a waveform; use [`Self::fork_with_vcd`] to keep
+        /// dumping into a new file. Injected component instances are carried
+        /// over and keep calling their shared handler; native and Wasm
+        /// component instances have opaque state and make the fork fail.
+        /// Forced signals stay forced in the fork. Compilation warnings and
+        /// value-change watches stay with the original simulator.
+        pub fn fork(&self) -> Result<Self, ForkError> {
+            self.fork_with_backend(self.backend.fork())
+        }
+
+        /// [`Self::fork`] onto a backend that already holds a copy of this
+        /// simulator's state.
+        pub(crate) fn fork_with_backend(&self, backend: B) -> Result<Self, ForkError> {
+            let components = self.components.try_fork().map_err(ForkError::Component)?;
+            Ok(Self {
+                backend,
+                program: self.program.clone(),
+                vcd_writer: None,
+                dirty: self.dirty,
+                warnings: Vec::new(),
+                components,
+                component_simulation: self.component_simulation.clone(),
+                runtime_event_read_seq: Arc::new(AtomicU64::new(0)),
+                runtime_event_drain_active: Arc::new(AtomicBool::new(false)),
+                comb_observer_snapshots: self.comb_observer_snapshots.clone(),
+                comb_observer_initial_eval: self.comb_observer_initial_eval,
+                diagnostics: self.diagnostics.clone(),
+                watchdog: self.watchdog.clone(),
+                profiler: self.profiler.clone().map(|mut profiler| {
+                    profiler.reset();
+                    profiler
+                }),
+                tick_timing_ticks: 0,
+                tick_timing_eval_apply_ns: 0,
+                tick_timing_eval_comb_ns: 0,
+                watchers: crate::WatchList::new(),
+                watch_time: self.watch_time,
+                forces: self.forces.clone(),
+                toggles: self.toggles.clone(),
+                fsm: self.fsm.clone(),
+                array_shapes: Arc::clone(&self.array_shapes),
+            })
+        }
+
+        /// Like [`Self::fork`], but the fork continues this simulator's
+        /// waveform in a new file at `path`, in the same format.
+        pub fn fork_with_vcd(&self, path: impl AsRef<std::path::Path>) -> Result<Self, ForkError> {
+            let mut fork = self.fork()?;
+            fork.vcd_writer = self
+                .vcd_writer
+                .as_ref()
+                .map(|writer| writer.fork_to(path))
+                .transpose()?;
+            Ok(fork)
+        }
+
         fn record_tick_timing(&mut self, eval_apply_ns: u64, eval_comb_ns: u64) {
             let Some(every) = self.diagnostics.tick_timing_every else {
                 return;
@@ -613,6 +763,63 @@ mod host {
             }
         }
 
+        /// Fill every register with the starting value its scope's policy
+        /// selects. Runs before [`Self::apply_initial_values`] so explicit
+        /// initializers override it.
+        pub(crate) fn apply_initial_state_policy(
+            &mut self,
+            policies: &crate::InitialStatePolicies,
+        ) {
+            if policies.is_default() {
+                return;
+            }
+            let four_state_mode = self.backend.layout().four_state;
+            let mut registers = self
+                .program
+                .runtime_schema
+                .registers
+                .iter()
+                .copied()
+                .filter(|address| self.program.design.variable(address).is_some())
+                .collect::<Vec<_>>();
+            registers.sort_unstable();
+            let top = self
+                .program
+                .design
+                .root_instance()
+                .map_or_else(String::new, |instance| instance.module_name.clone());
+
+            for address in registers {
+                let Some(instance) = self.program.design.instance(address.instance_id) else {
+                    continue;
+                };
+                let policy = policies.policy_for(&top, &instance.display_path);
+                let mut fill = crate::initial_state::RegisterFill::new(
+                    policy,
+                    &self.program.design.get_path(&address),
+                );
+                let signal = self.backend.resolve_signal(&address);
+                let four_state = four_state_mode && signal.is_4state;
+                if signal.array_layout.is_some() || self.array_shapes.contains_key(&signal.offset) {
+                    let elements = self.array_elements(signal);
+                    let (ptr, size) = self.backend.memory_as_mut_ptr();
+                    let memory = unsafe { std::slice::from_raw_parts_mut(ptr, size) };
+                    for index in 0..elements.count {
+                        let (value, mask) = fill.next(elements.width, four_state);
+                        elements.write(memory, index, &value, &mask);
+                    }
+                } else {
+                    let (value, mask) = fill.next(signal.width, four_state);
+                    if four_state {
+                        self.backend.set_four_state(signal, value, mask);
+                    } else {
+                        self.backend.set_wide(signal, value);
+                    }
+                }
+            }
+            self.dirty = true;
+        }
+
         pub(crate) fn apply_initial_values(&mut self) {
             let mut applied = false;
             let initial_memory_values = self.program.design.take_initial_state();
@@ -861,6 +1068,37 @@ mod host {
             &self.warnings
         }
 
+        /// Open the waveform writer at `path` for the signals and window
+        /// selected by `options.dump_filter`.
+        pub(crate) fn open_waveform(
+            &mut self,
+            path: std::path::PathBuf,
+            options: &SimulatorOptions,
+        ) -> Result<(), SimulatorError> {
+            let descs = self.build_filtered_vcd_descs(options.four_state, &options.dump_filter);
+            let mut writer = crate::WaveformWriter::new(path, &descs)
+                .map_err(|_| SimulatorError::from(crate::RuntimeErrorCode::InternalError))?;
+            writer.set_window(options.dump_filter.time_window());
+            self.vcd_writer = Some(writer);
+            Ok(())
+        }
+
+        /// Resume waveform recording after [`Self::dump_off`]. The next dump
+        /// writes every signal's current value.
+        pub fn dump_on(&mut self) {
+            if let Some(writer) = self.vcd_writer.as_mut() {
+                writer.set_enabled(true);
+            }
+        }
+
+        /// Stop waveform recording. Signals read as X in the waveform until
+        /// [`Self::dump_on`], as with `$dumpoff`.
+        pub fn dump_off(&mut self) {
+            if let Some(writer) = self.vcd_writer.as_mut() {
+                writer.set_enabled(false);
+            }
+        }
+
         /// Captures the current state of all signals and writes them to the VCD file.
         pub fn dump(&mut self, timestamp: u64) {
             if self.dirty {
@@ -881,21 +1119,21 @@ mod host {
         pub fn set<T: Copy>(&mut self, signal: SignalRef, val: T) {
             self.backend.set(signal, val);
             self.dirty = true;
-            self.settle_dirty_for_runtime_event_drain();
+            self.settle_dirty_after_write();
         }
 
         /// Sets a wide signal value and marks combinational logic as dirty.
         pub fn set_wide(&mut self, signal: SignalRef, val: BigUint) {
             self.backend.set_wide(signal, val);
             self.dirty = true;
-            self.settle_dirty_for_runtime_event_drain();
+            self.settle_dirty_after_write();
         }
 
         /// Sets a four-state signal value and marks combinational logic as dirty.
         pub fn set_four_state(&mut self, signal: SignalRef, val: BigUint, mask: BigUint) {
             self.backend.set_four_state(signal, val, mask);
             self.dirty = true;
-            self.settle_dirty_for_runtime_event_drain();
+            self.settle_dirty_after_write();
         }
 
         /// Modifies internal state via a callback and marks combinational logic as dirty.
@@ -908,32 +1146,501 @@ mod host {
             };
             f(&mut ctx);
             self.dirty = true;
-            if self.runtime_event_drain_active.load(Ordering::Acquire) {
+            if self.needs_eager_settle() {
                 self.eval_comb_checked()?;
                 self.dirty = false;
+                self.notify_watchers();
             }
             Ok(())
         }
 
-        fn settle_dirty_for_runtime_event_drain(&mut self) {
-            if self.runtime_event_drain_active.load(Ordering::Acquire) {
+        fn settle_dirty_after_write(&mut self) {
+            if self.needs_eager_settle() {
                 self.eval_comb_checked().unwrap();
                 self.dirty = false;
+                self.notify_watchers();
             }
         }
 
+        /// Writes settle combinational logic immediately while a runtime event
+        /// drain is active or any signal is watched, so observers see the
+        /// effect of each write.
+        fn needs_eager_settle(&self) -> bool {
+            !self.watchers.is_empty() || self.runtime_event_drain_active.load(Ordering::Acquire)
+        }
+
+        /// Report changes on watched signals and sample toggle and FSM
+        /// coverage. Callers must have settled combinational logic first.
+        pub(crate) fn notify_watchers(&mut self) {
+            if self.toggles.is_some() || self.fsm.is_some() {
+                let (ptr, size) = self.backend.memory_as_ptr();
+                let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+                if let Some(toggles) = self.toggles.as_mut() {
+                    toggles.sample(self.watch_time, memory);
+                }
+                if let Some(fsm) = self.fsm.as_mut() {
+                    fsm.sample(memory);
+                }
+            }
+            if self.watchers.is_empty() {
+                return;
+            }
+            let backend = &self.backend;
+            self.watchers.notify(
+                self.watch_time,
+                |signal| backend.get_four_state(signal),
+                |callback, change| callback(change),
+            );
+        }
+
+        /// Call `callback` whenever `signal` changes after a `tick`, `modify`,
+        /// `set`, or `eval_comb`.
+        ///
+        /// The callback receives the old and new `(value, mask)` pairs and
+        /// the current time, which counts completed ticks for a bare
+        /// simulator and is the simulation time under [`crate::Simulation`].
+        /// While any watch is registered, writes settle combinational logic
+        /// eagerly instead of on the next read.
+        pub fn watch(
+            &mut self,
+            signal: SignalRef,
+            callback: impl FnMut(&crate::ValueChange) + Send + 'static,
+        ) -> crate::WatchId {
+            let current = self.get_four_state(signal);
+            self.watchers.watch(signal, current, Box::new(callback))
+        }
+
+        /// Remove a watch registered with [`Self::watch`]. Unknown IDs are
+        /// ignored.
+        pub fn unwatch(&mut self, id: crate::WatchId) {
+            self.watchers.unwatch(id);
+        }
+
+        /// Start counting 0→1 and 1→0 transitions of every bit of every
+        /// signal, discarding any earlier counts. The current values are the
+        /// starting point, so calling this after reset leaves the reset
+        /// sequence out of the coverage.
+        ///
+        /// Values are sampled wherever watches are notified: after each
+        /// `tick`, `eval_comb` and, under [`crate::Simulation`], each time
+        /// step. Signals are named as in [`crate::DesignReflection`].
+        pub fn enable_toggle_coverage(&mut self) {
+            if self.dirty {
+                self.eval_comb_checked().unwrap();
+                self.dirty = false;
+            }
+            let layout = self.backend.layout();
+            let reflection = self.program.build_design_reflection(layout);
+            let descs = reflection
+                .signals()
+                .iter()
+                .map(|signal| crate::VcdSignalDesc {
+                    scope: reflection
+                        .scope(signal.parent)
+                        .expect("reflection signals have a scope")
+                        .full_name
+                        .clone(),
+                    name: signal.name.clone(),
+                    offset: signal.signal.offset,
+                    width: signal.signal.width,
+                    is_4state: layout.four_state && signal.signal.is_4state,
+                })
+                .collect();
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            self.toggles = Some(crate::ToggleCoverage::new(descs, self.watch_time, memory));
+        }
+
+        /// Stop counting transitions and return the counts so far.
+        pub fn disable_toggle_coverage(&mut self) -> Option<crate::ToggleCoverage> {
+            self.toggles.take()
+        }
+
+        /// Transition counts since [`Self::enable_toggle_coverage`], or
+        /// `None` while toggle coverage is off.
+        pub fn toggle_coverage(&self) -> Option<&crate::ToggleCoverage> {
+            self.toggles.as_ref()
+        }
+
+        /// Start counting state visits and transitions of every enum-typed
+        /// register assigned in `always_ff`, discarding any earlier counts.
+        /// The current state of each register counts as its first visit.
+        ///
+        /// Registers are sampled at the same points as toggle coverage, so a
+        /// state that is entered and left within one sample is not seen.
+        /// Registers wider than 64 bits are not tracked.
+        pub fn enable_fsm_coverage(&mut self) {
+            if self.dirty {
+                self.eval_comb_checked().unwrap();
+                self.dirty = false;
+            }
+            let layout = self.backend.layout();
+            let descs = self
+                .program
+                .runtime_schema
+                .fsm_registers
+                .iter()
+                .filter(|register| layout.widths[&register.var] <= 64)
+                .map(|register| crate::FsmDesc {
+                    name: match &register.scope {
+                        Some(scope) => format!("{scope}.{}", register.name),
+                        None => register.name.clone(),
+                    },
+                    type_name: register.type_name.clone(),
+                    states: register.states.clone(),
+                    offset: layout.offsets[&register.var],
+                    width: layout.widths[&register.var],
+                    is_4state: layout.four_state && layout.is_4states[&register.var],
+                })
+                .collect();
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            self.fsm = Some(crate::FsmCoverage::new(descs, memory));
+        }
+
+        /// Stop counting state visits and return the counts so far.
+        pub fn disable_fsm_coverage(&mut self) -> Option<crate::FsmCoverage> {
+            self.fsm.take()
+        }
+
+        /// State and transition counts since [`Self::enable_fsm_coverage`],
+        /// or `None` while FSM coverage is off.
+        pub fn fsm_coverage(&self) -> Option<&crate::FsmCoverage> {
+            self.fsm.as_ref()
+        }
+
+        /// Time spent in each execution unit since the build or the last
+        /// [`Self::reset_profile`], attributed to instances and modules.
+        /// `None` unless built with [`SimulatorBuilder::profile`].
+        pub fn profile(&self) -> Option<crate::Profile> {
+            self.profiler
+                .as_ref()
+                .map(|profiler| profiler.report(&self.program))
+        }
+
+        /// Clear the times collected for [`Self::profile`].
+        pub fn reset_profile(&mut self) {
+            if let Some(profiler) = &mut self.profiler {
+                profiler.reset();
+            }
+        }
+
+        /// Current branch hit counts of a simulator built with
+        /// [`SimulatorBuilder::branch_coverage`]; empty otherwise. Pending
+        /// input changes are evaluated first, like [`Self::get`] does.
+        pub fn coverage(&mut self) -> crate::BranchCoverage {
+            if self.dirty {
+                self.eval_comb_checked().unwrap();
+                self.dirty = false;
+            }
+            let layout = self.backend.layout();
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            crate::BranchCoverage::new(
+                self.program
+                    .runtime_schema
+                    .coverage_points
+                    .iter()
+                    .map(|point| {
+                        let offset = layout.offsets[&point.counter];
+                        let mut bytes = [0; 8];
+                        bytes.copy_from_slice(&memory[offset..offset + 8]);
+                        crate::BranchHits {
+                            scope: point.scope.clone().unwrap_or_default(),
+                            file: point.file.clone(),
+                            line: point.line,
+                            column: point.column,
+                            process: point.process,
+                            arm: point.arm,
+                            hits: u64::from_le_bytes(bytes),
+                        }
+                    })
+                    .collect(),
+            )
+        }
+
+        /// Hold `signal` at `value` until [`Self::release`] is called.
+        ///
+        /// The value is reapplied after every flip-flop update, so inputs and
+        /// registers stay forced across ticks. Forcing a net driven by
+        /// combinational logic also requires a simulator built with
+        /// [`SimulatorBuilder::force_support`]; otherwise logic that reads the
+        /// net within the same evaluation pass may still see the driven value.
+        pub fn force(&mut self, signal: SignalRef, value: BigUint) {
+            self.force_four_state(signal, value, BigUint::default());
+        }
+
+        /// Like [`Self::force`], but with an explicit X/Z mask.
+        pub fn force_four_state(&mut self, signal: SignalRef, value: BigUint, mask: BigUint) {
+            self.forces.force(signal, value.clone(), mask.clone());
+            self.backend.set_four_state(signal, value, mask);
+            self.dirty = true;
+            self.settle_dirty_after_write();
+        }
+
+        /// Release a force placed by [`Self::force`]. Combinational nets take
+        /// their driven value again on the next settle; inputs and registers
+        /// keep the forced value until they are next written or clocked.
+        /// Releasing a signal that is not forced has no effect.
+        pub fn release(&mut self, signal: SignalRef) {
+            if self.forces.release(signal) {
+                self.dirty = true;
+                self.settle_dirty_after_write();
+            }
+        }
+
+        /// Returns `true` if `signal` is currently forced.
+        pub fn is_forced(&self, signal: SignalRef) -> bool {
+            self.forces.contains(signal)
+        }
+
+        /// Write `value` once without holding it; the design may overwrite it
+        /// on the next update. Deposits to a forced signal are ignored.
+        pub fn deposit(&mut self, signal: SignalRef, value: BigUint) {
+            self.deposit_four_state(signal, value, BigUint::default());
+        }
+
+        /// Like [`Self::deposit`], but with an explicit X/Z mask.
+        pub fn deposit_four_state(&mut self, signal: SignalRef, value: BigUint, mask: BigUint) {
+            if self.forces.contains(signal) {
+                return;
+            }
+            self.set_four_state(signal, value, mask);
+        }
+
+        /// Reads element `index` of the unpacked array `signal`, evaluating
+        /// combinational logic first if the state is dirty.
+        ///
+        /// Panics if `signal` is not an unpacked array or `index` is out of
+        /// range.
+        pub fn read_array_element(&mut self, signal: SignalRef, index: usize) -> BigUint {
+            self.read_array_element_four_state(signal, index).0
+        }
+
+        /// Like [`Self::read_array_element`], but also returns the X/Z mask.
+        pub fn read_array_element_four_state(
+            &mut self,
+            signal: SignalRef,
+            index: usize,
+        ) -> (BigUint, BigUint) {
+            let elements = self.array_elements(signal);
+            if self.dirty {
+                self.eval_comb_checked().unwrap();
+                self.dirty = false;
+            }
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            elements.read(memory, index)
+        }
+
+        /// Writes element `index` of the unpacked array `signal` and marks
+        /// combinational logic as dirty.
+        ///
+        /// Panics if `signal` is not an unpacked array or `index` is out of
+        /// range.
+        pub fn write_array_element(&mut self, signal: SignalRef, index: usize, value: BigUint) {
+            self.write_array_element_four_state(signal, index, value, BigUint::default());
+        }
+
+        /// Like [`Self::write_array_element`], but with an explicit X/Z mask.
+        pub fn write_array_element_four_state(
+            &mut self,
+            signal: SignalRef,
+            index: usize,
+            value: BigUint,
+            mask: BigUint,
+        ) {
+            let elements = self.array_elements(signal);
+            let (ptr, size) = self.backend.memory_as_mut_ptr();
+            let memory = unsafe { std::slice::from_raw_parts_mut(ptr, size) };
+            elements.write(memory, index, &value, &mask);
+            self.dirty = true;
+            self.settle_dirty_after_write();
+        }
+
+        /// Writes `values` to the first `values.len()` elements of the
+        /// unpacked array `signal` and marks combinational logic as dirty.
+        ///
+        /// Panics if `signal` is not an unpacked array or `values` has more
+        /// elements than the array.
+        pub fn load_array(&mut self, signal: SignalRef, values: &[BigUint]) {
+            let elements = self.array_elements(signal);
+            assert!(
+                values.len() <= elements.count,
+                "{} values do not fit an array of {} elements",
+                values.len(),
+                elements.count
+            );
+            let (ptr, size) = self.backend.memory_as_mut_ptr();
+            let memory = unsafe { std::slice::from_raw_parts_mut(ptr, size) };
+            let zero = BigUint::default();
+            for (index, value) in values.iter().enumerate() {
+                elements.write(memory, index, value, &zero);
+            }
+            self.dirty = true;
+            self.settle_dirty_after_write();
+        }
+
+        /// Reads every element of the unpacked array `signal`, evaluating
+        /// combinational logic first if the state is dirty.
+        pub fn dump_array(&mut self, signal: SignalRef) -> Vec<BigUint> {
+            let elements = self.array_elements(signal);
+            if self.dirty {
+                self.eval_comb_checked().unwrap();
+                self.dirty = false;
+            }
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            (0..elements.count)
+                .map(|index| elements.read(memory, index).0)
+                .collect()
+        }
+
+        /// Loads the unpacked array `signal` from a `$readmemh` or
+        /// `$readmemb` style file. Elements the file does not mention keep
+        /// their value. Returns the number of words loaded.
+        pub fn load_memory_file(
+            &mut self,
+            signal: SignalRef,
+            path: impl AsRef<std::path::Path>,
+            format: crate::MemoryFileFormat,
+        ) -> Result<usize, crate::MemoryFileError> {
+            let elements = self.array_elements(signal);
+            let words =
+                celox_runtime::read_memory_file(path, format, elements.width, elements.count)?;
+            let (ptr, size) = self.backend.memory_as_mut_ptr();
+            let memory = unsafe { std::slice::from_raw_parts_mut(ptr, size) };
+            for word in &words {
+                elements.write(memory, word.address, &word.value, &word.mask);
+            }
+            self.dirty = true;
+            self.settle_dirty_after_write();
+            Ok(words.len())
+        }
+
+        /// Writes the elements of the unpacked array `signal` in `range` to a
+        /// `$writememh` or `$writememb` style file, evaluating combinational
+        /// logic first if the state is dirty. Returns the number of words
+        /// written.
+        ///
+        /// Panics if `range` reaches past the end of the array.
+        pub fn write_memory_file(
+            &mut self,
+            signal: SignalRef,
+            path: impl AsRef<std::path::Path>,
+            format: crate::MemoryFileFormat,
+            range: impl std::ops::RangeBounds<usize>,
+        ) -> Result<usize, crate::MemoryFileError> {
+            let elements = self.array_elements(signal);
+            let range = std::ops::Range {
+                start: match range.start_bound() {
+                    std::ops::Bound::Included(&start) => start,
+                    std::ops::Bound::Excluded(&start) => start + 1,
+                    std::ops::Bound::Unbounded => 0,
+                },
+                end: match range.end_bound() {
+                    std::ops::Bound::Included(&end) => end + 1,
+                    std::ops::Bound::Excluded(&end) => end,
+                    std::ops::Bound::Unbounded => elements.count,
+                },
+            };
+            assert!(
+                range.end <= elements.count,
+                "elements {range:?} are outside an array of {} elements",
+                elements.count
+            );
+            if self.dirty {
+                self.eval_comb_checked().unwrap();
+                self.dirty = false;
+            }
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            let words = range
+                .map(|address| {
+                    let (value, mask) = elements.read(memory, address);
+                    celox_runtime::MemoryWord {
+                        address,
+                        value,
+                        mask,
+                    }
+                })
+                .collect::<Vec<_>>();
+            celox_runtime::write_memory_file(path, &words, format, elements.width)?;
+            Ok(words.len())
+        }
+
+        /// Runs the `$writememh` / `$writememb` calls of the design's `final`
+        /// blocks in declaration order. Native testbenches call this when
+        /// they end; other hosts call it once the simulation is over.
+        pub fn run_final_blocks(&mut self) -> Result<(), crate::MemoryFileError> {
+            for dump in self.program.runtime_schema.final_memory_dumps.clone() {
+                let signal = self.backend.resolve_signal(&dump.memory);
+                let format = if dump.binary {
+                    crate::MemoryFileFormat::Binary
+                } else {
+                    crate::MemoryFileFormat::Hex
+                };
+                let start = dump.start.unwrap_or(0);
+                match dump.end {
+                    Some(end) => self.write_memory_file(signal, &dump.path, format, start..=end),
+                    None => self.write_memory_file(signal, &dump.path, format, start..),
+                }?;
+            }
+            Ok(())
+        }
+
+        fn array_elements(&self, signal: SignalRef) -> ArrayElements {
+            let four_state = self.backend.layout().four_state && signal.is_4state;
+            if let Some(array) = signal.array_layout {
+                return ArrayElements {
+                    width: array.element_width,
+                    count: array.element_count,
+                    value_offset: signal.offset * 8,
+                    mask_offset: four_state.then_some((signal.offset + array.plane_size) * 8),
+                    stride: array.element_stride * 8,
+                };
+            }
+            let &(width, count) = self
+                .array_shapes
+                .get(&signal.offset)
+                .expect("signal is not an unpacked array");
+            ArrayElements {
+                width,
+                count,
+                value_offset: signal.offset * 8,
+                mask_offset: four_state
+                    .then_some((signal.offset + crate::get_byte_size(signal.width)) * 8),
+                stride: width,
+            }
+        }
+
+        fn collect_array_shapes(&self) -> crate::HashMap<usize, (usize, usize)> {
+            let offsets = &self.backend.layout().offsets;
+            self.program
+                .design
+                .state_objects
+                .iter()
+                .filter_map(|(address, metadata)| {
+                    let count = metadata.array_dims.iter().product::<usize>();
+                    if count <= 1 || !metadata.width.is_multiple_of(count) {
+                        return None;
+                    }
+                    let offset = *offsets.get(address)?;
+                    Some((offset, (metadata.width / count, count)))
+                })
+                .collect()
+        }
+
         pub(crate) fn eval_comb_checked(&mut self) -> Result<(), RuntimeErrorCode> {
             if self.program.runtime_schema.runtime_event_sites.is_empty() {
                 return self
-                    .backend
-                    .eval_comb()
+                    .eval_comb_pass()
                     .map_err(|e| self.decorate_runtime_error(e));
             }
             if self.program.runtime_schema.comb_observers.is_empty() {
                 let runtime_event_start_seq = self.runtime_event_write_seq();
                 let eval_result = self
-                    .backend
-                    .eval_comb()
+                    .eval_comb_pass()
                     .map_err(|e| self.decorate_runtime_error(e));
                 let runtime_events = self.peek_backend_runtime_events_from(runtime_event_start_seq);
                 if let Some(err) = self.fatal_comb_capture_error(&runtime_events) {
@@ -969,8 +1676,7 @@ mod host {
             self.backend.set_comb_capture_event_enabled(&active_sites);
             let runtime_event_start_seq = self.runtime_event_write_seq();
             let eval_result = self
-                .backend
-                .eval_comb()
+                .eval_comb_pass()
                 .map_err(|e| self.decorate_runtime_error(e));
             let after = self.snapshot_all_comb_observers();
             let runtime_events = self.peek_backend_runtime_events_from(runtime_event_start_seq);
@@ -990,6 +1696,39 @@ mod host {
             eval_result
         }
 
+        /// Settle combinational logic while holding forced values, timing
+        /// each unit when profiling.
+        fn eval_comb_pass(&mut self) -> Result<(), RuntimeErrorCode> {
+            let Some(profiler) = self.profiler.as_mut() else {
+                return self.forces.eval_comb(&mut self.backend);
+            };
+            let forces = &self.forces;
+            forces.apply(&mut self.backend);
+            let mut unit = 0;
+            let mut start = crate::timing::now();
+            self.backend.eval_comb_units_with(&mut |backend| {
+                profiler.record_comb(unit, start.elapsed());
+                unit += 1;
+                forces.apply(backend);
+                start = crate::timing::now();
+            })
+        }
+
+        /// Run one flip-flop entry of `event`, timing it when profiling.
+        fn run_ff(
+            &mut self,
+            event: B::Event,
+            run: impl FnOnce(&mut B, B::Event) -> Result<(), RuntimeErrorCode>,
+        ) -> Result<(), RuntimeErrorCode> {
+            let Some(profiler) = self.profiler.as_mut() else {
+                return run(&mut self.backend, event);
+            };
+            let start = crate::timing::now();
+            let result = run(&mut self.backend, event);
+            profiler.record_ff(event.addr(), start.elapsed());
+            result
+        }
+
         fn snapshot_all_comb_observers(&self) -> Vec<Vec<(BigUint, BigUint)>> {
             self.program
                 .runtime_schema
@@ -1044,15 +1783,20 @@ mod host {
             &mut self,
             event: B::Event,
         ) -> Result<(), RuntimeErrorCode> {
-            self.backend
-                .eval_apply_ff_at(event)
-                .map_err(|e| self.decorate_runtime_error(e))
+            self.run_ff(event, B::eval_apply_ff_at)
+                .map_err(|e| self.decorate_runtime_error(e))?;
+            self.forces.apply(&mut self.backend);
+            Ok(())
         }
 
         pub(crate) fn eval_comb_apply_ff_at_checked(
             &mut self,
             event: B::Event,
         ) -> Result<(), RuntimeErrorCode> {
+            if !self.forces.is_empty() || self.profiler.is_some() {
+                self.eval_comb_checked()?;
+                return self.eval_apply_ff_at_checked(event);
+            }
             self.backend
                 .eval_comb_apply_ff_at(event)
                 .map_err(|e| self.decorate_runtime_error(e))
@@ -1062,8 +1806,7 @@ mod host {
             &mut self,
             event: B::Event,
         ) -> Result<(), RuntimeErrorCode> {
-            self.backend
-                .eval_only_ff_at(event)
+            self.run_ff(event, B::eval_only_ff_at)
                 .map_err(|e| self.decorate_runtime_error(e))
         }
 
@@ -1071,9 +1814,10 @@ mod host {
             &mut self,
             event: B::Event,
         ) -> Result<(), RuntimeErrorCode> {
-            self.backend
-                .apply_ff_at(event)
-                .map_err(|e| self.decorate_runtime_error(e))
+            self.run_ff(event, B::apply_ff_at)
+                .map_err(|e| self.decorate_runtime_error(e))?;
+            self.forces.apply(&mut self.backend);
+            Ok(())
         }
 
         /// Manually triggers a clock or event to process sequential logic.
@@ -1112,6 +1856,8 @@ mod host {
                 self.eval_comb_checked()?;
             }
             self.dirty = false;
+            self.watch_time += 1;
+            self.notify_watchers();
             Ok(())
         }
 
@@ -1122,7 +1868,10 @@ mod host {
             &mut self,
             event: B::Event,
         ) -> Result<(), RuntimeErrorCode> {
-            if !self.program.runtime_schema.comb_observers.is_empty() {
+            if !self.program.runtime_schema.comb_observers.is_empty()
+                || self.toggles.is_some()
+                || self.fsm.is_some()
+            {
                 return self.tick(event);
             }
             if self.dirty {
@@ -1145,7 +1894,13 @@ mod host {
             if count == 0 {
                 return (0, Ok(()));
             }
-            if !self.program.runtime_schema.comb_observers.is_empty() || !self.dirty {
+            if !self.program.runtime_schema.comb_observers.is_empty()
+                || !self.forces.is_empty()
+                || self.profiler.is_some()
+                || self.toggles.is_some()
+                || self.fsm.is_some()
+                || !self.dirty
+            {
                 return (1, self.tick_deferred_comb(event));
             }
             let (completed, result) = self.backend.eval_comb_apply_ff_many_at(event, count);
@@ -1215,6 +1970,7 @@ mod host {
         pub fn eval_comb(&mut self) -> Result<(), RuntimeErrorCode> {
             self.eval_comb_checked()?;
             self.dirty = false;
+            self.notify_watchers();
             Ok(())
         }
 
@@ -1238,17 +1994,126 @@ mod host {
             self.backend.layout()
         }
 
+        /// Capture the stable and working state of this simulator.
+        ///
+        /// The returned checkpoint can be restored into this simulator or any
+        /// other simulator built from the same design and layout options.
+        pub fn save_state(&self) -> crate::SimulatorCheckpoint {
+            let range = self.checkpoint_state_range();
+            let (ptr, size) = self.backend.memory_as_ptr();
+            let memory = unsafe { std::slice::from_raw_parts(ptr, size) };
+            crate::SimulatorCheckpoint {
+                layout_fingerprint: self.backend.layout().fingerprint(),
+                design_fingerprint: self.program.design.fingerprint(),
+                state: memory[range].to_vec(),
+                comb_observers: self
+                    .comb_observer_snapshots
+                    .iter()
+                    .map(|observer| {
+                        observer
+                            .iter()
+                            .map(|(value, mask)| (value.to_bytes_le(), mask.to_bytes_le()))
+                            .collect()
+                    })
+                    .collect(),
+                comb_observer_initial_eval: self.comb_observer_initial_eval,
+                dirty: self.dirty,
+                simulation: None,
+            }
+        }
+
+        /// Restore state captured by [`Self::save_state`].
+        ///
+        /// Scheduler state in checkpoints taken from a [`crate::Simulation`]
+        /// is ignored. The state header is left untouched so runtime event
+        /// buffers installed in this simulator keep working.
+        pub fn restore_state(
+            &mut self,
+            checkpoint: &crate::SimulatorCheckpoint,
+        ) -> Result<(), crate::CheckpointError> {
+            let expected = self.backend.layout().fingerprint();
+            if checkpoint.layout_fingerprint != expected {
+                return Err(crate::CheckpointError::LayoutMismatch {
+                    expected,
+                    found: checkpoint.layout_fingerprint,
+                });
+            }
+            let expected = self.program.design.fingerprint();
+            if checkpoint.design_fingerprint != expected {
+                return Err(crate::CheckpointError::DesignMismatch {
+                    expected,
+                    found: checkpoint.design_fingerprint,
+                });
+            }
+            let range = self.checkpoint_state_range();
+            if checkpoint.state.len() != range.len() {
+                return Err(crate::CheckpointError::StateSizeMismatch {
+                    expected: range.len(),
+                    found: checkpoint.state.len(),
+                });
+            }
+
+            let (ptr, size) = self.backend.memory_as_mut_ptr();
+            let memory = unsafe { std::slice::from_raw_parts_mut(ptr, size) };
+            memory[range].copy_from_slice(&checkpoint.state);
+            self.backend.clear_triggered_bits();
+            self.comb_observer_snapshots = checkpoint
+                .comb_observers
+                .iter()
+                .map(|observer| {
+                    observer
+                        .iter()
+                        .map(|(value, mask)| {
+                            (BigUint::from_bytes_le(value), BigUint::from_bytes_le(mask))
+                        })
+                        .collect()
+                })
+                .collect();
+            self.comb_observer_initial_eval = checkpoint.comb_observer_initial_eval;
+            self.dirty = checkpoint.dirty;
+            if !self.forces.is_empty() {
+                self.forces.apply(&mut self.backend);
+                self.dirty = true;
+            }
+            let backend = &self.backend;
+            self.watchers
+                .rebase(|signal| backend.get_four_state(signal));
+            Ok(())
+        }
+
+        fn checkpoint_state_range(&self) -> std::ops::Range<usize> {
+            celox_state_layout::STATE_HEADER_SIZE..self.backend.layout().triggered_bits_offset
+        }
+
         /// Build VCD signal descriptors for all instances.
         ///
         /// The returned descriptors are self-contained (no IR references) and can
         /// be cached alongside [`SharedJitCode`] so that VCD works on cache-hit
         /// paths without the original [`RuntimeProgram`].
         pub fn build_vcd_descs(&self, four_state_mode: bool) -> Vec<crate::VcdSignalDesc> {
+            self.build_filtered_vcd_descs(four_state_mode, &crate::DumpFilter::default())
+        }
+
+        /// Like [`Self::build_vcd_descs`], keeping only the signals selected
+        /// by `filter`.
+        pub fn build_filtered_vcd_descs(
+            &self,
+            four_state_mode: bool,
+            filter: &crate::DumpFilter,
+        ) -> Vec<crate::VcdSignalDesc> {
             let mut descs = Vec::new();
             let mut sorted_instances = self.program.design.instances().collect::<Vec<_>>();
             sorted_instances.sort_by_key(|instance| instance.id);
+            let top = self
+                .program
+                .design
+                .root_instance()
+                .map_or("", |instance| instance.module_name.as_str());
 
             for instance in sorted_instances {
+                if !filter.includes_instance(top, &instance.display_path) {
+                    continue;
+                }
                 let scope = format!("{}", instance.id);
 
                 let mut sorted_vars: Vec<_> = instance
@@ -1256,6 +2121,12 @@ mod host {
                     .iter()
                     .filter_map(|address| self.program.design.variable(address))
                     .filter(|variable| instance.resolves_path_to(&variable.path, variable.address))
+                    .filter(|variable| {
+                        filter.includes_arrays()
+                            || self.program.design.state_objects[&variable.address]
+                                .array_dims
+                                .is_empty()
+                    })
                     .collect();
                 sorted_vars.sort_by(|a, b| a.path.cmp(&b.path));
 
@@ -1363,19 +2234,41 @@ mod host {
         }
 
         /// Triggers a clock/event N times by its numeric ID.
-        /// Avoids repeated cross-boundary calls when used from FFI.
+        /// Avoids repeated cross-boundary calls when used from FFI. Stops with
+        /// [`RuntimeErrorCode::Interrupted`] between ticks once the cancel
+        /// token fires or the wall-clock limit runs out.
         pub fn tick_by_id_n(
             &mut self,
             event_id: usize,
             count: u32,
         ) -> Result<(), RuntimeErrorCode> {
             let event = self.backend.id_to_event_slice()[event_id];
+            self.watchdog.arm();
             for _ in 0..count {
+                self.watchdog.check(self.watch_time)?;
                 self.tick(event)?;
             }
             Ok(())
         }
 
+        /// Returns the token that cancels this simulator's runs from another
+        /// thread. Forks share the token with the simulator they came from.
+        pub fn cancel_token(&self) -> celox_runtime::CancelToken {
+            self.watchdog.token().clone()
+        }
+
+        /// Returns the limits applied to each run call.
+        pub fn run_limits(&self) -> celox_runtime::RunLimits {
+            self.watchdog.limits()
+        }
+
+        /// Replace the limits set with
+        /// [`crate::SimulatorBuilder::wall_clock_limit`] and
+        /// [`crate::SimulatorBuilder::max_cascade_rounds`].
+        pub fn set_run_limits(&mut self, limits: celox_runtime::RunLimits) {
+            self.watchdog.set_limits(limits);
+        }
+
         /// Resolves a signal inside a child instance.
         pub fn child_signal(&self, instance_path: &[(&str, usize)], var: &str) -> SignalRef {
             let addr = self.program.get_addr(instance_path, &[var]).unwrap();
@@ -1558,6 +2451,23 @@ mod host {
         pub fn into_backend(self) -> NativeBackend {
             self.backend
         }
+
+        /// Copy this simulator into `lanes` lanes of a [`crate::BatchSimulator`].
+        pub fn batch(&self, lanes: usize) -> Result<crate::BatchSimulator, ForkError> {
+            crate::BatchSimulator::from_simulator(self, lanes)
+        }
+
+        pub(crate) fn fork_into_lane(
+            &self,
+            arena: &Arc<crate::backend::native::LaneArena>,
+            lane: usize,
+        ) -> Result<Self, ForkError> {
+            self.fork_with_backend(self.backend.fork_into_lane(arena, lane))
+        }
+
+        pub(crate) fn memory_words(&self) -> usize {
+            self.backend.memory_words()
+        }
     }
 }
 
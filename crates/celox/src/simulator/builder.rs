@@ -3,11 +3,14 @@ use std::path::{Path, PathBuf};
 use veryl_analyzer::conv::utils::get_component;
 use veryl_analyzer::ir::{Comptime, Expression, Signature, VarPath};
 use veryl_analyzer::value::Value;
-use veryl_analyzer::{Analyzer, AnalyzerError, Context, attribute_table, ir::Ir, symbol_table};
+use veryl_analyzer::{
+    Analyzer, AnalyzerError, Context, attribute_table, ir::Ir, symbol_table, type_dag,
+};
 use veryl_metadata::{ClockType, Component, ComponentBackendKind, Metadata, ResetType};
 use veryl_parser::Parser;
 use veryl_parser::resource_table;
 
+use crate::XPropMode;
 use crate::parser::BuildConfig;
 use crate::{
     CompilationWarning, FrontendDiagnostic, HashMap, ParserError, SimulatorError,
@@ -203,6 +206,11 @@ fn analyze(
     injected_manifests: &[(String, veryl_metadata::ComponentManifest)],
     preserve_element_storage_layout: bool,
     recover_comb_loops: bool,
+    branch_coverage: bool,
+    xprop: XPropMode,
+    module_xprop: &[(String, XPropMode)],
+    comb_partitions: usize,
+    module_cache: bool,
 ) -> (
     Result<OptimizedSir, ParserError>,
     Vec<AnalyzerError>,
@@ -210,6 +218,9 @@ fn analyze(
 ) {
     symbol_table::clear();
     attribute_table::clear();
+    // The module cache reads file dependencies from the type DAG, which
+    // would otherwise still hold edges between the previous build's symbols.
+    type_dag::clear();
 
     let mut metadata = metadata.unwrap_or_else(|| Metadata::create_default("prj").unwrap());
     // Pass 1 uses this name as the source namespace. Preserve the namespaces
@@ -294,6 +305,15 @@ fn analyze(
     if !ignored_loops.is_empty() || !true_loops.is_empty() {
         errors.retain(|error| !matches!(error, AnalyzerError::CombinationalLoop { .. }));
     }
+    // The analyzer only knows the two-argument `$readmemh`. Calls with start
+    // and end addresses are recovered from the source by the frontend.
+    errors.retain(|error| {
+        !matches!(
+            error,
+            AnalyzerError::MismatchFunctionArity { name, args, .. }
+                if name == "$readmemh" && (3..=4).contains(args)
+        )
+    });
 
     let mut frontend_diagnostics = if errors.iter().any(AnalyzerError::is_error) {
         Vec::new()
@@ -317,6 +337,15 @@ fn analyze(
     if let Some(rt) = reset_type {
         build_config.reset_type = rt;
     }
+    build_config.branch_coverage = branch_coverage;
+    // Two-state designs have no X to propagate, so the modes only differ in
+    // four-state builds.
+    if four_state {
+        build_config.xprop = xprop;
+        build_config.module_xprop = module_xprop.to_vec();
+    }
+    build_config.comb_partitions = comb_partitions;
+    build_config.module_cache = module_cache;
     let sir = if let Some(external) = external_frontend {
         parser::parse_with_external_hierarchy(
             &top,
@@ -449,6 +478,11 @@ pub fn compile_to_sir(
         &[],
         crate::backend::memory_layout::MemoryLayoutMode::Packed,
         true,
+        false,
+        XPropMode::Lrm,
+        &[],
+        1,
+        true,
     )
 }
 
@@ -483,6 +517,7 @@ pub fn compile_frontend_to_sir(
         optimize_options,
         &crate::RuntimeDiagnostics::default(),
         crate::backend::memory_layout::MemoryLayoutMode::Packed,
+        1,
     )
 }
 
@@ -503,6 +538,7 @@ fn compile_frontend_to_sir_with_layout_mode(
     optimize_options: &crate::optimizer::OptimizeOptions,
     diagnostics: &crate::RuntimeDiagnostics,
     layout_mode: crate::backend::memory_layout::MemoryLayoutMode,
+    comb_partitions: usize,
 ) -> Result<(OptimizedSir, Vec<CompilationWarning>), SimulatorError> {
     let lowered = celox_frontend_core::lower_frontend_artifact(artifact)?;
     let frontend_trace_options = trace_opts.frontend(diagnostics);
@@ -513,6 +549,7 @@ fn compile_frontend_to_sir_with_layout_mode(
         ignored_loops,
         true_loops,
         four_state,
+        comb_partitions,
         &frontend_trace_options,
         trace_out.is_some().then_some(&mut frontend_trace),
     )
@@ -561,6 +598,11 @@ fn compile_frontend_testbench_to_sir_with_layout_mode(
     injected_manifests: &[(String, veryl_metadata::ComponentManifest)],
     layout_mode: crate::backend::memory_layout::MemoryLayoutMode,
     recover_comb_loops: bool,
+    branch_coverage: bool,
+    xprop: XPropMode,
+    module_xprop: &[(String, XPropMode)],
+    comb_partitions: usize,
+    module_cache: bool,
 ) -> Result<(OptimizedSir, Vec<CompilationWarning>), SimulatorError> {
     let lowered = celox_frontend_core::lower_frontend_artifact(artifact)?;
     let (sir, errors, frontend_diagnostics) = analyze(
@@ -582,6 +624,11 @@ fn compile_frontend_testbench_to_sir_with_layout_mode(
         injected_manifests,
         layout_mode == crate::backend::memory_layout::MemoryLayoutMode::ElementStrided,
         recover_comb_loops,
+        branch_coverage,
+        xprop,
+        module_xprop,
+        comb_partitions,
+        module_cache,
     );
     let (real_errors, analyzer_warnings): (Vec<_>, Vec<_>) =
         errors.into_iter().partition(AnalyzerError::is_error);
@@ -638,6 +685,11 @@ fn compile_to_sir_with_layout_mode(
     injected_manifests: &[(String, veryl_metadata::ComponentManifest)],
     layout_mode: crate::backend::memory_layout::MemoryLayoutMode,
     recover_comb_loops: bool,
+    branch_coverage: bool,
+    xprop: XPropMode,
+    module_xprop: &[(String, XPropMode)],
+    comb_partitions: usize,
+    module_cache: bool,
 ) -> Result<(OptimizedSir, Vec<CompilationWarning>), SimulatorError> {
     let (sir, errors, frontend_diagnostics) = analyze(
         sources,
@@ -658,6 +710,11 @@ fn compile_to_sir_with_layout_mode(
         injected_manifests,
         layout_mode == crate::backend::memory_layout::MemoryLayoutMode::ElementStrided,
         recover_comb_loops,
+        branch_coverage,
+        xprop,
+        module_xprop,
+        comb_partitions,
+        module_cache,
     );
     let (real_errors, analyzer_warnings): (Vec<_>, Vec<_>) =
         errors.into_iter().partition(AnalyzerError::is_error);
@@ -728,6 +785,7 @@ pub fn compile_sv_to_sir(
         optimize_options,
         &crate::RuntimeDiagnostics::default(),
         crate::backend::memory_layout::MemoryLayoutMode::Packed,
+        1,
     )
 }
 
@@ -755,6 +813,7 @@ fn compile_sv_to_sir_with_layout_mode(
     optimize_options: &crate::optimizer::OptimizeOptions,
     diagnostics: &crate::RuntimeDiagnostics,
     layout_mode: crate::backend::memory_layout::MemoryLayoutMode,
+    comb_partitions: usize,
 ) -> Result<(OptimizedSir, Vec<CompilationWarning>), SimulatorError> {
     let metadata = metadata.unwrap_or_else(|| Metadata::create_default("prj").unwrap());
     let mut build_config = BuildConfig::from(&metadata.build);
@@ -764,6 +823,7 @@ fn compile_sv_to_sir_with_layout_mode(
     if let Some(reset_type) = reset_type {
         build_config.reset_type = reset_type;
     }
+    build_config.comb_partitions = comb_partitions;
     let (component_libraries, component_file_base) = component_runtime_config(&metadata);
     parser::parse_sv(
         sources,
@@ -829,6 +889,11 @@ pub fn compile_mixed_to_sir(
         &[],
         crate::backend::memory_layout::MemoryLayoutMode::Packed,
         true,
+        false,
+        XPropMode::Lrm,
+        &[],
+        1,
+        true,
     )
 }
 
@@ -859,6 +924,11 @@ fn compile_mixed_to_sir_with_layout_mode(
     injected_manifests: &[(String, veryl_metadata::ComponentManifest)],
     layout_mode: crate::backend::memory_layout::MemoryLayoutMode,
     recover_comb_loops: bool,
+    branch_coverage: bool,
+    xprop: XPropMode,
+    module_xprop: &[(String, XPropMode)],
+    comb_partitions: usize,
+    module_cache: bool,
 ) -> Result<(OptimizedSir, Vec<CompilationWarning>), SimulatorError> {
     let (sir, errors, frontend_diagnostics) = analyze(
         sources,
@@ -879,6 +949,11 @@ fn compile_mixed_to_sir_with_layout_mode(
         injected_manifests,
         layout_mode == crate::backend::memory_layout::MemoryLayoutMode::ElementStrided,
         recover_comb_loops,
+        branch_coverage,
+        xprop,
+        module_xprop,
+        comb_partitions,
+        module_cache,
     );
     let (real_errors, analyzer_warnings): (Vec<_>, Vec<_>) =
         errors.into_iter().partition(AnalyzerError::is_error);
@@ -938,6 +1013,11 @@ fn compile_hdl_to_sir_with_layout_mode(
     injected_manifests: &[(String, veryl_metadata::ComponentManifest)],
     layout_mode: crate::backend::memory_layout::MemoryLayoutMode,
     recover_comb_loops: bool,
+    branch_coverage: bool,
+    xprop: XPropMode,
+    module_xprop: &[(String, XPropMode)],
+    comb_partitions: usize,
+    module_cache: bool,
 ) -> Result<(OptimizedSir, Vec<CompilationWarning>), SimulatorError> {
     #[cfg(not(feature = "systemverilog"))]
     {
@@ -959,6 +1039,11 @@ fn compile_hdl_to_sir_with_layout_mode(
             injected_manifests,
             layout_mode,
             recover_comb_loops,
+            branch_coverage,
+            xprop,
+            module_xprop,
+            comb_partitions,
+            module_cache,
         )
     }
     #[cfg(feature = "systemverilog")]
@@ -980,6 +1065,11 @@ fn compile_hdl_to_sir_with_layout_mode(
             injected_manifests,
             layout_mode,
             recover_comb_loops,
+            branch_coverage,
+            xprop,
+            module_xprop,
+            comb_partitions,
+            module_cache,
         ),
         (true, false) => compile_sv_to_sir_with_layout_mode(
             sv_sources,
@@ -996,6 +1086,7 @@ fn compile_hdl_to_sir_with_layout_mode(
             optimize_options,
             diagnostics,
             layout_mode,
+            comb_partitions,
         ),
         (false, false) => compile_mixed_to_sir_with_layout_mode(
             sources,
@@ -1015,6 +1106,11 @@ fn compile_hdl_to_sir_with_layout_mode(
             injected_manifests,
             layout_mode,
             recover_comb_loops,
+            branch_coverage,
+            xprop,
+            module_xprop,
+            comb_partitions,
+            module_cache,
         ),
     }
 }
@@ -1066,6 +1162,43 @@ mod host {
         pub native_force_support: bool,
         /// Dead store elimination policy.
         pub dead_store_policy: DeadStorePolicy,
+        /// Which signals the waveform records, and when.
+        pub dump_filter: crate::DumpFilter,
+        /// Count per-bit transitions from the start of the simulation. See
+        /// [`Simulator::toggle_coverage`].
+        pub toggle_coverage: bool,
+        /// Count visits and transitions of enum-typed state registers from the
+        /// start of the simulation. See [`Simulator::fsm_coverage`].
+        pub fsm_coverage: bool,
+        /// Instrument every `if`/`case` arm of the Veryl sources with a hit
+        /// counter, read back by [`Simulator::coverage`].
+        pub branch_coverage: bool,
+        /// Number of worker threads evaluating independent combinational and
+        /// flip-flop units. `1` runs every unit on the calling thread.
+        pub threads: usize,
+        /// Starting value of registers without an explicit initializer.
+        pub initial_state: crate::InitialStatePolicies,
+        /// How an `X` condition of `if`/`case` resolves in four-state mode.
+        pub xprop: XPropMode,
+        /// Per-module overrides of [`Self::xprop`], keyed by module name.
+        pub module_xprop: Vec<(String, XPropMode)>,
+        /// Reuse modules lowered by earlier builds on the same thread.
+        pub module_cache: bool,
+        /// Limits applied to each run call of the built simulator.
+        pub run_limits: celox_runtime::RunLimits,
+        /// Token shared with the built simulator; a fresh one when `None`.
+        pub cancel_token: Option<celox_runtime::CancelToken>,
+        /// Time every execution unit. See [`Simulator::profile`].
+        pub profile: bool,
+    }
+
+    impl SimulatorOptions {
+        fn watchdog(&self) -> celox_runtime::Watchdog {
+            celox_runtime::Watchdog::new(
+                self.cancel_token.clone().unwrap_or_default(),
+                self.run_limits,
+            )
+        }
     }
 
     /// A code-generated native program that has not been loaded into
@@ -1101,23 +1234,30 @@ mod host {
         options: SimulatorOptions,
         vcd_path: Option<std::path::PathBuf>,
         injected_components: crate::InjectedComponents,
+        profiler: Option<crate::profile::Profiler>,
     ) -> Result<Simulator<crate::backend::native::NativeBackend>, SimulatorError> {
         let mut sim = Simulator::with_backend_and_program(backend, program, warnings);
         sim.components.set_injected(injected_components);
         sim.diagnostics = options.diagnostics.clone();
+        sim.watchdog = options.watchdog();
+        sim.profiler = profiler;
         if let Some(path) = vcd_path {
-            let descs = sim.build_vcd_descs(options.four_state);
-            let vcd_writer = crate::VcdWriter::new(path, &descs)
-                .map_err(|_| SimulatorError::from(crate::RuntimeErrorCode::InternalError))?;
-            sim.vcd_writer = Some(vcd_writer);
+            sim.open_waveform(path, &options)?;
         }
         let apply_initial_start = options.diagnostics.phase_timing.then(crate::timing::now);
+        sim.apply_initial_state_policy(&options.initial_state);
         sim.apply_initial_values();
         if let Some(start) = apply_initial_start {
             tracing::debug!("[phase-timing] apply_initial_values: {:?}", start.elapsed());
         }
         let settle_start = options.diagnostics.phase_timing.then(crate::timing::now);
         sim.modify(|_| {}).map_err(SimulatorError::from)?;
+        if options.toggle_coverage {
+            sim.enable_toggle_coverage();
+        }
+        if options.fsm_coverage {
+            sim.enable_fsm_coverage();
+        }
         if let Some(start) = settle_start {
             tracing::debug!("[phase-timing] initial_settle: {:?}", start.elapsed());
         }
@@ -1157,6 +1297,9 @@ mod host {
         // Safety: callers either produced the image in this process or loaded
         // it from an explicitly trusted native-image artifact.
         let backend = unsafe { crate::backend::native::NativeBackend::from_image(image)? };
+        let profiler = options
+            .profile
+            .then(|| crate::profile::Profiler::new(&program, &options));
         initialize_native_backend(
             backend,
             program.into_runtime(),
@@ -1164,6 +1307,7 @@ mod host {
             options,
             vcd_path,
             injected_components,
+            profiler,
         )
     }
 
@@ -1237,6 +1381,18 @@ mod host {
                 emit_triggers: false,
                 native_force_support: false,
                 dead_store_policy: DeadStorePolicy::Off,
+                dump_filter: crate::DumpFilter::default(),
+                toggle_coverage: false,
+                fsm_coverage: false,
+                branch_coverage: false,
+                threads: 1,
+                initial_state: crate::InitialStatePolicies::default(),
+                xprop: XPropMode::Lrm,
+                module_xprop: Vec::new(),
+                module_cache: true,
+                run_limits: Default::default(),
+                cancel_token: None,
+                profile: false,
             }
         }
     }
@@ -1344,6 +1500,133 @@ mod host {
             self
         }
 
+        /// Enable waveform dumping to the specified file. A `.fst` extension
+        /// writes the compressed FST format; any other writes VCD.
+        pub fn waveform<P: AsRef<std::path::Path>>(self, path: P) -> Self {
+            self.vcd(path)
+        }
+
+        /// Restrict the waveform to some scopes, a time window, or signals
+        /// other than unpacked arrays.
+        pub fn dump_filter(mut self, filter: crate::DumpFilter) -> Self {
+            self.options.dump_filter = filter;
+            self
+        }
+
+        /// Count 0→1 and 1→0 transitions of every bit of every signal, for
+        /// toggle coverage and SAIF switching activity.
+        pub fn toggle_coverage(mut self, enable: bool) -> Self {
+            self.options.toggle_coverage = enable;
+            self
+        }
+
+        /// Count how often each arm of every `if`, `if_reset` and `case` in
+        /// `always_comb` and `always_ff` blocks executes, for statement and
+        /// branch coverage. The counters are compiled into the design.
+        pub fn branch_coverage(mut self, enable: bool) -> Self {
+            self.options.branch_coverage = enable;
+            self
+        }
+
+        /// Select how an `X` or `Z` condition of `if` and `case` statements in
+        /// `always_comb` and `always_ff` blocks resolves. Only affects
+        /// four-state simulation; the default follows the SystemVerilog LRM.
+        pub fn xprop(mut self, mode: XPropMode) -> Self {
+            self.options.xprop = mode;
+            self
+        }
+
+        /// Override [`Self::xprop`] for every instance of `module`.
+        pub fn xprop_in_module(mut self, module: &str, mode: XPropMode) -> Self {
+            self.options.module_xprop.push((module.to_string(), mode));
+            self
+        }
+
+        /// Reuse modules lowered by earlier builds on this thread when their
+        /// sources and configuration are unchanged, so a rebuild after an
+        /// edit only lowers the modules the edit affects. Enabled by default.
+        pub fn module_cache(mut self, enable: bool) -> Self {
+            self.options.module_cache = enable;
+            self
+        }
+
+        /// Stop runs of the built simulator when `token` is cancelled. One
+        /// token can be shared by several simulators; without this, each
+        /// simulator gets its own, returned by [`Simulator::cancel_token`].
+        pub fn cancel_token(mut self, token: celox_runtime::CancelToken) -> Self {
+            self.options.cancel_token = Some(token);
+            self
+        }
+
+        /// Fail a run call such as `run_until` or `tick_by_id_n` with
+        /// [`RuntimeErrorCode::Interrupted`](crate::RuntimeErrorCode::Interrupted)
+        /// once it has taken longer than `limit`. The check runs between time
+        /// steps, ticks and cascade rounds.
+        pub fn wall_clock_limit(mut self, limit: std::time::Duration) -> Self {
+            self.options.run_limits.wall_clock = Some(limit);
+            self
+        }
+
+        /// Fail a time step that needs more than `rounds` cascade rounds, i.e.
+        /// sequential domains triggered by the outputs of other domains.
+        pub fn max_cascade_rounds(mut self, rounds: u32) -> Self {
+            self.options.run_limits.max_cascade_rounds = Some(rounds);
+            self
+        }
+
+        /// Time every combinational execution unit and flip-flop domain and
+        /// attribute the time to the instances they belong to, read back by
+        /// [`Simulator::profile`]. Each combinational unit gets its own entry
+        /// point, so profiled builds run somewhat slower.
+        pub fn profile(mut self, enable: bool) -> Self {
+            self.options.profile = enable;
+            self
+        }
+
+        /// Count which states each enum-typed register assigned in
+        /// `always_ff` visits and which transitions it takes.
+        pub fn fsm_coverage(mut self, enable: bool) -> Self {
+            self.options.fsm_coverage = enable;
+            self
+        }
+
+        /// Start every register without an explicit initializer at `policy`.
+        ///
+        /// The default, [`InitialStatePolicy::X`](crate::InitialStatePolicy::X),
+        /// leaves registers unknown in four-state mode and zero otherwise.
+        /// `Zero`, `Ones` and `Random { seed }` expose missing resets in
+        /// two-state simulations too.
+        pub fn initial_state_policy(mut self, policy: crate::InitialStatePolicy) -> Self {
+            self.options.initial_state.set_default(policy);
+            self
+        }
+
+        /// Like [`Self::initial_state_policy`], for the instance at `scope`
+        /// (e.g. `"u_core.u_alu"`) and every instance below it. The deepest
+        /// matching scope wins.
+        pub fn initial_state_policy_in(
+            mut self,
+            scope: &str,
+            policy: crate::InitialStatePolicy,
+        ) -> Self {
+            self.options.initial_state =
+                std::mem::take(&mut self.options.initial_state).scope(scope, policy);
+            self
+        }
+
+        /// Evaluate independent execution units on `n` threads.
+        ///
+        /// The scheduler splits the combinational logic into dependency levels
+        /// and the native backend runs the units of one level, and the
+        /// flip-flop units of one event, on a worker pool with a barrier per
+        /// level. Results are bit-exact with single-threaded evaluation. Other
+        /// backends, 4-state mode, force support, and `n <= 1` evaluate
+        /// sequentially.
+        pub fn threads(mut self, n: usize) -> Self {
+            self.options.threads = n.max(1);
+            self
+        }
+
         /// Enable 4-state (0, 1, X, Z) simulation mode.
         pub fn four_state(mut self, enable: bool) -> Self {
             self.options.four_state = enable;
@@ -1361,6 +1644,16 @@ mod host {
             self
         }
 
+        /// Let [`Simulator::force`] hold nets driven by combinational logic.
+        ///
+        /// Every backend emits one extra entry point per combinational store
+        /// boundary so the forced value can be reapplied between them. Like
+        /// [`Self::native_force_support`], this forces SIR O0 at build time.
+        /// Forcing inputs and registers works without it.
+        pub fn force_support(self, enable: bool) -> Self {
+            self.native_force_support(enable)
+        }
+
         fn enforce_native_force_optimizer(&mut self) {
             if !self.options.native_force_support {
                 return;
@@ -1371,6 +1664,15 @@ mod host {
             self.options.dead_store_policy = DeadStorePolicy::Off;
         }
 
+        /// Forces are reapplied between sequential per-unit entries, and
+        /// partitioned 4-state code may leave different value bits under an
+        /// X mask, so both modes keep single-threaded evaluation.
+        fn enforce_sequential_evaluation(&mut self) {
+            if self.options.native_force_support || self.options.four_state {
+                self.options.threads = 1;
+            }
+        }
+
         /// Set the overall optimization level. Sets defaults for SIR passes,
         /// Cranelift options, and DSE policy. Per-pass overrides can be applied after.
         pub fn opt_level(mut self, level: crate::optimizer::OptLevel) -> Self {
@@ -1793,6 +2095,7 @@ mod host {
             SimulatorError,
         > {
             self.enforce_native_force_optimizer();
+            self.enforce_sequential_evaluation();
             let phase_timing = self.options.diagnostics.phase_timing;
             let compile_start = phase_timing.then(crate::timing::now);
             let injected_manifests = self.injected_components.manifests();
@@ -1808,6 +2111,7 @@ mod host {
                         &self.options.optimize_options,
                         &self.options.diagnostics,
                         layout_mode,
+                        self.options.threads,
                     )?
                 } else {
                     compile_frontend_testbench_to_sir_with_layout_mode(
@@ -1827,6 +2131,11 @@ mod host {
                         &injected_manifests,
                         layout_mode,
                         !self.options.native_force_support,
+                        self.options.branch_coverage,
+                        self.options.xprop,
+                        &self.options.module_xprop,
+                        self.options.threads,
+                        self.options.module_cache,
                     )?
                 }
             } else {
@@ -1848,6 +2157,11 @@ mod host {
                     &injected_manifests,
                     layout_mode,
                     !self.options.native_force_support,
+                    self.options.branch_coverage,
+                    self.options.xprop,
+                    &self.options.module_xprop,
+                    self.options.threads,
+                    self.options.module_cache,
                 )?
             };
             if let Some(start) = compile_start {
@@ -1903,7 +2217,9 @@ mod host {
         }
 
         /// Compiles using the Cranelift JIT backend.
-        pub fn build_cranelift(self) -> Result<Simulator<JitBackend>, SimulatorError> {
+        pub fn build_cranelift(mut self) -> Result<Simulator<JitBackend>, SimulatorError> {
+            // Only the native backend evaluates partitions concurrently.
+            self.options.threads = 1;
             let phase_timing = self.options.diagnostics.phase_timing;
             let phase_start = phase_timing.then(crate::timing::now);
 
@@ -1934,18 +2250,29 @@ mod host {
                 tracing::debug!("[phase-timing] jit_backend: {:?}", s.elapsed());
             }
 
+            let profiler = options
+                .profile
+                .then(|| crate::profile::Profiler::new(&laid_out, &options));
+
             let mut sim =
                 Simulator::with_backend_and_program(backend, laid_out.into_runtime(), warnings);
             sim.components.set_injected(injected_components);
             sim.diagnostics = options.diagnostics.clone();
+            sim.watchdog = options.watchdog();
+
+            sim.profiler = profiler;
             if let Some(path) = vcd_path {
-                let descs = sim.build_vcd_descs(options.four_state);
-                let vcd_writer = crate::VcdWriter::new(path, &descs)
-                    .map_err(|_| SimulatorError::from(crate::RuntimeErrorCode::InternalError))?;
-                sim.vcd_writer = Some(vcd_writer);
+                sim.open_waveform(path, &options)?;
             }
+            sim.apply_initial_state_policy(&options.initial_state);
             sim.apply_initial_values();
             sim.modify(|_| {}).map_err(SimulatorError::from)?;
+            if options.toggle_coverage {
+                sim.enable_toggle_coverage();
+            }
+            if options.fsm_coverage {
+                sim.enable_fsm_coverage();
+            }
             Ok(sim)
         }
 
@@ -2081,7 +2408,8 @@ mod host {
         /// This is the execution-side half of the host-codegen/QEMU workflow:
         /// source-independent runtime metadata and the semantic testbench are
         /// restored from `image`, while the target machine code is taken
-        /// entirely from it.
+        /// entirely from it. The image carries no SIR, so
+        /// [`Self::profile`] has no effect here.
         #[cfg(any(
             target_arch = "x86_64",
             feature = "arm64-codegen",
@@ -2117,28 +2445,75 @@ mod host {
                 options,
                 vcd_path,
                 injected_components,
+                None,
             )
         }
 
         /// Compiles using the Wasmtime WASM backend.
         pub fn build_wasm(
-            self,
+            mut self,
         ) -> Result<Simulator<crate::backend::wasm_runtime::WasmBackend>, SimulatorError> {
+            // Only the native backend evaluates partitions concurrently.
+            self.options.threads = 1;
             let (laid_out, warnings, options, vcd_path, injected_components) = self
                 .into_laid_out_program(crate::backend::memory_layout::MemoryLayoutMode::Packed)?;
             let backend = crate::backend::wasm_runtime::WasmBackend::new(&laid_out, &options)?;
+            let profiler = options
+                .profile
+                .then(|| crate::profile::Profiler::new(&laid_out, &options));
             let mut sim =
                 Simulator::with_backend_and_program(backend, laid_out.into_runtime(), warnings);
             sim.components.set_injected(injected_components);
             sim.diagnostics = options.diagnostics.clone();
+            sim.watchdog = options.watchdog();
+            sim.profiler = profiler;
             if let Some(path) = vcd_path {
-                let descs = sim.build_vcd_descs(options.four_state);
-                let vcd_writer = crate::VcdWriter::new(path, &descs)
-                    .map_err(|_| SimulatorError::from(crate::RuntimeErrorCode::InternalError))?;
-                sim.vcd_writer = Some(vcd_writer);
+                sim.open_waveform(path, &options)?;
             }
+            sim.apply_initial_state_policy(&options.initial_state);
             sim.apply_initial_values();
             sim.modify(|_| {}).map_err(SimulatorError::from)?;
+            if options.toggle_coverage {
+                sim.enable_toggle_coverage();
+            }
+            if options.fsm_coverage {
+                sim.enable_fsm_coverage();
+            }
+            Ok(sim)
+        }
+
+        /// Builds a simulator on the reference SIR interpreter. Nothing is
+        /// compiled to machine code, so it runs on any host, slowly.
+        pub fn build_interpreter(
+            mut self,
+        ) -> Result<Simulator<crate::backend::interpreter::InterpreterBackend>, SimulatorError>
+        {
+            self.options.threads = 1;
+            let (laid_out, warnings, options, vcd_path, injected_components) = self
+                .into_laid_out_program(crate::backend::memory_layout::MemoryLayoutMode::Packed)?;
+            let backend =
+                crate::backend::interpreter::InterpreterBackend::new(&laid_out, &options)?;
+            let profiler = options
+                .profile
+                .then(|| crate::profile::Profiler::new(&laid_out, &options));
+            let mut sim =
+                Simulator::with_backend_and_program(backend, laid_out.into_runtime(), warnings);
+            sim.components.set_injected(injected_components);
+            sim.diagnostics = options.diagnostics.clone();
+            sim.watchdog = options.watchdog();
+            sim.profiler = profiler;
+            if let Some(path) = vcd_path {
+                sim.open_waveform(path, &options)?;
+            }
+            sim.apply_initial_state_policy(&options.initial_state);
+            sim.apply_initial_values();
+            sim.modify(|_| {}).map_err(SimulatorError::from)?;
+            if options.toggle_coverage {
+                sim.enable_toggle_coverage();
+            }
+            if options.fsm_coverage {
+                sim.enable_fsm_coverage();
+            }
             Ok(sim)
         }
 
@@ -2181,6 +2556,7 @@ mod host {
         /// while capturing compilation trace data as configured by TraceOptions.
         pub fn build_with_trace(mut self) -> crate::debug::CompilationTraceResult {
             self.enforce_native_force_optimizer();
+            self.enforce_sequential_evaluation();
             let mut trace = crate::debug::CompilationTrace::default();
             #[cfg(any(
                 all(target_arch = "x86_64", not(feature = "arm64-codegen")),
@@ -2205,6 +2581,7 @@ mod host {
                         &self.options.optimize_options,
                         &self.options.diagnostics,
                         layout_mode,
+                        self.options.threads,
                     )
                 } else {
                     compile_frontend_testbench_to_sir_with_layout_mode(
@@ -2224,6 +2601,11 @@ mod host {
                         &injected_manifests,
                         layout_mode,
                         !self.options.native_force_support,
+                        self.options.branch_coverage,
+                        self.options.xprop,
+                        &self.options.module_xprop,
+                        self.options.threads,
+                        self.options.module_cache,
                     )
                 }
             } else {
@@ -2245,6 +2627,11 @@ mod host {
                     &injected_manifests,
                     layout_mode,
                     !self.options.native_force_support,
+                    self.options.branch_coverage,
+                    self.options.xprop,
+                    &self.options.module_xprop,
+                    self.options.threads,
+                    self.options.module_cache,
                 )
             };
 
@@ -2282,13 +2669,28 @@ mod host {
                 )))]
                 let backend = JitBackend::new(&laid_out, &self.options, None)?;
 
+                let profiler = self
+                    .options
+                    .profile
+                    .then(|| crate::profile::Profiler::new(&laid_out, &self.options));
+
                 let mut sim =
                     Simulator::with_backend_and_program(backend, laid_out.into_runtime(), warnings);
                 sim.components
                     .set_injected(self.injected_components.clone());
                 sim.diagnostics = self.options.diagnostics.clone();
+                sim.watchdog = self.options.watchdog();
+
+                sim.profiler = profiler;
+                sim.apply_initial_state_policy(&self.options.initial_state);
                 sim.apply_initial_values();
                 sim.modify(|_| {}).map_err(SimulatorError::from)?;
+                if self.options.toggle_coverage {
+                    sim.enable_toggle_coverage();
+                }
+                if self.options.fsm_coverage {
+                    sim.enable_fsm_coverage();
+                }
                 Ok(sim)
             });
 
@@ -2387,6 +2789,7 @@ mod host {
         pub fn build(mut self) -> Result<crate::Simulation, SimulatorError> {
             self.options.emit_triggers = true;
             self.enforce_native_force_optimizer();
+            self.enforce_sequential_evaluation();
             #[cfg(any(
                 all(target_arch = "x86_64", not(feature = "arm64-codegen")),
                 all(target_arch = "aarch64", not(feature = "x86_64-codegen"))
@@ -2408,6 +2811,7 @@ mod host {
                     &self.options.optimize_options,
                     &self.options.diagnostics,
                     layout_mode,
+                    self.options.threads,
                 )?
             } else {
                 compile_hdl_to_sir_with_layout_mode(
@@ -2428,6 +2832,11 @@ mod host {
                     &self.injected_components.manifests(),
                     layout_mode,
                     !self.options.native_force_support,
+                    self.options.branch_coverage,
+                    self.options.xprop,
+                    &self.options.module_xprop,
+                    self.options.threads,
+                    self.options.module_cache,
                 )?
             };
             let mut laid_out =
@@ -2447,18 +2856,30 @@ mod host {
             )))]
             let backend = crate::backend::JitBackend::new(&laid_out, &self.options, None)?;
 
+            let profiler = self
+                .options
+                .profile
+                .then(|| crate::profile::Profiler::new(&laid_out, &self.options));
+
             let mut sim =
                 Simulator::with_backend_and_program(backend, laid_out.into_runtime(), warnings);
             sim.components.set_injected(self.injected_components);
             sim.diagnostics = self.options.diagnostics.clone();
+            sim.watchdog = self.options.watchdog();
+
+            sim.profiler = profiler;
             if let Some(path) = self.vcd_path {
-                let descs = sim.build_vcd_descs(self.options.four_state);
-                let vcd_writer = crate::VcdWriter::new(path, &descs)
-                    .map_err(|_| SimulatorError::from(crate::RuntimeErrorCode::InternalError))?;
-                sim.vcd_writer = Some(vcd_writer);
+                sim.open_waveform(path, &self.options)?;
             }
+            sim.apply_initial_state_policy(&self.options.initial_state);
             sim.apply_initial_values();
             sim.modify(|_| {}).map_err(SimulatorError::from)?;
+            if self.options.toggle_coverage {
+                sim.enable_toggle_coverage();
+            }
+            if self.options.fsm_coverage {
+                sim.enable_fsm_coverage();
+            }
             Ok(crate::Simulation::new(sim))
         }
     }
@@ -2476,6 +2897,14 @@ mod host {
         // directly. Their inputs are therefore external DSE roots just like
         // signals named with `live_signal()`.
         externally_live.extend(program.runtime_schema.testbench_read_roots.iter().copied());
+        // Final-block memory dumps read whole memories from the host.
+        externally_live.extend(
+            program
+                .runtime_schema
+                .final_memory_dumps
+                .iter()
+                .map(|dump| dump.memory),
+        );
 
         // User-specified live signals
         for (inst_path, var_path) in live_signals {
@@ -918,6 +918,10 @@ fn exec_for_loop<B: SimBackend>(
     ExecResult::Continue
 }
 
+/// Longest run of ticks the native tick loop executes before the host checks
+/// the simulator's cancel token and wall-clock limit.
+const WATCHDOG_TICK_BATCH: u64 = 1 << 16;
+
 pub(crate) fn run_testbench<B: SimBackend>(
     sim: &mut Simulator<B>,
     testbench: &CompiledTestbench<B>,
@@ -932,6 +936,7 @@ fn run_testbench_limited<B: SimBackend>(
 ) -> LimitedTestbenchResult {
     let test_name = root_testbench_name(sim);
     let use_4state = sim.backend.layout().four_state;
+    sim.watchdog.arm();
     let initial_writes = match sim.components.initialize(
         testbench.components(),
         testbench.component_bindings(),
@@ -982,6 +987,11 @@ fn run_testbench_limited<B: SimBackend>(
     {
         result = ExecResult::Fail(message);
     }
+    if let Err(error) = sim.run_final_blocks()
+        && !matches!(result, ExecResult::Fail(_))
+    {
+        result = ExecResult::Fail(format!("final block: {error}"));
+    }
     let failed_messages = ctx
         .assertions
         .iter()
@@ -1060,6 +1070,7 @@ pub(crate) fn run_testbench_detailed<B: SimBackend>(
 ) -> TestResultDetailed {
     let test_name = root_testbench_name(sim);
     let use_4state = sim.backend.layout().four_state;
+    sim.watchdog.arm();
     let initial_writes = match sim.components.initialize(
         testbench.components(),
         testbench.component_bindings(),
@@ -1114,6 +1125,11 @@ pub(crate) fn run_testbench_detailed<B: SimBackend>(
     {
         error = Some(message);
     }
+    if let Err(final_error) = sim.run_final_blocks()
+        && error.is_none()
+    {
+        error = Some(format!("final block: {final_error}"));
+    }
     let passed = error.is_none() && ctx.assertions.iter().all(|a| a.passed);
     TestResultDetailed {
         passed,
@@ -1352,7 +1368,10 @@ fn exec_one_detailed<B: SimBackend>(
                         if tick_limit_reached(ctx) {
                             return ExecResult::Finished;
                         }
-                        let mut batch = remaining;
+                        if let Err(error) = sim.watchdog.check(ctx.current_time) {
+                            return ExecResult::Fail(error.to_string());
+                        }
+                        let mut batch = remaining.min(WATCHDOG_TICK_BATCH);
                         if let Some(limit) = ctx.tick_limit {
                             batch = batch.min(limit.saturating_sub(ctx.current_time));
                         }
@@ -1415,7 +1434,10 @@ fn exec_one_detailed<B: SimBackend>(
                     if tick_limit_reached(ctx) {
                         return ExecResult::Finished;
                     }
-                    let mut batch = remaining;
+                    if let Err(error) = sim.watchdog.check(ctx.current_time) {
+                        return ExecResult::Fail(error.to_string());
+                    }
+                    let mut batch = remaining.min(WATCHDOG_TICK_BATCH);
                     if let Some(limit) = ctx.tick_limit {
                         batch = batch.min(limit.saturating_sub(ctx.current_time));
                     }
@@ -1677,6 +1699,28 @@ fn exec_one_detailed<B: SimBackend>(
             );
             ExecResult::Continue
         }
+        GenericTestbenchStatement::WriteMemory {
+            memory,
+            path,
+            binary,
+            start,
+            end,
+        } => {
+            let format = if *binary {
+                crate::MemoryFileFormat::Binary
+            } else {
+                crate::MemoryFileFormat::Hex
+            };
+            let start = start.unwrap_or(0);
+            let written = match end {
+                Some(end) => sim.write_memory_file(*memory, path, format, start..=*end),
+                None => sim.write_memory_file(*memory, path, format, start..),
+            };
+            match written {
+                Ok(_) => ExecResult::Continue,
+                Err(error) => ExecResult::Fail(format!("$writemem {path}: {error}")),
+            }
+        }
         GenericTestbenchStatement::Break => ExecResult::Break,
         GenericTestbenchStatement::Finish => ExecResult::Finished,
     }
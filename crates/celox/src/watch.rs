@@ -0,0 +1,146 @@
+//! Value-change callbacks.
+//!
+//! Watches are detected by snapshot compare: each watch remembers the last
+//! `(value, mask)` pair it reported, and [`WatchList::notify`] re-reads the
+//! signal after the simulator has settled and reports every difference. This
+//! is the scheme described in ADR-0001; it costs one read per watched signal
+//! per notification point, which is negligible next to the scheduler for the
+//! handful of signals a testbench typically monitors.
+
+use num_bigint::BigUint;
+
+use crate::ir::SignalRef;
+
+/// Identifier returned by `watch` and accepted by `unwatch`.
+pub type WatchId = u32;
+
+/// Boxed callback stored by [`crate::Simulator::watch`].
+pub type WatchCallback = Box<dyn FnMut(&ValueChange) + Send>;
+
+/// A change observed on a watched signal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueChange {
+    /// The watch that reported this change.
+    pub id: WatchId,
+    pub signal: SignalRef,
+    pub old_value: BigUint,
+    pub old_mask: BigUint,
+    pub new_value: BigUint,
+    pub new_mask: BigUint,
+    /// Simulation time of the change. For a bare [`crate::Simulator`] this is
+    /// the number of completed ticks.
+    pub time: u64,
+}
+
+struct Watch<C> {
+    id: WatchId,
+    signal: SignalRef,
+    value: BigUint,
+    mask: BigUint,
+    callback: C,
+}
+
+/// Registered watches and their last observed values.
+///
+/// The callback type is generic so language bindings can store their own
+/// function handles and dispatch them with the context they need.
+pub struct WatchList<C = WatchCallback> {
+    next_id: WatchId,
+    watches: Vec<Watch<C>>,
+}
+
+impl<C> Default for WatchList<C> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            watches: Vec::new(),
+        }
+    }
+}
+
+impl<C> std::fmt::Debug for WatchList<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchList")
+            .field("len", &self.watches.len())
+            .finish()
+    }
+}
+
+impl<C> WatchList<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `callback` for `signal`, whose settled value is `current`.
+    pub fn watch(
+        &mut self,
+        signal: SignalRef,
+        current: (BigUint, BigUint),
+        callback: C,
+    ) -> WatchId {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        let (value, mask) = current;
+        self.watches.push(Watch {
+            id,
+            signal,
+            value,
+            mask,
+            callback,
+        });
+        id
+    }
+
+    /// Remove a watch. Returns `false` if `id` was not registered.
+    pub fn unwatch(&mut self, id: WatchId) -> bool {
+        let len = self.watches.len();
+        self.watches.retain(|watch| watch.id != id);
+        self.watches.len() != len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.watches.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.watches.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.watches.clear();
+    }
+
+    /// Adopt the current values as the new baseline without reporting them.
+    pub fn rebase(&mut self, mut read: impl FnMut(SignalRef) -> (BigUint, BigUint)) {
+        for watch in &mut self.watches {
+            (watch.value, watch.mask) = read(watch.signal);
+        }
+    }
+
+    /// Re-read every watched signal and dispatch a [`ValueChange`] for each
+    /// one whose value or mask differs from the last report. Watches fire in
+    /// registration order.
+    pub fn notify(
+        &mut self,
+        time: u64,
+        mut read: impl FnMut(SignalRef) -> (BigUint, BigUint),
+        mut dispatch: impl FnMut(&mut C, &ValueChange),
+    ) {
+        for watch in &mut self.watches {
+            let (value, mask) = read(watch.signal);
+            if value == watch.value && mask == watch.mask {
+                continue;
+            }
+            let change = ValueChange {
+                id: watch.id,
+                signal: watch.signal,
+                old_value: std::mem::replace(&mut watch.value, value.clone()),
+                old_mask: std::mem::replace(&mut watch.mask, mask.clone()),
+                new_value: value,
+                new_mask: mask,
+                time,
+            };
+            dispatch(&mut watch.callback, &change);
+        }
+    }
+}
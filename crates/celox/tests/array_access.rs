@@ -0,0 +1,102 @@
+use celox::{BigUint, MemoryFileFormat, Simulator};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[path = "test_utils/mod.rs"]
+#[macro_use]
+#[allow(unused_macros)]
+mod test_utils;
+
+fn temp_mem_file(name: &str, content: &str) -> std::path::PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("celox_{name}_{}_{id}.mem", std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+const RAM: &str = r#"
+module Top (
+    addr : input  logic<10>,
+    data : output logic<32>,
+    naddr: input  logic<3>,
+    nib  : output logic<3>,
+) {
+    var mem   : logic<32>[1024];
+    var nibble: logic<3>[8];
+    assign data = mem[addr];
+    assign nib  = nibble[naddr];
+}
+"#;
+
+all_backends! {
+
+fn test_array_elements_round_trip(sim) {
+    @omit_veryl;
+    @build Simulator::builder(RAM, "Top");
+    let mem = sim.signal("mem");
+    let nibble = sim.signal("nibble");
+    let addr = sim.signal("addr");
+    let data = sim.signal("data");
+    let naddr = sim.signal("naddr");
+    let nib = sim.signal("nib");
+
+    sim.write_array_element(mem, 513, BigUint::from(0xdead_beefu32));
+    sim.write_array_element(nibble, 5, BigUint::from(0b101u8));
+    sim.write_array_element(nibble, 6, BigUint::from(0b011u8));
+    assert_eq!(sim.read_array_element(mem, 513), BigUint::from(0xdead_beefu32));
+    assert_eq!(sim.read_array_element(nibble, 5), BigUint::from(0b101u8));
+    assert_eq!(sim.read_array_element(nibble, 6), BigUint::from(0b011u8));
+    assert_eq!(sim.read_array_element(nibble, 4), BigUint::from(0u8));
+
+    sim.modify(|io| {
+        io.set(addr, 513u16);
+        io.set(naddr, 6u8);
+    })
+    .unwrap();
+    assert_eq!(sim.get(data), BigUint::from(0xdead_beefu32));
+    assert_eq!(sim.get(nib), BigUint::from(0b011u8));
+}
+
+fn test_load_and_dump_array(sim) {
+    @omit_veryl;
+    @build Simulator::builder(RAM, "Top");
+    let mem = sim.signal("mem");
+    let addr = sim.signal("addr");
+    let data = sim.signal("data");
+
+    let image: Vec<BigUint> = (0..1024u32)
+        .map(|i| BigUint::from(i.wrapping_mul(0x9e37_79b9)))
+        .collect();
+    sim.load_array(mem, &image);
+    assert_eq!(sim.dump_array(mem), image);
+
+    sim.modify(|io| io.set(addr, 1000u16)).unwrap();
+    assert_eq!(sim.get(data), image[1000]);
+}
+
+fn test_load_memory_file(sim) {
+    @omit_veryl;
+    @setup {
+        let hex = temp_mem_file("array_access_hex", "// firmware\n0000_0013\n@3ff deadbeef\n");
+        let bin = temp_mem_file("array_access_bin", "@2 101 111 // tail\n");
+    }
+    @build Simulator::builder(RAM, "Top");
+    let mem = sim.signal("mem");
+    let nibble = sim.signal("nibble");
+
+    assert_eq!(sim.load_memory_file(mem, &hex, MemoryFileFormat::Hex).unwrap(), 2);
+    assert_eq!(sim.load_memory_file(nibble, &bin, MemoryFileFormat::Binary).unwrap(), 2);
+    assert_eq!(sim.read_array_element(mem, 0), BigUint::from(0x13u32));
+    assert_eq!(sim.read_array_element(mem, 1023), BigUint::from(0xdead_beefu32));
+    assert_eq!(
+        sim.dump_array(nibble),
+        [0u8, 0, 0b101, 0b111, 0, 0, 0, 0].map(BigUint::from).to_vec()
+    );
+
+    let overflow = temp_mem_file("array_access_overflow", "@8 1");
+    assert!(sim
+        .load_memory_file(nibble, &overflow, MemoryFileFormat::Binary)
+        .is_err());
+}
+
+}
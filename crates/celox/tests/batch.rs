@@ -0,0 +1,94 @@
+#![cfg(any(
+    all(target_arch = "x86_64", not(feature = "arm64-codegen")),
+    all(target_arch = "aarch64", not(feature = "x86_64-codegen"))
+))]
+
+use celox::Simulator;
+
+const DESIGN: &str = r#"module Top (
+    clk : input  '_ clock,
+    rst : input  '_ reset,
+    seed: input  logic<32>,
+    out : output logic<32>,
+) {
+    var state: logic<32>;
+    always_ff (clk, rst) {
+        if_reset {
+            state = 32'h1234_5678;
+        } else {
+            state = (state ^ (state << 7)) + seed;
+        }
+    }
+    assign out = state ^ (state >> 11);
+}
+"#;
+
+fn build() -> Simulator<celox::NativeBackend> {
+    let mut sim = Simulator::builder(DESIGN, "Top").build_native().unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    sim.modify(|io| io.set::<u8>(rst, 0)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    sim
+}
+
+#[test]
+fn lanes_match_individual_simulators() {
+    let lanes = 5;
+    let mut batch = build().batch(lanes).unwrap();
+    let clk = batch.event("clk");
+    let seed = batch.signal("seed");
+    let out = batch.signal("out");
+
+    let mut singles: Vec<_> = (0..lanes).map(|_| build()).collect();
+    for (lane, sim) in singles.iter_mut().enumerate() {
+        let value = 0x9e37_79b9u32.wrapping_mul(lane as u32 + 1);
+        batch.set::<u32>(lane, seed, value);
+        sim.modify(|io| io.set::<u32>(seed, value)).unwrap();
+    }
+
+    for _ in 0..50 {
+        batch.tick(clk).unwrap();
+        for (lane, sim) in singles.iter_mut().enumerate() {
+            sim.tick(clk).unwrap();
+            assert_eq!(batch.get_as::<u32>(lane, out), sim.get_as::<u32>(out));
+        }
+    }
+    assert_ne!(batch.get(0, out), batch.get(1, out));
+}
+
+#[test]
+fn masked_ticks_let_lanes_diverge() {
+    let mut batch = build().batch(3).unwrap();
+    let clk = batch.event("clk");
+    let seed = batch.signal("seed");
+    let out = batch.signal("out");
+    for lane in 0..3 {
+        batch.set::<u32>(lane, seed, 3u32);
+    }
+
+    let mut reference = build();
+    reference.modify(|io| io.set::<u32>(seed, 3u32)).unwrap();
+    let initial = reference.get_as::<u32>(out);
+
+    for _ in 0..4 {
+        batch.tick_lanes(clk, &[true, false, true]).unwrap();
+    }
+    batch.tick_lanes(clk, &[true]).unwrap();
+    for _ in 0..5 {
+        reference.tick(clk).unwrap();
+    }
+
+    assert_eq!(batch.get_as::<u32>(0, out), reference.get_as::<u32>(out));
+    assert_eq!(batch.get_as::<u32>(1, out), initial);
+    assert_ne!(batch.get_as::<u32>(2, out), batch.get_as::<u32>(0, out));
+}
+
+#[test]
+fn lanes_are_cache_line_strided() {
+    let batch = build().batch(4).unwrap();
+    assert_eq!(batch.lanes(), 4);
+    assert!(batch.lane_stride() > 0);
+    assert_eq!(batch.lane_stride() % 8, 0);
+}
@@ -0,0 +1,186 @@
+use celox::{BranchCoverage, CoverageArmKind, CoverageProcess, Simulator};
+
+const DESIGN: &str = r#"module Top (
+    clk: input  '_ clock,
+    rst: input  '_ reset,
+    sel: input  logic<2>,
+    en : input  logic,
+    o  : output logic<4>,
+) {
+    var count: logic<4>;
+    always_ff (clk, rst) {
+        if_reset {
+            count = 4'd0;
+        } else if en {
+            count = count + 4'd1;
+        }
+    }
+    always_comb {
+        case sel {
+            2'd0: o = count;
+            2'd1: o = ~count;
+            default: o = 4'd0;
+        }
+    }
+}
+"#;
+
+fn arm(coverage: &BranchCoverage, line: u32, arm: CoverageArmKind) -> u64 {
+    coverage
+        .branches()
+        .iter()
+        .find(|branch| branch.scope == "Top" && branch.line == line && branch.arm == arm)
+        .map(|branch| branch.hits)
+        .unwrap()
+}
+
+#[test]
+fn counts_ff_and_comb_arms() {
+    let mut sim = Simulator::builder(DESIGN, "Top")
+        .branch_coverage(true)
+        .build()
+        .unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let en = sim.signal("en");
+    let sel = sim.signal("sel");
+    let o = sim.signal("o");
+
+    sim.modify(|io| {
+        io.set::<u8>(rst, 0);
+        io.set::<u8>(sel, 0);
+    })
+    .unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(en, 1);
+    })
+    .unwrap();
+    for _ in 0..3 {
+        sim.tick(clk).unwrap();
+    }
+    sim.modify(|io| io.set::<u8>(en, 0)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| io.set::<u8>(sel, 1)).unwrap();
+    assert_eq!(sim.get(o), 0b1100u8.into());
+
+    let coverage = sim.coverage();
+    assert!(
+        coverage
+            .branches()
+            .iter()
+            .all(|branch| branch.scope == "Top"),
+        "{coverage:?}"
+    );
+    // if_reset at line 10, `if en` at line 12, case at line 17.
+    assert_eq!(arm(&coverage, 10, CoverageArmKind::Then), 1);
+    assert_eq!(arm(&coverage, 10, CoverageArmKind::Else), 4);
+    assert_eq!(arm(&coverage, 12, CoverageArmKind::Then), 3);
+    assert_eq!(arm(&coverage, 12, CoverageArmKind::Else), 1);
+    assert!(arm(&coverage, 17, CoverageArmKind::CaseArm(0)) > 0);
+    assert!(arm(&coverage, 17, CoverageArmKind::CaseArm(1)) > 0);
+    assert_eq!(arm(&coverage, 17, CoverageArmKind::Default), 0);
+    assert!(
+        coverage
+            .branches()
+            .iter()
+            .filter(|branch| branch.line == 17)
+            .all(|branch| branch.process == CoverageProcess::Comb)
+    );
+    assert_eq!(coverage.total_branches(), 7);
+    assert_eq!(coverage.covered_branches(), 6);
+
+    let mut lcov = Vec::new();
+    coverage.write_lcov(&mut lcov).unwrap();
+    let lcov = String::from_utf8(lcov).unwrap();
+    assert!(lcov.contains("DA:12,4\n"), "{lcov}");
+    assert!(lcov.contains("BRF:7\nBRH:6\n"), "{lcov}");
+}
+
+#[test]
+fn coverage_is_empty_unless_requested() {
+    let mut sim = Simulator::builder(DESIGN, "Top").build().unwrap();
+    assert_eq!(sim.coverage().total_branches(), 0);
+}
+
+#[test]
+fn runs_merge_into_one_report() {
+    let run = |sel: u8| {
+        let mut sim = Simulator::builder(DESIGN, "Top")
+            .branch_coverage(true)
+            .build()
+            .unwrap();
+        let s = sim.signal("sel");
+        sim.modify(|io| io.set::<u8>(s, sel)).unwrap();
+        sim.coverage()
+    };
+    let mut merged = run(2);
+    assert_eq!(arm(&merged, 17, CoverageArmKind::CaseArm(1)), 0);
+    merged.merge(&run(1));
+    assert!(arm(&merged, 17, CoverageArmKind::CaseArm(1)) > 0);
+    assert!(arm(&merged, 17, CoverageArmKind::Default) > 0);
+    assert_eq!(merged.total_branches(), 7);
+
+    let mut xml = Vec::new();
+    merged.write_cobertura(&mut xml).unwrap();
+    let xml = String::from_utf8(xml).unwrap();
+    assert!(xml.contains("<line number=\"17\""), "{xml}");
+}
+
+const HIERARCHY: &str = r#"module Sat (
+    i: input  logic<4>,
+    o: output logic<4>,
+) {
+    always_comb {
+        if i >: 4'd9 {
+            o = 4'd9;
+        } else {
+            o = i;
+        }
+    }
+}
+module Top (
+    a: input  logic<4>,
+    b: input  logic<4>,
+    x: output logic<4>,
+    y: output logic<4>,
+) {
+    inst ua: Sat (i: a, o: x);
+    inst ub: Sat (i: b, o: y);
+}
+"#;
+
+#[test]
+fn instances_are_counted_separately() {
+    let mut sim = Simulator::builder(HIERARCHY, "Top")
+        .branch_coverage(true)
+        .build()
+        .unwrap();
+    let (a, b, x) = (sim.signal("a"), sim.signal("b"), sim.signal("x"));
+    sim.modify(|io| {
+        io.set::<u8>(a, 12);
+        io.set::<u8>(b, 3);
+    })
+    .unwrap();
+    assert_eq!(sim.get(x), 9u8.into());
+
+    let coverage = sim.coverage();
+    let hits = |scope: &str, arm| {
+        coverage
+            .branches()
+            .iter()
+            .find(|branch| branch.scope == scope && branch.arm == arm)
+            .unwrap()
+            .hits
+    };
+    assert!(hits("Top.ua", CoverageArmKind::Then) > 0);
+    assert_eq!(hits("Top.ua", CoverageArmKind::Else), 0);
+    assert_eq!(hits("Top.ub", CoverageArmKind::Then), 0);
+    assert!(hits("Top.ub", CoverageArmKind::Else) > 0);
+
+    let mut lcov = Vec::new();
+    coverage.write_lcov(&mut lcov).unwrap();
+    let lcov = String::from_utf8(lcov).unwrap();
+    assert!(lcov.contains("BRF:2\nBRH:2\n"), "{lcov}");
+}
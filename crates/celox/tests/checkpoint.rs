@@ -0,0 +1,108 @@
+use celox::{CheckpointError, Simulation, Simulator, SimulatorCheckpoint};
+
+const COUNTER: &str = r#"
+    module Top (
+        clk: input  clock,
+        rst: input  reset,
+        cnt: output logic<8>
+    ) {
+        var counter: logic<8>;
+        always_ff (clk, rst) {
+            if_reset {
+                counter = 8'd0;
+            } else {
+                counter = counter + 8'd1;
+            }
+        }
+        assign cnt = counter;
+    }
+"#;
+
+#[test]
+fn simulator_restore_rewinds_state() {
+    let mut sim = Simulator::builder(COUNTER, "Top").build().unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let cnt = sim.signal("cnt");
+
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    for _ in 0..3 {
+        sim.tick(clk).unwrap();
+    }
+    let checkpoint = sim.save_state();
+    let saved = sim.get(cnt);
+
+    for _ in 0..5 {
+        sim.tick(clk).unwrap();
+    }
+    assert_ne!(sim.get(cnt), saved);
+
+    sim.restore_state(&checkpoint).unwrap();
+    assert_eq!(sim.get(cnt), saved);
+    sim.tick(clk).unwrap();
+    assert_eq!(sim.get(cnt), saved + 1u32);
+}
+
+#[test]
+fn simulation_checkpoint_round_trips_through_file() {
+    let mut sim = Simulation::builder(COUNTER, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    let cnt = sim.signal("cnt");
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    sim.run_until(35).unwrap();
+
+    let path = std::env::temp_dir().join(format!("celox-checkpoint-{}.ckpt", std::process::id()));
+    sim.save_state().write(&path).unwrap();
+    let checkpoint = SimulatorCheckpoint::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(checkpoint.time(), Some(35));
+
+    sim.run_until(100).unwrap();
+    let expected = sim.get(cnt);
+
+    let mut resumed = Simulation::builder(COUNTER, "Top").build().unwrap();
+    resumed.restore_state(&checkpoint).unwrap();
+    assert_eq!(resumed.time(), 35);
+    assert_eq!(resumed.next_event_time(), Some(40));
+    resumed.run_until(100).unwrap();
+    assert_eq!(resumed.get(cnt), expected);
+}
+
+#[test]
+fn restore_rejects_different_design() {
+    let sim = Simulator::builder(COUNTER, "Top").build().unwrap();
+    let checkpoint = sim.save_state();
+
+    let renamed = COUNTER
+        .replace("var counter", "var count")
+        .replace("counter", "count");
+    let mut different = Simulator::builder(&renamed, "Top").build().unwrap();
+    assert!(matches!(
+        different.restore_state(&checkpoint),
+        Err(CheckpointError::DesignMismatch { .. })
+    ));
+}
+
+#[test]
+fn simulation_rejects_simulator_checkpoint() {
+    let sim = Simulator::builder(COUNTER, "Top").build().unwrap();
+    let checkpoint = sim.save_state();
+    let mut timed = Simulation::builder(COUNTER, "Top").build().unwrap();
+    assert!(matches!(
+        timed.restore_state(&checkpoint),
+        Err(CheckpointError::MissingSimulationState)
+    ));
+}
+
+#[test]
+fn corrupted_checkpoint_is_rejected() {
+    let sim = Simulator::builder(COUNTER, "Top").build().unwrap();
+    let mut bytes = sim.save_state().to_bytes().unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    assert!(matches!(
+        SimulatorCheckpoint::from_bytes(&bytes),
+        Err(CheckpointError::ChecksumMismatch)
+    ));
+}
@@ -0,0 +1,196 @@
+use celox::{BigUint, ClockSpec, RuntimeErrorCode, Simulation, Timescale, TimescaleError};
+
+const COUNTERS: &str = r#"
+module Top (
+    clk:  input  '_ clock,
+    clk2: input  '_ clock,
+    rst:  input  '_ reset,
+    cnt:  output logic<8>,
+    cnt2: output logic<8>,
+) {
+    var count: logic<8>;
+    always_ff (clk, rst) {
+        if_reset {
+            count = 8'd0;
+        } else {
+            count = count + 8'd1;
+        }
+    }
+    assign cnt = count;
+
+    var count2: logic<8>;
+    always_ff (clk2, rst) {
+        if_reset {
+            count2 = 8'd0;
+        } else {
+            count2 = count2 + 8'd1;
+        }
+    }
+    assign cnt2 = count2;
+}
+"#;
+
+fn build() -> Simulation {
+    let mut sim = Simulation::builder(COUNTERS, "Top").build().unwrap();
+    let rst = sim.signal("rst");
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    sim
+}
+
+/// Step until `until` and record `(time, level)` of `port` after each step.
+fn edges(sim: &mut Simulation, port: &str, until: u64) -> Vec<(u64, u32)> {
+    let signal = sim.signal(port);
+    let mut edges = Vec::new();
+    while let Some(next) = sim.next_event_time() {
+        if next > until {
+            break;
+        }
+        let time = sim.step().unwrap().unwrap();
+        let level = u32::try_from(sim.get(signal)).unwrap();
+        edges.push((time, level));
+    }
+    edges
+}
+
+#[test]
+fn duty_cycle_and_phase_place_edges() {
+    let mut sim = build();
+    sim.add_clock_with("clk", ClockSpec::new(10).high_time(3).phase(2))
+        .unwrap();
+    assert_eq!(
+        edges(&mut sim, "clk", 25),
+        vec![(2, 1), (5, 0), (12, 1), (15, 0), (22, 1), (25, 0)]
+    );
+    assert_eq!(sim.get(sim.signal("cnt")), BigUint::from(3u32));
+}
+
+#[test]
+fn clocks_with_unrelated_periods_interleave() {
+    let mut sim = build();
+    sim.add_clock_with("clk", ClockSpec::new(4)).unwrap();
+    sim.add_clock_with("clk2", ClockSpec::new(7).phase(1))
+        .unwrap();
+    sim.run_until(28).unwrap();
+    // clk rises at 0, 4, ..., 28; clk2 at 1, 8, 15, 22.
+    assert_eq!(sim.get(sim.signal("cnt")), BigUint::from(8u32));
+    assert_eq!(sim.get(sim.signal("cnt2")), BigUint::from(4u32));
+}
+
+#[test]
+fn paused_clock_resumes_on_its_original_grid() {
+    let mut sim = build();
+    let cnt = sim.signal("cnt");
+    let clk = sim.signal("clk");
+    sim.add_clock_with("clk", ClockSpec::new(10).high_time(4))
+        .unwrap();
+
+    sim.run_until(12).unwrap();
+    assert_eq!(sim.get(cnt), BigUint::from(2u32));
+    assert_eq!(sim.get(clk), BigUint::from(1u32));
+
+    sim.pause_clock("clk").unwrap();
+    assert_eq!(sim.next_event_time(), None);
+    sim.run_until(47).unwrap();
+    assert_eq!(sim.get(cnt), BigUint::from(2u32));
+    assert_eq!(sim.get(clk), BigUint::from(1u32));
+
+    // Held high, so the next edge is the grid fall at 54, then a rise at 60.
+    sim.resume_clock("clk").unwrap();
+    assert_eq!(sim.next_event_time(), Some(54));
+    assert_eq!(edges(&mut sim, "clk", 60), vec![(54, 0), (60, 1)]);
+    assert_eq!(sim.get(cnt), BigUint::from(3u32));
+}
+
+#[test]
+fn removed_clock_stops_and_keeps_its_level() {
+    let mut sim = build();
+    let clk = sim.signal("clk");
+    sim.add_clock("clk", 10, 0);
+    sim.run_until(2).unwrap();
+    sim.remove_clock("clk").unwrap();
+    assert_eq!(sim.next_event_time(), None);
+    sim.run_until(100).unwrap();
+    assert_eq!(sim.get(clk), BigUint::from(1u32));
+    assert_eq!(sim.get(sim.signal("cnt")), BigUint::from(1u32));
+
+    // Resuming a removed clock is a no-op.
+    sim.resume_clock("clk").unwrap();
+    assert_eq!(sim.next_event_time(), None);
+}
+
+#[test]
+fn clock_control_rejects_non_event_ports() {
+    let mut sim = build();
+    assert!(matches!(
+        sim.pause_clock("cnt"),
+        Err(RuntimeErrorCode::NotAnEvent(_))
+    ));
+    assert!(matches!(
+        sim.add_clock_with("cnt", ClockSpec::new(10)),
+        Err(RuntimeErrorCode::NotAnEvent(_))
+    ));
+}
+
+#[test]
+fn paused_clock_survives_checkpoints() {
+    let mut sim = build();
+    sim.add_clock_with("clk", ClockSpec::new(10).high_time(2))
+        .unwrap();
+    sim.run_until(5).unwrap();
+    sim.pause_clock("clk").unwrap();
+    let bytes = sim.save_state().to_bytes().unwrap();
+
+    let mut restored = build();
+    restored
+        .restore_state(&celox::SimulatorCheckpoint::from_bytes(&bytes).unwrap())
+        .unwrap();
+    assert_eq!(restored.next_event_time(), None);
+    restored.resume_clock("clk").unwrap();
+    assert_eq!(restored.next_event_time(), Some(10));
+}
+
+#[test]
+fn timescale_converts_physical_units() {
+    let mut sim = build();
+    assert_eq!(sim.ticks("10ns"), Err(TimescaleError::Unset));
+
+    sim.set_timescale("1ps".parse::<Timescale>().unwrap())
+        .unwrap();
+    let period = sim.ticks("2.5ns").unwrap();
+    assert_eq!(period, 2500);
+    sim.add_clock_with(
+        "clk",
+        ClockSpec::new(period).high_time(sim.ticks("1ns").unwrap()),
+    )
+    .unwrap();
+    sim.run_until(sim.ticks("10ns").unwrap()).unwrap();
+    assert_eq!(sim.time_in("ns").unwrap(), 10.0);
+    assert_eq!(sim.get(sim.signal("cnt")), BigUint::from(5u32));
+    assert!(matches!(
+        sim.ticks("0.5ps"),
+        Err(TimescaleError::NotMultiple { .. })
+    ));
+}
+
+#[test]
+fn timescale_is_written_to_vcd_header() {
+    let dir = std::env::temp_dir().join(format!("celox-clock-vcd-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("timescale.vcd");
+    let mut sim = Simulation::builder(COUNTERS, "Top")
+        .vcd(&path)
+        .build()
+        .unwrap();
+    sim.set_timescale("10ps".parse().unwrap()).unwrap();
+    sim.add_clock("clk", 10, 0);
+    sim.run_until(20).unwrap();
+    assert_eq!(
+        sim.set_timescale("1ns".parse().unwrap()),
+        Err(TimescaleError::WaveformStarted)
+    );
+    drop(sim);
+
+    let waveform = std::fs::read_to_string(&path).unwrap();
+    assert!(waveform.contains("$timescale 10ps $end"), "{waveform}");
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,133 @@
+use celox::{Simulation, Simulator};
+
+#[path = "test_utils/mod.rs"]
+#[macro_use]
+#[allow(unused_macros)]
+mod test_utils;
+
+const PIPELINE: &str = r#"
+module Top (
+    clk: input  clock,
+    rst: input  reset,
+    a:   input  logic<8>,
+    sum: output logic<8>,
+    acc: output logic<8>,
+) {
+    var mid: logic<8>;
+    var total: logic<8>;
+    always_comb {
+        mid = a + 8'd1;
+        sum = mid + 8'd1;
+    }
+    always_ff (clk, rst) {
+        if_reset {
+            total = 8'd0;
+        } else {
+            total = total + sum;
+        }
+    }
+    assign acc = total;
+}
+"#;
+
+all_backends! {
+fn test_force_holds_combinational_net(sim) {
+    @omit_veryl;
+    @build Simulator::builder(PIPELINE, "Top").force_support(true);
+    let a = sim.signal("a");
+    let mid = sim.signal("mid");
+    let sum = sim.signal("sum");
+
+    sim.modify(|io| io.set::<u8>(a, 3)).unwrap();
+    assert_eq!(sim.get(sum), 5u32.into());
+
+    sim.force(mid, 40u32.into());
+    assert!(sim.is_forced(mid));
+    assert_eq!(sim.get(mid), 40u32.into());
+    assert_eq!(sim.get(sum), 41u32.into());
+
+    sim.modify(|io| io.set::<u8>(a, 7)).unwrap();
+    assert_eq!(sim.get(mid), 40u32.into());
+    assert_eq!(sim.get(sum), 41u32.into());
+
+    sim.release(mid);
+    assert!(!sim.is_forced(mid));
+    assert_eq!(sim.get(mid), 8u32.into());
+    assert_eq!(sim.get(sum), 9u32.into());
+}
+}
+
+all_backends! {
+fn test_force_holds_register_across_ticks(sim) {
+    @omit_veryl;
+    @build Simulator::builder(PIPELINE, "Top");
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let a = sim.signal("a");
+    let total = sim.signal("total");
+    let acc = sim.signal("acc");
+
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(a, 0);
+    })
+    .unwrap();
+    sim.force(total, 100u32.into());
+    sim.tick(clk).unwrap();
+    sim.tick(clk).unwrap();
+    assert_eq!(sim.get(acc), 100u32.into());
+
+    sim.release(total);
+    sim.tick(clk).unwrap();
+    assert_eq!(sim.get(acc), 102u32.into());
+}
+}
+
+all_backends! {
+fn test_deposit_is_overwritten_by_design(sim) {
+    @omit_veryl;
+    @build Simulator::builder(PIPELINE, "Top");
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let a = sim.signal("a");
+    let total = sim.signal("total");
+    let acc = sim.signal("acc");
+
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(a, 0);
+    })
+    .unwrap();
+    sim.deposit(total, 50u32.into());
+    assert_eq!(sim.get(acc), 50u32.into());
+    sim.tick(clk).unwrap();
+    assert_eq!(sim.get(acc), 52u32.into());
+
+    sim.force(total, 10u32.into());
+    sim.deposit(total, 60u32.into());
+    assert_eq!(sim.get(acc), 10u32.into());
+}
+}
+
+#[test]
+fn simulation_force_survives_clock_edges() {
+    let mut sim = Simulation::builder(PIPELINE, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    let a = sim.signal("a");
+    let total = sim.signal("total");
+    let acc = sim.signal("acc");
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(a, 0);
+    })
+    .unwrap();
+
+    sim.force(total, 7u32.into());
+    sim.run_until(30).unwrap();
+    assert_eq!(sim.get(acc), 7u32.into());
+
+    sim.release(total);
+    sim.run_until(40).unwrap();
+    assert_eq!(sim.get(acc), 9u32.into());
+}
@@ -0,0 +1,117 @@
+use celox::{Simulation, Simulator};
+
+#[path = "test_utils/mod.rs"]
+#[macro_use]
+#[allow(unused_macros)]
+mod test_utils;
+
+const COUNTER: &str = r#"
+module Top (
+    clk: input  clock,
+    rst: input  reset,
+    inc: input  logic<8>,
+    cnt: output logic<8>,
+) {
+    var counter: logic<8>;
+    always_ff (clk, rst) {
+        if_reset {
+            counter = 8'd0;
+        } else {
+            counter = counter + inc;
+        }
+    }
+    assign cnt = counter;
+}
+"#;
+
+all_backends! {
+fn test_fork_continues_independently(sim) {
+    @omit_veryl;
+    @build Simulator::builder(COUNTER, "Top");
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let inc = sim.signal("inc");
+    let cnt = sim.signal("cnt");
+
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(inc, 1);
+    })
+    .unwrap();
+    for _ in 0..3 {
+        sim.tick(clk).unwrap();
+    }
+    assert_eq!(sim.get(cnt), 3u32.into());
+
+    let mut branch = sim.fork().unwrap();
+    branch.modify(|io| io.set::<u8>(inc, 10)).unwrap();
+    branch.tick(clk).unwrap();
+    sim.tick(clk).unwrap();
+
+    assert_eq!(branch.get(cnt), 13u32.into());
+    assert_eq!(sim.get(cnt), 4u32.into());
+}
+}
+
+#[test]
+fn simulation_fork_copies_pending_events() {
+    let mut sim = Simulation::builder(COUNTER, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    let inc = sim.signal("inc");
+    let cnt = sim.signal("cnt");
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(inc, 1);
+    })
+    .unwrap();
+    sim.run_until(25).unwrap();
+
+    let mut branches = (0..3).map(|_| sim.fork().unwrap()).collect::<Vec<_>>();
+    for (step, branch) in branches.iter_mut().enumerate() {
+        assert_eq!(branch.time(), 25);
+        assert_eq!(branch.next_event_time(), sim.next_event_time());
+        branch.modify(|io| io.set::<u8>(inc, step as u8)).unwrap();
+        branch.run_until(55).unwrap();
+    }
+    sim.run_until(55).unwrap();
+
+    let base = 3u32;
+    assert_eq!(sim.get(cnt), (base + 3).into());
+    for (step, branch) in branches.iter_mut().enumerate() {
+        assert_eq!(branch.get(cnt), (base + 3 * step as u32).into());
+    }
+}
+
+#[test]
+fn fork_with_vcd_writes_a_self_contained_waveform() {
+    let dir = std::env::temp_dir().join(format!("celox-fork-vcd-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let parent_path = dir.join("parent.vcd");
+    let fork_path = dir.join("fork.vcd");
+
+    let mut sim = Simulation::builder(COUNTER, "Top")
+        .vcd(&parent_path)
+        .build()
+        .unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    let inc = sim.signal("inc");
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(inc, 1);
+    })
+    .unwrap();
+    sim.run_until(20).unwrap();
+
+    let mut branch = sim.fork_with_vcd(&fork_path).unwrap();
+    branch.run_until(40).unwrap();
+    drop(branch);
+    drop(sim);
+
+    let waveform = std::fs::read_to_string(&fork_path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(waveform.contains("$enddefinitions"));
+    assert!(waveform.contains("#25"));
+    assert!(!waveform.contains("#5\n"));
+}
@@ -0,0 +1,96 @@
+use celox::Simulator;
+
+const DESIGN: &str = r#"module Top (
+    clk  : input  '_ clock,
+    rst  : input  '_ reset,
+    start: input  logic,
+    abort: input  logic,
+    busy : output logic,
+) {
+    enum State: logic<2> {
+        Idle,
+        Run,
+        Done,
+        Error,
+    }
+    var state: State;
+    var count: logic<4>;
+    always_ff (clk, rst) {
+        if_reset {
+            state = State::Idle;
+            count = 4'd0;
+        } else {
+            count = count + 4'd1;
+            case state {
+                State::Idle: if start {
+                    state = State::Run;
+                }
+                State::Run: if abort {
+                    state = State::Error;
+                } else {
+                    state = State::Done;
+                }
+                default: state = State::Idle;
+            }
+        }
+    }
+    assign busy = state == State::Run;
+}
+"#;
+
+#[test]
+fn records_states_and_transitions_by_name() {
+    let mut sim = Simulator::builder(DESIGN, "Top")
+        .fsm_coverage(true)
+        .build()
+        .unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let start = sim.signal("start");
+
+    sim.modify(|io| io.set::<u8>(rst, 0)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(start, 1);
+    })
+    .unwrap();
+    // Idle -> Run -> Done -> Idle -> Run
+    for _ in 0..4 {
+        sim.tick(clk).unwrap();
+    }
+
+    let coverage = sim.fsm_coverage().unwrap();
+    // Only the enum-typed register is a state machine.
+    assert_eq!(coverage.machines().len(), 1);
+    let machine = coverage.machine("Top.state").unwrap();
+    assert_eq!(machine.type_name, "State");
+    assert_eq!(machine.states(), ["Idle", "Run", "Done", "Error"]);
+    assert_eq!(machine.visits("Idle"), Some(2));
+    assert_eq!(machine.visits("Run"), Some(2));
+    assert_eq!(machine.transitions("Idle", "Run"), Some(2));
+    assert_eq!(machine.transitions("Run", "Done"), Some(1));
+    assert_eq!(machine.transitions("Done", "Idle"), Some(1));
+    assert_eq!(machine.unreached_states(), ["Error"]);
+    assert!(machine.untaken_transitions().contains(&("Run", "Error")));
+    assert!(machine.untaken_transitions().contains(&("Done", "Run")));
+    assert_eq!(coverage.covered_states(), 3);
+    assert_eq!(coverage.total_transitions(), 12);
+    assert_eq!(coverage.covered_transitions(), 3);
+
+    let mut json = Vec::new();
+    coverage.write_json(&mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["machines"][0]["name"], "Top.state");
+    assert_eq!(json["machines"][0]["unreached_states"][0], "Error");
+}
+
+#[test]
+fn fsm_coverage_is_off_by_default() {
+    let mut sim = Simulator::builder(DESIGN, "Top").build().unwrap();
+    assert!(sim.fsm_coverage().is_none());
+    sim.enable_fsm_coverage();
+    assert_eq!(sim.fsm_coverage().unwrap().machines().len(), 1);
+    assert!(sim.disable_fsm_coverage().is_some());
+    assert!(sim.fsm_coverage().is_none());
+}
@@ -1,4 +1,4 @@
-use celox::{BigUint, ParserError, Simulator, SimulatorErrorKind};
+use celox::{BigUint, MemoryFileFormat, ParserError, Simulator, SimulatorErrorKind};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 #[path = "test_utils/mod.rs"]
@@ -11,6 +11,18 @@ fn temp_mem_file(name: &str, content: &str) -> String {
     let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
     let path = std::env::temp_dir().join(format!("celox_{name}_{}_{id}.mem", std::process::id()));
     std::fs::write(&path, content).unwrap();
+    escape(&path)
+}
+
+fn temp_out_path(name: &str) -> std::path::PathBuf {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("celox_{name}_{}_{id}.out", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn escape(path: &std::path::Path) -> String {
     path.to_string_lossy().replace('\\', "\\\\")
 }
 
@@ -192,17 +204,134 @@ fn test_initial_readmemh_multiple_files_merge_in_order(sim) {
     assert_eq!(sim.get(sim.signal("out3")), BigUint::from(0x44u32));
 }
 
+fn test_initial_readmemb_loads_address_range(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @setup {
+        let mem_path = temp_mem_file("readmemb_range", "0001_0010\n0011_0100\n");
+        let code = format!(r#"
+            module Top #(
+                param START: u32 = 1,
+            ) (
+                out0: output logic<8>,
+                out1: output logic<8>,
+                out2: output logic<8>,
+                out3: output logic<8>,
+            ) {{
+                var mem: logic<8>[4];
+                initial {{
+                    $readmemb("{}", mem, START, 'h2);
+                }}
+                assign out0 = mem[0];
+                assign out1 = mem[1];
+                assign out2 = mem[2];
+                assign out3 = mem[3];
+            }}
+        "#, mem_path);
+    }
+    @build Simulator::builder(&code, "Top");
+    assert_eq!(sim.get(sim.signal("out0")), BigUint::from(0u32));
+    assert_eq!(sim.get(sim.signal("out1")), BigUint::from(0x12u32));
+    assert_eq!(sim.get(sim.signal("out2")), BigUint::from(0x34u32));
+    assert_eq!(sim.get(sim.signal("out3")), BigUint::from(0u32));
+}
+
+fn test_final_writemem_dumps_current_contents(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @setup {
+        let mem_path = temp_mem_file("writemem_init", "12\n34\n56\n78\n");
+        let hex_path = temp_out_path("writememh_final");
+        let bin_path = temp_out_path("writememb_final");
+        let code = format!(r#"
+            module Top (out0: output logic<8>) {{
+                var mem: logic<8>[4];
+                initial {{
+                    $readmemh("{}", mem);
+                }}
+                final {{
+                    $writememh("{}", mem);
+                    $writememb("{}", mem, 1, 2);
+                }}
+                assign out0 = mem[0];
+            }}
+        "#, mem_path, escape(&hex_path), escape(&bin_path));
+    }
+    @build Simulator::builder(&code, "Top");
+    let mem = sim.signal("mem");
+    sim.write_array_element(mem, 3, BigUint::from(0xffu32));
+    assert!(!hex_path.exists());
+
+    sim.run_final_blocks().unwrap();
+    assert_eq!(std::fs::read_to_string(&hex_path).unwrap(), "12\n34\n56\nff\n");
+    assert_eq!(
+        std::fs::read_to_string(&bin_path).unwrap(),
+        "@1\n00110100\n01010110\n"
+    );
+}
+
+fn test_write_memory_file_dumps_element_range(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @setup {
+        let mem_path = temp_mem_file("write_memory_file", "12\n34\n56\n78\n");
+        let out_path = temp_out_path("write_memory_file");
+        let code = format!(r#"
+            module Top (out0: output logic<8>) {{
+                var mem: logic<8>[4];
+                initial {{
+                    $readmemh("{}", mem);
+                }}
+                assign out0 = mem[0];
+            }}
+        "#, mem_path);
+    }
+    @build Simulator::builder(&code, "Top");
+    let mem = sim.signal("mem");
+    assert_eq!(
+        sim.write_memory_file(mem, &out_path, MemoryFileFormat::Hex, 2..).unwrap(),
+        2
+    );
+    assert_eq!(std::fs::read_to_string(&out_path).unwrap(), "@2\n56\n78\n");
+}
+
+}
+
+#[test]
+fn test_initial_unknown_task_reports_illegal_context() {
+    let code = r#"
+        module Top (out0: output logic<8>) {
+            var mem: logic<8>[4];
+            initial {
+                $dumpfile("dump.vcd");
+            }
+            assign out0 = mem[0];
+        }
+    "#;
+
+    let err = Simulator::builder(code, "Top")
+        .build()
+        .expect_err("an unknown initial task should not be silently ignored");
+    match err.kind() {
+        SimulatorErrorKind::SIRParser(ParserError::IllegalContext {
+            feature, detail, ..
+        }) => {
+            assert_eq!(*feature, "initial statement");
+            assert!(detail.contains("only direct $readmemh and $readmemb"));
+        }
+        other => panic!("expected illegal initial statement context error, got {other:?}"),
+    }
 }
 
 #[test]
-fn test_initial_readmemb_reports_illegal_context() {
-    let mem_path = temp_mem_file("readmemb", "00010010\n00110100\n01010110\n01111000\n");
+fn test_initial_readmem_rejects_words_past_end_address() {
+    let mem_path = temp_mem_file("readmemh_past_end", "11\n22\n33\n");
     let code = format!(
         r#"
             module Top (out0: output logic<8>) {{
                 var mem: logic<8>[4];
                 initial {{
-                    $readmemb("{}", mem);
+                    $readmemh("{}", mem, 1, 2);
                 }}
                 assign out0 = mem[0];
             }}
@@ -212,15 +341,15 @@ fn test_initial_readmemb_reports_illegal_context() {
 
     let err = Simulator::builder(&code, "Top")
         .build()
-        .expect_err("$readmemb should not be silently ignored");
+        .expect_err("a word past the end address should be rejected");
     match err.kind() {
-        SimulatorErrorKind::SIRParser(ParserError::IllegalContext {
+        SimulatorErrorKind::SIRParser(ParserError::Unsupported {
             feature, detail, ..
         }) => {
-            assert_eq!(*feature, "initial statement");
-            assert!(detail.contains("only direct $readmemh"));
+            assert_eq!(*feature, "$readmemh address");
+            assert!(detail.contains("past the end address 2"));
         }
-        other => panic!("expected illegal initial statement context error, got {other:?}"),
+        other => panic!("expected unsupported address error, got {other:?}"),
     }
 }
 
@@ -0,0 +1,116 @@
+use celox::{BigUint, InitialStatePolicy, Simulator};
+
+#[path = "test_utils/mod.rs"]
+#[macro_use]
+#[allow(unused_macros)]
+mod test_utils;
+
+const REGS: &str = r#"
+module Reg (
+    clk: input  clock    ,
+    d  : input  logic<64>,
+    q  : output logic<64>,
+) {
+    var r: logic<64>;
+    always_ff (clk) {
+        r = d;
+    }
+    assign q = r;
+}
+
+module Top (
+    clk  : input  clock    ,
+    d    : input  logic<64>,
+    q0   : output logic<64>,
+    q1   : output logic<64>,
+    comb : output logic<8> ,
+) {
+    inst u0: Reg (clk, d, q: q0);
+    inst u1: Reg (clk, d, q: q1);
+    assign comb = 8'h5a;
+}
+"#;
+
+all_backends! {
+
+fn test_ones_policy_fills_registers_only(sim) {
+    @omit_veryl;
+    @build Simulator::builder(REGS, "Top").initial_state_policy(InitialStatePolicy::Ones);
+    let ones = BigUint::from(u64::MAX);
+    assert_eq!(sim.get(sim.signal("q0")), ones);
+    assert_eq!(sim.get(sim.signal("q1")), ones);
+    assert_eq!(sim.get(sim.signal("d")), BigUint::from(0u8));
+    assert_eq!(sim.get(sim.signal("comb")), BigUint::from(0x5au8));
+}
+
+fn test_zero_policy_clears_x_in_four_state_mode(sim) {
+    @omit_veryl;
+    @build Simulator::builder(REGS, "Top")
+        .four_state(true)
+        .initial_state_policy(InitialStatePolicy::Zero)
+        .initial_state_policy_in("u1", InitialStatePolicy::X);
+    let ones = BigUint::from(u64::MAX);
+    assert_eq!(
+        sim.get_four_state(sim.signal("q0")),
+        (BigUint::from(0u8), BigUint::from(0u8))
+    );
+    assert_eq!(sim.get_four_state(sim.signal("q1")).1, ones);
+}
+
+fn test_scoped_policy_overrides_default(sim) {
+    @omit_veryl;
+    @build Simulator::builder(REGS, "Top")
+        .initial_state_policy(InitialStatePolicy::Ones)
+        .initial_state_policy_in("Top.u1", InitialStatePolicy::Zero);
+    assert_eq!(sim.get(sim.signal("q0")), BigUint::from(u64::MAX));
+    assert_eq!(sim.get(sim.signal("q1")), BigUint::from(0u8));
+}
+
+fn test_random_policy_is_reproducible(sim) {
+    @omit_veryl;
+    @build Simulator::builder(REGS, "Top")
+        .initial_state_policy(InitialStatePolicy::Random { seed: 42 });
+    let q0 = sim.get(sim.signal("q0"));
+    let q1 = sim.get(sim.signal("q1"));
+    assert_ne!(q0, q1, "registers draw independent values");
+
+    let mut again = Simulator::builder(REGS, "Top")
+        .initial_state_policy(InitialStatePolicy::Random { seed: 42 })
+        .build()
+        .unwrap();
+    assert_eq!(again.get(again.signal("q0")), q0);
+    assert_eq!(again.get(again.signal("q1")), q1);
+
+    let mut other = Simulator::builder(REGS, "Top")
+        .initial_state_policy(InitialStatePolicy::Random { seed: 43 })
+        .build()
+        .unwrap();
+    assert_ne!(other.get(other.signal("q0")), q0);
+}
+
+fn test_policy_fills_every_array_element(sim) {
+    @omit_veryl;
+    @build Simulator::builder(r#"
+        module Top (
+            clk : input  clock   ,
+            we  : input  logic   ,
+            addr: input  logic<2>,
+            d   : input  logic<4>,
+            out0: output logic<4>,
+            out3: output logic<4>,
+        ) {
+            var mem: logic<4>[4];
+            always_ff (clk) {
+                if we {
+                    mem[addr] = d;
+                }
+            }
+            assign out0 = mem[0];
+            assign out3 = mem[3];
+        }
+    "#, "Top").initial_state_policy(InitialStatePolicy::Ones);
+    assert_eq!(sim.get(sim.signal("out0")), BigUint::from(0xfu8));
+    assert_eq!(sim.get(sim.signal("out3")), BigUint::from(0xfu8));
+}
+
+}
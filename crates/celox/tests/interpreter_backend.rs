@@ -0,0 +1,331 @@
+//! Differential tests: the reference interpreter must agree with the default
+//! compiled backend on the same design and stimulus.
+
+use celox::{BigUint, InterpreterBackend, RuntimeEvent, Simulator, SimulatorBuilder};
+
+/// Deterministic xorshift stimulus so failures reproduce.
+struct Stimulus(u64);
+
+impl Stimulus {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn wide(&mut self, width: usize) -> BigUint {
+        let words = (0..width.div_ceil(64))
+            .map(|_| self.next())
+            .collect::<Vec<_>>();
+        BigUint::from_slice(
+            &words
+                .iter()
+                .flat_map(|word| [*word as u32, (*word >> 32) as u32])
+                .collect::<Vec<_>>(),
+        ) & ((BigUint::from(1u8) << width) - 1u8)
+    }
+}
+
+fn build_pair(
+    builder: impl Fn() -> SimulatorBuilder<'static>,
+) -> (Simulator, Simulator<InterpreterBackend>) {
+    (
+        builder().build().unwrap(),
+        builder().build_interpreter().unwrap(),
+    )
+}
+
+/// Drive random values into `inputs` and compare `outputs` after each step.
+fn compare_comb(code: &'static str, inputs: &[(&str, usize)], outputs: &[&str], four_state: bool) {
+    let (mut compiled, mut interpreted) =
+        build_pair(|| Simulator::builder(code, "Top").four_state(four_state));
+    let mut stimulus = Stimulus(0x9E37_79B9_7F4A_7C15);
+    for step in 0..64 {
+        let values = inputs
+            .iter()
+            .map(|&(name, width)| {
+                let value = stimulus.wide(width);
+                // Every fourth step leaves some bits unknown.
+                let mask = if four_state && step % 4 == 3 {
+                    stimulus.wide(width)
+                } else {
+                    BigUint::ZERO
+                };
+                (name, value | &mask, mask)
+            })
+            .collect::<Vec<_>>();
+        let compiled_signals = values
+            .iter()
+            .map(|(name, _, _)| compiled.signal(name))
+            .collect::<Vec<_>>();
+        let interpreted_signals = values
+            .iter()
+            .map(|(name, _, _)| interpreted.signal(name))
+            .collect::<Vec<_>>();
+        compiled
+            .modify(|io| {
+                for (signal, (_, value, mask)) in compiled_signals.iter().zip(&values) {
+                    io.set_four_state(*signal, value.clone(), mask.clone());
+                }
+            })
+            .unwrap();
+        interpreted
+            .modify(|io| {
+                for (signal, (_, value, mask)) in interpreted_signals.iter().zip(&values) {
+                    io.set_four_state(*signal, value.clone(), mask.clone());
+                }
+            })
+            .unwrap();
+        for &name in outputs {
+            let expected = compiled.get_four_state(compiled.signal(name));
+            let actual = interpreted.get_four_state(interpreted.signal(name));
+            assert_eq!(
+                actual, expected,
+                "step {step}, signal '{name}': {values:x?}"
+            );
+        }
+    }
+}
+
+const ARITHMETIC: &str = r#"
+module Top (
+    a: input logic<16>,
+    b: input logic<16>,
+    s: input logic<4>,
+    sa: input signed logic<16>,
+    sb: input signed logic<16>,
+    o_add: output logic<16>,
+    o_sub: output logic<16>,
+    o_mul: output logic<32>,
+    o_div: output logic<16>,
+    o_rem: output logic<16>,
+    o_sdiv: output signed logic<16>,
+    o_srem: output signed logic<16>,
+    o_shl: output logic<16>,
+    o_shr: output logic<16>,
+    o_sar: output signed logic<16>,
+    o_cmp: output logic<6>,
+    o_bits: output logic<16>,
+    o_red: output logic<3>,
+    o_mux: output logic<16>,
+    o_case: output logic<8>,
+) {
+    assign o_add = a + b;
+    assign o_sub = a - b;
+    assign o_mul = a * b;
+    assign o_div = a / b;
+    assign o_rem = a % b;
+    assign o_sdiv = sa / sb;
+    assign o_srem = sa % sb;
+    assign o_shl = a << s;
+    assign o_shr = a >> s;
+    assign o_sar = sa >>> s;
+    assign o_cmp = {a <: b, a >: b, a == b, a != b, sa <: sb, sa >= sb};
+    assign o_bits = (a & b) ^ ~(a | b);
+    assign o_red = {&a, |a, ^a};
+    assign o_mux = if a[0] ? a[15:8] + b : {b[7:0], a[15:8]};
+    always_comb {
+        case s {
+            4'd0   : o_case = a[7:0];
+            4'd1   : o_case = b[7:0];
+            4'd2, 4'd3: o_case = a[15:8] ^ b[15:8];
+            default: o_case = 8'hA5;
+        }
+    }
+}
+"#;
+
+const ARITHMETIC_INPUTS: &[(&str, usize)] =
+    &[("a", 16), ("b", 16), ("s", 4), ("sa", 16), ("sb", 16)];
+const ARITHMETIC_OUTPUTS: &[&str] = &[
+    "o_add", "o_sub", "o_mul", "o_div", "o_rem", "o_sdiv", "o_srem", "o_shl", "o_shr", "o_sar",
+    "o_cmp", "o_bits", "o_red", "o_mux", "o_case",
+];
+
+#[test]
+fn arithmetic_matches_compiled_backend() {
+    compare_comb(ARITHMETIC, ARITHMETIC_INPUTS, ARITHMETIC_OUTPUTS, false);
+}
+
+#[test]
+fn four_state_arithmetic_matches_compiled_backend() {
+    compare_comb(ARITHMETIC, ARITHMETIC_INPUTS, ARITHMETIC_OUTPUTS, true);
+}
+
+#[test]
+fn wide_values_match_compiled_backend() {
+    let code = r#"
+module Top (
+    a: input logic<200>,
+    b: input logic<200>,
+    i: input logic<3>,
+    o_add: output logic<200>,
+    o_mul: output logic<200>,
+    o_xor: output logic<200>,
+    o_shift: output logic<200>,
+    o_lt: output logic,
+    o_cat: output logic<264>,
+    o_elem: output logic<64>,
+) {
+    var lut: logic<64> [8];
+    assign o_add = a + b;
+    assign o_mul = a * b;
+    assign o_xor = a ^ b;
+    assign o_shift = a >> b[6:0];
+    assign o_lt = a <: b;
+    assign o_cat = {a[63:0], b};
+    always_comb {
+        for k in 0..8 {
+            lut[k] = a[63:0] + k;
+        }
+        o_elem = lut[i];
+    }
+}
+"#;
+    compare_comb(
+        code,
+        &[("a", 200), ("b", 200), ("i", 3)],
+        &[
+            "o_add", "o_mul", "o_xor", "o_shift", "o_lt", "o_cat", "o_elem",
+        ],
+        false,
+    );
+}
+
+#[test]
+fn clocked_logic_matches_compiled_backend() {
+    let code = r#"
+module Top (
+    clk: input 'a clock,
+    clk2: input 'b clock,
+    rst: input 'a reset,
+    en: input 'a logic,
+    d: input 'a logic<8>,
+    cnt: output 'a logic<8>,
+    acc: output 'a logic<16>,
+    seen: output 'b logic<8>,
+) {
+    always_ff (clk, rst) {
+        if_reset {
+            cnt = 0;
+            acc = 0;
+        } else if en {
+            cnt = cnt + 1;
+            acc = acc + d * cnt;
+        }
+    }
+    unsafe (cdc) {
+        always_ff (clk2) {
+            seen = cnt ^ d;
+        }
+    }
+}
+"#;
+    let (mut compiled, mut interpreted) = build_pair(|| Simulator::builder(code, "Top"));
+    let mut stimulus = Stimulus(42);
+    for step in 0..48 {
+        // Reset is active low.
+        let rst = u8::from(step >= 2);
+        let en = (stimulus.next() & 3 != 0) as u8;
+        let d = stimulus.next() as u8;
+        let second = stimulus.next() & 1 == 0;
+        let compiled_clk = compiled.event(if second { "clk2" } else { "clk" });
+        let interpreted_clk = interpreted.event(if second { "clk2" } else { "clk" });
+        let [c_rst, c_en, c_d] = ["rst", "en", "d"].map(|name| compiled.signal(name));
+        let [i_rst, i_en, i_d] = ["rst", "en", "d"].map(|name| interpreted.signal(name));
+        compiled
+            .modify(|io| {
+                io.set(c_rst, rst);
+                io.set(c_en, en);
+                io.set(c_d, d);
+            })
+            .unwrap();
+        interpreted
+            .modify(|io| {
+                io.set(i_rst, rst);
+                io.set(i_en, en);
+                io.set(i_d, d);
+            })
+            .unwrap();
+        compiled.tick(compiled_clk).unwrap();
+        interpreted.tick(interpreted_clk).unwrap();
+        for name in ["cnt", "acc", "seen"] {
+            assert_eq!(
+                interpreted.get(interpreted.signal(name)),
+                compiled.get(compiled.signal(name)),
+                "step {step}, signal '{name}'"
+            );
+        }
+    }
+}
+
+#[test]
+fn runtime_events_match_compiled_backend() {
+    let code = r#"
+module Top (
+    a: input logic<8>,
+) {
+    var x: logic<8>;
+    always_comb {
+        x = a + 1;
+    }
+    always_comb {
+        $display("x=%0d", x);
+    }
+}
+"#;
+    let (mut compiled, mut interpreted) = build_pair(|| Simulator::builder(code, "Top"));
+    compiled.drain_runtime_events();
+    interpreted.drain_runtime_events();
+    let compiled_a = compiled.signal("a");
+    let interpreted_a = interpreted.signal("a");
+    for a in [3u8, 3, 200] {
+        compiled.modify(|io| io.set(compiled_a, a)).unwrap();
+        interpreted.modify(|io| io.set(interpreted_a, a)).unwrap();
+        assert_eq!(
+            interpreted.drain_runtime_events(),
+            compiled.drain_runtime_events()
+        );
+    }
+    interpreted.modify(|io| io.set(interpreted_a, 9u8)).unwrap();
+    assert_eq!(
+        interpreted.drain_runtime_events(),
+        vec![RuntimeEvent::Display {
+            message: "x=10".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn forked_interpreter_runs_independently() {
+    let code = r#"
+module Top (
+    clk: input clock,
+    rst: input reset,
+    cnt: output logic<8>,
+) {
+    always_ff (clk, rst) {
+        if_reset {
+            cnt = 0;
+        } else {
+            cnt = cnt + 1;
+        }
+    }
+}
+"#;
+    let mut sim = Simulator::builder(code, "Top").build_interpreter().unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let cnt = sim.signal("cnt");
+    sim.modify(|io| io.set(rst, 0u8)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| io.set(rst, 1u8)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.tick(clk).unwrap();
+
+    let mut fork = sim.fork().unwrap();
+    fork.tick(clk).unwrap();
+    assert_eq!(sim.get(cnt), BigUint::from(2u8));
+    assert_eq!(fork.get(cnt), BigUint::from(3u8));
+}
@@ -0,0 +1,133 @@
+//! Rebuilds reuse lowered modules whose sources are unchanged, and re-lower
+//! every module an edit can affect.
+
+use std::path::Path;
+
+use celox::Simulator;
+use celox_frontend_veryl::module_cache;
+
+const PKG: &str = r#"
+package Pkg {
+    const OFFSET: u32 = 8;
+}
+"#;
+
+const LEAF: &str = r#"
+module Leaf (
+    i: input  logic<8>,
+    o: output logic<8>,
+) {
+    assign o = i + Pkg::OFFSET;
+}
+"#;
+
+const SCALE: &str = r#"
+module Scale #(
+    param FACTOR: u32 = 1,
+) (
+    i: input  logic<8>,
+    o: output logic<8>,
+) {
+    assign o = i * FACTOR;
+}
+"#;
+
+const OTHER: &str = r#"
+module Other (
+    i: input  logic<8>,
+    o: output logic<8>,
+) {
+    assign o = ~i;
+}
+"#;
+
+const TOP: &str = r#"
+module Top (
+    a: input  logic<8>,
+    y: output logic<8>,
+    z: output logic<8>,
+    w: output logic<8>,
+) {
+    inst u_leaf: Leaf (
+        i: a,
+        o: y,
+    );
+    inst u_scale: Scale #(FACTOR: 3) (
+        i: a,
+        o: z,
+    );
+    inst u_other: Other (
+        i: a,
+        o: w,
+    );
+}
+"#;
+
+/// Build the design from `(pkg, leaf, top)` sources and return `(y, z, w)`
+/// for `a = 1`.
+fn eval(pkg: &str, leaf: &str, top: &str, module_cache: bool) -> (u64, u64, u64) {
+    let sources = vec![
+        (pkg, Path::new("pkg.veryl")),
+        (leaf, Path::new("leaf.veryl")),
+        (SCALE, Path::new("scale.veryl")),
+        (OTHER, Path::new("other.veryl")),
+        (top, Path::new("top.veryl")),
+    ];
+    let mut sim = Simulator::from_sources(sources, "Top")
+        .module_cache(module_cache)
+        .build()
+        .unwrap();
+    let a = sim.signal("a");
+    sim.modify(|io| io.set(a, 1u8)).unwrap();
+    let [y, z, w] = ["y", "z", "w"].map(|name| {
+        let signal = sim.signal(name);
+        u64::try_from(sim.get(signal)).unwrap()
+    });
+    (y, z, w)
+}
+
+#[test]
+fn unchanged_rebuild_reuses_every_module() {
+    assert_eq!(eval(PKG, LEAF, TOP, true), (9, 3, 0xFE));
+    let cached = module_cache::len();
+    assert_eq!(cached, 4, "Top, Leaf, Scale and Other are lowered once");
+
+    assert_eq!(eval(PKG, LEAF, TOP, true), (9, 3, 0xFE));
+    assert_eq!(module_cache::len(), cached, "nothing was lowered again");
+}
+
+#[test]
+fn leaf_edit_relowers_leaf_and_its_parents() {
+    eval(PKG, LEAF, TOP, true);
+    let cached = module_cache::len();
+
+    let leaf = LEAF.replace("i + Pkg::OFFSET", "i - Pkg::OFFSET");
+    assert_eq!(eval(PKG, &leaf, TOP, true), (0xF9, 3, 0xFE));
+    // Leaf, Top and Top's parameterized Scale instance depend on leaf.veryl.
+    assert_eq!(module_cache::len(), cached + 3);
+}
+
+#[test]
+fn package_edit_relowers_dependents() {
+    assert_eq!(eval(PKG, LEAF, TOP, true).0, 9);
+    let cached = module_cache::len();
+
+    let pkg = PKG.replace("OFFSET: u32 = 8", "OFFSET: u32 = 16");
+    assert_eq!(eval(&pkg, LEAF, TOP, true).0, 17);
+    // Leaf uses the package; Top and Scale depend on it through Leaf.
+    assert_eq!(module_cache::len(), cached + 3);
+}
+
+#[test]
+fn parameter_edit_relowers_instance() {
+    assert_eq!(eval(PKG, LEAF, TOP, true).1, 3);
+    let top = TOP.replace("FACTOR: 3", "FACTOR: 5");
+    assert_eq!(eval(PKG, LEAF, &top, true).1, 5);
+    assert_eq!(eval(PKG, LEAF, TOP, true).1, 3);
+}
+
+#[test]
+fn disabled_cache_stores_nothing() {
+    assert_eq!(eval(PKG, LEAF, TOP, false), (9, 3, 0xFE));
+    assert_eq!(module_cache::len(), 0);
+}
@@ -563,6 +563,58 @@ fn test_counter_fail() {
     ));
 }
 
+#[test]
+fn test_testbench_writemem_dumps_memory_range() {
+    let path = std::env::temp_dir().join(format!(
+        "celox_testbench_writememb_{}.mem",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let code = format!(
+        r#"
+        module Ram (
+            clk : input clock    ,
+            we  : input logic    ,
+            addr: input logic<2> ,
+            wdat: input logic<4> ,
+        ) {{
+            var mem: logic<4>[4];
+            always_ff {{
+                if we {{
+                    mem[addr] = wdat;
+                }}
+            }}
+        }}
+        #[test(t)]
+        module t {{
+            inst clk: $tb::clock_gen;
+            var we  : logic;
+            var addr: logic<2>;
+            var wdat: logic<4>;
+            inst dut: Ram (clk, we, addr, wdat);
+            initial {{
+                we   = 1;
+                addr = 2'd1;
+                wdat = 4'b1010;
+                clk.next();
+                addr = 2'd2;
+                wdat = 4'b0110;
+                clk.next();
+                $writememb("{}", dut.mem, 1, 2);
+                $finish();
+            }}
+        }}
+    "#,
+        path.to_string_lossy().replace('\\', "\\\\")
+    );
+
+    assert_eq!(
+        Simulator::builder(&code, "t").run_test().unwrap(),
+        TestResult::Pass
+    );
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "@1\n1010\n0110\n");
+}
+
 #[test]
 fn test_testbench_direct_reads_are_dead_store_roots() {
     let code = r#"
@@ -0,0 +1,137 @@
+use celox::{ProfilePhase, Simulation, Simulator};
+
+const DESIGN: &str = r#"
+module Acc (
+    clk: input  clock   ,
+    rst: input  reset   ,
+    d  : input  logic<16>,
+    q  : output logic<16>,
+) {
+    var acc: logic<16>;
+    var sum: logic<16>;
+    assign sum = acc + d * d;
+    always_ff (clk, rst) {
+        if_reset {
+            acc = 0;
+        } else {
+            acc = sum;
+        }
+    }
+    assign q = acc;
+}
+
+module Top (
+    clk: input  clock    ,
+    rst: input  reset    ,
+    a  : input  logic<16>,
+    b  : input  logic<16>,
+    y  : output logic<16>,
+) {
+    var qa: logic<16>;
+    var qb: logic<16>;
+    inst u_a: Acc (
+        clk,
+        rst,
+        d: a,
+        q: qa,
+    );
+    inst u_b: Acc (
+        clk,
+        rst,
+        d: b,
+        q: qb,
+    );
+    assign y = qa ^ qb;
+}
+"#;
+
+fn run(sim: &mut Simulation) -> celox::BigUint {
+    let a = sim.signal("a");
+    let b = sim.signal("b");
+    let y = sim.signal("y");
+    sim.modify(|io| {
+        io.set(a, 3u16);
+        io.set(b, 5u16);
+    })
+    .unwrap();
+    sim.add_clock("clk", 10, 5);
+    sim.run_until(1000).unwrap();
+    sim.get(y)
+}
+
+#[test]
+fn profile_attributes_time_to_instances_and_modules() {
+    let mut sim = Simulation::builder(DESIGN, "Top")
+        .profile(true)
+        .build()
+        .unwrap();
+    let y = run(&mut sim);
+    assert_eq!(
+        y,
+        run(&mut Simulation::builder(DESIGN, "Top").build().unwrap())
+    );
+
+    let profile = sim.profile().unwrap();
+    let instances = profile
+        .by_instance()
+        .into_iter()
+        .map(|row| row.name)
+        .collect::<Vec<_>>();
+    assert!(instances.contains(&"Top.u_a".to_string()), "{instances:?}");
+    assert!(instances.contains(&"Top.u_b".to_string()), "{instances:?}");
+    assert!(profile.by_module().iter().any(|row| row.name == "Acc"));
+
+    let ff = profile
+        .entries()
+        .iter()
+        .find(|entry| entry.phase == ProfilePhase::Sequential && entry.instance == "Top.u_a")
+        .unwrap();
+    assert_eq!(ff.module, "Acc");
+    assert_eq!(ff.unit, "always_ff(clk)");
+    assert!(ff.calls >= 100);
+    assert!(
+        profile
+            .entries()
+            .windows(2)
+            .all(|pair| pair[0].time >= pair[1].time)
+    );
+
+    let mut folded = Vec::new();
+    profile.write_folded(&mut folded).unwrap();
+    let folded = String::from_utf8(folded).unwrap();
+    assert!(!folded.is_empty());
+    for line in folded.lines() {
+        let (stack, nanos) = line.rsplit_once(' ').unwrap();
+        assert!(stack.starts_with("Top;"));
+        assert!(nanos.parse::<u64>().unwrap() > 0);
+    }
+    assert!(profile.to_string().starts_with("        time"));
+
+    sim.reset_profile();
+    assert_eq!(sim.profile().unwrap().total(), std::time::Duration::ZERO);
+}
+
+#[test]
+fn profile_is_off_by_default_and_keeps_forces_working() {
+    let sim = Simulator::builder(DESIGN, "Top").build().unwrap();
+    assert!(sim.profile().is_none());
+
+    let mut sim = Simulator::builder(DESIGN, "Top")
+        .profile(true)
+        .force_support(true)
+        .build()
+        .unwrap();
+    let qa = sim.signal("qa");
+    let y = sim.signal("y");
+    sim.force(qa, 0xffu16.into());
+    sim.modify(|_| {}).unwrap();
+    assert_eq!(sim.get(y), 0xffu16.into());
+    let profile = sim.profile().unwrap();
+    assert!(
+        profile
+            .entries()
+            .iter()
+            .all(|entry| entry.phase == ProfilePhase::Comb)
+    );
+    assert!(profile.entries().iter().any(|entry| entry.calls > 0));
+}
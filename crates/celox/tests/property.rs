@@ -0,0 +1,148 @@
+use celox::{
+    BigUint, Delay, Implication, Property, PropertyError, PropertyExpr, PropertyFailure, Sequence,
+    Simulation,
+};
+
+const HANDSHAKE: &str = r#"
+module Top (
+    clk  : input  clock   ,
+    rst  : input  logic   ,
+    req  : input  logic   ,
+    valid: input  logic   ,
+    lat  : input  logic<3>,
+    ack  : output logic   ,
+) {
+    var history: logic<8>;
+    always_ff (clk) {
+        history = {history[6:0], req};
+    }
+    assign ack = history[lat];
+}
+"#;
+
+/// A simulation with rising clock edges at 0, 10, 20, ... and a single-cycle
+/// `req` pulse sampled at the edge at time 0. `ack` follows `lat + 1` edges
+/// later.
+fn handshake(lat: u8) -> Simulation {
+    let mut sim = Simulation::builder(HANDSHAKE, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let req = sim.signal("req");
+    let lat_signal = sim.signal("lat");
+    sim.modify(|io| {
+        io.set(req, 1u8);
+        io.set(lat_signal, lat);
+    })
+    .unwrap();
+    sim.schedule_value(req, 5, BigUint::from(0u8));
+    sim
+}
+
+#[test]
+fn ack_inside_the_window_passes() {
+    let mut sim = handshake(2);
+    sim.assert_property("req_ack", "@(posedge clk) req |-> ##[1:4] ack")
+        .unwrap();
+    sim.run_until(100).unwrap();
+    assert_eq!(sim.property_failures(), &[]);
+}
+
+#[test]
+fn late_ack_reports_failure_time_and_attempt_start() {
+    let mut sim = handshake(5);
+    let id = sim
+        .assert_property("req_ack", "@(posedge clk) req |-> ##[1:4] ack")
+        .unwrap();
+    sim.run_until(100).unwrap();
+    assert_eq!(
+        sim.take_property_failures(),
+        vec![PropertyFailure {
+            id,
+            name: "req_ack".to_string(),
+            start_time: 0,
+            time: 40,
+        }]
+    );
+    assert!(sim.property_failures().is_empty());
+}
+
+#[test]
+fn non_overlapping_implication_checks_the_next_edge() {
+    let mut sim = handshake(0);
+    let valid = sim.signal("valid");
+    sim.assert_property("pulse", "@(posedge clk) $rose(valid) |=> !valid")
+        .unwrap();
+    // Sampled high at the edge at 10 only.
+    sim.schedule_value(valid, 5, BigUint::from(1u8));
+    sim.schedule_value(valid, 15, BigUint::from(0u8));
+    // Sampled high at the edges at 50 and 60.
+    sim.schedule_value(valid, 45, BigUint::from(1u8));
+    sim.schedule_value(valid, 65, BigUint::from(0u8));
+    sim.run_until(100).unwrap();
+    let failures: Vec<_> = sim
+        .property_failures()
+        .iter()
+        .map(|failure| (failure.start_time, failure.time))
+        .collect();
+    assert_eq!(failures, vec![(50, 60)]);
+}
+
+#[test]
+fn disable_iff_abandons_attempts_in_flight() {
+    let mut sim = handshake(5);
+    let rst = sim.signal("rst");
+    sim.assert_property(
+        "req_ack",
+        "@(posedge clk) disable iff (rst) req |-> ##[1:4] ack",
+    )
+    .unwrap();
+    sim.schedule_value(rst, 25, BigUint::from(1u8));
+    sim.schedule_value(rst, 35, BigUint::from(0u8));
+    sim.run_until(100).unwrap();
+    assert_eq!(sim.property_failures(), &[]);
+}
+
+#[test]
+fn property_built_in_rust_matches_parsed_text() {
+    let mut sim = handshake(5);
+    let property = Property::implies(
+        "clk",
+        Sequence::new(PropertyExpr::signal("req")),
+        Implication::Overlapping,
+        Sequence::after(Delay::range(1, Some(4)), PropertyExpr::signal("ack")),
+    );
+    sim.add_property("built", &property).unwrap();
+    sim.assert_property("parsed", "@(posedge clk) req |-> ##[1:4] ack")
+        .unwrap();
+    sim.run_until(100).unwrap();
+    let failures: Vec<_> = sim
+        .property_failures()
+        .iter()
+        .map(|failure| (failure.name.as_str(), failure.time))
+        .collect();
+    assert_eq!(failures, vec![("built", 40), ("parsed", 40)]);
+}
+
+#[test]
+fn removed_property_stops_checking() {
+    let mut sim = handshake(5);
+    let id = sim
+        .assert_property("req_ack", "@(posedge clk) req |-> ##[1:4] ack")
+        .unwrap();
+    sim.run_until(20).unwrap();
+    sim.remove_property(id);
+    sim.run_until(100).unwrap();
+    assert_eq!(sim.property_failures(), &[]);
+}
+
+#[test]
+fn unknown_signals_and_syntax_errors_are_rejected() {
+    let mut sim = handshake(0);
+    assert_eq!(
+        sim.assert_property("p", "@(posedge clk) req |-> grant"),
+        Err(PropertyError::UnknownSignal("grant".to_string()))
+    );
+    assert!(matches!(
+        sim.assert_property("p", "@(posedge clk) req |-> ##[1: ack"),
+        Err(PropertyError::Syntax { .. })
+    ));
+}
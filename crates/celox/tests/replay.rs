@@ -0,0 +1,125 @@
+use celox::{BigUint, ReplayError, ReplayMapping, Simulation};
+use std::path::PathBuf;
+
+const ACCUMULATOR: &str = r#"
+module Top (
+    clk: input  '_ clock,
+    rst: input  '_ reset,
+    din: input  logic<8>,
+    sum: output logic<8>,
+    odd: output logic,
+) {
+    var acc: logic<8>;
+    always_ff (clk, rst) {
+        if_reset {
+            acc = 8'd0;
+        } else {
+            acc = acc + din;
+        }
+    }
+    assign sum = acc;
+    assign odd = acc[0];
+}
+"#;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("celox-replay-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run the accumulator with a clock and a few data drives, recording to `path`.
+fn record(path: &std::path::Path) -> BigUint {
+    let mut sim = Simulation::builder(ACCUMULATOR, "Top")
+        .waveform(path)
+        .build()
+        .unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    let din = sim.signal("din");
+    let sum = sim.signal("sum");
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    for (time, value) in [(3u64, 1u32), (13, 4), (27, 9), (41, 2)] {
+        sim.schedule_value(din, time, value.into());
+    }
+    sim.run_until(60).unwrap();
+    sim.get(sum)
+}
+
+#[test]
+fn replayed_inputs_reproduce_recorded_outputs() {
+    for extension in ["vcd", "fst"] {
+        let dir = temp_dir(extension);
+        let path = dir.join(format!("recorded.{extension}"));
+        let expected = record(&path);
+        assert_ne!(expected, BigUint::from(0u32));
+
+        let mut sim = Simulation::builder(ACCUMULATOR, "Top").build().unwrap();
+        let report = sim
+            .replay_inputs(&path, &ReplayMapping::new().check_outputs(true))
+            .unwrap();
+        assert!(report.passed(), "{extension}: {:?}", report.mismatches);
+        assert_eq!(report.inputs, ["clk", "rst", "din"]);
+        assert_eq!(report.outputs, ["sum", "odd"]);
+        assert_eq!(sim.time(), 60);
+        let sum = sim.signal("sum");
+        assert_eq!(sim.get(sum), expected);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
+
+#[test]
+fn output_mismatches_are_reported_with_time() {
+    let dir = temp_dir("mismatch");
+    let path = dir.join("stimulus.vcd");
+    std::fs::write(
+        &path,
+        "\
+$timescale 1ns $end
+$scope module tb $end
+$scope module dut $end
+$var wire 1 ! clk $end
+$var wire 1 \" rst $end
+$var wire 8 # din $end
+$var wire 8 $ sum $end
+$var wire 1 % odd $end
+$upscope $end
+$upscope $end
+$enddefinitions $end
+#0
+0!
+1\"
+b11 #
+bx $
+x%
+#5
+1!
+b11 $
+#10
+0!
+#15
+1!
+b111 $
+",
+    )
+    .unwrap();
+
+    let mut sim = Simulation::builder(ACCUMULATOR, "Top").build().unwrap();
+    sim.set_timescale("100ps".parse().unwrap()).unwrap();
+    let mapping = ReplayMapping::new().scope("tb.dut").check_outputs(true);
+    let report = sim.replay_inputs(&path, &mapping).unwrap();
+    assert_eq!(sim.time(), 150);
+    assert_eq!(report.mismatches.len(), 1);
+    let mismatch = &report.mismatches[0];
+    assert_eq!(mismatch.time, 150);
+    assert_eq!(mismatch.port, "sum");
+    assert_eq!(mismatch.expected, "00000111");
+    assert_eq!(mismatch.actual, "00000110");
+
+    let unmapped = ReplayMapping::new().signal("tb.dut.missing", "din");
+    assert!(matches!(
+        sim.replay_inputs(&path, &unmapped),
+        Err(ReplayError::UnknownSignal(name)) if name == "tb.dut.missing"
+    ));
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,116 @@
+use celox::{BigUint, Simulation};
+
+const SAMPLER: &str = r#"
+module Top (
+    clk:  input  '_ clock,
+    rst:  input  '_ reset,
+    din:  input  logic<96>,
+    en:   input  logic,
+    q:    output logic<96>,
+    gcnt: output logic<8>,
+) {
+    var sampled: logic<96>;
+    always_ff (clk, rst) {
+        if_reset {
+            sampled = 96'd0;
+        } else {
+            sampled = din;
+        }
+    }
+    assign q = sampled;
+
+    let gclk: '_ clock = clk & en;
+    var count: logic<8>;
+    always_ff (gclk, rst) {
+        if_reset {
+            count = 8'd0;
+        } else {
+            count = count + 8'd1;
+        }
+    }
+    assign gcnt = count;
+}
+"#;
+
+fn build() -> Simulation {
+    let mut sim = Simulation::builder(SAMPLER, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    sim
+}
+
+#[test]
+fn drives_apply_after_edges_at_the_same_time() {
+    let mut sim = build();
+    let din = sim.signal("din");
+    let q = sim.signal("q");
+    let wide = BigUint::from(1u8) << 90u32;
+
+    sim.schedule_value(din, 10, wide.clone() + 5u32);
+    sim.run_until(10).unwrap();
+    assert_eq!(sim.get(din), wide.clone() + 5u32);
+    assert_eq!(sim.get(q), BigUint::from(0u32));
+
+    sim.run_until(20).unwrap();
+    assert_eq!(sim.get(q), wide + 5u32);
+}
+
+#[test]
+fn drives_between_edges_advance_time() {
+    let mut sim = build();
+    let din = sim.signal("din");
+    let q = sim.signal("q");
+
+    sim.schedule_value(din, 13, 7u32.into());
+    assert_eq!(sim.next_event_time(), Some(0));
+    sim.run_until(12).unwrap();
+    assert_eq!(sim.get(din), BigUint::from(0u32));
+    assert_eq!(sim.step().unwrap(), Some(13));
+    assert_eq!(sim.time(), 13);
+    assert_eq!(sim.get(din), BigUint::from(7u32));
+    sim.run_until(20).unwrap();
+    assert_eq!(sim.get(q), BigUint::from(7u32));
+}
+
+#[test]
+fn drives_at_the_same_time_apply_in_queue_order() {
+    let mut sim = build();
+    let din = sim.signal("din");
+
+    sim.schedule_value(din, 5, 1u32.into());
+    sim.schedule_value(din, 5, 2u32.into());
+    sim.schedule_value(din, 5, 3u32.into());
+    sim.run_until(5).unwrap();
+    assert_eq!(sim.get(din), BigUint::from(3u32));
+}
+
+#[test]
+fn drives_trigger_derived_clock_edges() {
+    let mut sim = build();
+    let en = sim.signal("en");
+    let gcnt = sim.signal("gcnt");
+
+    // clk is high from 0 to 5; raising en at 2 produces a gclk edge.
+    sim.schedule_value(en, 2, 1u32.into());
+    sim.run_until(4).unwrap();
+    assert_eq!(sim.get(gcnt), BigUint::from(1u32));
+    sim.run_until(10).unwrap();
+    assert_eq!(sim.get(gcnt), BigUint::from(2u32));
+}
+
+#[test]
+fn pending_drives_survive_checkpoints() {
+    let mut sim = build();
+    let din = sim.signal("din");
+    sim.schedule_value(din, 25, 9u32.into());
+    sim.schedule_value(din, 25, 11u32.into());
+    let checkpoint = sim.save_state();
+
+    let bytes = checkpoint.to_bytes().unwrap();
+    let checkpoint = celox::SimulatorCheckpoint::from_bytes(&bytes).unwrap();
+    let mut restored = build();
+    restored.restore_state(&checkpoint).unwrap();
+    restored.run_until(25).unwrap();
+    assert_eq!(restored.get(din), BigUint::from(11u32));
+}
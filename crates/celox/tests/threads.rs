@@ -0,0 +1,96 @@
+use celox::Simulator;
+
+const DESIGN: &str = r#"module Lane (
+    clk : input  '_ clock,
+    rst : input  '_ reset,
+    seed: input  logic<32>,
+    sum : output logic<32>,
+) {
+    var state: logic<32>;
+    var mixed: logic<32>;
+    var acc  : logic<32>;
+    always_comb {
+        mixed = (state ^ (state << 7)) + (seed ^ (state >> 3));
+    }
+    always_ff (clk, rst) {
+        if_reset {
+            state = 32'h1234_5678;
+        } else {
+            state = mixed ^ (mixed >> 11);
+        }
+    }
+    always_ff (clk, rst) {
+        if_reset {
+            acc = 32'd0;
+        } else {
+            acc = acc + (mixed & 32'hffff);
+        }
+    }
+    assign sum = acc ^ state;
+}
+
+module Top (
+    clk  : input  '_ clock,
+    rst  : input  '_ reset,
+    seed : input  logic<32>,
+    s0   : output logic<32>,
+    s1   : output logic<32>,
+    s2   : output logic<32>,
+    s3   : output logic<32>,
+    total: output logic<32>,
+) {
+    var seed1: logic<32>;
+    var seed2: logic<32>;
+    var seed3: logic<32>;
+    assign seed1 = seed + 32'd1;
+    assign seed2 = seed + 32'd2;
+    assign seed3 = seed + 32'd3;
+    inst l0: Lane (clk, rst, seed, sum: s0);
+    inst l1: Lane (clk, rst, seed: seed1, sum: s1);
+    inst l2: Lane (clk, rst, seed: seed2, sum: s2);
+    inst l3: Lane (clk, rst, seed: seed3, sum: s3);
+    assign total = s0 + s1 + s2 + s3;
+}
+"#;
+
+fn trace(threads: usize) -> Vec<[u32; 5]> {
+    let mut sim = Simulator::builder(DESIGN, "Top")
+        .threads(threads)
+        .build()
+        .unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let seed = sim.signal("seed");
+    let outputs = ["s0", "s1", "s2", "s3", "total"].map(|name| sim.signal(name));
+
+    sim.modify(|io| {
+        io.set::<u8>(rst, 0);
+        io.set::<u32>(seed, 0x1234_5678);
+    })
+    .unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+
+    let mut samples = Vec::new();
+    for cycle in 0..200u32 {
+        if cycle % 50 == 0 {
+            sim.modify(|io| io.set::<u32>(seed, cycle.wrapping_mul(0x9e37_79b9)))
+                .unwrap();
+        }
+        sim.tick(clk).unwrap();
+        samples.push(outputs.map(|signal| sim.get_as::<u32>(signal)));
+    }
+    samples
+}
+
+#[test]
+fn multi_threaded_evaluation_matches_single_threaded() {
+    let sequential = trace(1);
+    assert_eq!(trace(2), sequential);
+    assert_eq!(trace(4), sequential);
+}
+
+#[test]
+fn zero_threads_is_single_threaded() {
+    assert_eq!(trace(0), trace(1));
+}
@@ -0,0 +1,96 @@
+use celox::{BitToggles, Simulation, Simulator};
+
+const COUNTER: &str = r#"
+module Top (
+    clk: input  '_ clock,
+    rst: input  '_ reset,
+    cnt: output logic<4>,
+) {
+    var count: logic<4>;
+    always_ff (clk, rst) {
+        if_reset {
+            count = 4'd0;
+        } else {
+            count = count + 4'd1;
+        }
+    }
+    assign cnt = count;
+}
+"#;
+
+#[test]
+fn timed_simulation_counts_toggles_and_writes_saif() {
+    let mut sim = Simulation::builder(COUNTER, "Top")
+        .toggle_coverage(true)
+        .build()
+        .unwrap();
+    sim.set_timescale("1ns".parse().unwrap()).unwrap();
+    let rst = sim.signal("rst");
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    sim.add_clock("clk", 10, 0);
+    sim.run_until(100).unwrap();
+
+    let coverage = sim.toggle_coverage().unwrap();
+    let count = coverage.signal("Top.count").unwrap();
+    assert_eq!(count.width, 4);
+    // The first edge is at time 0, so eleven edges fire by time 100.
+    assert_eq!(count.bits[0], BitToggles { rise: 6, fall: 5 });
+    assert_eq!(count.bits[3], BitToggles { rise: 1, fall: 0 });
+    assert_eq!(count.covered_bits(), 3);
+    let clk = coverage.signal("Top.clk").unwrap();
+    assert!(clk.bits[0].is_covered());
+    assert_eq!(coverage.duration(), 100);
+
+    let dir = std::env::temp_dir().join(format!("celox-toggle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("activity.saif");
+    sim.write_saif(&path).unwrap();
+    let saif = std::fs::read_to_string(&path).unwrap();
+    assert!(saif.contains("(TIMESCALE 1 ns)"), "{saif}");
+    assert!(saif.contains("(DURATION 100)"), "{saif}");
+    assert!(saif.contains("(INSTANCE Top"), "{saif}");
+    assert!(saif.contains("(count\\[0\\] (T0 "), "{saif}");
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn coverage_can_start_after_reset() {
+    let mut sim = Simulator::builder(COUNTER, "Top").build().unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    sim.modify(|io| io.set::<u8>(rst, 0)).unwrap();
+    sim.tick(clk).unwrap();
+    assert!(sim.toggle_coverage().is_none());
+
+    sim.modify(|io| io.set::<u8>(rst, 1)).unwrap();
+    sim.enable_toggle_coverage();
+    for _ in 0..3 {
+        sim.tick(clk).unwrap();
+    }
+    let coverage = sim.toggle_coverage().unwrap();
+    assert_eq!(
+        coverage.signal("Top.rst").unwrap().bits[0],
+        BitToggles::default()
+    );
+    assert_eq!(
+        coverage.signal("Top.cnt").unwrap().bits[0],
+        BitToggles { rise: 2, fall: 1 }
+    );
+
+    let mut json = Vec::new();
+    coverage.write_json(&mut json).unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(report["total_bits"], coverage.total_bits());
+    assert_eq!(report["covered_bits"], 2);
+    assert!(
+        report["signals"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|signal| signal["name"] == "Top.cnt" && signal["bits"][1]["rise"] == 1)
+    );
+
+    let coverage = sim.disable_toggle_coverage().unwrap();
+    assert_eq!(coverage.duration(), 3);
+    assert!(sim.toggle_coverage().is_none());
+}
@@ -5,7 +5,9 @@ mod test_utils;
 
 #[cfg(test)]
 mod tests {
-    use celox::{IOContext, SimulatorBuilder};
+    use celox::{
+        DumpFilter, IOContext, SimulatorBuilder, Waveform, WaveformDiff, WaveformDiffOptions,
+    };
     use std::fs;
     use std::path::Path;
 
@@ -43,4 +45,175 @@ mod tests {
 
         fs::remove_file(vcd_path).unwrap();
     }
+
+    #[test]
+    fn test_waveform_extension_selects_fst() {
+        let code = r#"
+        module Top (
+            a: input logic<8>,
+            b: output logic<8>,
+        ) {
+            assign b = a;
+        }
+        "#;
+
+        let fst_path = "test_output.fst";
+        let mut sim = SimulatorBuilder::new(code, "Top")
+            .waveform(fst_path)
+            .build()
+            .unwrap();
+
+        let a = sim.signal("a");
+        sim.dump(0);
+        sim.modify(|ctx: &mut IOContext| {
+            ctx.set(a, 8u8);
+        })
+        .unwrap();
+        sim.dump(10);
+        drop(sim);
+
+        let content = fs::read(fst_path).unwrap();
+        // An FST file opens with a header block (type 0); VCD is text.
+        assert_eq!(content.first(), Some(&0));
+        assert!(!content.starts_with(b"$date"));
+
+        fs::remove_file(fst_path).unwrap();
+    }
+
+    const HIERARCHY: &str = r#"
+    module Leaf ( li: input logic<8>, lo: output logic<8> ) {
+        assign lo = li + 8'd1;
+    }
+    module Mid ( mi: input logic<8>, mo: output logic<8> ) {
+        var mem: logic<8> [4];
+        always_comb {
+            mem[0] = mi;
+            mem[1] = mi;
+            mem[2] = mi;
+            mem[3] = mi;
+        }
+        inst u_leaf: Leaf ( li: mem[3], lo: mo );
+    }
+    module Top ( top_i: input logic<8>, top_o: output logic<8> ) {
+        inst u_mid: Mid ( mi: top_i, mo: top_o );
+    }
+    "#;
+
+    fn dump_with_filter(vcd_path: &str, filter: DumpFilter) -> String {
+        let mut sim = SimulatorBuilder::new(HIERARCHY, "Top")
+            .vcd(vcd_path)
+            .dump_filter(filter)
+            .build()
+            .unwrap();
+        sim.dump(0);
+        drop(sim);
+        let content = fs::read_to_string(vcd_path).unwrap();
+        fs::remove_file(vcd_path).unwrap();
+        content
+    }
+
+    fn declares(content: &str, name: &str) -> bool {
+        content
+            .lines()
+            .any(|line| line.starts_with("$var") && line.ends_with(&format!(" {name} $end")))
+    }
+
+    #[test]
+    fn test_dump_filter_limits_scope_depth() {
+        let content = dump_with_filter(
+            "test_dump_scope.vcd",
+            DumpFilter::new().scope("Top.u_mid", 1),
+        );
+        assert!(declares(&content, "mi"), "{content}");
+        assert!(declares(&content, "mem"), "{content}");
+        assert!(!declares(&content, "top_i"), "{content}");
+        assert!(!declares(&content, "li"), "{content}");
+
+        let content = dump_with_filter("test_dump_depth.vcd", DumpFilter::new().scope("", 0));
+        assert!(declares(&content, "top_i"), "{content}");
+        assert!(declares(&content, "li"), "{content}");
+    }
+
+    #[test]
+    fn test_dump_filter_excludes_unpacked_arrays() {
+        let content = dump_with_filter("test_dump_arrays.vcd", DumpFilter::new().arrays(false));
+        assert!(!declares(&content, "mem"), "{content}");
+        assert!(declares(&content, "mi"), "{content}");
+    }
+
+    #[test]
+    fn test_dump_off_marks_gap_with_x() {
+        let vcd_path = "test_dump_off.vcd";
+        let mut sim = SimulatorBuilder::new(HIERARCHY, "Top")
+            .vcd(vcd_path)
+            .build()
+            .unwrap();
+        let top_i = sim.signal("top_i");
+        sim.dump(0);
+        sim.dump_off();
+        sim.dump(10);
+        sim.modify(|ctx: &mut IOContext| ctx.set(top_i, 5u8))
+            .unwrap();
+        sim.dump(20);
+        sim.dump_on();
+        sim.dump(30);
+        drop(sim);
+
+        let content = fs::read_to_string(vcd_path).unwrap();
+        fs::remove_file(vcd_path).unwrap();
+        let gap = content.find("#10\n$dumpoff\n").expect(&content);
+        assert!(content[gap..].contains("bxxxxxxxx "), "{content}");
+        assert!(!content.contains("#20"), "{content}");
+        let resumed = content.find("#30").expect(&content);
+        assert!(content[resumed..].contains("b101 "), "{content}");
+    }
+
+    #[test]
+    fn test_dump_window_records_only_inside() {
+        let vcd_path = "test_dump_window.vcd";
+        let mut sim = SimulatorBuilder::new(HIERARCHY, "Top")
+            .vcd(vcd_path)
+            .dump_filter(DumpFilter::new().window(10, 20))
+            .build()
+            .unwrap();
+        for time in [0, 10, 20, 30, 40] {
+            sim.dump(time);
+        }
+        drop(sim);
+
+        let content = fs::read_to_string(vcd_path).unwrap();
+        fs::remove_file(vcd_path).unwrap();
+        assert!(!content.contains("#0\n"), "{content}");
+        assert!(content.contains("#10\n"), "{content}");
+        assert!(content.contains("#30\n$dumpoff"), "{content}");
+        assert!(!content.contains("#40"), "{content}");
+    }
+
+    #[test]
+    fn test_vcd_and_fst_read_back_identically() {
+        let dump = |path: &str| {
+            let mut sim = SimulatorBuilder::new(HIERARCHY, "Top")
+                .waveform(path)
+                .build()
+                .unwrap();
+            let top_i = sim.signal("top_i");
+            for (time, value) in [(0, 1u8), (10, 7), (20, 7), (30, 200)] {
+                sim.modify(|ctx: &mut IOContext| ctx.set(top_i, value))
+                    .unwrap();
+                sim.dump(time);
+            }
+            drop(sim);
+            let waveform = Waveform::read(path).unwrap();
+            fs::remove_file(path).unwrap();
+            waveform
+        };
+        let vcd = dump("test_read_back.vcd");
+        let fst = dump("test_read_back.fst");
+
+        let top_i = vcd.signal("inst0.top_i").expect("top port is dumped");
+        assert_eq!(top_i.value_at(25), Some("00000111"));
+        let diff = WaveformDiff::new(&vcd, &fst, &WaveformDiffOptions::new());
+        assert!(diff.is_identical(), "{diff:?}");
+        assert!(diff.compared > 0);
+    }
 }
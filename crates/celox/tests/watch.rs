@@ -0,0 +1,138 @@
+use std::sync::{Arc, Mutex};
+
+use celox::{Simulation, Simulator, ValueChange};
+
+#[path = "test_utils/mod.rs"]
+#[macro_use]
+#[allow(unused_macros)]
+mod test_utils;
+
+const COUNTER: &str = r#"
+module Top (
+    clk: input  clock,
+    rst: input  reset,
+    en:  input  logic,
+    cnt: output logic<8>,
+    odd: output logic,
+) {
+    var counter: logic<8>;
+    always_ff (clk, rst) {
+        if_reset {
+            counter = 8'd0;
+        } else if en {
+            counter = counter + 8'd1;
+        }
+    }
+    assign cnt = counter;
+    assign odd = counter[0];
+}
+"#;
+
+fn recorder() -> (
+    Arc<Mutex<Vec<ValueChange>>>,
+    impl FnMut(&ValueChange) + Send + 'static,
+) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&log);
+    (log, move |change: &ValueChange| {
+        sink.lock().unwrap().push(change.clone())
+    })
+}
+
+all_backends! {
+fn test_watch_reports_tick_changes(sim) {
+    @omit_veryl;
+    @build Simulator::builder(COUNTER, "Top");
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let en = sim.signal("en");
+    let cnt = sim.signal("cnt");
+
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(en, 1);
+    })
+    .unwrap();
+    sim.tick(clk).unwrap();
+
+    let (log, callback) = recorder();
+    let id = sim.watch(cnt, callback);
+    sim.tick(clk).unwrap();
+    sim.modify(|io| io.set::<u8>(en, 0)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.modify(|io| io.set::<u8>(en, 1)).unwrap();
+    sim.tick(clk).unwrap();
+    sim.unwatch(id);
+    sim.tick(clk).unwrap();
+
+    let log = log.lock().unwrap();
+    let changes = log
+        .iter()
+        .map(|change| (change.time, change.old_value.clone(), change.new_value.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![(2, 1u32.into(), 2u32.into()), (4, 2u32.into(), 3u32.into())]
+    );
+    assert!(log.iter().all(|change| change.id == id && change.signal == cnt));
+}
+}
+
+#[test]
+fn modify_reports_combinational_changes() {
+    let mut sim = Simulator::builder(COUNTER, "Top").build().unwrap();
+    let clk = sim.event("clk");
+    let rst = sim.signal("rst");
+    let en = sim.signal("en");
+    let odd = sim.signal("odd");
+    let cnt = sim.signal("cnt");
+
+    let (log, callback) = recorder();
+    sim.watch(odd, callback);
+    let (cnt_log, cnt_callback) = recorder();
+    sim.watch(cnt, cnt_callback);
+    sim.set::<u8>(rst, 1);
+    sim.set::<u8>(en, 1);
+    assert!(log.lock().unwrap().is_empty());
+
+    sim.tick(clk).unwrap();
+    let log = log.lock().unwrap();
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].old_value, 0u32.into());
+    assert_eq!(log[0].new_value, 1u32.into());
+    assert_eq!(log[0].new_mask, 0u32.into());
+    assert_eq!(cnt_log.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn simulation_watch_reports_simulation_time() {
+    let mut sim = Simulation::builder(COUNTER, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let rst = sim.signal("rst");
+    let en = sim.signal("en");
+    let cnt = sim.signal("cnt");
+    sim.modify(|io| {
+        io.set::<u8>(rst, 1);
+        io.set::<u8>(en, 1);
+    })
+    .unwrap();
+
+    let (log, callback) = recorder();
+    sim.watch(cnt, callback);
+    sim.run_until(30).unwrap();
+
+    let log = log.lock().unwrap();
+    let changes = log
+        .iter()
+        .map(|change| (change.time, change.new_value.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![
+            (0, 1u32.into()),
+            (10, 2u32.into()),
+            (20, 3u32.into()),
+            (30, 4u32.into())
+        ]
+    );
+}
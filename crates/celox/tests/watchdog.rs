@@ -0,0 +1,140 @@
+use std::time::{Duration, Instant};
+
+use celox::{InterruptReason, RuntimeErrorCode, Simulation, Simulator};
+
+const COUNTER: &str = r#"
+module Top (
+    clk: input  clock   ,
+    q  : output logic<8>,
+) {
+    var cnt: logic<8>;
+    always_ff (clk) {
+        cnt = cnt + 8'd1;
+    }
+    assign q = cnt;
+}
+"#;
+
+/// `cnt` is clocked by a flip-flop output, so every second `clk` edge needs a
+/// second cascade round.
+const RIPPLE: &str = r#"
+module Top (
+    clk: input  '_ clock   ,
+    q  : output '_ logic<8>,
+) {
+    var div: '_ logic   ;
+    var cnt: '_ logic<8>;
+    let div_clk: '_ clock = div;
+    always_ff (clk) {
+        div = ~div;
+    }
+    always_ff (div_clk) {
+        cnt = cnt + 8'd1;
+    }
+    assign q = cnt;
+}
+"#;
+
+#[test]
+fn cancel_token_stops_run_until_from_another_thread() {
+    let mut sim = Simulation::builder(COUNTER, "Top").build().unwrap();
+    sim.add_clock("clk", 10, 0);
+    let token = sim.cancel_token();
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        token.cancel();
+    });
+    let error = sim.run_until(u64::MAX).unwrap_err();
+    canceller.join().unwrap();
+    let RuntimeErrorCode::Interrupted { reason, time } = error else {
+        panic!("unexpected error: {error}");
+    };
+    assert_eq!(reason, InterruptReason::Cancelled);
+    assert!(time > 0);
+    assert_eq!(time, sim.time());
+
+    // The token stays cancelled until it is reset.
+    assert!(sim.run_until(time + 100).is_err());
+    sim.cancel_token().reset();
+    sim.run_until(time + 100).unwrap();
+}
+
+#[test]
+fn wall_clock_limit_interrupts_each_run_call() {
+    let mut sim = Simulation::builder(COUNTER, "Top")
+        .wall_clock_limit(Duration::from_millis(50))
+        .build()
+        .unwrap();
+    sim.add_clock("clk", 10, 0);
+    let start = Instant::now();
+    let error = sim.run_until(u64::MAX).unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(matches!(
+        error,
+        RuntimeErrorCode::Interrupted {
+            reason: InterruptReason::WallClockLimit(_),
+            ..
+        }
+    ));
+    assert!(
+        error
+            .to_string()
+            .contains(&format!("at time {}", sim.time()))
+    );
+
+    // A later call gets a fresh budget.
+    let resumed = sim.time();
+    sim.run_until(resumed + 100).unwrap();
+    assert_eq!(sim.time(), resumed + 100);
+}
+
+#[test]
+fn cascade_limit_reports_the_step_time() {
+    let mut sim = Simulation::builder(RIPPLE, "Top")
+        .max_cascade_rounds(1)
+        .build()
+        .unwrap();
+    sim.add_clock("clk", 10, 5);
+    let error = sim.run_until(100).unwrap_err();
+    assert_eq!(
+        error,
+        RuntimeErrorCode::Interrupted {
+            reason: InterruptReason::CascadeLimit(1),
+            time: sim.time(),
+        }
+    );
+    assert!(sim.time() < 100);
+
+    let run = |builder: celox::SimulatorBuilder<'_, Simulation>| {
+        let mut sim = builder.build().unwrap();
+        sim.add_clock("clk", 10, 5);
+        sim.run_until(100).unwrap();
+        let q = sim.signal("q");
+        sim.get(q)
+    };
+    assert_eq!(
+        run(Simulation::builder(RIPPLE, "Top").max_cascade_rounds(2)),
+        run(Simulation::builder(RIPPLE, "Top"))
+    );
+}
+
+#[test]
+fn cancel_token_shared_through_the_builder_stops_tick_loops() {
+    let token = celox::CancelToken::new();
+    let mut sim = Simulator::builder(COUNTER, "Top")
+        .cancel_token(token.clone())
+        .build()
+        .unwrap();
+    let clk = sim.named_events()[0].id;
+    sim.tick_by_id_n(clk, 3).unwrap();
+    token.cancel();
+    assert_eq!(
+        sim.tick_by_id_n(clk, 3),
+        Err(RuntimeErrorCode::Interrupted {
+            reason: InterruptReason::Cancelled,
+            time: 3,
+        })
+    );
+    let q = sim.signal("q");
+    assert_eq!(sim.get(q), 3u8.into());
+}
@@ -0,0 +1,200 @@
+use celox::{BigUint, Simulator, XPropMode};
+
+#[path = "test_utils/mod.rs"]
+#[macro_use]
+#[allow(unused_macros)]
+mod test_utils;
+
+const BRANCHES: &str = r#"
+module Top (
+    clk: input  clock   ,
+    sel: input  logic   ,
+    k  : input  logic<2>,
+    a  : input  logic<8>,
+    b  : input  logic<8>,
+    y  : output logic<8>,
+    yc : output logic<8>,
+    q  : output logic<8>,
+    qc : output logic<8>,
+) {
+    always_comb {
+        if sel {
+            y = a;
+        } else {
+            y = b;
+        }
+    }
+    always_comb {
+        case k {
+            2'd0   : yc = a;
+            2'd1   : yc = b;
+            default: yc = 8'h00;
+        }
+    }
+    always_ff (clk) {
+        if sel {
+            q = a;
+        } else {
+            q = b;
+        }
+    }
+    always_ff (clk) {
+        case k {
+            2'd0   : qc = a;
+            2'd1   : qc = b;
+            default: qc = 8'h00;
+        }
+    }
+}
+"#;
+
+const HIER: &str = r#"
+module Pick (
+    sel: input  logic   ,
+    a  : input  logic<8>,
+    b  : input  logic<8>,
+    y  : output logic<8>,
+) {
+    always_comb {
+        if sel {
+            y = a;
+        } else {
+            y = b;
+        }
+    }
+}
+
+module Top (
+    sel: input  logic   ,
+    a  : input  logic<8>,
+    b  : input  logic<8>,
+    y0 : output logic<8>,
+    y1 : output logic<8>,
+) {
+    inst u0: Pick (sel, a, b, y: y0);
+    always_comb {
+        if sel {
+            y1 = a;
+        } else {
+            y1 = b;
+        }
+    }
+}
+"#;
+
+/// Drives `sel` and the low bit of `k` to X with `a = 0xAA`, `b = 0xBB` and
+/// clocks once, so every output sees an unknown condition.
+macro_rules! drive_unknown_conditions {
+    ($sim:ident) => {{
+        let clk = $sim.event("clk");
+        let sel = $sim.signal("sel");
+        let k = $sim.signal("k");
+        let a = $sim.signal("a");
+        let b = $sim.signal("b");
+        $sim.modify(|io| {
+            io.set(a, 0xAAu8);
+            io.set(b, 0xBBu8);
+            io.set_four_state(sel, BigUint::from(1u8), BigUint::from(1u8));
+            io.set_four_state(k, BigUint::from(1u8), BigUint::from(1u8));
+        })
+        .unwrap();
+        $sim.tick(clk).unwrap();
+    }};
+}
+
+all_backends! {
+
+fn test_lrm_takes_else_branch_on_x(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @build Simulator::builder(BRANCHES, "Top").four_state(true);
+    drive_unknown_conditions!(sim);
+    let known = |v: u8| (BigUint::from(v), BigUint::from(0u8));
+    assert_eq!(sim.get_four_state(sim.signal("y")), known(0xBB));
+    assert_eq!(sim.get_four_state(sim.signal("q")), known(0xBB));
+    assert_eq!(sim.get_four_state(sim.signal("yc")), known(0x00));
+    assert_eq!(sim.get_four_state(sim.signal("qc")), known(0x00));
+}
+
+fn test_tmerge_keeps_bits_where_arms_agree(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @build Simulator::builder(BRANCHES, "Top")
+        .four_state(true)
+        .xprop(XPropMode::TMerge);
+    drive_unknown_conditions!(sim);
+    for name in ["y", "q"] {
+        let (value, mask) = sim.get_four_state(sim.signal(name));
+        assert_eq!(mask, BigUint::from(0x11u8), "{name}: 0xAA and 0xBB differ in bits 0 and 4");
+        assert_eq!(value & BigUint::from(0xEEu8), BigUint::from(0xAAu8), "{name}");
+    }
+    // `k = 2'b0x` matches neither arm for certain, so the default `8'h00`
+    // merges in as well.
+    for name in ["yc", "qc"] {
+        let (value, mask) = sim.get_four_state(sim.signal(name));
+        assert_eq!(mask, BigUint::from(0xBBu8), "{name}");
+        assert_eq!(value & BigUint::from(0x44u8), BigUint::from(0u8), "{name}");
+    }
+}
+
+fn test_xmerge_makes_every_assigned_bit_x(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @build Simulator::builder(BRANCHES, "Top")
+        .four_state(true)
+        .xprop(XPropMode::XMerge);
+    drive_unknown_conditions!(sim);
+    for name in ["y", "yc", "q", "qc"] {
+        assert_eq!(sim.get_four_state(sim.signal(name)).1, BigUint::from(0xFFu8), "{name}");
+    }
+}
+
+fn test_xprop_mode_ignores_known_conditions(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @build Simulator::builder(BRANCHES, "Top")
+        .four_state(true)
+        .xprop(XPropMode::XMerge);
+    let clk = sim.event("clk");
+    let sel = sim.signal("sel");
+    let k = sim.signal("k");
+    let a = sim.signal("a");
+    let b = sim.signal("b");
+    sim.modify(|io| {
+        io.set(a, 0xAAu8);
+        io.set(b, 0xBBu8);
+        io.set(sel, 1u8);
+        io.set(k, 1u8);
+    })
+    .unwrap();
+    sim.tick(clk).unwrap();
+    let known = |v: u8| (BigUint::from(v), BigUint::from(0u8));
+    assert_eq!(sim.get_four_state(sim.signal("y")), known(0xAA));
+    assert_eq!(sim.get_four_state(sim.signal("q")), known(0xAA));
+    assert_eq!(sim.get_four_state(sim.signal("yc")), known(0xBB));
+    assert_eq!(sim.get_four_state(sim.signal("qc")), known(0xBB));
+}
+
+fn test_module_override_applies_to_its_instances_only(sim) {
+    @omit_veryl;
+    @ignore_on(sv);
+    @build Simulator::builder(HIER, "Top")
+        .four_state(true)
+        .xprop_in_module("Pick", XPropMode::XMerge);
+    let sel = sim.signal("sel");
+    let a = sim.signal("a");
+    let b = sim.signal("b");
+    sim.modify(|io| {
+        io.set(a, 0xAAu8);
+        io.set(b, 0xBBu8);
+        io.set_four_state(sel, BigUint::from(1u8), BigUint::from(1u8));
+    })
+    .unwrap();
+    assert_eq!(sim.get_four_state(sim.signal("y0")).1, BigUint::from(0xFFu8));
+    assert_eq!(
+        sim.get_four_state(sim.signal("y1")),
+        (BigUint::from(0xBBu8), BigUint::from(0u8))
+    );
+}
+
+}
@@ -76,6 +76,10 @@ export default defineConfig({
                   text: "VCD 波形出力",
                   link: "/ja/guide/vcd",
                 },
+                {
+                  text: "カバレッジ",
+                  link: "/ja/guide/coverage",
+                },
                 {
                   text: "型変換",
                   link: "/ja/guide/type-conversion",
@@ -162,6 +166,7 @@ export default defineConfig({
             { text: "Parameter Overrides", link: "/guide/parameter-overrides" },
             { text: "Child Instance Access", link: "/guide/hierarchy" },
             { text: "VCD Waveform Output", link: "/guide/vcd" },
+            { text: "Coverage", link: "/guide/coverage" },
             { text: "Type Conversion", link: "/guide/type-conversion" },
           ],
         },
@@ -61,6 +61,7 @@ export {
 /** @internal */
 export type {
 	CreateResult,
+	DumpFilter,
 	EventHandle,
 	FourStateSignalValue,
 	FourStateValue,
@@ -80,6 +81,7 @@ export type {
 	TrueLoopSpec,
 	WasmFrontendSimulatorHandle,
 } from "./types.js";
+export type { PropertyFailure, RunLimits, ValueChange } from "./types.js";
 // 4-state helpers
 // Error types
 export {
@@ -13,16 +13,19 @@ import type { NativeCreateSimulationFn } from "./simulation.js";
 import type { NativeCreateFn } from "./simulator.js";
 import type {
 	CreateResult,
+	DumpFilter,
 	FrontendSimulatorHandle,
 	LoopBreak,
 	NativeFrontendSimulatorHandle,
 	NativeSimulationHandle,
 	NativeSimulatorHandle,
 	PortInfo,
+	PropertyFailure,
 	SignalLayout,
 	SimulatorOptions,
 	SourceFile,
 	TrueLoopSpec,
+	ValueChange,
 } from "./types.js";
 import { createWasmSimulatorBridge, isWasmHandle } from "./wasm-bridge.js";
 
@@ -41,13 +44,70 @@ export interface RawNapiSimulationHandle {
 	readonly totalSize: number;
 	readonly defaultMaxSteps: number | null;
 	addClock(eventId: number, period: number, initialDelay: number): void;
+	addClockWith(
+		eventId: number,
+		period: number,
+		highTime: number,
+		phase: number,
+	): void;
+	pauseClock(eventId: number): void;
+	resumeClock(eventId: number): void;
+	removeClock(eventId: number): void;
+	setTimescale(timescale: string): void;
+	dumpOn(): void;
+	dumpOff(): void;
+	writeSaif(path: string): void;
+	writeToggleCoverage(path: string): void;
+	writeCoverage(path: string, format: string): void;
+	writeFsmCoverage(path: string): void;
+	profileTable(): string;
+	writeProfile(path: string): void;
+	assertProperty(name: string, property: string): number;
+	removeProperty(id: number): void;
+	takePropertyFailures(): PropertyFailure[];
+	setRunLimits(
+		wallClockMs: number | null,
+		maxCascadeRounds: number | null,
+	): void;
+	ticks(duration: string): number;
+	timeIn(unit: string): number;
 	schedule(eventId: number, time: number, value: number): void;
+	scheduleValue(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		time: number,
+		value: bigint,
+		mask?: bigint,
+	): void;
 	runUntil(endTime: number): void;
 	step(): number | null;
 	time(): number;
 	nextEventTime(): number | null;
 	evalComb(): void;
 	dump(timestamp: number): void;
+	watch(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		callback: (change: ValueChange) => void,
+	): number;
+	unwatch(id: number): void;
+	force(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		value: bigint,
+		mask?: bigint,
+	): void;
+	release(offset: number, width: number, is4state: boolean): void;
+	deposit(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		value: bigint,
+		mask?: bigint,
+	): void;
 	sharedMemory(): Uint8Array;
 	dispose(): void;
 }
@@ -94,6 +154,8 @@ export interface NapiOptimizeOptions {
 export interface NapiOptions {
 	fourState?: boolean;
 	vcd?: string;
+	waveform?: string;
+	dumpFilter?: DumpFilter;
 	optLevel?: string;
 	passOverrides?: string[];
 	optimize?: boolean;
@@ -109,6 +171,12 @@ export interface NapiOptions {
 	extraSource?: string;
 	parameters?: NapiParamOverride[];
 	deadStorePolicy?: string;
+	forceSupport?: boolean;
+	toggleCoverage?: boolean;
+	branchCoverage?: boolean;
+	fsmCoverage?: boolean;
+	profile?: boolean;
+	threads?: number;
 }
 
 export interface NapiSourceFile {
@@ -331,6 +399,14 @@ export function buildNapiOpts(
 		napiOpts.vcd = options.vcd;
 		hasOpt = true;
 	}
+	if (options.waveform) {
+		napiOpts.waveform = options.waveform;
+		hasOpt = true;
+	}
+	if (options.dumpFilter) {
+		napiOpts.dumpFilter = options.dumpFilter;
+		hasOpt = true;
+	}
 	if (options.optLevel) {
 		napiOpts.optLevel = options.optLevel;
 		hasOpt = true;
@@ -439,6 +515,30 @@ export function buildNapiOpts(
 			map[options.deadStorePolicy] ?? options.deadStorePolicy;
 		hasOpt = true;
 	}
+	if (options.forceSupport) {
+		napiOpts.forceSupport = true;
+		hasOpt = true;
+	}
+	if (options.toggleCoverage) {
+		napiOpts.toggleCoverage = true;
+		hasOpt = true;
+	}
+	if (options.branchCoverage) {
+		napiOpts.branchCoverage = true;
+		hasOpt = true;
+	}
+	if (options.fsmCoverage) {
+		napiOpts.fsmCoverage = true;
+		hasOpt = true;
+	}
+	if (options.profile) {
+		napiOpts.profile = true;
+		hasOpt = true;
+	}
+	if (options.threads !== undefined && options.threads > 1) {
+		napiOpts.threads = options.threads;
+		hasOpt = true;
+	}
 
 	return hasOpt ? napiOpts : undefined;
 }
@@ -775,6 +875,13 @@ export function wrapDirectSimulatorHandle(
 		dump(timestamp: number): void {
 			raw.dump(timestamp);
 		},
+		watch: raw.watch?.bind(raw),
+		unwatch: raw.unwatch?.bind(raw),
+		force: raw.force?.bind(raw),
+		release: raw.release?.bind(raw),
+		deposit: raw.deposit?.bind(raw),
+		dumpOn: raw.dumpOn?.bind(raw),
+		dumpOff: raw.dumpOff?.bind(raw),
 		dispose(): void {
 			raw.dispose();
 		},
@@ -791,9 +898,84 @@ export function wrapDirectSimulationHandle(
 		addClock(eventId: number, period: number, initialDelay: number): void {
 			raw.addClock(eventId, period, initialDelay);
 		},
+		addClockWith(
+			eventId: number,
+			period: number,
+			highTime: number,
+			phase: number,
+		): void {
+			raw.addClockWith(eventId, period, highTime, phase);
+		},
+		pauseClock(eventId: number): void {
+			raw.pauseClock(eventId);
+		},
+		resumeClock(eventId: number): void {
+			raw.resumeClock(eventId);
+		},
+		removeClock(eventId: number): void {
+			raw.removeClock(eventId);
+		},
+		setTimescale(timescale: string): void {
+			raw.setTimescale(timescale);
+		},
+		dumpOn(): void {
+			raw.dumpOn();
+		},
+		dumpOff(): void {
+			raw.dumpOff();
+		},
+		writeSaif(path: string): void {
+			raw.writeSaif(path);
+		},
+		writeToggleCoverage(path: string): void {
+			raw.writeToggleCoverage(path);
+		},
+		writeCoverage(path: string, format: string): void {
+			raw.writeCoverage(path, format);
+		},
+		writeFsmCoverage(path: string): void {
+			raw.writeFsmCoverage(path);
+		},
+		profileTable(): string {
+			return raw.profileTable();
+		},
+		writeProfile(path: string): void {
+			raw.writeProfile(path);
+		},
+		assertProperty(name: string, property: string): number {
+			return raw.assertProperty(name, property);
+		},
+		removeProperty(id: number): void {
+			raw.removeProperty(id);
+		},
+		takePropertyFailures(): PropertyFailure[] {
+			return raw.takePropertyFailures();
+		},
+		setRunLimits(
+			wallClockMs: number | null,
+			maxCascadeRounds: number | null,
+		): void {
+			raw.setRunLimits(wallClockMs, maxCascadeRounds);
+		},
+		ticks(duration: string): number {
+			return raw.ticks(duration);
+		},
+		timeIn(unit: string): number {
+			return raw.timeIn(unit);
+		},
 		schedule(eventId: number, time: number, value: number): void {
 			raw.schedule(eventId, time, value);
 		},
+		scheduleValue(
+			offset: number,
+			width: number,
+			is4state: boolean,
+			time: number,
+			value: bigint,
+			mask?: bigint,
+		): void {
+			raw.scheduleValue(offset, width, is4state, time, value, mask);
+		},
 		runUntil(endTime: number): void {
 			raw.runUntil(endTime);
 		},
@@ -812,6 +994,38 @@ export function wrapDirectSimulationHandle(
 		dump(timestamp: number): void {
 			raw.dump(timestamp);
 		},
+		watch(
+			offset: number,
+			width: number,
+			is4state: boolean,
+			callback: (change: ValueChange) => void,
+		): number {
+			return raw.watch(offset, width, is4state, callback);
+		},
+		unwatch(id: number): void {
+			raw.unwatch(id);
+		},
+		force(
+			offset: number,
+			width: number,
+			is4state: boolean,
+			value: bigint,
+			mask?: bigint,
+		): void {
+			raw.force(offset, width, is4state, value, mask);
+		},
+		release(offset: number, width: number, is4state: boolean): void {
+			raw.release(offset, width, is4state);
+		},
+		deposit(
+			offset: number,
+			width: number,
+			is4state: boolean,
+			value: bigint,
+			mask?: bigint,
+		): void {
+			raw.deposit(offset, width, is4state, value, mask);
+		},
 		dispose(): void {
 			raw.dispose();
 		},
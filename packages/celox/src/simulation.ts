@@ -20,9 +20,12 @@ import type {
 	FourStateValue,
 	ModuleDefinition,
 	NativeSimulationHandle,
+	PropertyFailure,
+	RunLimits,
 	SignalLayout,
 	SimulatorOptions,
 	SourceFile,
+	ValueChange,
 } from "./types.js";
 import { SimulationTimeoutError } from "./types.js";
 import { isWasmHandle } from "./wasm-bridge.js";
@@ -126,6 +129,8 @@ export class Simulation<P = Record<string, unknown>> {
 		const {
 			fourState,
 			vcd,
+			waveform,
+			dumpFilter,
 			optimize,
 			falseLoops,
 			trueLoops,
@@ -133,10 +138,17 @@ export class Simulation<P = Record<string, unknown>> {
 			resetType,
 			parameters,
 			deadStorePolicy,
+			toggleCoverage,
+			branchCoverage,
+			fsmCoverage,
+			profile,
+			threads,
 		} = merged ?? {};
 		const result = createFn(module.sources, module.name, {
 			fourState,
 			vcd,
+			waveform,
+			dumpFilter,
 			optimize,
 			falseLoops,
 			trueLoops,
@@ -144,6 +156,11 @@ export class Simulation<P = Record<string, unknown>> {
 			resetType,
 			parameters,
 			deadStorePolicy,
+			toggleCoverage,
+			branchCoverage,
+			fsmCoverage,
+			profile,
+			threads,
 		});
 		const state: DirtyState = { dirty: false };
 
@@ -395,18 +412,109 @@ export class Simulation<P = Record<string, unknown>> {
 	 * Register a periodic clock.
 	 *
 	 * @param name    Clock event name (must match a `clock` port).
-	 * @param opts    `period` in time units; optional `initialDelay`.
+	 * @param opts    `period` in time units; optional `initialDelay` (time of
+	 *                the first rising edge) and `highTime` (ticks high per
+	 *                period, default half the period). `highTime` is only
+	 *                supported by the native addon.
 	 */
 	addClock(
 		name: string,
-		opts: { period: number; initialDelay?: number },
+		opts: { period: number; initialDelay?: number; highTime?: number },
 	): void {
 		this.ensureAlive();
 		const eventId = this.resolveEvent(name);
-		this._handle.addClock(eventId, opts.period, opts.initialDelay ?? 0);
+		if (opts.highTime !== undefined) {
+			if (!this._handle.addClockWith) {
+				throw new Error(
+					"addClock() highTime is not supported by this backend",
+				);
+			}
+			this._handle.addClockWith(
+				eventId,
+				opts.period,
+				opts.highTime,
+				opts.initialDelay ?? 0,
+			);
+		} else {
+			this._handle.addClock(eventId, opts.period, opts.initialDelay ?? 0);
+		}
 		this._clocks.set(name, { period: opts.period, eventId });
 	}
 
+	/**
+	 * Stop a clock registered with `addClock`. It holds its current level
+	 * until `resumeClock`.
+	 */
+	pauseClock(name: string): void {
+		this.ensureAlive();
+		const eventId = this.resolveEvent(name);
+		if (!this._handle.pauseClock) {
+			throw new Error("pauseClock() is not supported by this backend");
+		}
+		this._handle.pauseClock(eventId);
+	}
+
+	/**
+	 * Restart a paused clock. Edges stay aligned to the original phase.
+	 */
+	resumeClock(name: string): void {
+		this.ensureAlive();
+		const eventId = this.resolveEvent(name);
+		if (!this._handle.resumeClock) {
+			throw new Error("resumeClock() is not supported by this backend");
+		}
+		this._handle.resumeClock(eventId);
+	}
+
+	/**
+	 * Unregister a clock and drop its pending edges.
+	 */
+	removeClock(name: string): void {
+		this.ensureAlive();
+		const eventId = this.resolveEvent(name);
+		if (!this._handle.removeClock) {
+			throw new Error("removeClock() is not supported by this backend");
+		}
+		this._handle.removeClock(eventId);
+		this._clocks.delete(name);
+	}
+
+	/**
+	 * Set the physical length of one time unit, e.g. `"1ns"` or `"10ps"`.
+	 * Must be called before the first waveform dump.
+	 */
+	setTimescale(timescale: string): void {
+		this.ensureAlive();
+		if (!this._handle.setTimescale) {
+			throw new Error("setTimescale() is not supported by this backend");
+		}
+		this._handle.setTimescale(timescale);
+	}
+
+	/**
+	 * Convert a duration such as `"10ns"` into time units. Requires
+	 * `setTimescale`.
+	 */
+	ticks(duration: string): number {
+		this.ensureAlive();
+		if (!this._handle.ticks) {
+			throw new Error("ticks() is not supported by this backend");
+		}
+		return this._handle.ticks(duration);
+	}
+
+	/**
+	 * Current simulation time in `unit` (`"s"`, `"ms"`, `"us"`, `"ns"`,
+	 * `"ps"`, or `"fs"`). Requires `setTimescale`.
+	 */
+	timeIn(unit: string): number {
+		this.ensureAlive();
+		if (!this._handle.timeIn) {
+			throw new Error("timeIn() is not supported by this backend");
+		}
+		return this._handle.timeIn(unit);
+	}
+
 	/**
 	 * Schedule a one-shot value change for a signal.
 	 *
@@ -419,6 +527,39 @@ export class Simulation<P = Record<string, unknown>> {
 		this._handle.schedule(eventId, opts.time, opts.value);
 	}
 
+	/**
+	 * Schedule a write of any port, at any width. Writes land after the
+	 * clock edges at the same time, so flip-flops clocked at `opts.time`
+	 * still sample the old value. Only the native addon supports this.
+	 *
+	 * @param name  Port name.
+	 * @param opts  `time` — absolute time to apply; `value` — value to write;
+	 *              `mask` — optional X/Z mask.
+	 */
+	scheduleValue(
+		name: string,
+		opts: { time: number; value: bigint; mask?: bigint },
+	): void {
+		this.ensureAlive();
+		const sig = this._layout[name];
+		if (!sig) {
+			throw new Error(
+				`Unknown port '${name}'. Available: ${Object.keys(this._layout).join(", ")}`,
+			);
+		}
+		if (!this._handle.scheduleValue) {
+			throw new Error("scheduleValue() is not supported by this backend");
+		}
+		this._handle.scheduleValue(
+			sig.offset,
+			sig.width,
+			sig.is4state,
+			opts.time,
+			opts.value,
+			opts.mask,
+		);
+	}
+
 	/**
 	 * Run the simulation until the given time.
 	 * Processes all scheduled events up to and including `endTime`.
@@ -615,12 +756,226 @@ export class Simulation<P = Record<string, unknown>> {
 		return { __fourState: true, value, mask };
 	}
 
+	/**
+	 * Call `callback` whenever the named port changes after `step()`, `runUntil()`
+	 * or a combinational settle. `change.time` is the simulation time.
+	 * Only the native addon supports watches.
+	 *
+	 * @returns An ID for `unwatch()`.
+	 */
+	watch(portName: string, callback: (change: ValueChange) => void): number {
+		this.ensureAlive();
+		const sig = this._layout[portName];
+		if (!sig) {
+			throw new Error(
+				`Unknown port '${portName}'. Available: ${Object.keys(this._layout).join(", ")}`,
+			);
+		}
+		if (!this._handle.watch) {
+			throw new Error("watch() is not supported by this backend");
+		}
+		if (this._state.dirty) {
+			this._handle.evalComb();
+			this._state.dirty = false;
+		}
+		return this._handle.watch(sig.offset, sig.width, sig.is4state, callback);
+	}
+
+	/** Remove a watch registered with `watch()`. */
+	unwatch(id: number): void {
+		this.ensureAlive();
+		this._handle.unwatch?.(id);
+	}
+
+	/**
+	 * Hold the named port at `value` until `release()`. Forcing a net driven
+	 * by combinational logic needs the `forceSupport` build option.
+	 * Only the native addon supports forces.
+	 */
+	force(portName: string, value: bigint, mask?: bigint): void {
+		const sig = this.forceTarget(portName, "force");
+		this._handle.force?.(sig.offset, sig.width, sig.is4state, value, mask);
+		this._state.dirty = true;
+	}
+
+	/** Release a force placed by `force()`. */
+	release(portName: string): void {
+		const sig = this.forceTarget(portName, "release");
+		this._handle.release?.(sig.offset, sig.width, sig.is4state);
+		this._state.dirty = true;
+	}
+
+	/**
+	 * Write `value` to the named port once without holding it; the design
+	 * may overwrite it on the next update. Ignored while the port is forced.
+	 */
+	deposit(portName: string, value: bigint, mask?: bigint): void {
+		const sig = this.forceTarget(portName, "deposit");
+		this._handle.deposit?.(sig.offset, sig.width, sig.is4state, value, mask);
+		this._state.dirty = true;
+	}
+
+	private forceTarget(
+		portName: string,
+		method: "force" | "release" | "deposit",
+	): SignalLayout {
+		this.ensureAlive();
+		const sig = this._layout[portName];
+		if (!sig) {
+			throw new Error(
+				`Unknown port '${portName}'. Available: ${Object.keys(this._layout).join(", ")}`,
+			);
+		}
+		if (!this._handle[method]) {
+			throw new Error(`${method}() is not supported by this backend`);
+		}
+		return sig;
+	}
+
 	/** Write current signal values to VCD at the given timestamp. */
 	dump(timestamp: number): void {
 		this.ensureAlive();
 		this._handle.dump(timestamp);
 	}
 
+	/** Resume waveform recording after `dumpOff()`. */
+	dumpOn(): void {
+		this.ensureAlive();
+		if (!this._handle.dumpOn) {
+			throw new Error("dumpOn() is not supported by this backend");
+		}
+		this._handle.dumpOn();
+	}
+
+	/** Stop waveform recording; signals read as X until `dumpOn()`. */
+	dumpOff(): void {
+		this.ensureAlive();
+		if (!this._handle.dumpOff) {
+			throw new Error("dumpOff() is not supported by this backend");
+		}
+		this._handle.dumpOff();
+	}
+
+	/**
+	 * Write the bit toggle counts collected so far as a SAIF 2.0 backward
+	 * file. Requires the `toggleCoverage` option.
+	 */
+	writeSaif(path: string): void {
+		this.ensureAlive();
+		if (!this._handle.writeSaif) {
+			throw new Error("writeSaif() is not supported by this backend");
+		}
+		this._handle.writeSaif(path);
+	}
+
+	/**
+	 * Write the bit toggle counts collected so far as a JSON coverage
+	 * report. Requires the `toggleCoverage` option.
+	 */
+	writeToggleCoverage(path: string): void {
+		this.ensureAlive();
+		if (!this._handle.writeToggleCoverage) {
+			throw new Error("writeToggleCoverage() is not supported by this backend");
+		}
+		this._handle.writeToggleCoverage(path);
+	}
+
+	/**
+	 * Write the branch hit counts collected so far as an LCOV tracefile,
+	 * Cobertura XML or JSON. Requires the `branchCoverage` option.
+	 */
+	writeCoverage(
+		path: string,
+		format: "lcov" | "cobertura" | "json" = "lcov",
+	): void {
+		this.ensureAlive();
+		if (!this._handle.writeCoverage) {
+			throw new Error("writeCoverage() is not supported by this backend");
+		}
+		this._handle.writeCoverage(path, format);
+	}
+
+	/**
+	 * Write the FSM state and transition counts collected so far as a JSON
+	 * coverage report. Requires the `fsmCoverage` option.
+	 */
+	writeFsmCoverage(path: string): void {
+		this.ensureAlive();
+		if (!this._handle.writeFsmCoverage) {
+			throw new Error("writeFsmCoverage() is not supported by this backend");
+		}
+		this._handle.writeFsmCoverage(path);
+	}
+
+	/**
+	 * Ranked table of the time spent per execution unit and instance so far.
+	 * Requires the `profile` option.
+	 */
+	profileTable(): string {
+		this.ensureAlive();
+		if (!this._handle.profileTable) {
+			throw new Error("profileTable() is not supported by this backend");
+		}
+		return this._handle.profileTable();
+	}
+
+	/**
+	 * Write the profile as folded stacks (`Top;u_core;comb[3] <ns>` per
+	 * line) for `flamegraph.pl` or `inferno-flamegraph`. Requires the
+	 * `profile` option.
+	 */
+	writeProfile(path: string): void {
+		this.ensureAlive();
+		if (!this._handle.writeProfile) {
+			throw new Error("writeProfile() is not supported by this backend");
+		}
+		this._handle.writeProfile(path);
+	}
+
+	/**
+	 * Check a clocked property written in SVA syntax on every edge of its
+	 * clock, e.g. `"@(posedge clk) disable iff (rst) req |-> ##[1:4] ack"`.
+	 * Failed attempts are collected under `name`.
+	 *
+	 * @returns An ID for `removeProperty()`.
+	 */
+	assertProperty(name: string, property: string): number {
+		this.ensureAlive();
+		if (!this._handle.assertProperty) {
+			throw new Error("assertProperty() is not supported by this backend");
+		}
+		return this._handle.assertProperty(name, property);
+	}
+
+	/** Stop checking a property registered with `assertProperty()`. */
+	removeProperty(id: number): void {
+		this.ensureAlive();
+		this._handle.removeProperty?.(id);
+	}
+
+	/** Return and clear the property failures collected so far. */
+	takePropertyFailures(): PropertyFailure[] {
+		this.ensureAlive();
+		return this._handle.takePropertyFailures?.() ?? [];
+	}
+
+	/**
+	 * Make each `runUntil()` and `step()` call throw once it runs longer than
+	 * `wallClockMs`, or once a time step needs more than `maxCascadeRounds`
+	 * cascade rounds. The error message includes the simulation time at
+	 * which the run stopped. Omitted limits are removed.
+	 */
+	setRunLimits(limits: RunLimits): void {
+		this.ensureAlive();
+		if (!this._handle.setRunLimits) {
+			throw new Error("setRunLimits() is not supported by this backend");
+		}
+		this._handle.setRunLimits(
+			limits.wallClockMs ?? null,
+			limits.maxCascadeRounds ?? null,
+		);
+	}
+
 	/** Release native resources. */
 	dispose(): void {
 		if (!this._disposed) {
@@ -26,6 +26,7 @@ import type {
 	SignalLayout,
 	SimulatorOptions,
 	SourceFile,
+	ValueChange,
 } from "./types.js";
 import { createWasmSimulatorBridge, isWasmHandle } from "./wasm-bridge.js";
 
@@ -121,6 +122,8 @@ export class Simulator<P = Record<string, unknown>> {
 		const {
 			fourState,
 			vcd,
+			waveform,
+			dumpFilter,
 			optimize,
 			falseLoops,
 			trueLoops,
@@ -128,10 +131,13 @@ export class Simulator<P = Record<string, unknown>> {
 			resetType,
 			parameters,
 			deadStorePolicy,
+			threads,
 		} = merged ?? {};
 		const result = createFn(module.sources, module.name, {
 			fourState,
 			vcd,
+			waveform,
+			dumpFilter,
 			optimize,
 			falseLoops,
 			trueLoops,
@@ -139,6 +145,7 @@ export class Simulator<P = Record<string, unknown>> {
 			resetType,
 			parameters,
 			deadStorePolicy,
+			threads,
 		});
 		const state: DirtyState = { dirty: false };
 
@@ -486,12 +493,106 @@ export class Simulator<P = Record<string, unknown>> {
 		return { __fourState: true, value, mask };
 	}
 
+	/**
+	 * Call `callback` whenever the named port changes after `tick()`
+	 * or a combinational settle. `change.time` counts completed ticks.
+	 * Only the native addon supports watches.
+	 *
+	 * @returns An ID for `unwatch()`.
+	 */
+	watch(portName: string, callback: (change: ValueChange) => void): number {
+		this.ensureAlive();
+		const sig = this._layout[portName];
+		if (!sig) {
+			throw new Error(
+				`Unknown port '${portName}'. Available: ${Object.keys(this._layout).join(", ")}`,
+			);
+		}
+		if (!this._handle.watch) {
+			throw new Error("watch() is not supported by this backend");
+		}
+		if (this._state.dirty) {
+			this._handle.evalComb();
+			this._state.dirty = false;
+		}
+		return this._handle.watch(sig.offset, sig.width, sig.is4state, callback);
+	}
+
+	/** Remove a watch registered with `watch()`. */
+	unwatch(id: number): void {
+		this.ensureAlive();
+		this._handle.unwatch?.(id);
+	}
+
+	/**
+	 * Hold the named port at `value` until `release()`. Forcing a net driven
+	 * by combinational logic needs the `forceSupport` build option.
+	 * Only the native addon supports forces.
+	 */
+	force(portName: string, value: bigint, mask?: bigint): void {
+		const sig = this.forceTarget(portName, "force");
+		this._handle.force?.(sig.offset, sig.width, sig.is4state, value, mask);
+		this._state.dirty = true;
+	}
+
+	/** Release a force placed by `force()`. */
+	release(portName: string): void {
+		const sig = this.forceTarget(portName, "release");
+		this._handle.release?.(sig.offset, sig.width, sig.is4state);
+		this._state.dirty = true;
+	}
+
+	/**
+	 * Write `value` to the named port once without holding it; the design
+	 * may overwrite it on the next update. Ignored while the port is forced.
+	 */
+	deposit(portName: string, value: bigint, mask?: bigint): void {
+		const sig = this.forceTarget(portName, "deposit");
+		this._handle.deposit?.(sig.offset, sig.width, sig.is4state, value, mask);
+		this._state.dirty = true;
+	}
+
+	private forceTarget(
+		portName: string,
+		method: "force" | "release" | "deposit",
+	): SignalLayout {
+		this.ensureAlive();
+		const sig = this._layout[portName];
+		if (!sig) {
+			throw new Error(
+				`Unknown port '${portName}'. Available: ${Object.keys(this._layout).join(", ")}`,
+			);
+		}
+		if (!this._handle[method]) {
+			throw new Error(`${method}() is not supported by this backend`);
+		}
+		return sig;
+	}
+
 	/** Write current signal values to VCD at the given timestamp. */
 	dump(timestamp: number): void {
 		this.ensureAlive();
 		this._handle.dump(timestamp);
 	}
 
+	/** Resume waveform recording after `dumpOff()`. */
+	dumpOn(): void {
+		this.ensureAlive();
+		if (!this._handle.dumpOn) {
+			throw new Error("dumpOn() is not supported by this backend");
+		}
+		this._handle.dumpOn();
+	}
+
+	/** Stop waveform recording; signals read as X until `dumpOn()`. */
+	dumpOff(): void {
+		this.ensureAlive();
+		if (!this._handle.dumpOff) {
+			throw new Error("dumpOff() is not supported by this backend");
+		}
+		this._handle.dumpOff();
+	}
+
 	/** Release native resources. */
 	dispose(): void {
 		if (!this._disposed) {
@@ -95,7 +95,10 @@ interface FrontendSimulatorHandleMetadata {
 
 /** Raw simulator handle returned by a frontend-owned native addon. */
 export interface NativeFrontendSimulatorHandle
-	extends FrontendSimulatorHandleMetadata {
+	extends FrontendSimulatorHandleMetadata,
+		NativeWatchHandle,
+		NativeForceHandle,
+		NativeDumpHandle {
 	tick(eventId: number): void;
 	tickN(eventId: number, count: number): void;
 	evalComb(): void;
@@ -127,11 +130,94 @@ export type FrontendSimulatorHandle =
 	| NativeFrontendSimulatorHandle
 	| WasmFrontendSimulatorHandle;
 
+/**
+ * A change observed on a watched port, delivered to `watch` callbacks.
+ */
+export interface ValueChange {
+	readonly id: number;
+	/** Completed ticks for a simulator handle, simulation time otherwise. */
+	readonly time: number;
+	readonly oldValue: bigint;
+	readonly oldMask: bigint;
+	readonly newValue: bigint;
+	readonly newMask: bigint;
+}
+
+/**
+ * A failed attempt of a property registered with `assertProperty`.
+ */
+export interface PropertyFailure {
+	readonly id: number;
+	/** The name the property was registered with. */
+	readonly name: string;
+	/** Simulation time of the clock edge where the attempt started. */
+	readonly startTime: number;
+	/** Simulation time of the clock edge where the attempt failed. */
+	readonly time: number;
+}
+
+/** Limits applied to each `runUntil()` or `step()` call. */
+export interface RunLimits {
+	/** Wall-clock budget of one call, in milliseconds. */
+	readonly wallClockMs?: number;
+	/** Maximum cascade rounds within one time step. */
+	readonly maxCascadeRounds?: number;
+}
+
+/**
+ * Value-change callbacks. Only the native addon implements these; the WASM
+ * bridge leaves them undefined.
+ * @internal
+ */
+interface NativeWatchHandle {
+	watch?(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		callback: (change: ValueChange) => void,
+	): number;
+	unwatch?(id: number): void;
+}
+
+/**
+ * Force, release and deposit. Only the native addon implements these.
+ * @internal
+ */
+interface NativeForceHandle {
+	force?(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		value: bigint,
+		mask?: bigint,
+	): void;
+	release?(offset: number, width: number, is4state: boolean): void;
+	deposit?(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		value: bigint,
+		mask?: bigint,
+	): void;
+}
+
+/**
+ * Waveform recording control. Only the native addon implements these.
+ * @internal
+ */
+interface NativeDumpHandle {
+	dumpOn?(): void;
+	dumpOff?(): void;
+}
+
 /**
  * Opaque handle returned by NAPI for event-based simulation.
  * @internal
  */
-export interface NativeSimulatorHandle {
+export interface NativeSimulatorHandle
+	extends NativeWatchHandle,
+		NativeForceHandle,
+		NativeDumpHandle {
 	tick(eventId: number): void;
 	tickN(eventId: number, count: number): void;
 	evalComb(): void;
@@ -143,9 +229,45 @@ export interface NativeSimulatorHandle {
  * Opaque handle returned by NAPI for time-based simulation.
  * @internal
  */
-export interface NativeSimulationHandle {
+export interface NativeSimulationHandle
+	extends NativeWatchHandle,
+		NativeForceHandle,
+		NativeDumpHandle {
 	addClock(eventId: number, period: number, initialDelay: number): void;
+	addClockWith?(
+		eventId: number,
+		period: number,
+		highTime: number,
+		phase: number,
+	): void;
+	pauseClock?(eventId: number): void;
+	resumeClock?(eventId: number): void;
+	removeClock?(eventId: number): void;
+	setTimescale?(timescale: string): void;
+	writeSaif?(path: string): void;
+	writeToggleCoverage?(path: string): void;
+	writeCoverage?(path: string, format: string): void;
+	writeFsmCoverage?(path: string): void;
+	profileTable?(): string;
+	writeProfile?(path: string): void;
+	assertProperty?(name: string, property: string): number;
+	removeProperty?(id: number): void;
+	takePropertyFailures?(): PropertyFailure[];
+	setRunLimits?(
+		wallClockMs: number | null,
+		maxCascadeRounds: number | null,
+	): void;
+	ticks?(duration: string): number;
+	timeIn?(unit: string): number;
 	schedule(eventId: number, time: number, value: number): void;
+	scheduleValue?(
+		offset: number,
+		width: number,
+		is4state: boolean,
+		time: number,
+		value: bigint,
+		mask?: bigint,
+	): void;
 	runUntil(endTime: number): void;
 	step(): number | null;
 	time(): number;
@@ -213,6 +335,13 @@ export interface SimulatorOptions {
 	fourState?: boolean;
 	/** Path to write VCD waveform output. */
 	vcd?: string;
+	/**
+	 * Path to write waveform output. A `.fst` extension selects the
+	 * compressed FST format; any other writes VCD. Takes precedence over `vcd`.
+	 */
+	waveform?: string;
+	/** Restrict the waveform to some scopes, a time window, or non-array signals. */
+	dumpFilter?: DumpFilter;
 	/**
 	 * Optimization level preset. Default: "O1".
 	 * - "O0": No SIR optimizations (except TailCallSplit). Fast compilation.
@@ -280,6 +409,60 @@ export interface SimulatorOptions {
 	 * When `optLevel` is "O2", defaults to "preserveTopPorts" unless explicitly set.
 	 */
 	deadStorePolicy?: "off" | "preserveTopPorts" | "preserveAllPorts";
+	/**
+	 * Let `force()` hold nets driven by combinational logic. Default: false.
+	 * Forces `optLevel` "O0". Forcing inputs and registers works without it.
+	 */
+	forceSupport?: boolean;
+	/**
+	 * Count 0→1 and 1→0 transitions of every bit, for toggle coverage and
+	 * SAIF switching activity. Only `Simulation` reports the counts.
+	 * Default: false.
+	 */
+	toggleCoverage?: boolean;
+	/**
+	 * Count how often each arm of every `if`, `if_reset` and `case` in
+	 * `always_comb` and `always_ff` blocks executes, for statement and branch
+	 * coverage. Only `Simulation` reports the counts. Default: false.
+	 */
+	branchCoverage?: boolean;
+	/**
+	 * Count which variants each enum-typed register assigned in `always_ff`
+	 * takes and which transitions between them occur, for FSM coverage. Only
+	 * `Simulation` reports the counts. Default: false.
+	 */
+	fsmCoverage?: boolean;
+	/**
+	 * Time every execution unit and attribute the time to the instances it
+	 * belongs to, for `profileTable()` and `writeProfile()`. Only
+	 * `Simulation` reports the times. Default: false.
+	 */
+	profile?: boolean;
+	/**
+	 * Worker threads for evaluating independent execution units. Results
+	 * are bit-exact with single-threaded evaluation. Only the native
+	 * backend runs in parallel. Default: 1.
+	 */
+	threads?: number;
+}
+
+/**
+ * Selects which signals the waveform records, and when. Scopes work like
+ * `$dumpvars(depth, scope)`.
+ */
+export interface DumpFilter {
+	/**
+	 * Instance paths such as `"u_core.u_alu"` (`""` for the top module) and
+	 * how many levels of each to dump: 1 for the scope alone, 0 or omitted
+	 * for every level. Every instance is dumped when omitted.
+	 */
+	scopes?: { path: string; depth?: number }[];
+	/** Include unpacked arrays. Default: true. */
+	arrays?: boolean;
+	/** Record from this time on. */
+	start?: number;
+	/** Record up to and including this time. */
+	end?: number;
 }
 
 /** A parameter override for a top-level module parameter. */